@@ -1,5 +1,6 @@
 #![deny(missing_docs)]
 mod common;
+mod errors;
 mod events;
 mod methods;
 mod structs;
@@ -36,6 +37,8 @@ pub struct ExpandedContract {
     pub events: TokenStream,
     /// All contract call struct related types
     pub call_structs: TokenStream,
+    /// All custom error types of the contract
+    pub errors: TokenStream,
     /// The contract's internal structs
     pub abi_structs: TokenStream,
 }
@@ -43,8 +46,15 @@ pub struct ExpandedContract {
 impl ExpandedContract {
     /// Merges everything into a single module
     pub fn into_tokens(self) -> TokenStream {
-        let ExpandedContract { module, imports, contract, events, call_structs, abi_structs } =
-            self;
+        let ExpandedContract {
+            module,
+            imports,
+            contract,
+            events,
+            call_structs,
+            errors,
+            abi_structs,
+        } = self;
         quote! {
            // export all the created data types
             pub use #module::*;
@@ -55,6 +65,7 @@ impl ExpandedContract {
                 #contract
                 #events
                 #call_structs
+                #errors
                 #abi_structs
             }
         }
@@ -123,7 +134,10 @@ impl Context {
         // 5. generate deploy function if
         let deployment_methods = self.deployment_methods();
 
-        // 6. Declare the structs parsed from the human readable abi
+        // 6. Declare the custom errors of the contract
+        let errors = self.errors_declaration()?;
+
+        // 7. Declare the structs parsed from the human readable abi
         let abi_structs_decl = self.abi_structs()?;
 
         let ethers_core = ethers_core_crate();
@@ -161,6 +175,7 @@ impl Context {
             contract,
             events: events_decl,
             call_structs,
+            errors,
             abi_structs: abi_structs_decl,
         })
     }
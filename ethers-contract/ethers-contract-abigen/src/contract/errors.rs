@@ -0,0 +1,138 @@
+//! Custom error expansion
+
+use super::{
+    methods::{expand_data_struct, expand_data_tuple},
+    util, Context,
+};
+use ethers_core::{
+    abi::{ethabi::AbiError, Function, FunctionExt, StateMutability},
+    macros::{ethers_contract_crate, ethers_core_crate},
+};
+use eyre::Result;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+impl Context {
+    /// Expands each custom error to a struct and all errors into an enum
+    pub(crate) fn errors_declaration(&self) -> Result<TokenStream> {
+        let mut struct_defs = Vec::new();
+        let mut struct_names = Vec::new();
+        for (name, errors) in &self.abi.errors {
+            for (idx, error) in errors.iter().enumerate() {
+                // overloaded errors are suffixed with their index
+                let struct_name = if errors.len() > 1 {
+                    util::ident(&format!("{}{}", util::safe_pascal_case(name), idx))
+                } else {
+                    util::ident(&util::safe_pascal_case(name))
+                };
+                struct_defs.push(self.expand_error_struct(error, &struct_name)?);
+                struct_names.push(struct_name);
+            }
+        }
+
+        if struct_defs.is_empty() {
+            return Ok(quote! {})
+        }
+
+        let ethers_core = ethers_core_crate();
+        let ethers_contract = ethers_contract_crate();
+
+        // use the same derives as for events
+        let derives = util::expand_derives(&self.event_derives);
+        let enum_name = self.expand_errors_enum_name();
+
+        Ok(quote! {
+            #(#struct_defs)*
+
+            #[derive(Debug, Clone, PartialEq, Eq, #ethers_contract::EthAbiType, #derives)]
+            pub enum #enum_name {
+                #(#struct_names(#struct_names)),*
+            }
+
+            impl #ethers_core::abi::AbiDecode for #enum_name {
+                fn decode(data: impl AsRef<[u8]>) -> Result<Self, #ethers_core::abi::AbiError> {
+                    #(
+                        if let Ok(decoded) = <#struct_names as #ethers_core::abi::AbiDecode>::decode(data.as_ref()) {
+                            return Ok(#enum_name::#struct_names(decoded))
+                        }
+                    )*
+                    Err(#ethers_core::abi::Error::InvalidData.into())
+                }
+            }
+
+            impl #ethers_core::abi::AbiEncode for #enum_name {
+                fn encode(self) -> Vec<u8> {
+                    match self {
+                        #(
+                            #enum_name::#struct_names(element) => element.encode()
+                        ),*
+                    }
+                }
+            }
+
+            impl ::std::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    match self {
+                        #(
+                            #enum_name::#struct_names(element) => element.fmt(f)
+                        ),*
+                    }
+                }
+            }
+
+            #(
+                impl ::std::convert::From<#struct_names> for #enum_name {
+                    fn from(var: #struct_names) -> Self {
+                        #enum_name::#struct_names(var)
+                    }
+                }
+            )*
+        })
+    }
+
+    /// Expands a custom error into a struct.
+    ///
+    /// Custom errors are ABI encoded like function calls, so the generated type derives `EthCall`
+    /// and only decodes revert data with a matching selector.
+    fn expand_error_struct(&self, error: &AbiError, struct_name: &Ident) -> Result<TokenStream> {
+        #[allow(deprecated)]
+        let function = Function {
+            name: error.name.clone(),
+            inputs: error.inputs.clone(),
+            outputs: vec![],
+            constant: None,
+            state_mutability: StateMutability::NonPayable,
+        };
+        let fields = self.expand_input_params(&function)?;
+        // expand as a tuple if all fields are anonymous
+        let all_anonymous_fields = error.inputs.iter().all(|input| input.name.is_empty());
+        let error_type_definition = if all_anonymous_fields {
+            expand_data_tuple(struct_name, &fields)
+        } else {
+            expand_data_struct(struct_name, &fields)
+        };
+        let error_name = &error.name;
+        let abi_signature = function.abi_signature();
+        let doc = format!(
+            "Custom error `{}` with signature `{}` and selector `{:?}`",
+            error.name,
+            abi_signature,
+            function.selector()
+        );
+        let abi_signature_doc = util::expand_doc(&doc);
+        let ethers_contract = ethers_contract_crate();
+        let derives = util::expand_derives(&self.event_derives);
+
+        Ok(quote! {
+            #abi_signature_doc
+            #[derive(Clone, Debug, Default, Eq, PartialEq, #ethers_contract::EthCall, #ethers_contract::EthDisplay, #derives)]
+            #[ethcall( name = #error_name, abi = #abi_signature )]
+            pub #error_type_definition
+        })
+    }
+
+    /// The name of the enum that holds all custom errors of the contract
+    fn expand_errors_enum_name(&self) -> Ident {
+        util::ident(&format!("{}Errors", self.contract_ident))
+    }
+}
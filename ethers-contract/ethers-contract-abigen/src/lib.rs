@@ -73,6 +73,9 @@ pub struct Abigen {
 
     /// Manually specified event name aliases.
     event_aliases: HashMap<String, String>,
+
+    /// Whether to print `cargo:rerun-if-changed` for a local ABI file.
+    emit_rerun_if_changed: bool,
 }
 
 impl Abigen {
@@ -86,6 +89,7 @@ impl Abigen {
             event_derives: Vec::new(),
             event_aliases: HashMap::new(),
             rustfmt: true,
+            emit_rerun_if_changed: false,
         })
     }
 
@@ -151,8 +155,24 @@ impl Abigen {
         self
     }
 
+    /// Print `cargo:rerun-if-changed` for the ABI file when generating the bindings, if the ABI
+    /// is read from the local file system.
+    ///
+    /// Use this in a `build.rs` so that the bindings are regenerated whenever the ABI or artifact
+    /// file changes.
+    #[must_use]
+    pub fn emit_rerun_if_changed(mut self) -> Self {
+        self.emit_rerun_if_changed = true;
+        self
+    }
+
     /// Generates the contract bindings.
     pub fn generate(self) -> Result<ContractBindings> {
+        if self.emit_rerun_if_changed {
+            if let Some(path) = self.abi_source.local_path() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
         let rustfmt = self.rustfmt;
         let name = self.contract_name.clone();
         let (expanded, _) = self.expand()?;
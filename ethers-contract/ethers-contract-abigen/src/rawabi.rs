@@ -151,6 +151,14 @@ impl<'de> Visitor<'de> for AbiObjectVisitor {
             object: Bytes,
         }
 
+        /// Hardhat artifacts store the bytecode as plain hex string, solc and forge as object
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum BytecodeField {
+            Object(BytecodeObject),
+            Hex(DeserializeBytes),
+        }
+
         struct DeserializeBytes(Bytes);
 
         impl<'de> Deserialize<'de> for DeserializeBytes {
@@ -169,9 +177,12 @@ impl<'de> Visitor<'de> for AbiObjectVisitor {
                 }
                 "bytecode" | "byteCode" => {
                     bytecode = map
-                        .next_value::<BytecodeObject>()
+                        .next_value::<BytecodeField>()
                         .ok()
-                        .map(|obj| obj.object)
+                        .map(|field| match field {
+                            BytecodeField::Object(obj) => obj.object,
+                            BytecodeField::Hex(b) => b.0,
+                        })
                         .filter(|bytecode| !bytecode.0.is_empty());
                 }
                 "bin" => {
@@ -278,6 +289,22 @@ mod tests {
         assert_has_bytecode(artifact);
     }
 
+    #[test]
+    fn can_parse_hardhat_bytecode() {
+        let artifact = include_str!("../../tests/solidity-contracts/greeter_hardhat.json");
+        assert_has_bytecode(artifact);
+
+        let s = r#"{"abi": [], "bytecode" : "0x6080" }"#;
+        match serde_json::from_str::<JsonAbi>(s).unwrap() {
+            JsonAbi::Object(abi) => {
+                assert_eq!(abi.bytecode, Some(Bytes::from(vec![0x60, 0x80])));
+            }
+            _ => {
+                panic!("expected abi object")
+            }
+        }
+    }
+
     #[test]
     fn ignores_empty_bytecode() {
         let abi_str = r#"[{"anonymous":false,"inputs":[{"indexed":true,"internalType":"uint64","name":"number","type":"uint64"}],"name":"MyEvent","type":"event"},{"inputs":[],"name":"greet","outputs":[],"stateMutability":"nonpayable","type":"function"}]"#;
@@ -5,7 +5,11 @@ use ethers_core::types::Address;
 use crate::util::resolve_path;
 use cfg_if::cfg_if;
 use eyre::{eyre, Context, Error, Result};
-use std::{env, fs, path::Path, str::FromStr};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use url::Url;
 
 /// A source of a Truffle artifact JSON.
@@ -206,6 +210,17 @@ impl Source {
             }
         }
     }
+
+    /// Returns the resolved path of a [`Source::Local`] ABI file, if it exists.
+    ///
+    /// This is the file to track for changes, e.g. with `cargo:rerun-if-changed`, so that the
+    /// bindings are regenerated when the ABI changes.
+    pub fn local_path(&self) -> Option<PathBuf> {
+        match self {
+            Source::Local(path) => local_contract_path(path).ok(),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for Source {
@@ -223,6 +238,14 @@ impl FromStr for Source {
 /// If the path is relative after all env vars have been resolved then we assume the root is either
 /// `CARGO_MANIFEST_DIR` or the current working directory.
 fn get_local_contract(path: impl AsRef<str>) -> Result<String> {
+    let path = local_contract_path(path)?;
+    let json = fs::read_to_string(&path)
+        .context(format!("failed to read artifact JSON file with path {}", &path.display()))?;
+    Ok(json)
+}
+
+/// Resolves the path of a local artifact JSON file, see [`get_local_contract`]
+fn local_contract_path(path: impl AsRef<str>) -> Result<PathBuf> {
     let path = resolve_path(path.as_ref())?;
     let path = if path.is_relative() {
         let manifest_path = env::var("CARGO_MANIFEST_DIR")?;
@@ -238,10 +261,7 @@ fn get_local_contract(path: impl AsRef<str>) -> Result<String> {
     } else {
         path
     };
-
-    let json = fs::read_to_string(&path)
-        .context(format!("failed to read artifact JSON file with path {}", &path.display()))?;
-    Ok(json)
+    Ok(path)
 }
 
 /// Retrieves a Truffle artifact or ABI from an HTTP URL.
@@ -358,6 +378,17 @@ mod tests {
         assert_eq!(hardhat_parsed, Source::String(hardhat_src));
     }
 
+    #[test]
+    fn resolves_local_path() {
+        let source = Source::parse("../tests/solidity-contracts/greeter.json").unwrap();
+        let path = source.local_path().unwrap();
+        assert!(path.is_absolute());
+        assert!(path.ends_with("tests/solidity-contracts/greeter.json"));
+
+        assert!(Source::local("does/not/exist.json").local_path().is_none());
+        assert!(Source::parse("[]").unwrap().local_path().is_none());
+    }
+
     #[test]
     #[ignore]
     fn get_etherscan_contract() {
@@ -2,7 +2,7 @@
 //! ethereum smart contract.
 use crate::spanned::{ParseInner, Spanned};
 
-use ethers_contract_abigen::Abigen;
+use ethers_contract_abigen::{Abigen, Source};
 use ethers_core::abi::{Function, FunctionExt, Param, StateMutability};
 
 use ethers_contract_abigen::{
@@ -10,7 +10,7 @@ use ethers_contract_abigen::{
     multi::MultiExpansion,
 };
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use std::{collections::HashSet, error::Error};
 use syn::{
     braced,
@@ -29,16 +29,29 @@ pub(crate) struct Contracts {
 impl Contracts {
     pub(crate) fn expand(self) -> Result<TokenStream2, syn::Error> {
         let mut expansions = Vec::with_capacity(self.inner.len());
+        let mut abi_files = Vec::new();
 
         // expand all contracts
         for (span, contract) in self.inner {
+            if let Some(path) = Source::parse(&contract.abi).ok().and_then(|s| s.local_path()) {
+                abi_files.push(path.display().to_string());
+            }
             let contract = Self::expand_contract(contract)
                 .map_err(|err| syn::Error::new(span, err.to_string()))?;
             expansions.push(contract);
         }
 
         // expand all contract expansions
-        Ok(MultiExpansion::new(expansions).expand_inplace())
+        let mut tokens = MultiExpansion::new(expansions).expand_inplace();
+
+        // include the ABI files so that the bindings are regenerated when they change
+        tokens.extend(abi_files.into_iter().map(|path| {
+            quote! {
+                const _: &[u8] = include_bytes!(#path);
+            }
+        }));
+
+        Ok(tokens)
     }
 
     fn expand_contract(
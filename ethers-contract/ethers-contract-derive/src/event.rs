@@ -65,8 +65,8 @@ pub(crate) fn derive_eth_event_impl(input: DeriveInput) -> TokenStream {
         event.anonymous = *anon;
     }
 
-    let decode_log_impl = match derive_decode_from_log_impl(&input, &event) {
-        Ok(log) => log,
+    let (decode_log_impl, indexed_params) = match derive_decode_from_log_impl(&input, &event) {
+        Ok(res) => res,
         Err(err) => return err.to_compile_error(),
     };
 
@@ -102,6 +102,10 @@ pub(crate) fn derive_eth_event_impl(input: DeriveInput) -> TokenStream {
             fn is_anonymous() -> bool {
                 #anon
             }
+
+            fn indexed_params() -> ::std::vec::Vec<#core_crate::abi::ParamType> {
+                #indexed_params
+            }
         }
     };
 
@@ -129,7 +133,7 @@ impl EventField {
 fn derive_decode_from_log_impl(
     input: &DeriveInput,
     event: &Event,
-) -> Result<proc_macro2::TokenStream, Error> {
+) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream), Error> {
     let core_crate = ethers_core_crate();
 
     let fields: Vec<_> = match input.data {
@@ -197,6 +201,13 @@ fn derive_decode_from_log_impl(
 
     let topic_types_init = quote! {let topic_types = ::std::vec![#( #topic_types ),*];};
 
+    let indexed_types = event_fields
+        .iter()
+        .filter(|f| f.is_indexed())
+        .map(|f| utils::param_type_quote(&f.param.kind));
+
+    let indexed_params = quote! {::std::vec![#( #indexed_types ),*]};
+
     let data_types = event_fields
         .iter()
         .filter(|f| !f.is_indexed())
@@ -266,7 +277,7 @@ fn derive_decode_from_log_impl(
             #( tokens.push(#swap_tokens); )*
         }
     };
-    Ok(quote! {
+    let decode_log_impl = quote! {
 
         let #core_crate::abi::RawLog {data, topics} = log;
 
@@ -280,7 +291,9 @@ fn derive_decode_from_log_impl(
         #tokens_init
 
         #core_crate::abi::Tokenizable::from_token(#core_crate::abi::Token::Tuple(tokens)).map_err(|_|#core_crate::abi::Error::InvalidData)
-    })
+    };
+
+    Ok((decode_log_impl, indexed_params))
 }
 
 /// Determine the event's ABI by parsing the AST
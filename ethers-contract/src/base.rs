@@ -40,6 +40,21 @@ impl BaseContract {
     ///
     /// If the function exists multiple times and you want to use one of the overloaded
     /// versions, consider using `encode_with_selector`
+    ///
+    /// This does not require a client, which makes it suitable for building calldata that is
+    /// handed to external tools (e.g. a multisig). The arguments are type-checked against the
+    /// function's inputs and an error is returned on mismatch.
+    ///
+    /// ```
+    /// use ethers_contract::BaseContract;
+    /// use ethers_core::{abi::parse_abi, types::{Address, U256}};
+    ///
+    /// let erc20 = BaseContract::from(
+    ///     parse_abi(&["function approve(address spender, uint256 value) returns (bool)"]).unwrap(),
+    /// );
+    /// let calldata = erc20.encode("approve", (Address::zero(), U256::MAX)).unwrap();
+    /// assert_eq!(&calldata[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+    /// ```
     pub fn encode<T: Tokenize>(&self, name: &str, args: T) -> Result<Bytes, AbiError> {
         let function = self.abi.function(name)?;
         encode_function_data(function, args)
@@ -181,6 +196,38 @@ impl BaseContract {
         decode_function_data_raw(function, bytes, false)
     }
 
+    /// Decodes the calldata of a transaction calling one of the contract's functions, e.g. the
+    /// `input` of a mined [`Transaction`](ethers_core::types::Transaction).
+    ///
+    /// The function is looked up by the 4 byte selector at the start of the calldata, so
+    /// overloaded functions are told apart. Returns the called function and its decoded arguments.
+    ///
+    /// ```
+    /// use ethers_contract::BaseContract;
+    /// use ethers_core::{abi::{parse_abi, Token}, types::{Address, U256}};
+    ///
+    /// let erc20 = BaseContract::from(
+    ///     parse_abi(&["function transfer(address to, uint256 value) returns (bool)"]).unwrap(),
+    /// );
+    /// let input = erc20.encode("transfer", (Address::zero(), U256::one())).unwrap();
+    /// let (function, args) = erc20.decode_input(input).unwrap();
+    /// assert_eq!(function.name, "transfer");
+    /// assert_eq!(args, vec![Token::Address(Address::zero()), Token::Uint(U256::one())]);
+    /// ```
+    pub fn decode_input<T: AsRef<[u8]>>(
+        &self,
+        calldata: T,
+    ) -> Result<(&Function, Vec<Token>), AbiError> {
+        let calldata = calldata.as_ref();
+        if calldata.len() < 4 {
+            return Err(AbiError::WrongSelector)
+        }
+        let mut selector = Selector::default();
+        selector.copy_from_slice(&calldata[..4]);
+        let function = self.get_from_signature(selector)?;
+        Ok((function, function.decode_input(&calldata[4..])?))
+    }
+
     fn get_from_signature(&self, signature: Selector) -> Result<&Function, AbiError> {
         Ok(self
             .methods
@@ -310,6 +357,25 @@ mod tests {
         assert_eq!(amount, amount2);
     }
 
+    #[test]
+    fn rejects_mismatched_function_inputs() {
+        let abi = BaseContract::from(
+            parse_abi(&[
+                "function approve(address _spender, uint256 value) external returns (bool)",
+            ])
+            .unwrap(),
+        );
+
+        let spender = "7a250d5630b4cf539739df2c5dacb4c659f2488d".parse::<Address>().unwrap();
+
+        // arguments swapped
+        assert!(abi.encode("approve", (U256::MAX, spender)).is_err());
+        // missing argument
+        assert!(abi.encode("approve", spender).is_err());
+        // unknown function
+        assert!(abi.encode("transfer", (spender, U256::MAX)).is_err());
+    }
+
     #[test]
     fn can_parse_events() {
         let abi = BaseContract::from(
@@ -338,4 +404,43 @@ mod tests {
         assert_eq!(owner, "e4e60fdf9bf188fa57b7a5022230363d5bd56d08".parse::<Address>().unwrap());
         assert_eq!(spender, "7a250d5630b4cf539739df2c5dacb4c659f2488d".parse::<Address>().unwrap());
     }
+
+    #[test]
+    fn decodes_input_of_overloaded_functions() {
+        let abi = BaseContract::from(
+            parse_abi(&[
+                "function safeTransferFrom(address from, address to, uint256 id)",
+                "function safeTransferFrom(address from, address to, uint256 id, bytes data)",
+                "function approve(address spender, uint256 value) returns (bool)",
+            ])
+            .unwrap(),
+        );
+        let (from, to) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let function = &abi.abi().functions["safeTransferFrom"][1];
+        let input = encode_function_data(function, (from, to, U256::from(7), Bytes::from(vec![1])))
+            .unwrap();
+        let (decoded, args) = abi.decode_input(&input).unwrap();
+        assert_eq!(decoded.abi_signature(), "safeTransferFrom(address,address,uint256,bytes)");
+        assert_eq!(
+            args,
+            vec![
+                Token::Address(from),
+                Token::Address(to),
+                Token::Uint(7.into()),
+                Token::Bytes(vec![1])
+            ]
+        );
+
+        let function = &abi.abi().functions["safeTransferFrom"][0];
+        let input = encode_function_data(function, (from, to, U256::from(7))).unwrap();
+        let (decoded, args) = abi.decode_input(&input).unwrap();
+        assert_eq!(decoded.abi_signature(), "safeTransferFrom(address,address,uint256)");
+        assert_eq!(args.len(), 3);
+
+        // unknown selector, truncated calldata and missing arguments
+        assert!(abi.decode_input([0xde, 0xad, 0xbe, 0xef]).is_err());
+        assert!(abi.decode_input([0x09, 0x5e]).is_err());
+        assert!(abi.decode_input(&input[..36]).is_err());
+    }
 }
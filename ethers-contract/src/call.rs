@@ -1,17 +1,21 @@
 #![allow(clippy::return_self_not_must_use)]
 
-use super::base::{decode_function_data, AbiError};
+use super::{
+    base::{decode_function_data, AbiError},
+    revert::{DecodedError, RevertDecoder},
+};
 use ethers_core::{
     abi::{AbiDecode, AbiEncode, Detokenize, Function, InvalidOutputType, Tokenizable},
     types::{
-        transaction::eip2718::TypedTransaction, Address, BlockId, Bytes, Selector,
+        transaction::eip2718::TypedTransaction, Address, BlockId, Bytes, CallProfile,
+        Eip1559TransactionRequest, GethDebugTracingOptions, GethTrace, Selector,
         TransactionRequest, U256,
     },
     utils::id,
 };
 use ethers_providers::{
     call_raw::{CallBuilder, RawCall},
-    Middleware, PendingTransaction, ProviderError,
+    JsonRpcError, Middleware, PendingTransaction, ProviderError,
 };
 
 use std::{borrow::Cow, fmt::Debug, future::Future, marker::PhantomData, sync::Arc};
@@ -55,15 +59,51 @@ pub enum ContractError<M: Middleware> {
     #[error("{0}")]
     ProviderError(ProviderError),
 
+    /// Thrown when a call reverts, containing the revert data decoded with the contract's known
+    /// errors
+    #[error("contract call reverted: {0}")]
+    Revert(DecodedError),
+
     /// Thrown during deployment if a constructor argument was passed in the `deploy`
     /// call but a constructor was not present in the ABI
     #[error("constructor is not defined in the ABI")]
     ConstructorError,
 
+    /// Thrown when filtering on an event parameter which is not indexed, or with a value of the
+    /// wrong type
+    #[error("invalid topic filter: {0}")]
+    InvalidTopic(String),
+
+    /// Thrown when deploying a contract whose bytecode still contains placeholders for the
+    /// addresses of libraries
+    #[error("bytecode contains unlinked library placeholders: {}", .0.join(", "))]
+    UnlinkedLibraries(Vec<String>),
+
     /// Thrown if a contract address is not found in the deployment transaction's
     /// receipt
     #[error("Contract was not deployed")]
     ContractNotDeployed,
+
+    /// Thrown if an etherscan request fails
+    #[cfg(feature = "etherscan")]
+    #[error(transparent)]
+    EtherscanError(ethers_etherscan::errors::EtherscanError),
+
+    /// Thrown if etherscan rejects the source code submitted for verification
+    #[cfg(feature = "etherscan")]
+    #[error("contract verification failed: {0}")]
+    VerificationFailed(String),
+}
+
+impl<M: Middleware> ContractError<M> {
+    /// Decodes the revert data of a failed call into `E`, e.g. the errors enum generated by
+    /// `abigen!`
+    pub fn decode_revert<E: AbiDecode>(&self) -> Option<E> {
+        match self {
+            ContractError::Revert(err) => err.decode_as(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -76,7 +116,12 @@ pub struct ContractCall<M, D> {
     pub function: Function,
     /// Optional block number to be used when calculating the transaction's gas and nonce
     pub block: Option<BlockId>,
+    /// The percentage gas estimates are scaled by, see [`ContractCall::gas_estimate_multiplier`]
+    pub(crate) gas_estimate_multiplier: Option<u64>,
+    /// The minimum gas estimate, see [`ContractCall::gas_estimate_floor`]
+    pub(crate) gas_estimate_floor: Option<U256>,
     pub(crate) client: Arc<M>,
+    pub(crate) revert_decoder: Arc<RevertDecoder>,
     pub(crate) datatype: PhantomData<D>,
 }
 
@@ -86,7 +131,10 @@ impl<M, D> Clone for ContractCall<M, D> {
             tx: self.tx.clone(),
             function: self.function.clone(),
             block: self.block,
+            gas_estimate_multiplier: self.gas_estimate_multiplier,
+            gas_estimate_floor: self.gas_estimate_floor,
             client: self.client.clone(),
+            revert_decoder: self.revert_decoder.clone(),
             datatype: self.datatype,
         }
     }
@@ -100,6 +148,9 @@ impl<M, D: Detokenize> ContractCall<M, D> {
     }
 
     /// Uses a Legacy transaction instead of an EIP-1559 one to execute the call
+    ///
+    /// This is required on networks which don't support EIP-1559. A previously set
+    /// `max_fee_per_gas` is used as the gas price.
     pub fn legacy(mut self) -> Self {
         self.tx = match self.tx {
             TypedTransaction::Eip1559(inner) => {
@@ -125,6 +176,25 @@ impl<M, D: Detokenize> ContractCall<M, D> {
         self
     }
 
+    /// Sets the `max_fee_per_gas` field in the transaction to the provided value
+    ///
+    /// This turns the transaction into an EIP-1559 one if it isn't already.
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.tx = Eip1559TransactionRequest::from(self.tx).max_fee_per_gas(max_fee_per_gas).into();
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the transaction to the provided value
+    ///
+    /// This turns the transaction into an EIP-1559 one if it isn't already, with the gas price
+    /// as max fee per gas.
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.tx = Eip1559TransactionRequest::from(self.tx)
+            .max_priority_fee_per_gas(max_priority_fee_per_gas)
+            .into();
+        self
+    }
+
     /// Sets the `value` field in the transaction to the provided value
     pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
         self.tx.set_value(value);
@@ -136,6 +206,32 @@ impl<M, D: Detokenize> ContractCall<M, D> {
         self.block = Some(block.into());
         self
     }
+
+    /// Scales the gas estimate by `percent`, e.g. `120` adds a 20% buffer, to avoid running out
+    /// of gas when the gas used depends on state which changes until the transaction is mined.
+    ///
+    /// This applies to [`ContractCall::estimate_gas`] and to the gas limit estimated by
+    /// [`ContractCall::send`] if the `gas` field is not set.
+    ///
+    /// The estimate is taken from the client, so if the client is a `SignerMiddleware` with its
+    /// own gas estimate multiplier both buffers compound, e.g. `120` on both scales the estimate
+    /// by 144%.
+    ///
+    /// # Panics
+    ///
+    /// If `percent` is below `100`, which would make the transaction run out of gas.
+    pub fn gas_estimate_multiplier(mut self, percent: u64) -> Self {
+        assert!(percent >= 100, "gas estimate multiplier must be at least 100%");
+        self.gas_estimate_multiplier = Some(percent);
+        self
+    }
+
+    /// Sets the minimum gas estimate, which applies after the
+    /// [`ContractCall::gas_estimate_multiplier`]
+    pub fn gas_estimate_floor<T: Into<U256>>(mut self, floor: T) -> Self {
+        self.gas_estimate_floor = Some(floor.into());
+        self
+    }
 }
 
 impl<M, D> ContractCall<M, D>
@@ -148,9 +244,17 @@ where
         self.tx.data().cloned()
     }
 
-    /// Returns the estimated gas cost for the underlying transaction to be executed
+    /// Returns the estimated gas cost for the underlying transaction to be executed, scaled by the
+    /// [`ContractCall::gas_estimate_multiplier`] and raised to the
+    /// [`ContractCall::gas_estimate_floor`], if set
     pub async fn estimate_gas(&self) -> Result<U256, ContractError<M>> {
-        self.client.estimate_gas(&self.tx).await.map_err(ContractError::MiddlewareError)
+        let estimate =
+            self.client.estimate_gas(&self.tx).await.map_err(ContractError::MiddlewareError)?;
+        let estimate = match self.gas_estimate_multiplier {
+            Some(percent) => estimate * percent / 100,
+            None => estimate,
+        };
+        Ok(self.gas_estimate_floor.map_or(estimate, |floor| estimate.max(floor)))
     }
 
     /// Queries the blockchain via an `eth_call` for the provided transaction.
@@ -161,10 +265,15 @@ where
     /// If executed on a mutating smart contract function, it will do a "dry run" of the call
     /// and return the return type of the transaction without mutating the state
     ///
+    /// If the call reverts with revert data, it fails with [`ContractError::Revert`], decoding the
+    /// data with the custom errors registered for the contract.
+    ///
     /// Note: this function _does not_ send a transaction from your account
     pub async fn call(&self) -> Result<D, ContractError<M>> {
-        let bytes =
-            self.client.call(&self.tx, self.block).await.map_err(ContractError::MiddlewareError)?;
+        let bytes = self.client.call(&self.tx, self.block).await.map_err(|err| {
+            self.decode_revert(self.client.as_error_response(&err))
+                .unwrap_or(ContractError::MiddlewareError(err))
+        })?;
 
         // decode output
         let data = decode_function_data(&self.function, &bytes, false)?;
@@ -183,7 +292,10 @@ where
     ) -> impl RawCall<'_> + Future<Output = Result<D, ContractError<M>>> + Debug {
         let call = self.call_raw_bytes();
         call.map(move |res: Result<Bytes, ProviderError>| {
-            let bytes = res.map_err(ContractError::ProviderError)?;
+            let bytes = res.map_err(|err| {
+                self.decode_revert(err.as_error_response())
+                    .unwrap_or(ContractError::ProviderError(err))
+            })?;
             decode_function_data(&self.function, &bytes, false).map_err(From::from)
         })
     }
@@ -203,11 +315,38 @@ where
         }
     }
 
+    /// Simulates the call with `debug_traceCall` and geth's call tracer, returning the tree of all
+    /// sub-calls annotated with the gas used by each call frame.
+    ///
+    /// Note: this requires a node which supports the `debug` namespace
+    pub async fn profile(&self) -> Result<CallProfile, ContractError<M>> {
+        let trace = self
+            .client
+            .debug_trace_call(self.tx.clone(), self.block, GethDebugTracingOptions::call_tracer())
+            .await
+            .map_err(ContractError::MiddlewareError)?;
+        match trace {
+            GethTrace::CallTracer(frame) => Ok(frame.into()),
+            trace => Err(ContractError::ProviderError(ProviderError::CustomError(format!(
+                "expected a call tracer trace, got {:?}",
+                trace
+            )))),
+        }
+    }
+
+    /// Returns the decoded revert error if the node responded to a failed call with revert data
+    fn decode_revert(&self, err: Option<&JsonRpcError>) -> Option<ContractError<M>> {
+        self.revert_decoder.decode_error(err?).map(ContractError::Revert)
+    }
+
     /// Signs and broadcasts the provided transaction
     pub async fn send(&self) -> Result<PendingTransaction<'_, M::Provider>, ContractError<M>> {
-        self.client
-            .send_transaction(self.tx.clone(), self.block)
-            .await
-            .map_err(ContractError::MiddlewareError)
+        let mut tx = self.tx.clone();
+        let buffered = self.gas_estimate_multiplier.is_some() || self.gas_estimate_floor.is_some();
+        if buffered && tx.gas().is_none() {
+            tx.set_gas(self.estimate_gas().await?);
+        }
+
+        self.client.send_transaction(tx, self.block).await.map_err(ContractError::MiddlewareError)
     }
 }
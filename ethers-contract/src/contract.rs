@@ -2,11 +2,12 @@ use crate::{
     base::{encode_function_data, AbiError, BaseContract},
     call::ContractCall,
     event::{EthEvent, Event},
+    revert::RevertDecoder,
     EthLogDecode,
 };
 
 use ethers_core::{
-    abi::{Abi, Detokenize, Error, EventExt, Function, Tokenize},
+    abi::{ethabi, Abi, Detokenize, Error, EventExt, Function, FunctionExt, Token, Tokenize},
     types::{Address, Filter, NameOrAddress, Selector, ValueOrArray},
 };
 
@@ -155,6 +156,7 @@ pub struct Contract<M> {
     base_contract: BaseContract,
     client: Arc<M>,
     address: Address,
+    revert_decoder: Arc<RevertDecoder>,
 }
 
 impl<M> Clone for Contract<M> {
@@ -163,6 +165,7 @@ impl<M> Clone for Contract<M> {
             base_contract: self.base_contract.clone(),
             client: self.client.clone(),
             address: self.address,
+            revert_decoder: self.revert_decoder.clone(),
         }
     }
 }
@@ -170,7 +173,42 @@ impl<M> Clone for Contract<M> {
 impl<M: Middleware> Contract<M> {
     /// Creates a new contract from the provided client, abi and address
     pub fn new(address: Address, abi: impl Into<BaseContract>, client: impl Into<Arc<M>>) -> Self {
-        Self { base_contract: abi.into(), client: client.into(), address }
+        let base_contract = abi.into();
+        let revert_decoder = Arc::new(RevertDecoder::from_abi(&base_contract.abi));
+        Self { base_contract, client: client.into(), address, revert_decoder }
+    }
+
+    /// Creates a new contract at `address` with the ABI of its verified source code, fetched
+    /// from etherscan
+    #[cfg(feature = "etherscan")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "etherscan")))]
+    pub async fn from_etherscan(
+        address: Address,
+        etherscan: &ethers_etherscan::Client,
+        client: impl Into<Arc<M>>,
+    ) -> Result<Self, crate::ContractError<M>> {
+        let abi =
+            etherscan.contract_abi(address).await.map_err(crate::ContractError::EtherscanError)?;
+        Ok(Self::new(address, abi, client))
+    }
+
+    /// Registers additional custom errors which calls of the contract may revert with, e.g. errors
+    /// of libraries or other contracts it calls into.
+    ///
+    /// The errors of the contract's ABI are registered by default. A call reverting with one of
+    /// the registered errors fails with a decoded [`ContractError::Revert`].
+    ///
+    /// [`ContractError::Revert`]: crate::ContractError::Revert
+    #[must_use]
+    pub fn with_errors(mut self, errors: impl IntoIterator<Item = ethabi::AbiError>) -> Self {
+        let decoder = Arc::make_mut(&mut self.revert_decoder);
+        errors.into_iter().for_each(|error| decoder.register(error));
+        self
+    }
+
+    /// Returns the decoder used for the revert data of failed calls of the contract
+    pub fn revert_decoder(&self) -> &RevertDecoder {
+        &self.revert_decoder
     }
 
     /// Returns an [`Event`](crate::builders::Event) builder for the provided event.
@@ -194,9 +232,30 @@ impl<M: Middleware> Contract<M> {
         Ok(self.event_with_filter(Filter::new().event(&event.abi_signature())))
     }
 
+    /// Decodes the calldata of a transaction calling one of the contract's functions, returning
+    /// the called function and its decoded arguments.
+    ///
+    /// See [`BaseContract::decode_input`]
+    pub fn decode_input<T: AsRef<[u8]>>(
+        &self,
+        calldata: T,
+    ) -> Result<(&Function, Vec<Token>), AbiError> {
+        self.base_contract.decode_input(calldata)
+    }
+
+    /// Returns the signatures of the contract's functions, e.g. `transfer(address,uint256)`,
+    /// sorted by name. Overloaded functions are listed once per overload.
+    pub fn methods(&self) -> Vec<String> {
+        self.base_contract.abi.functions().map(|function| function.abi_signature()).collect()
+    }
+
     /// Returns a transaction builder for the provided function name. If there are
     /// multiple functions with the same name due to overloading, consider using
     /// the `method_hash` method instead, since this will use the first match.
+    ///
+    /// The arguments can also be passed as a slice of [`Token`](ethers_core::abi::Token)s, and the
+    /// output decoded into a `Token`, which allows calling contracts whose ABI is only known at
+    /// runtime.
     pub fn method<T: Tokenize, D: Detokenize>(
         &self,
         name: &str,
@@ -249,7 +308,10 @@ impl<M: Middleware> Contract<M> {
             tx,
             client: Arc::clone(&self.client), // cheap clone behind the Arc
             block: None,
+            gas_estimate_multiplier: None,
+            gas_estimate_floor: None,
             function: function.to_owned(),
+            revert_decoder: self.revert_decoder.clone(),
             datatype: PhantomData,
         })
     }
@@ -275,7 +337,12 @@ impl<M: Middleware> Contract<M> {
     where
         N: Clone,
     {
-        Contract { base_contract: self.base_contract.clone(), client, address: self.address }
+        Contract {
+            base_contract: self.base_contract.clone(),
+            client,
+            address: self.address,
+            revert_decoder: self.revert_decoder.clone(),
+        }
     }
 
     /// Returns the contract's address
@@ -300,3 +367,154 @@ impl<M: Middleware> std::ops::Deref for Contract<M> {
         &self.base_contract
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::{Bytes, U256},
+    };
+    use ethers_providers::Provider;
+
+    const ABI: &str = r#"[
+        {"type":"function","name":"swap","stateMutability":"view",
+         "inputs":[{"name":"order","type":"tuple","components":[
+             {"name":"amount","type":"uint256"},{"name":"path","type":"address[]"}]}],
+         "outputs":[{"name":"","type":"uint256"},{"name":"","type":"bool"}]},
+        {"type":"function","name":"balanceOf","stateMutability":"view",
+         "inputs":[{"name":"owner","type":"address"}],
+         "outputs":[{"name":"","type":"uint256"}]}
+    ]"#;
+
+    #[tokio::test]
+    async fn calls_functions_of_runtime_abi() {
+        let abi: Abi = serde_json::from_str(ABI).unwrap();
+        let (provider, mock) = Provider::mocked();
+        let contract = Contract::new(Address::repeat_byte(1), abi, provider);
+
+        assert_eq!(contract.methods(), vec!["balanceOf(address)", "swap((uint256,address[]))"]);
+
+        let order = Token::Tuple(vec![
+            Token::Uint(7u64.into()),
+            Token::Array(vec![
+                Token::Address(Address::repeat_byte(2)),
+                Token::Address(Address::repeat_byte(3)),
+            ]),
+        ]);
+        let call = contract.method::<_, Token>("swap", &[order.clone()][..]).unwrap();
+        let function = contract.abi().function("swap").unwrap();
+        assert_eq!(call.calldata().unwrap(), Bytes::from(function.encode_input(&[order]).unwrap()));
+
+        let output = abi::encode(&[Token::Uint(42u64.into()), Token::Bool(true)]);
+        mock.push::<Bytes, _>(output.into()).unwrap();
+        assert_eq!(
+            call.call().await.unwrap(),
+            Token::Tuple(vec![Token::Uint(42u64.into()), Token::Bool(true)])
+        );
+
+        let output = abi::encode(&[Token::Uint(5u64.into())]);
+        mock.push::<Bytes, _>(output.into()).unwrap();
+        let balance: U256 =
+            contract.method("balanceOf", Address::repeat_byte(2)).unwrap().call().await.unwrap();
+        assert_eq!(balance, 5u64.into());
+    }
+
+    #[test]
+    fn sets_transaction_fees() {
+        let abi: Abi = serde_json::from_str(ABI).unwrap();
+        let (provider, _) = Provider::mocked();
+        let contract = Contract::new(Address::repeat_byte(1), abi, provider);
+        let call = contract.method::<_, U256>("balanceOf", Address::repeat_byte(2)).unwrap();
+
+        let legacy = call.clone().legacy().gas_price(7u64);
+        let tx = legacy.tx.as_legacy_ref().unwrap();
+        assert_eq!(tx.gas_price, Some(7u64.into()));
+
+        // 1559 fees turn legacy calls into 1559 ones
+        let eip1559 = legacy.max_fee_per_gas(10u64).max_priority_fee_per_gas(2u64);
+        let tx = eip1559.tx.as_eip1559_ref().unwrap();
+        assert_eq!(tx.max_fee_per_gas, Some(10u64.into()));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(2u64.into()));
+        assert_eq!(tx.to, Some(Address::repeat_byte(1).into()));
+        assert_eq!(tx.data, call.calldata());
+
+        // the gas price is kept as max fee
+        let priced = call.clone().legacy().gas_price(7u64).max_priority_fee_per_gas(2u64);
+        let tx = priced.tx.as_eip1559_ref().unwrap();
+        assert_eq!(tx.max_fee_per_gas, Some(7u64.into()));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(2u64.into()));
+
+        // and the max fee becomes the gas price of legacy ones
+        let legacy = eip1559.legacy();
+        assert_eq!(legacy.tx.as_legacy_ref().unwrap().gas_price, Some(10u64.into()));
+    }
+
+    #[tokio::test]
+    async fn buffers_gas_estimates() {
+        let abi: Abi = serde_json::from_str(ABI).unwrap();
+        let (provider, mock) = Provider::mocked();
+        let contract = Contract::new(Address::repeat_byte(1), abi, provider);
+        let call = contract.method::<_, U256>("balanceOf", Address::repeat_byte(2)).unwrap();
+
+        mock.push(U256::from(50_000u64)).unwrap();
+        assert_eq!(call.estimate_gas().await.unwrap(), 50_000u64.into());
+
+        let call = call.gas_estimate_multiplier(120);
+        mock.push(U256::from(50_000u64)).unwrap();
+        assert_eq!(call.estimate_gas().await.unwrap(), 60_000u64.into());
+
+        let call = call.gas_estimate_floor(100_000u64);
+        mock.push(U256::from(50_000u64)).unwrap();
+        assert_eq!(call.estimate_gas().await.unwrap(), 100_000u64.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 100%")]
+    fn rejects_gas_estimate_multipliers_below_100() {
+        let abi: Abi = serde_json::from_str(ABI).unwrap();
+        let (provider, _) = Provider::mocked();
+        let contract = Contract::new(Address::repeat_byte(1), abi, provider);
+        let call = contract.method::<_, U256>("balanceOf", Address::repeat_byte(2)).unwrap();
+        let _ = call.gas_estimate_multiplier(90);
+    }
+
+    #[tokio::test]
+    async fn profiles_calls() {
+        let abi: Abi = serde_json::from_str(ABI).unwrap();
+        let (provider, mock) = Provider::mocked();
+        let contract = Contract::new(Address::repeat_byte(1), abi, provider);
+
+        let trace = serde_json::json!({
+            "type": "CALL",
+            "from": Address::zero(),
+            "to": Address::repeat_byte(1),
+            "gas": "0x30d40",
+            "gasUsed": "0x7530",
+            "input": "0x70a08231",
+            "calls": [{
+                "type": "STATICCALL",
+                "from": Address::repeat_byte(1),
+                "to": Address::repeat_byte(2),
+                "gas": "0x2710",
+                "gasUsed": "0x1388",
+                "input": "0x"
+            }]
+        });
+        mock.push(trace).unwrap();
+
+        let call =
+            contract.method::<_, U256>("balanceOf", Address::repeat_byte(2)).unwrap().legacy();
+        let profile = call.profile().await.unwrap();
+        assert_eq!(profile.gas_used, 30000u64.into());
+        assert_eq!(profile.self_gas_used, 25000u64.into());
+        assert_eq!(profile.calls[0].to, Some(Address::repeat_byte(2)));
+
+        let tx = serde_json::to_value(&call.tx).unwrap();
+        mock.assert_request(
+            "debug_traceCall",
+            [tx, serde_json::json!("latest"), serde_json::json!({"tracer": "callTracer"})],
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,179 @@
+//! Detection of the interfaces a contract implements via [EIP-165](https://eips.ethereum.org/EIPS/eip-165)
+
+use crate::ContractError;
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Address, Bytes, Selector, TransactionRequest,
+};
+use ethers_providers::{JsonRpcError, Middleware};
+
+/// The interface id of EIP-165 itself, i.e. the selector of `supportsInterface(bytes4)`
+pub const INTERFACE_ID_ERC165: Selector = [0x01, 0xff, 0xc9, 0xa7];
+
+/// The interface id which must not be supported by any EIP-165 compliant contract
+pub const INTERFACE_ID_INVALID: Selector = [0xff, 0xff, 0xff, 0xff];
+
+/// The interface id of ERC-20, which is only reported by the few ERC-20 tokens implementing EIP-165
+pub const INTERFACE_ID_ERC20: Selector = [0x36, 0x37, 0x2b, 0x07];
+
+/// The interface id of ERC-721
+pub const INTERFACE_ID_ERC721: Selector = [0x80, 0xac, 0x58, 0xcd];
+
+/// The interface id of the ERC-721 metadata extension
+pub const INTERFACE_ID_ERC721_METADATA: Selector = [0x5b, 0x5e, 0x13, 0x9f];
+
+/// The interface id of the ERC-721 enumeration extension
+pub const INTERFACE_ID_ERC721_ENUMERABLE: Selector = [0x78, 0x0e, 0x9d, 0x63];
+
+/// The interface id of ERC-1155
+pub const INTERFACE_ID_ERC1155: Selector = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// The interface id of the ERC-1155 metadata URI extension
+pub const INTERFACE_ID_ERC1155_METADATA_URI: Selector = [0x0e, 0x89, 0x34, 0x1c];
+
+/// The interface id of the ERC-2981 royalty standard
+pub const INTERFACE_ID_ERC2981: Selector = [0x2a, 0x55, 0x20, 0x5a];
+
+/// The gas limit of `supportsInterface` calls mandated by EIP-165
+const SUPPORTS_INTERFACE_GAS: u64 = 30_000;
+
+/// Error messages of nodes for calls which failed during execution, other than reverts
+const EXECUTION_ERRORS: &[&str] =
+    &["invalid opcode", "out of gas", "stack underflow", "invalid jump"];
+
+/// Returns whether the contract at `address` implements EIP-165.
+///
+/// This follows the detection procedure of the EIP: the contract must report support for
+/// [INTERFACE_ID_ERC165], but not for [INTERFACE_ID_INVALID]. Contracts which revert, return no
+/// data or return anything else than a `bool` don't implement EIP-165. Only failed requests result
+/// in an error.
+pub async fn supports_erc165<M: Middleware>(
+    client: &M,
+    address: Address,
+) -> Result<bool, ContractError<M>> {
+    Ok(call_supports_interface(client, address, INTERFACE_ID_ERC165).await? &&
+        !call_supports_interface(client, address, INTERFACE_ID_INVALID).await?)
+}
+
+/// Returns whether the contract at `address` implements the interface with the given id.
+///
+/// Contracts which don't implement EIP-165 are reported to not support the interface, see
+/// [supports_erc165].
+///
+/// # Example
+///
+/// ```no_run
+/// # use ethers_providers::{Provider, Http};
+/// # use ethers_core::types::Address;
+/// use ethers_contract::eip165::{supports_interface, INTERFACE_ID_ERC1155, INTERFACE_ID_ERC721};
+/// # async fn foo(provider: Provider<Http>) -> Result<(), Box<dyn std::error::Error>> {
+/// let token: Address = "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d".parse()?;
+/// if supports_interface(&provider, token, INTERFACE_ID_ERC721).await? {
+///     println!("ERC-721")
+/// } else if supports_interface(&provider, token, INTERFACE_ID_ERC1155).await? {
+///     println!("ERC-1155")
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn supports_interface<M: Middleware>(
+    client: &M,
+    address: Address,
+    interface_id: Selector,
+) -> Result<bool, ContractError<M>> {
+    if interface_id == INTERFACE_ID_INVALID || !supports_erc165(client, address).await? {
+        return Ok(false)
+    }
+    call_supports_interface(client, address, interface_id).await
+}
+
+/// Calls `supportsInterface(interface_id)`, treating failed executions as `false`
+async fn call_supports_interface<M: Middleware>(
+    client: &M,
+    address: Address,
+    interface_id: Selector,
+) -> Result<bool, ContractError<M>> {
+    let mut data = INTERFACE_ID_ERC165.to_vec();
+    data.extend_from_slice(&interface_id);
+    data.extend_from_slice(&[0; 28]);
+    let tx: TypedTransaction =
+        TransactionRequest::new().to(address).data(data).gas(SUPPORTS_INTERFACE_GAS).into();
+
+    match client.call(&tx, None).await {
+        Ok(output) => Ok(decode_bool(&output)),
+        Err(err) if client.as_error_response(&err).map_or(false, is_execution_error) => Ok(false),
+        Err(err) => Err(ContractError::MiddlewareError(err)),
+    }
+}
+
+/// Returns true only if the output is an ABI encoded `true`
+fn decode_bool(output: &Bytes) -> bool {
+    output.len() >= 32 && output[..31].iter().all(|b| *b == 0) && output[31] == 1
+}
+
+/// Returns whether the node responded with an error because the call failed during execution
+fn is_execution_error(err: &JsonRpcError) -> bool {
+    if err.is_revert() {
+        return true
+    }
+    let message = err.message.to_lowercase();
+    EXECUTION_ERRORS.iter().any(|msg| message.contains(msg))
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_providers::Provider;
+
+    fn encoded_bool(value: bool) -> Bytes {
+        let mut output = vec![0; 32];
+        output[31] = value as u8;
+        output.into()
+    }
+
+    #[tokio::test]
+    async fn detects_interfaces() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+
+        // responses are returned last in, first out
+        mock.push::<Bytes, _>(encoded_bool(true)).unwrap();
+        mock.push::<Bytes, _>(encoded_bool(false)).unwrap();
+        mock.push::<Bytes, _>(encoded_bool(true)).unwrap();
+        assert!(supports_interface(&provider, address, INTERFACE_ID_ERC721).await.unwrap());
+
+        mock.push::<Bytes, _>(encoded_bool(false)).unwrap();
+        mock.push::<Bytes, _>(encoded_bool(false)).unwrap();
+        mock.push::<Bytes, _>(encoded_bool(true)).unwrap();
+        assert!(!supports_interface(&provider, address, INTERFACE_ID_ERC1155).await.unwrap());
+
+        // contracts which claim to support every interface don't implement EIP-165
+        mock.push::<Bytes, _>(encoded_bool(true)).unwrap();
+        mock.push::<Bytes, _>(encoded_bool(true)).unwrap();
+        assert!(!supports_interface(&provider, address, INTERFACE_ID_ERC721).await.unwrap());
+        assert!(!supports_interface(&provider, address, INTERFACE_ID_INVALID).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn handles_contracts_without_eip165() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+
+        // no code or a fallback function which doesn't return anything
+        mock.push::<Bytes, _>(Bytes::default()).unwrap();
+        assert!(!supports_erc165(&provider, address).await.unwrap());
+
+        // garbage
+        mock.push::<Bytes, _>(Bytes::from(vec![0xff; 32])).unwrap();
+        assert!(!supports_erc165(&provider, address).await.unwrap());
+
+        mock.push_error(3, "execution reverted");
+        assert!(!supports_interface(&provider, address, INTERFACE_ID_ERC721).await.unwrap());
+
+        mock.push_error(-32000, "VM Exception while processing transaction: invalid opcode");
+        assert!(!supports_erc165(&provider, address).await.unwrap());
+
+        // other errors are surfaced
+        mock.push_error(-32603, "connection lost");
+        assert!(supports_erc165(&provider, address).await.is_err());
+    }
+}
@@ -0,0 +1,308 @@
+//! Bindings for [ERC-20](https://eips.ethereum.org/EIPS/eip-20) tokens
+
+use crate::{
+    builders::{ContractCall, Event},
+    Contract, EthEvent, EthLogDecode, Lazy,
+};
+use ethers_core::{
+    abi::{self, Abi, InvalidOutputType, ParamType, RawLog, Token, Tokenizable},
+    types::{Address, H256, U256},
+    utils::keccak256,
+};
+use ethers_providers::Middleware;
+use std::{borrow::Cow, fmt, ops::Deref, sync::Arc};
+
+/// The ABI of the functions and events every ERC-20 token implements
+pub static ERC20_ABI: Lazy<Abi> = Lazy::new(|| {
+    serde_json::from_str(
+        r#"[
+    {"type":"function","name":"name","inputs":[],"outputs":[{"name":"","type":"string"}],"stateMutability":"view"},
+    {"type":"function","name":"symbol","inputs":[],"outputs":[{"name":"","type":"string"}],"stateMutability":"view"},
+    {"type":"function","name":"decimals","inputs":[],"outputs":[{"name":"","type":"uint8"}],"stateMutability":"view"},
+    {"type":"function","name":"totalSupply","inputs":[],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"},
+    {"type":"function","name":"balanceOf","inputs":[{"name":"owner","type":"address"}],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"},
+    {"type":"function","name":"allowance","inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"},
+    {"type":"function","name":"transfer","inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable"},
+    {"type":"function","name":"transferFrom","inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable"},
+    {"type":"function","name":"approve","inputs":[{"name":"spender","type":"address"},{"name":"value","type":"uint256"}],"outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable"},
+    {"type":"event","name":"Transfer","inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"value","type":"uint256","indexed":false}],"anonymous":false},
+    {"type":"event","name":"Approval","inputs":[{"name":"owner","type":"address","indexed":true},{"name":"spender","type":"address","indexed":true},{"name":"value","type":"uint256","indexed":false}],"anonymous":false}
+]"#,
+    )
+    .expect("invalid abi")
+});
+
+/// An ERC-20 token contract
+///
+/// # Example
+///
+/// ```no_run
+/// # use ethers_providers::{Provider, Http};
+/// # use ethers_core::types::Address;
+/// use ethers_contract::erc20::Erc20;
+/// # async fn foo(provider: Provider<Http>) -> Result<(), Box<dyn std::error::Error>> {
+/// let dai: Address = "0x6b175474e89094c44da98b954eedeac495271d0f".parse()?;
+/// let owner: Address = "0x5d3a536e4d6dbd6114cc1ead35777bab948e3643".parse()?;
+///
+/// let dai = Erc20::new(dai, provider);
+/// let balance = dai.balance_of(owner).call().await?;
+/// println!("{} {}", balance, dai.symbol().call().await?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Erc20<M>(Contract<M>);
+
+impl<M> Clone for Erc20<M> {
+    fn clone(&self) -> Self {
+        Erc20(self.0.clone())
+    }
+}
+
+impl<M> Deref for Erc20<M> {
+    type Target = Contract<M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M: Middleware> fmt::Debug for Erc20<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Erc20").field(&self.address()).finish()
+    }
+}
+
+impl<M: Middleware> Erc20<M> {
+    /// Creates a new instance of the token at `address`
+    pub fn new<T: Into<Address>, C: Into<Arc<M>>>(address: T, client: C) -> Self {
+        Self(Contract::new(address.into(), ERC20_ABI.clone(), client.into()))
+    }
+
+    /// Calls the token's `name` function
+    pub fn name(&self) -> ContractCall<M, String> {
+        self.function_call("name", ())
+    }
+
+    /// Calls the token's `symbol` function
+    pub fn symbol(&self) -> ContractCall<M, String> {
+        self.function_call("symbol", ())
+    }
+
+    /// Calls the token's `decimals` function
+    pub fn decimals(&self) -> ContractCall<M, u8> {
+        self.function_call("decimals", ())
+    }
+
+    /// Calls the token's `totalSupply` function
+    pub fn total_supply(&self) -> ContractCall<M, U256> {
+        self.function_call("totalSupply", ())
+    }
+
+    /// Calls the token's `balanceOf` function
+    pub fn balance_of(&self, owner: Address) -> ContractCall<M, U256> {
+        self.function_call("balanceOf", owner)
+    }
+
+    /// Calls the token's `allowance` function
+    pub fn allowance(&self, owner: Address, spender: Address) -> ContractCall<M, U256> {
+        self.function_call("allowance", (owner, spender))
+    }
+
+    /// Calls the token's `transfer` function
+    pub fn transfer(&self, to: Address, value: U256) -> ContractCall<M, bool> {
+        self.function_call("transfer", (to, value))
+    }
+
+    /// Calls the token's `transferFrom` function
+    pub fn transfer_from(&self, from: Address, to: Address, value: U256) -> ContractCall<M, bool> {
+        self.function_call("transferFrom", (from, to, value))
+    }
+
+    /// Calls the token's `approve` function
+    pub fn approve(&self, spender: Address, value: U256) -> ContractCall<M, bool> {
+        self.function_call("approve", (spender, value))
+    }
+
+    /// Returns an [`Event`] builder for the token's `Transfer` event
+    pub fn transfer_filter(&self) -> Event<M, TransferFilter> {
+        self.0.event()
+    }
+
+    /// Returns an [`Event`] builder for the token's `Approval` event
+    pub fn approval_filter(&self) -> Event<M, ApprovalFilter> {
+        self.0.event()
+    }
+
+    /// Returns an [`Event`] builder for all events of the token
+    pub fn events(&self) -> Event<M, Erc20Events> {
+        self.0.event_with_filter(Default::default())
+    }
+
+    fn function_call<T: abi::Tokenize, D: abi::Detokenize>(
+        &self,
+        name: &str,
+        args: T,
+    ) -> ContractCall<M, D> {
+        self.0.method(name, args).expect("method not found (this should never happen)")
+    }
+}
+
+/// The `Transfer(address indexed from, address indexed to, uint256 value)` event
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransferFilter {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+/// The `Approval(address indexed owner, address indexed spender, uint256 value)` event
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApprovalFilter {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+}
+
+/// Implements [`EthEvent`] for an event with two indexed addresses and an amount
+macro_rules! impl_event {
+    ($ty:ident, $name:literal, $first:ident, $second:ident) => {
+        impl Tokenizable for $ty {
+            fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+                let (first, second, value): (Address, Address, U256) =
+                    Tokenizable::from_token(token)?;
+                Ok(Self { $first: first, $second: second, value })
+            }
+
+            fn into_token(self) -> Token {
+                (self.$first, self.$second, self.value).into_token()
+            }
+        }
+
+        impl EthEvent for $ty {
+            fn name() -> Cow<'static, str> {
+                Cow::Borrowed($name)
+            }
+
+            fn signature() -> H256 {
+                H256(keccak256(Self::abi_signature().as_bytes()))
+            }
+
+            fn abi_signature() -> Cow<'static, str> {
+                Cow::Borrowed(concat!($name, "(address,address,uint256)"))
+            }
+
+            fn decode_log(log: &RawLog) -> Result<Self, abi::Error> {
+                let (first, second, value) = decode_log(log, Self::signature())?;
+                Ok(Self { $first: first, $second: second, value })
+            }
+
+            fn is_anonymous() -> bool {
+                false
+            }
+
+            fn indexed_params() -> Vec<ParamType> {
+                vec![ParamType::Address, ParamType::Address]
+            }
+        }
+    };
+}
+
+impl_event!(TransferFilter, "Transfer", from, to);
+impl_event!(ApprovalFilter, "Approval", owner, spender);
+
+/// Decodes the two indexed addresses and the amount of a log with the given signature
+fn decode_log(log: &RawLog, signature: H256) -> Result<(Address, Address, U256), abi::Error> {
+    if log.topics.len() != 3 || log.topics[0] != signature {
+        return Err(abi::Error::InvalidData)
+    }
+    let value = abi::decode(&[ParamType::Uint(256)], &log.data)?
+        .pop()
+        .and_then(Token::into_uint)
+        .ok_or(abi::Error::InvalidData)?;
+    Ok((
+        Address::from_slice(&log.topics[1][12..]),
+        Address::from_slice(&log.topics[2][12..]),
+        value,
+    ))
+}
+
+/// All events of an ERC-20 token
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Erc20Events {
+    TransferFilter(TransferFilter),
+    ApprovalFilter(ApprovalFilter),
+}
+
+impl EthLogDecode for Erc20Events {
+    fn decode_log(log: &RawLog) -> Result<Self, abi::Error> {
+        if let Ok(decoded) = TransferFilter::decode_log(log) {
+            return Ok(Erc20Events::TransferFilter(decoded))
+        }
+        if let Ok(decoded) = ApprovalFilter::decode_log(log) {
+            return Ok(Erc20Events::ApprovalFilter(decoded))
+        }
+        Err(abi::Error::InvalidData)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::Bytes;
+    use ethers_providers::Provider;
+
+    #[test]
+    fn abi_matches_bindings() {
+        for function in ERC20_ABI.functions() {
+            assert!(!function.outputs.is_empty());
+        }
+        assert_eq!(ERC20_ABI.event("Transfer").unwrap().signature(), TransferFilter::signature());
+        assert_eq!(ERC20_ABI.event("Approval").unwrap().signature(), ApprovalFilter::signature());
+        assert_eq!(
+            format!("{:?}", TransferFilter::signature()),
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_token() {
+        let (provider, mock) = Provider::mocked();
+        let token = Address::repeat_byte(1);
+        let owner = Address::repeat_byte(2);
+        let erc20 = Erc20::new(token, provider);
+
+        mock.push::<Bytes, _>(abi::encode(&[Token::Uint(1337u64.into())]).into()).unwrap();
+        assert_eq!(erc20.balance_of(owner).call().await.unwrap(), 1337u64.into());
+
+        let call = erc20.balance_of(owner);
+        let mut data = vec![0x70, 0xa0, 0x82, 0x31];
+        data.extend(abi::encode(&[Token::Address(owner)]));
+        assert_eq!(call.calldata().unwrap(), Bytes::from(data));
+        assert_eq!(call.tx.to(), Some(&token.into()));
+
+        let call = erc20.transfer(owner, 5u64.into());
+        let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        data.extend(abi::encode(&[Token::Address(owner), Token::Uint(5u64.into())]));
+        assert_eq!(call.calldata().unwrap(), Bytes::from(data));
+    }
+
+    #[test]
+    fn decodes_events() {
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+        let log = RawLog {
+            topics: vec![TransferFilter::signature(), H256::from(from), H256::from(to)],
+            data: abi::encode(&[Token::Uint(100u64.into())]),
+        };
+        let transfer = TransferFilter { from, to, value: 100u64.into() };
+        assert_eq!(<TransferFilter as EthEvent>::decode_log(&log).unwrap(), transfer);
+        assert!(<ApprovalFilter as EthEvent>::decode_log(&log).is_err());
+        assert_eq!(
+            <Erc20Events as EthLogDecode>::decode_log(&log).unwrap(),
+            Erc20Events::TransferFilter(transfer)
+        );
+
+        let log = RawLog { topics: vec![ApprovalFilter::signature(), H256::from(from)], ..log };
+        assert!(<Erc20Events as EthLogDecode>::decode_log(&log).is_err());
+    }
+}
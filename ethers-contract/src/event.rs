@@ -1,12 +1,20 @@
 #![allow(clippy::return_self_not_must_use)]
 
-use crate::{log::LogMeta, stream::EventStream, ContractError, EthLogDecode};
+use crate::{
+    log::LogMeta,
+    stream::{EventStream, EventUpdate},
+    ContractError, EthLogDecode,
+};
 use ethers_core::{
-    abi::{Detokenize, RawLog},
-    types::{BlockNumber, Filter, Log, Topic, H256},
+    abi::{encode_topic, Detokenize, ParamType, RawLog, Tokenizable},
+    types::{BlockNumber, Filter, Log, Topic, ValueOrArray, H256},
+};
+use ethers_providers::{FilterWatcher, JsonRpcError, Middleware, PubsubClient, SubscriptionStream};
+use futures_util::{
+    future::{self, Future},
+    stream::{self, Stream, StreamExt},
 };
-use ethers_providers::{FilterWatcher, Middleware, PubsubClient, SubscriptionStream};
-use std::{borrow::Cow, marker::PhantomData};
+use std::{borrow::Cow, collections::VecDeque, marker::PhantomData};
 
 /// A trait for implementing event bindings
 pub trait EthEvent: Detokenize + Send + Sync {
@@ -30,6 +38,13 @@ pub trait EthEvent: Detokenize + Send + Sync {
     /// Returns true if this is an anonymous event
     fn is_anonymous() -> bool;
 
+    /// Returns the types of the indexed parameters of the event, in order.
+    ///
+    /// These are implemented by `#[derive(EthEvent)]` and used to validate topic filters.
+    fn indexed_params() -> Vec<ParamType> {
+        Vec::new()
+    }
+
     /// Returns an Event builder for the ethereum event represented by this types ABI signature.
     fn new<M: Middleware>(filter: Filter, provider: &M) -> Event<M, Self>
     where
@@ -110,6 +125,78 @@ impl<M, D: EthLogDecode> Event<'_, M, D> {
     }
 }
 
+impl<M, D> Event<'_, M, D>
+where
+    M: Middleware,
+    D: EthEvent,
+{
+    /// Filters on the value of the `index`th indexed parameter of the event, which is stored in
+    /// `topic{index + 1}` for non-anonymous events.
+    ///
+    /// The values are ABI encoded into their topic. A log matches if the parameter is equal to any
+    /// of the values, and no values match any log.
+    ///
+    /// Returns an error if the event has no indexed parameter at `index` or a value doesn't match
+    /// its type.
+    ///
+    /// ```no_run
+    /// # async fn test<M:ethers_providers::Middleware>(contract: ethers_contract::Contract<M>) {
+    /// # use ethers_core::types::*;
+    /// # use ethers_contract::EthEvent;
+    /// #[derive(Clone, Debug, EthEvent)]
+    /// pub struct Transfer {
+    ///     #[ethevent(indexed)]
+    ///     pub from: Address,
+    ///     #[ethevent(indexed)]
+    ///     pub to: Address,
+    ///     pub tokens: U256,
+    /// }
+    ///
+    /// let from: Address = "0x7a250d5630b4cf539739df2c5dacb4c659f2488d".parse().unwrap();
+    /// let to: Address = "0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f".parse().unwrap();
+    /// // transfers from `from` to any address
+    /// let event = contract.event::<Transfer>().indexed_topic(0, Some(from)).unwrap();
+    /// // transfers from `from` to `to` or `from`
+    /// let event = event.indexed_topic(1, vec![to, from]).unwrap();
+    /// let logs: Vec<Transfer> = event.query().await.unwrap();
+    /// # }
+    /// ```
+    pub fn indexed_topic<T, I>(mut self, index: usize, values: I) -> Result<Self, ContractError<M>>
+    where
+        T: Tokenizable,
+        I: IntoIterator<Item = T>,
+    {
+        let params = D::indexed_params();
+        let param = params.get(index).ok_or_else(|| {
+            ContractError::InvalidTopic(format!(
+                "event `{}` has no indexed parameter at index {}",
+                D::name(),
+                index
+            ))
+        })?;
+
+        let mut topics = Vec::new();
+        for value in values {
+            let token = value.into_token();
+            if !token.type_check(param) {
+                return Err(ContractError::InvalidTopic(format!(
+                    "value {:?} does not match the type {} of indexed parameter {}",
+                    token, param, index
+                )))
+            }
+            topics.push(Some(encode_topic(&token)));
+        }
+
+        let slot = if D::is_anonymous() { index } else { index + 1 };
+        self.filter.topics[slot] = match topics.len() {
+            0 => None,
+            1 => Some(ValueOrArray::Value(topics.remove(0))),
+            _ => Some(ValueOrArray::Array(topics)),
+        };
+        Ok(self)
+    }
+}
+
 impl<'a, M, D> Event<'a, M, D>
 where
     M: Middleware,
@@ -164,6 +251,136 @@ where
             self.provider.watch(&self.filter).await.map_err(ContractError::MiddlewareError)?;
         Ok(EventStream::new(filter.id, filter, Box::new(move |log| self.parse_log(log))))
     }
+
+    /// Turns this event filter into a `Stream` that only yields events once their block is
+    /// `confirmations` blocks deep, i.e. an event in the latest block has 1 confirmation.
+    ///
+    /// The canonical chain is tracked with a block filter (see [`Middleware::watch_blocks`]). For
+    /// every new block, the logs of the blocks which reached the required confirmations are
+    /// fetched via `eth_getLogs` and yielded as [`EventUpdate::Added`], so no event is delivered
+    /// unless it survived `confirmations` blocks. The stream starts at the filter's `from_block`,
+    /// or at the next block if it isn't set.
+    ///
+    /// Should a reorg still replace blocks with confirmed events, the events are yielded again as
+    /// [`EventUpdate::Removed`] (newest first) and the logs of the new canonical blocks are
+    /// yielded once they are confirmed. Reorgs are detected for the last `2 * confirmations`
+    /// blocks.
+    ///
+    /// ```no_run
+    /// # async fn test<M:ethers_providers::Middleware>(contract: ethers_contract::Contract<M>) {
+    /// # use ethers_core::types::*;
+    /// # use futures_util::stream::StreamExt;
+    /// # use ethers_contract::{stream::EventUpdate, EthEvent};
+    /// #[derive(Clone, Debug, EthEvent)]
+    /// pub struct Transfer {
+    ///     #[ethevent(indexed)]
+    ///     pub from: Address,
+    ///     #[ethevent(indexed)]
+    ///     pub to: Address,
+    ///     pub tokens: U256,
+    /// }
+    ///
+    /// let event = contract.event::<Transfer>();
+    /// let mut transfers = Box::pin(event.stream_with_confirmations(12).await.unwrap());
+    /// while let Some(Ok(update)) = transfers.next().await {
+    ///     match update {
+    ///         EventUpdate::Added(transfer, meta) => { /* apply the transfer */ }
+    ///         EventUpdate::Removed(transfer, meta) => { /* roll the transfer back */ }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn stream_with_confirmations(
+        &'a self,
+        confirmations: u64,
+    ) -> Result<impl Stream<Item = Result<EventUpdate<D>, ContractError<M>>> + 'a, ContractError<M>>
+    {
+        let blocks = self.provider.watch_blocks().await.map_err(ContractError::MiddlewareError)?;
+        let from_block = match self.filter.get_from_block() {
+            Some(block) => block.as_u64(),
+            None => {
+                let head = self
+                    .provider
+                    .get_block_number()
+                    .await
+                    .map_err(ContractError::MiddlewareError)?;
+                head.as_u64() + 1
+            }
+        };
+
+        // check for newly confirmed logs right away and then on every new block
+        let ticks = stream::once(future::ready(())).chain(blocks.map(|_| ()));
+        let state = ConfirmedLogs::new(confirmations, from_block);
+        Ok(stream::unfold((ticks, state), move |(mut ticks, mut state)| async move {
+            ticks.next().await?;
+            let mut updates = Vec::new();
+            if let Err(err) = self.update_confirmed(&mut state, &mut updates).await {
+                updates.push(Err(err));
+            }
+            Some((stream::iter(updates), (ticks, state)))
+        })
+        .flatten())
+    }
+
+    /// Rolls back the confirmed logs of reorged blocks and confirms the logs of the blocks which
+    /// are deep enough now
+    async fn update_confirmed(
+        &self,
+        state: &mut ConfirmedLogs,
+        updates: &mut Vec<Result<EventUpdate<D>, ContractError<M>>>,
+    ) -> Result<(), ContractError<M>> {
+        while let Some((number, hash)) = state.latest_checkpoint() {
+            let block =
+                self.provider.get_block(number).await.map_err(ContractError::MiddlewareError)?;
+            if block.and_then(|block| block.hash) == Some(hash) {
+                break
+            }
+            state.discard_checkpoint();
+        }
+        for log in state.rewind() {
+            let meta = LogMeta::from(&log);
+            updates.push(self.parse_log(log).map(|event| EventUpdate::Removed(event, meta)));
+        }
+
+        let head =
+            self.provider.get_block_number().await.map_err(ContractError::MiddlewareError)?;
+        let (from, tip) = match state.confirmed_range(head.as_u64()) {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+        let block = self.provider.get_block(tip).await.map_err(ContractError::MiddlewareError)?;
+        let hash = match block.and_then(|block| block.hash) {
+            Some(hash) => hash,
+            None => return Ok(()),
+        };
+
+        let filter = self.filter.clone().from_block(from).to_block(tip);
+        let logs: Vec<_> = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(ContractError::MiddlewareError)?
+            .into_iter()
+            .filter(|log| !log.removed.unwrap_or_default())
+            .collect();
+
+        // the logs may be of another chain than the checkpoint if a reorg happened in between the
+        // requests, in which case the range is confirmed on the next block
+        if logs.iter().any(|log| block_number(log) == tip && log.block_hash != Some(hash)) {
+            return Ok(())
+        }
+        let block = self.provider.get_block(tip).await.map_err(ContractError::MiddlewareError)?;
+        if block.and_then(|block| block.hash) != Some(hash) {
+            return Ok(())
+        }
+
+        for log in &logs {
+            let meta = LogMeta::from(log);
+            updates.push(self.parse_log(log.clone()).map(|event| EventUpdate::Added(event, meta)));
+        }
+        state.confirm(tip, hash, logs);
+        Ok(())
+    }
 }
 
 impl<'a, M, D> Event<'a, M, D>
@@ -174,6 +391,39 @@ where
 {
     /// Returns a subscription for the event
     ///
+    /// Logs which are removed from the canonical chain by a reorg are redelivered by the node
+    /// with `removed: true`. Use [`EventStream::with_updates`] to tell them apart from newly
+    /// emitted events:
+    ///
+    /// ```no_run
+    /// # async fn test<M>(contract: ethers_contract::Contract<M>)
+    /// # where
+    /// #     M: ethers_providers::Middleware,
+    /// #     M::Provider: ethers_providers::PubsubClient,
+    /// # {
+    /// # use ethers_core::types::*;
+    /// # use futures_util::stream::StreamExt;
+    /// # use ethers_contract::{stream::EventUpdate, EthEvent};
+    /// #[derive(Clone, Debug, EthEvent)]
+    /// pub struct Transfer {
+    ///     #[ethevent(indexed)]
+    ///     pub from: Address,
+    ///     #[ethevent(indexed)]
+    ///     pub to: Address,
+    ///     pub tokens: U256,
+    /// }
+    ///
+    /// let event = contract.event::<Transfer>();
+    /// let mut updates = event.subscribe().await.unwrap().with_updates();
+    /// while let Some(Ok(update)) = updates.next().await {
+    ///     match update {
+    ///         EventUpdate::Added(transfer, meta) => { /* apply the transfer */ }
+    ///         EventUpdate::Removed(transfer, meta) => { /* roll the transfer back */ }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
     /// See also [Self::stream()].
     pub async fn subscribe(
         &'a self,
@@ -224,7 +474,448 @@ where
         Ok(events)
     }
 
+    /// Queries the blockchain for the selected filter in the blocks `from_block..=to_block` and
+    /// returns a stream of the matching events, replacing the filter's block range.
+    ///
+    /// The range is split into chunks of at most `page_size` blocks which are requested one after
+    /// the other, so that huge ranges can be processed without buffering all of their events and
+    /// without exceeding the range limits of `eth_getLogs`. If the node rejects a chunk because it
+    /// matches too many logs (e.g. `query returned more than 10000 results`), the chunk is halved
+    /// and retried. After each successful chunk its size is doubled again, up to `page_size`.
+    ///
+    /// The stream ends after the first failed request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn test<M: ethers_providers::Middleware>(contract: ethers_contract::Contract<M>) {
+    /// # use ethers_core::types::*;
+    /// # use futures_util::stream::StreamExt;
+    /// # use ethers_contract::EthEvent;
+    /// #[derive(Clone, Debug, EthEvent)]
+    /// pub struct Transfer {
+    ///     #[ethevent(indexed)]
+    ///     pub from: Address,
+    ///     #[ethevent(indexed)]
+    ///     pub to: Address,
+    ///     pub tokens: U256,
+    /// }
+    ///
+    /// let event = contract.event::<Transfer>();
+    /// let mut transfers = Box::pin(event.query_paginated(10_000_000, 15_000_000, 100_000));
+    /// while let Some(transfer) = transfers.next().await {
+    ///     let Transfer { from, to, tokens } = transfer.unwrap();
+    /// }
+    /// # }
+    /// ```
+    pub fn query_paginated(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        page_size: u64,
+    ) -> impl Stream<Item = Result<D, ContractError<M>>> + '_ {
+        let is_too_large = move |err: &M::Error| {
+            self.provider.as_error_response(err).map_or(false, JsonRpcError::is_log_query_too_large)
+        };
+        paginate(from_block, to_block, page_size, is_too_large, move |from, to| {
+            let filter = self.filter.clone().from_block(from).to_block(to);
+            async move { self.provider.get_logs(&filter).await }
+        })
+        .flat_map(move |page| {
+            let events: Vec<_> = match page {
+                Ok(logs) => logs.into_iter().map(|log| self.parse_log(log)).collect(),
+                Err(err) => vec![Err(ContractError::MiddlewareError(err))],
+            };
+            stream::iter(events)
+        })
+    }
+
     pub fn parse_log(&self, log: Log) -> Result<D, ContractError<M>> {
         D::decode_log(&RawLog { topics: log.topics, data: log.data.to_vec() }).map_err(From::from)
     }
 }
+
+/// Fetches the blocks `from..=to` in consecutive chunks of at most `page_size` blocks, halving the
+/// chunk size while a chunk fails with an error `is_too_large` accepts and doubling it again (up to
+/// `page_size`) after each successful chunk
+fn paginate<'a, T, E, G, F, Fut>(
+    from: u64,
+    to: u64,
+    page_size: u64,
+    is_too_large: G,
+    fetch: F,
+) -> impl Stream<Item = Result<Vec<T>, E>> + 'a
+where
+    T: 'a,
+    E: 'a,
+    G: Fn(&E) -> bool + 'a,
+    F: Fn(u64, u64) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>, E>> + 'a,
+{
+    let page_size = page_size.max(1);
+    let initial = if from <= to { Some(((is_too_large, fetch), from, page_size)) } else { None };
+    stream::unfold(initial, move |state| async move {
+        let ((is_too_large, fetch), from, mut chunk) = state?;
+        loop {
+            let end = from.saturating_add(chunk - 1).min(to);
+            match fetch(from, end).await {
+                Ok(items) => {
+                    let next = (end < to).then(|| {
+                        ((is_too_large, fetch), end + 1, chunk.saturating_mul(2).min(page_size))
+                    });
+                    return Some((Ok(items), next))
+                }
+                Err(err) if end > from && is_too_large(&err) => {
+                    chunk = (end - from + 1) / 2;
+                }
+                Err(err) => return Some((Err(err), None)),
+            }
+        }
+    })
+}
+
+/// Tracks the logs yielded by [`Event::stream_with_confirmations`], so they can be rolled back if
+/// their blocks are reorged
+#[derive(Debug)]
+struct ConfirmedLogs {
+    confirmations: u64,
+    /// The first block whose logs haven't been confirmed yet
+    next_block: u64,
+    /// The first block whose confirmed logs are retained
+    first_block: u64,
+    /// The hashes of the blocks up to which logs were confirmed, oldest first
+    checkpoints: VecDeque<(u64, H256)>,
+    /// The retained confirmed logs, oldest first
+    logs: VecDeque<Log>,
+    /// Whether checkpoints were discarded since the last rewind
+    reorged: bool,
+}
+
+impl ConfirmedLogs {
+    fn new(confirmations: u64, from_block: u64) -> Self {
+        Self {
+            confirmations: confirmations.max(1),
+            next_block: from_block,
+            first_block: from_block,
+            checkpoints: VecDeque::new(),
+            logs: VecDeque::new(),
+            reorged: false,
+        }
+    }
+
+    /// Returns the range of blocks whose logs have enough confirmations at `head`, if any
+    fn confirmed_range(&self, head: u64) -> Option<(u64, u64)> {
+        let tip = (head + 1).checked_sub(self.confirmations)?;
+        if tip >= self.next_block {
+            Some((self.next_block, tip))
+        } else {
+            None
+        }
+    }
+
+    /// Records the logs which were confirmed up to block `tip` with the given hash
+    fn confirm(&mut self, tip: u64, hash: H256, logs: Vec<Log>) {
+        self.logs.extend(logs);
+        self.checkpoints.push_back((tip, hash));
+        self.next_block = tip + 1;
+
+        // only the checkpoints of the last `confirmations` blocks are retained
+        while self.checkpoints.len() > 1 && self.checkpoints[0].0 + self.confirmations < tip {
+            if let Some((number, _)) = self.checkpoints.pop_front() {
+                self.first_block = number + 1;
+            }
+        }
+        while self.logs.front().map_or(false, |log| block_number(log) < self.first_block) {
+            self.logs.pop_front();
+        }
+    }
+
+    /// Returns the latest checkpoint, which should be compared with the canonical chain
+    fn latest_checkpoint(&self) -> Option<(u64, H256)> {
+        self.checkpoints.back().copied()
+    }
+
+    /// Discards the latest checkpoint because its block is no longer part of the canonical chain
+    fn discard_checkpoint(&mut self) {
+        self.checkpoints.pop_back();
+        self.reorged = true;
+    }
+
+    /// Rewinds to the latest checkpoint after checkpoints were discarded and returns the confirmed
+    /// logs after it, newest first
+    fn rewind(&mut self) -> Vec<Log> {
+        if !std::mem::take(&mut self.reorged) {
+            return Vec::new()
+        }
+        let from = self.checkpoints.back().map_or(self.first_block, |(number, _)| number + 1);
+        self.next_block = from;
+
+        let mut removed = Vec::new();
+        while let Some(log) = self.logs.pop_back() {
+            if block_number(&log) < from {
+                self.logs.push_back(log);
+                break
+            }
+            removed.push(log);
+        }
+        removed
+    }
+}
+
+fn block_number(log: &Log) -> u64 {
+    log.block_number.unwrap_or_default().as_u64()
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Block, TxHash, U256, U64};
+    use ethers_providers::Provider;
+    use futures_util::future;
+    use std::{sync::Mutex, time::Duration};
+
+    #[allow(clippy::ptr_arg)]
+    fn is_too_large(err: &String) -> bool {
+        err.starts_with("query returned more than")
+    }
+
+    #[tokio::test]
+    async fn paginates_block_range() {
+        let requests = Mutex::new(Vec::new());
+        let pages: Vec<_> = paginate(10, 34, 10, is_too_large, |from, to| {
+            requests.lock().unwrap().push((from, to));
+            future::ready(Ok::<_, String>((from..=to).collect::<Vec<_>>()))
+        })
+        .collect()
+        .await;
+
+        assert_eq!(*requests.lock().unwrap(), vec![(10, 19), (20, 29), (30, 34)]);
+        let blocks: Vec<u64> = pages.into_iter().flat_map(Result::unwrap).collect();
+        assert_eq!(blocks, (10..=34).collect::<Vec<_>>());
+
+        let pages: Vec<_> =
+            paginate(2, 1, 10, is_too_large, |_, _| future::ready(Ok::<Vec<u64>, String>(vec![])))
+                .collect()
+                .await;
+        assert!(pages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn halves_chunks_returning_too_many_results() {
+        // blocks 0..=99 hold a log each, the node returns at most 20 logs per request
+        let requests = Mutex::new(Vec::new());
+        let pages: Vec<_> = paginate(0, 99, 64, is_too_large, |from, to| {
+            requests.lock().unwrap().push((from, to));
+            let res = if to - from + 1 > 20 {
+                Err("query returned more than 20 results".to_string())
+            } else {
+                Ok((from..=to).collect::<Vec<_>>())
+            };
+            future::ready(res)
+        })
+        .collect()
+        .await;
+
+        let blocks: Vec<u64> = pages.into_iter().flat_map(Result::unwrap).collect();
+        assert_eq!(blocks, (0..=99).collect::<Vec<_>>());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                (0, 63),
+                (0, 31),
+                (0, 15),
+                // grows again after a successful chunk
+                (16, 47),
+                (16, 31),
+                (32, 63),
+                (32, 47),
+                (48, 79),
+                (48, 63),
+                (64, 95),
+                (64, 79),
+                (80, 99),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_at_other_errors() {
+        let pages: Vec<_> = paginate(0, 99, 10, is_too_large, |from, to| {
+            let res =
+                if from >= 20 { Err("connection reset".to_string()) } else { Ok(vec![(from, to)]) };
+            future::ready(res)
+        })
+        .collect()
+        .await;
+
+        assert_eq!(
+            pages,
+            vec![Ok(vec![(0, 9)]), Ok(vec![(10, 19)]), Err("connection reset".to_string())]
+        );
+
+        // a single block is not split any further
+        let pages: Vec<_> = paginate(0, 99, 10, is_too_large, |_, _| {
+            future::ready(Err::<Vec<u64>, _>("query returned more than 10000 results".to_string()))
+        })
+        .collect()
+        .await;
+        assert_eq!(pages.len(), 1);
+    }
+
+    fn log(block: u64) -> Log {
+        Log {
+            block_number: Some(block.into()),
+            block_hash: Some(H256::from_low_u64_be(block)),
+            ..Default::default()
+        }
+    }
+
+    fn blocks(logs: &[Log]) -> Vec<u64> {
+        logs.iter().map(block_number).collect()
+    }
+
+    #[test]
+    fn confirms_logs_deep_enough() {
+        let mut state = ConfirmedLogs::new(3, 10);
+        // the latest block has one confirmation
+        assert_eq!(state.confirmed_range(11), None);
+        assert_eq!(state.confirmed_range(12), Some((10, 10)));
+        state.confirm(10, H256::from_low_u64_be(10), vec![log(10)]);
+        assert_eq!(state.confirmed_range(12), None);
+        assert_eq!(state.confirmed_range(15), Some((11, 13)));
+
+        // nothing to roll back without a reorg
+        assert_eq!(state.latest_checkpoint(), Some((10, H256::from_low_u64_be(10))));
+        assert!(state.rewind().is_empty());
+        assert_eq!(state.confirmed_range(15), Some((11, 13)));
+    }
+
+    #[test]
+    fn rewinds_reorged_logs() {
+        let mut state = ConfirmedLogs::new(2, 10);
+        state.confirm(10, H256::from_low_u64_be(10), vec![log(10)]);
+        state.confirm(12, H256::from_low_u64_be(12), vec![log(11), log(12), log(12)]);
+
+        // block 12 was reorged, block 10 is still canonical
+        state.discard_checkpoint();
+        assert_eq!(state.latest_checkpoint(), Some((10, H256::from_low_u64_be(10))));
+        assert_eq!(blocks(&state.rewind()), vec![12, 12, 11]);
+        assert!(state.rewind().is_empty());
+        assert_eq!(state.confirmed_range(13), Some((11, 12)));
+
+        // all checkpoints were reorged
+        state.discard_checkpoint();
+        assert_eq!(blocks(&state.rewind()), vec![10]);
+        assert_eq!(state.confirmed_range(13), Some((10, 12)));
+    }
+
+    #[test]
+    fn prunes_old_checkpoints() {
+        let mut state = ConfirmedLogs::new(2, 10);
+        for block in 10..=15 {
+            state.confirm(block, H256::from_low_u64_be(block), vec![log(block)]);
+        }
+        // checkpoints of the last 2 blocks below the tip are retained
+        assert_eq!(state.checkpoints.iter().map(|(n, _)| *n).collect::<Vec<_>>(), vec![13, 14, 15]);
+        assert_eq!(blocks(state.logs.make_contiguous()), vec![13, 14, 15]);
+
+        // a reorg of all retained checkpoints rolls back to the first retained block
+        for _ in 0..3 {
+            state.discard_checkpoint();
+        }
+        assert_eq!(blocks(&state.rewind()), vec![15, 14, 13]);
+        assert_eq!(state.confirmed_range(16), Some((13, 15)));
+    }
+
+    /// The data of a log
+    #[derive(Debug, PartialEq)]
+    struct LogData(Vec<u8>);
+
+    impl EthLogDecode for LogData {
+        fn decode_log(log: &RawLog) -> Result<Self, ethers_core::abi::Error> {
+            Ok(LogData(log.data.clone()))
+        }
+    }
+
+    fn json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn streams_confirmed_logs_across_a_reorg() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+        let event = Event::<_, LogData> {
+            filter: Filter::new().from_block(10u64),
+            provider: &provider,
+            datatype: PhantomData,
+        };
+
+        // the versions of block 10 and of its log
+        let block = |version: u64| Block::<TxHash> {
+            number: Some(10u64.into()),
+            hash: Some(H256::from_low_u64_be(version)),
+            ..Default::default()
+        };
+        let log = |version: u64| Log {
+            block_number: Some(10u64.into()),
+            block_hash: Some(H256::from_low_u64_be(version)),
+            transaction_hash: Some(H256::zero()),
+            transaction_index: Some(0u64.into()),
+            log_index: Some(U256::zero()),
+            data: vec![version as u8].into(),
+            ..Default::default()
+        };
+        let head = json(&U64::from(11u64));
+        let new_block = json(&vec![H256::repeat_byte(1)]);
+        let responses = vec![
+            // eth_newBlockFilter
+            json(&U256::one()),
+            // block 10 is reorged while its logs are fetched, nothing is confirmed
+            head.clone(),
+            json(&block(1)),
+            json(&vec![log(1)]),
+            json(&block(2)),
+            // the logs of block 10 are confirmed on the next block
+            new_block.clone(),
+            head.clone(),
+            json(&block(2)),
+            json(&vec![log(2)]),
+            json(&block(2)),
+            // block 10 is reorged after its logs were confirmed
+            new_block,
+            json(&block(3)),
+            head,
+            json(&block(3)),
+            json(&vec![log(3)]),
+            json(&block(3)),
+        ];
+        // responses are returned last in, first out
+        for response in responses.into_iter().rev() {
+            mock.push::<serde_json::Value, _>(response).unwrap();
+        }
+
+        let mut updates = Box::pin(event.stream_with_confirmations(2).await.unwrap());
+        match updates.next().await.unwrap().unwrap() {
+            EventUpdate::Added(data, meta) => {
+                assert_eq!(data, LogData(vec![2]));
+                assert_eq!(meta.block_hash, H256::from_low_u64_be(2));
+            }
+            update => panic!("expected an added event, got {:?}", update),
+        }
+        match updates.next().await.unwrap().unwrap() {
+            EventUpdate::Removed(data, meta) => {
+                assert_eq!(data, LogData(vec![2]));
+                assert_eq!(meta.block_hash, H256::from_low_u64_be(2));
+            }
+            update => panic!("expected a removed event, got {:?}", update),
+        }
+        match updates.next().await.unwrap().unwrap() {
+            EventUpdate::Added(data, meta) => {
+                assert_eq!(data, LogData(vec![3]));
+                assert_eq!(meta.block_hash, H256::from_low_u64_be(3));
+            }
+            update => panic!("expected an added event, got {:?}", update),
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{Contract, ContractError};
 
 use ethers_core::{
-    abi::{Abi, Token, Tokenize},
+    abi::{self, Abi, AbiError, Token, Tokenize},
     types::{
-        transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes, NameOrAddress,
-        TransactionReceipt, TransactionRequest, U256, U64,
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes,
+        Eip1559TransactionRequest, NameOrAddress, TransactionReceipt, TransactionRequest, U256,
+        U64,
     },
 };
 use ethers_providers::{
@@ -12,10 +13,19 @@ use ethers_providers::{
     Middleware,
 };
 
-#[cfg(not(feature = "legacy"))]
-use ethers_core::types::Eip1559TransactionRequest;
+#[cfg(feature = "etherscan")]
+use ethers_etherscan::contract::{CodeFormat, VerificationStatus, VerifyContract};
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+/// The interval in which the status of a contract verification is polled from etherscan
+#[cfg(feature = "etherscan")]
+const VERIFICATION_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The number of times the verification is resubmitted while etherscan has not yet indexed the
+/// deployed contract
+#[cfg(feature = "etherscan")]
+const VERIFICATION_RETRIES: usize = 12;
 
 /// Helper which manages the deployment transaction of a smart contract.
 ///
@@ -56,6 +66,8 @@ impl<M: Middleware, C: From<Contract<M>>> ContractDeployer<M, C> {
     }
 
     /// Uses a Legacy transaction instead of an EIP-1559 one to do the deployment
+    ///
+    /// See [Deployer::legacy]
     pub fn legacy(mut self) -> Self {
         self.deployer = self.deployer.legacy();
         self
@@ -81,7 +93,23 @@ impl<M: Middleware, C: From<Contract<M>>> ContractDeployer<M, C> {
 
     /// Sets the `gas_price` field in the deploy transaction to the provided value
     pub fn gas_price<T: Into<U256>>(mut self, gas_price: T) -> Self {
-        self.deployer.tx.set_gas_price(gas_price.into());
+        self.deployer = self.deployer.gas_price(gas_price);
+        self
+    }
+
+    /// Sets the `max_fee_per_gas` field in the deploy transaction to the provided value
+    ///
+    /// See [Deployer::max_fee_per_gas]
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.deployer = self.deployer.max_fee_per_gas(max_fee_per_gas);
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the deploy transaction to the provided value
+    ///
+    /// See [Deployer::max_priority_fee_per_gas]
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.deployer = self.deployer.max_priority_fee_per_gas(max_priority_fee_per_gas);
         self
     }
 
@@ -142,6 +170,27 @@ impl<M: Middleware, C: From<Contract<M>>> ContractDeployer<M, C> {
         Ok((C::from(contract), receipt))
     }
 
+    /// Broadcasts the contract deployment transaction and after waiting for it to be sufficiently
+    /// confirmed (default: 1), submits the contract's `source` for verification on etherscan.
+    ///
+    /// See [`Deployer::send_and_verify`]
+    #[cfg(feature = "etherscan")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "etherscan")))]
+    pub async fn send_and_verify(
+        self,
+        etherscan: &ethers_etherscan::Client,
+        contract_name: impl Into<String>,
+        source: impl Into<String>,
+        compiler_version: impl Into<String>,
+        optimization: bool,
+    ) -> Result<C, ContractError<M>> {
+        let contract = self
+            .deployer
+            .send_and_verify(etherscan, contract_name, source, compiler_version, optimization)
+            .await?;
+        Ok(C::from(contract))
+    }
+
     /// Returns a reference to the deployer's ABI
     pub fn abi(&self) -> &Abi {
         self.deployer.abi()
@@ -163,6 +212,8 @@ pub struct Deployer<M> {
     client: Arc<M>,
     confs: usize,
     block: BlockNumber,
+    /// The ABI encoded constructor arguments, which are part of the transaction's data
+    constructor_args: Bytes,
 }
 
 impl<M> Clone for Deployer<M> {
@@ -173,6 +224,7 @@ impl<M> Clone for Deployer<M> {
             client: self.client.clone(),
             confs: self.confs,
             block: self.block,
+            constructor_args: self.constructor_args.clone(),
         }
     }
 }
@@ -190,6 +242,9 @@ impl<M: Middleware> Deployer<M> {
     }
 
     /// Uses a Legacy transaction instead of an EIP-1559 one to do the deployment
+    ///
+    /// This is required on networks which don't support EIP-1559. A previously set
+    /// `max_fee_per_gas` is used as the gas price.
     pub fn legacy(mut self) -> Self {
         self.tx = match self.tx {
             TypedTransaction::Eip1559(inner) => {
@@ -201,6 +256,34 @@ impl<M: Middleware> Deployer<M> {
         self
     }
 
+    /// Sets the `gas_price` field in the deploy transaction to the provided value
+    ///
+    /// If the deploy transaction is an EIP-1559 one, then it sets both `max_fee_per_gas` and
+    /// `max_priority_fee_per_gas` to the same value
+    pub fn gas_price<T: Into<U256>>(mut self, gas_price: T) -> Self {
+        self.tx.set_gas_price(gas_price);
+        self
+    }
+
+    /// Sets the `max_fee_per_gas` field in the deploy transaction to the provided value
+    ///
+    /// This turns the deploy transaction into an EIP-1559 one if it isn't already.
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.tx = Eip1559TransactionRequest::from(self.tx).max_fee_per_gas(max_fee_per_gas).into();
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the deploy transaction to the provided value
+    ///
+    /// This turns the deploy transaction into an EIP-1559 one if it isn't already, with the gas
+    /// price as max fee per gas.
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.tx = Eip1559TransactionRequest::from(self.tx)
+            .max_priority_fee_per_gas(max_priority_fee_per_gas)
+            .into();
+        self
+    }
+
     /// Dry runs the deployment of the contract
     ///
     /// Note: this function _does not_ send a transaction from your account
@@ -256,6 +339,42 @@ impl<M: Middleware> Deployer<M> {
         Ok((contract, receipt))
     }
 
+    /// Broadcasts the contract deployment transaction and after waiting for it to be sufficiently
+    /// confirmed (default: 1), submits the contract's single file `source` for verification on
+    /// etherscan and waits until etherscan verified it.
+    ///
+    /// The `compiler_version` is the full solc version the contract was compiled with, e.g.
+    /// `v0.8.10+commit.fc410830`. The constructor arguments are taken from the deployment
+    /// transaction.
+    ///
+    /// Verifying a contract whose source code has already been verified (e.g. a contract with the
+    /// same bytecode) succeeds.
+    #[cfg(feature = "etherscan")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "etherscan")))]
+    pub async fn send_and_verify(
+        self,
+        etherscan: &ethers_etherscan::Client,
+        contract_name: impl Into<String>,
+        source: impl Into<String>,
+        compiler_version: impl Into<String>,
+        optimization: bool,
+    ) -> Result<Contract<M>, ContractError<M>> {
+        let constructor_args = self.constructor_args.clone();
+        let contract = self.send().await?;
+
+        let request = verification_request(
+            contract.address(),
+            contract_name.into(),
+            source.into(),
+            compiler_version.into(),
+            optimization,
+            &constructor_args,
+        );
+        verify::<M>(etherscan, &request).await?;
+
+        Ok(contract)
+    }
+
     /// Returns a reference to the deployer's ABI
     pub fn abi(&self) -> &Abi {
         &self.abi
@@ -312,7 +431,15 @@ impl<M: Middleware> Deployer<M> {
 pub struct ContractFactory<M> {
     client: Arc<M>,
     abi: Abi,
-    bytecode: Bytes,
+    bytecode: FactoryBytecode,
+}
+
+/// The bytecode of a [ContractFactory]
+#[derive(Debug, Clone)]
+enum FactoryBytecode {
+    Linked(Bytes),
+    /// Hex encoded bytecode which may contain library placeholders
+    Unlinked(String),
 }
 
 impl<M> Clone for ContractFactory<M> {
@@ -330,17 +457,62 @@ impl<M: Middleware> ContractFactory<M> {
     /// constructor defined in the abi. The client will be used to send any deployment
     /// transaction.
     pub fn new(abi: Abi, bytecode: Bytes, client: Arc<M>) -> Self {
-        Self { client, abi, bytecode }
+        Self { client, abi, bytecode: FactoryBytecode::Linked(bytecode) }
+    }
+
+    /// Creates a factory for the hex encoded `bytecode` which contains placeholders for the
+    /// addresses of the libraries the contract uses (e.g. `__$cb3e..c0f0$__`), as emitted by
+    /// `solc`.
+    ///
+    /// The libraries need to be linked via [Self::link_libraries] before deploying the contract.
+    pub fn new_unlinked(abi: Abi, bytecode: impl Into<String>, client: Arc<M>) -> Self {
+        Self { client, abi, bytecode: FactoryBytecode::Unlinked(bytecode.into()) }
+    }
+
+    /// Replaces the placeholders of the given libraries with their deployed addresses.
+    ///
+    /// Libraries are identified by their fully qualified name, i.e. the path of their source file
+    /// and their name separated by `:` like `contracts/Math.sol:Math`. Both the hashed
+    /// placeholders of recent `solc` versions and the legacy name based placeholders are
+    /// replaced.
+    ///
+    /// Deploying the contract fails with [ContractError::UnlinkedLibraries] if placeholders are
+    /// left unresolved.
+    ///
+    /// See also <https://docs.soliditylang.org/en/latest/using-the-compiler.html#library-linking>
+    pub fn link_libraries(mut self, libraries: &HashMap<String, Address>) -> Self {
+        if let FactoryBytecode::Unlinked(ref mut bytecode) = self.bytecode {
+            for (name, address) in libraries {
+                let address = hex::encode(address);
+                *bytecode = bytecode
+                    .replace(&library_hash_placeholder(name), &address)
+                    .replace(&library_name_placeholder(name), &address);
+            }
+        }
+        self
+    }
+
+    /// Returns the bytecode of the contract, if all libraries are linked
+    fn linked_bytecode(&self) -> Result<Bytes, ContractError<M>> {
+        match &self.bytecode {
+            FactoryBytecode::Linked(bytecode) => Ok(bytecode.clone()),
+            FactoryBytecode::Unlinked(bytecode) => {
+                let placeholders = library_placeholders(bytecode);
+                if !placeholders.is_empty() {
+                    return Err(ContractError::UnlinkedLibraries(placeholders))
+                }
+                Ok(bytecode.parse::<Bytes>().map_err(AbiError::from)?)
+            }
+        }
     }
 
     pub fn deploy_tokens(self, params: Vec<Token>) -> Result<Deployer<M>, ContractError<M>> {
         // Encode the constructor args & concatenate with the bytecode if necessary
+        let bytecode = self.linked_bytecode()?;
         let data: Bytes = match (self.abi.constructor(), params.is_empty()) {
             (None, false) => return Err(ContractError::ConstructorError),
-            (None, true) => self.bytecode.clone(),
-            (Some(constructor), _) => {
-                constructor.encode_input(self.bytecode.to_vec(), &params)?.into()
-            }
+            (None, true) => bytecode,
+            (Some(constructor), _) => constructor.encode_input(bytecode.to_vec(), &params)?.into(),
         };
 
         // create the tx object. Since we're deploying a contract, `to` is `None`
@@ -358,6 +530,7 @@ impl<M: Middleware> ContractFactory<M> {
             tx,
             confs: 1,
             block: BlockNumber::Latest,
+            constructor_args: abi::encode(&params).into(),
         })
     }
 
@@ -373,3 +546,179 @@ impl<M: Middleware> ContractFactory<M> {
         self.deploy_tokens(constructor_args.into_tokens())
     }
 }
+
+/// The placeholder of `solc` >= 0.5 for the library with the fully qualified `name`, which is the
+/// 34 character prefix of the hex encoded keccak256 hash of the name
+fn library_hash_placeholder(name: &str) -> String {
+    let hash = hex::encode(ethers_core::utils::keccak256(name));
+    format!("__${}$__", &hash[..34])
+}
+
+/// The legacy placeholder of `solc` < 0.5 for the library with the fully qualified `name`
+fn library_name_placeholder(name: &str) -> String {
+    let name: String = name.chars().chain(std::iter::repeat('_')).take(36).collect();
+    format!("__{}__", name)
+}
+
+/// Returns the distinct library placeholders in the hex encoded `bytecode`
+fn library_placeholders(bytecode: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut rest = bytecode;
+    while let Some(start) = rest.find("__") {
+        let placeholder: String = rest[start..].chars().take(40).collect();
+        rest = &rest[start + placeholder.len()..];
+        if !placeholders.contains(&placeholder) {
+            placeholders.push(placeholder);
+        }
+    }
+    placeholders
+}
+
+/// Creates the request to verify the single file `source` of the contract deployed at `address`
+#[cfg(feature = "etherscan")]
+fn verification_request(
+    address: Address,
+    contract_name: String,
+    source: String,
+    compiler_version: String,
+    optimization: bool,
+    constructor_args: &Bytes,
+) -> VerifyContract {
+    let constructor_args =
+        if constructor_args.is_empty() { None } else { Some(hex::encode(constructor_args)) };
+    VerifyContract::new(address, contract_name, source, compiler_version)
+        .code_format(CodeFormat::SingleFile)
+        .optimization(optimization)
+        .constructor_arguments(constructor_args)
+}
+
+/// Submits the verification request and polls etherscan until the verification completed
+#[cfg(feature = "etherscan")]
+async fn verify<M: Middleware>(
+    etherscan: &ethers_etherscan::Client,
+    request: &VerifyContract,
+) -> Result<(), ContractError<M>> {
+    use futures_util::StreamExt;
+
+    let mut interval = ethers_providers::interval(VERIFICATION_POLL_INTERVAL);
+    let mut retries = VERIFICATION_RETRIES;
+
+    let guid = loop {
+        let resp = etherscan
+            .submit_contract_verification(request)
+            .await
+            .map_err(ContractError::EtherscanError)?;
+        if resp.status == "1" {
+            break resp.result
+        }
+
+        match VerificationStatus::from_result(&resp.result) {
+            VerificationStatus::AlreadyVerified => return Ok(()),
+            // etherscan has not indexed the freshly deployed contract yet
+            _ if resp.result.contains("Unable to locate ContractCode") && retries > 0 => {
+                retries -= 1;
+                interval.next().await;
+            }
+            _ => return Err(ContractError::VerificationFailed(resp.result)),
+        }
+    };
+
+    loop {
+        interval.next().await;
+        let status = etherscan
+            .contract_verification_status(&guid)
+            .await
+            .map_err(ContractError::EtherscanError)?;
+        match status {
+            VerificationStatus::Pending => {}
+            VerificationStatus::Verified | VerificationStatus::AlreadyVerified => return Ok(()),
+            VerificationStatus::Failed(reason) => {
+                return Err(ContractError::VerificationFailed(reason))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_providers::Provider;
+
+    #[test]
+    #[cfg(feature = "etherscan")]
+    fn creates_verification_request() {
+        let address: Address = "0x9e744c9115b74834c0f33f4097f40c02a9ac5c33".parse().unwrap();
+        let args = abi::encode(&[Token::Uint(42u64.into())]);
+        let request = verification_request(
+            address,
+            "SimpleStorage".to_string(),
+            "contract SimpleStorage {}".to_string(),
+            "v0.8.10+commit.fc410830".to_string(),
+            true,
+            &args.into(),
+        );
+
+        let payload = serde_json::to_value(&request).unwrap();
+        assert_eq!(payload["contractaddress"], format!("{:?}", address));
+        assert_eq!(payload["contractname"], "SimpleStorage");
+        assert_eq!(payload["sourceCode"], "contract SimpleStorage {}");
+        assert_eq!(payload["codeformat"], "solidity-single-file");
+        assert_eq!(payload["compilerversion"], "v0.8.10+commit.fc410830");
+        assert_eq!(payload["optimizationUsed"], "1");
+        assert_eq!(
+            payload["constructorArguements"],
+            "000000000000000000000000000000000000000000000000000000000000002a"
+        );
+
+        // no constructor arguments are submitted for contracts without any
+        let request = verification_request(
+            address,
+            "SimpleStorage".to_string(),
+            String::new(),
+            "v0.8.10+commit.fc410830".to_string(),
+            false,
+            &Bytes::default(),
+        );
+        let payload = serde_json::to_value(&request).unwrap();
+        assert_eq!(payload["optimizationUsed"], "0");
+        assert!(payload.get("constructorArguements").is_none());
+    }
+
+    #[test]
+    fn links_libraries() {
+        let (provider, _) = Provider::mocked();
+        let math = Address::repeat_byte(0x11);
+        let strings = Address::repeat_byte(0x22);
+
+        // placeholders of current and legacy solc versions
+        let hashed = library_hash_placeholder("contracts/Math.sol:Math");
+        assert_eq!(hashed.len(), 40);
+        let legacy = library_name_placeholder("contracts/Strings.sol:Strings");
+        assert_eq!(legacy, format!("__contracts/Strings.sol:Strings{}", "_".repeat(9)));
+        let bytecode = format!("6080{}73{}{}00", hashed, legacy, hashed);
+
+        let factory = ContractFactory::new_unlinked(
+            serde_json::from_str("[]").unwrap(),
+            bytecode,
+            Arc::new(provider),
+        );
+        let err = factory.clone().deploy(()).unwrap_err();
+        assert!(
+            matches!(err, ContractError::UnlinkedLibraries(ref placeholders) if placeholders == &[hashed.clone(), legacy.clone()])
+        );
+
+        let mut libraries = HashMap::new();
+        libraries.insert("contracts/Math.sol:Math".to_string(), math);
+        let factory = factory.link_libraries(&libraries);
+        let err = factory.clone().deploy(()).unwrap_err();
+        assert!(
+            matches!(err, ContractError::UnlinkedLibraries(ref placeholders) if placeholders == &[legacy.clone()])
+        );
+
+        libraries.insert("contracts/Strings.sol:Strings".to_string(), strings);
+        let deployer = factory.link_libraries(&libraries).deploy(()).unwrap();
+        let expected =
+            format!("6080{}73{}{}00", hex::encode(math), hex::encode(strings), hex::encode(math));
+        assert_eq!(deployer.tx.data().unwrap().to_vec(), hex::decode(expected).unwrap());
+    }
+}
@@ -11,6 +11,9 @@ pub use base::{decode_function_data, encode_function_data, AbiError, BaseContrac
 mod call;
 pub use call::{ContractError, EthCall};
 
+mod revert;
+pub use revert::{decode_revert, error_selector, CustomError, DecodedError, RevertDecoder};
+
 mod factory;
 pub use factory::{ContractDeployer, ContractFactory};
 
@@ -22,6 +25,10 @@ pub use log::{decode_logs, EthLogDecode, LogMeta};
 
 pub mod stream;
 
+pub mod eip165;
+
+pub mod erc20;
+
 mod multicall;
 pub use multicall::Multicall;
 
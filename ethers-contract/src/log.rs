@@ -4,6 +4,7 @@ use ethers_core::{
     types::{Address, Log, TxHash, H256, U256, U64},
 };
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
 /// A trait for types (events) that can be decoded from a `RawLog`
 pub trait EthLogDecode: Send + Sync {
@@ -19,7 +20,11 @@ pub fn decode_logs<T: EthLogDecode>(logs: &[RawLog]) -> Result<Vec<T>, Error> {
 }
 
 /// Metadata inside a log
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// The metadata identifies a log on the chain, so it can be used to deduplicate events, e.g. in a
+/// `HashSet`, and to persist them with a stable ordering key: the metadata of logs is ordered by
+/// their position in the chain, i.e. by block number and then by log index.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct LogMeta {
     /// Address from which this log originated
     pub address: Address,
@@ -40,6 +45,25 @@ pub struct LogMeta {
     pub log_index: U256,
 }
 
+impl Ord for LogMeta {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.block_number, self.log_index)
+            .cmp(&(other.block_number, other.log_index))
+            // only logs of different blocks at the same height, i.e. of a reorg, are compared
+            // further, which keeps the ordering consistent with `Eq`
+            .then_with(|| self.block_hash.cmp(&other.block_hash))
+            .then_with(|| self.transaction_index.cmp(&other.transaction_index))
+            .then_with(|| self.transaction_hash.cmp(&other.transaction_hash))
+            .then_with(|| self.address.cmp(&other.address))
+    }
+}
+
+impl PartialOrd for LogMeta {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl From<&Log> for LogMeta {
     fn from(src: &Log) -> Self {
         LogMeta {
@@ -52,3 +76,30 @@ impl From<&Log> for LogMeta {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn meta(block_number: u64, log_index: u64) -> LogMeta {
+        LogMeta::from(&Log {
+            block_number: Some(block_number.into()),
+            block_hash: Some(H256::from_low_u64_be(block_number)),
+            transaction_hash: Some(H256::repeat_byte(1)),
+            transaction_index: Some(0u64.into()),
+            log_index: Some(log_index.into()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn orders_and_deduplicates_by_position() {
+        let mut metas = vec![meta(2, 0), meta(1, 5), meta(1, 0), meta(1, 5)];
+        metas.sort();
+        assert_eq!(metas, vec![meta(1, 0), meta(1, 5), meta(1, 5), meta(2, 0)]);
+
+        let unique: HashSet<_> = metas.into_iter().collect();
+        assert_eq!(unique.len(), 3);
+    }
+}
@@ -0,0 +1,303 @@
+//! Decoding of the revert data of failed contract calls
+use ethers_core::{
+    abi::{self, ethabi, Abi, AbiDecode, Token},
+    types::{Bytes, Selector, U256},
+    utils::id,
+};
+use ethers_providers::{JsonRpcError, RevertReason};
+use std::{collections::HashMap, convert::TryInto, fmt};
+
+/// The decoded revert data of a failed contract call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedError {
+    /// A revert with a reason string, i.e. `Error(string)`
+    Reason(String),
+    /// A panic with its error code, i.e. `Panic(uint256)`
+    Panic(U256),
+    /// One of the known custom errors
+    Custom(CustomError),
+    /// Revert data which does not match any known error
+    Raw(Bytes),
+}
+
+impl fmt::Display for DecodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodedError::Reason(reason) => f.write_str(reason),
+            DecodedError::Panic(code) => write!(f, "panic code {:#x}", code),
+            DecodedError::Custom(error) => write!(f, "{}", error),
+            DecodedError::Raw(data) => write!(f, "{}", data),
+        }
+    }
+}
+
+impl DecodedError {
+    /// Decodes the revert data of a custom or unknown error into `E`, e.g. the errors enum
+    /// generated by `abigen!`
+    pub fn decode_as<E: AbiDecode>(&self) -> Option<E> {
+        let data = match self {
+            DecodedError::Custom(error) => &error.data,
+            DecodedError::Raw(data) => data,
+            _ => return None,
+        };
+        E::decode(data).ok()
+    }
+}
+
+/// A decoded custom error, e.g. `InsufficientBalance(uint256 available, uint256 required)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomError {
+    /// The name of the error
+    pub name: String,
+    /// The names and values of the error's parameters
+    pub params: Vec<(String, Token)>,
+    /// The raw revert data, including the selector
+    pub data: Bytes,
+}
+
+impl CustomError {
+    /// Returns the value of the parameter with the given name
+    pub fn param(&self, name: &str) -> Option<&Token> {
+        self.params.iter().find(|(param, _)| param == name).map(|(_, token)| token)
+    }
+}
+
+impl fmt::Display for CustomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (i, (_, token)) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", token)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Decodes revert data into [`DecodedError`]s, using a set of known custom errors.
+///
+/// `Error(string)` and `Panic(uint256)` are always decoded. Revert data matching none of the
+/// known errors is returned as [`DecodedError::Raw`].
+///
+/// ```
+/// use ethers_contract::{DecodedError, RevertDecoder};
+/// use ethers_core::abi::{ethabi, Param, ParamType, Token};
+///
+/// let error = ethabi::AbiError {
+///     name: "Unauthorized".to_string(),
+///     inputs: vec![Param { name: "code".to_string(), kind: ParamType::Uint(8), internal_type: None }],
+/// };
+/// let decoder = RevertDecoder::default().with_error(error);
+///
+/// let data = hex::decode(
+///     "2347864c0000000000000000000000000000000000000000000000000000000000000002",
+/// ).unwrap();
+/// match decoder.decode(&data) {
+///     DecodedError::Custom(error) => {
+///         assert_eq!(error.name, "Unauthorized");
+///         assert_eq!(error.param("code"), Some(&Token::Uint(2u64.into())));
+///     }
+///     other => panic!("unexpected error {:?}", other),
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RevertDecoder {
+    errors: HashMap<Selector, ethabi::AbiError>,
+}
+
+impl RevertDecoder {
+    /// Creates a decoder for the custom errors of the `abi`
+    pub fn from_abi(abi: &Abi) -> Self {
+        Self::default().with_errors(abi.errors.values().flatten().cloned())
+    }
+
+    /// Registers a custom error
+    #[must_use]
+    pub fn with_error(mut self, error: ethabi::AbiError) -> Self {
+        self.register(error);
+        self
+    }
+
+    /// Registers multiple custom errors
+    #[must_use]
+    pub fn with_errors(mut self, errors: impl IntoIterator<Item = ethabi::AbiError>) -> Self {
+        errors.into_iter().for_each(|error| self.register(error));
+        self
+    }
+
+    /// Registers a custom error, replacing any error with the same selector
+    pub fn register(&mut self, error: ethabi::AbiError) {
+        self.errors.insert(error_selector(&error), error);
+    }
+
+    /// Returns the custom error with the given selector, if known
+    pub fn get(&self, selector: Selector) -> Option<&ethabi::AbiError> {
+        self.errors.get(&selector)
+    }
+
+    /// Decodes the revert data, falling back to [`DecodedError::Raw`] if it does not match any of
+    /// the known errors or cannot be decoded as such
+    pub fn decode(&self, data: &[u8]) -> DecodedError {
+        match RevertReason::decode(data) {
+            RevertReason::Message(reason) => DecodedError::Reason(reason),
+            RevertReason::Panic(code) => DecodedError::Panic(code),
+            RevertReason::Custom(data) => {
+                self.decode_custom(&data).unwrap_or(DecodedError::Raw(data))
+            }
+        }
+    }
+
+    /// Decodes the revert data the node responded with to a failed call, if it included any, see
+    /// [`JsonRpcError::as_revert_data`]
+    pub fn decode_error(&self, err: &JsonRpcError) -> Option<DecodedError> {
+        err.as_revert_data().map(|data| self.decode(&data))
+    }
+
+    fn decode_custom(&self, data: &[u8]) -> Option<DecodedError> {
+        if data.len() < 4 {
+            return None
+        }
+        let (selector, args) = data.split_at(4);
+        let selector: Selector = selector.try_into().ok()?;
+
+        let error = self.errors.get(&selector)?;
+        let kinds: Vec<_> = error.inputs.iter().map(|param| param.kind.clone()).collect();
+        let tokens = abi::decode(&kinds, args).ok()?;
+        let params = error.inputs.iter().map(|param| param.name.clone()).zip(tokens).collect();
+        Some(DecodedError::Custom(CustomError {
+            name: error.name.clone(),
+            params,
+            data: data.to_vec().into(),
+        }))
+    }
+}
+
+/// Decodes the revert data of a failed call with the custom errors of the `abi`.
+///
+/// `Error(string)` and `Panic(uint256)` are always decoded, see [`RevertDecoder`].
+pub fn decode_revert(data: &[u8], abi: &Abi) -> DecodedError {
+    RevertDecoder::from_abi(abi).decode(data)
+}
+
+/// Returns the selector of the custom error
+pub fn error_selector(error: &ethabi::AbiError) -> Selector {
+    let kinds: Vec<_> = error.inputs.iter().map(|param| param.kind.to_string()).collect();
+    id(format!("{}({})", error.name, kinds.join(",")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::abi::{Param, ParamType};
+
+    fn insufficient_balance() -> ethabi::AbiError {
+        ethabi::AbiError {
+            name: "InsufficientBalance".to_string(),
+            inputs: vec![
+                Param {
+                    name: "available".to_string(),
+                    kind: ParamType::Uint(256),
+                    internal_type: None,
+                },
+                Param {
+                    name: "required".to_string(),
+                    kind: ParamType::Uint(256),
+                    internal_type: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn decodes_builtin_errors() {
+        let decoder = RevertDecoder::default();
+
+        let reason = hex::decode("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000076e6f74206f776e00000000000000000000000000000000000000000000000000").unwrap();
+        assert_eq!(decoder.decode(&reason), DecodedError::Reason("not own".to_string()));
+
+        let panic =
+            hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011")
+                .unwrap();
+        assert_eq!(decoder.decode(&panic), DecodedError::Panic(0x11.into()));
+        assert_eq!(decoder.decode(&panic).to_string(), "panic code 0x11");
+    }
+
+    #[test]
+    fn decodes_registered_custom_errors() {
+        let error = insufficient_balance();
+        assert_eq!(error_selector(&error), id("InsufficientBalance(uint256,uint256)"));
+
+        let mut data = error_selector(&error).to_vec();
+        data.extend(abi::encode(&[Token::Uint(7.into()), Token::Uint(42.into())]));
+
+        // unknown errors are returned as is
+        assert_eq!(RevertDecoder::default().decode(&data), DecodedError::Raw(data.clone().into()));
+
+        let decoded = RevertDecoder::default().with_error(error).decode(&data);
+        let custom = match decoded {
+            DecodedError::Custom(ref custom) => custom,
+            _ => panic!("expected a custom error, got {:?}", decoded),
+        };
+        assert_eq!(custom.name, "InsufficientBalance");
+        assert_eq!(custom.param("available"), Some(&Token::Uint(7.into())));
+        assert_eq!(custom.param("required"), Some(&Token::Uint(42.into())));
+        assert_eq!(custom.data.as_ref(), &data[..]);
+        assert!(decoded.to_string().starts_with("InsufficientBalance("));
+
+        // malformed data of a known error is returned as is
+        let decoder = RevertDecoder::default().with_error(insufficient_balance());
+        assert_eq!(decoder.decode(&data[..36]), DecodedError::Raw(data[..36].to_vec().into()));
+    }
+
+    #[test]
+    fn decodes_revert_with_abi() {
+        let abi =
+            abi::parse_abi(&["error InsufficientBalance(uint256 available, uint256 required)"])
+                .unwrap();
+        let mut data = error_selector(&insufficient_balance()).to_vec();
+        data.extend(abi::encode(&[Token::Uint(7.into()), Token::Uint(42.into())]));
+
+        match decode_revert(&data, &abi) {
+            DecodedError::Custom(custom) => assert_eq!(custom.name, "InsufficientBalance"),
+            other => panic!("expected a custom error, got {:?}", other),
+        }
+
+        // revert data without a known selector can still be decoded into a type
+        let raw = decode_revert(&data[4..], &abi);
+        assert_eq!(raw, DecodedError::Raw(data[4..].to_vec().into()));
+        assert_eq!(raw.decode_as::<(U256, U256)>(), Some((7.into(), 42.into())));
+    }
+
+    #[test]
+    fn decodes_json_rpc_errors() {
+        let decoder = RevertDecoder::default().with_error(insufficient_balance());
+        let mut data = error_selector(&insufficient_balance()).to_vec();
+        data.extend(abi::encode(&[Token::Uint(7.into()), Token::Uint(42.into())]));
+        let hex = format!("0x{}", hex::encode(&data));
+        let err = |data| JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(data),
+        };
+
+        // geth, ganache and nodes which wrap the revert data in an object
+        for data in [
+            serde_json::json!(hex),
+            serde_json::json!(format!("Reverted {}", hex)),
+            serde_json::json!({ "data": hex }),
+        ] {
+            match decoder.decode_error(&err(data)) {
+                Some(DecodedError::Custom(custom)) => {
+                    assert_eq!(custom.name, "InsufficientBalance")
+                }
+                other => panic!("expected a custom error, got {:?}", other),
+            }
+        }
+
+        assert!(decoder.decode_error(&err(serde_json::json!("0x"))).is_none());
+        let err =
+            JsonRpcError { code: -32000, message: "execution reverted".to_string(), data: None };
+        assert!(decoder.decode_error(&err).is_none());
+    }
+}
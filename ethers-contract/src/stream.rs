@@ -30,6 +30,13 @@ impl<'a, T, R, E> EventStream<'a, T, R, E> {
     pub fn with_meta(self) -> EventStreamMeta<'a, T, R, E> {
         EventStreamMeta(self)
     }
+
+    /// Turns this stream of events into a stream of [`EventUpdate`]s, which distinguishes events
+    /// that were removed from the canonical chain by a reorg (logs with `removed: true`) from
+    /// newly emitted ones.
+    pub fn with_updates(self) -> EventStreamUpdates<'a, T, R, E> {
+        EventStreamUpdates(self)
+    }
 }
 
 impl<'a, T, R, E> EventStream<'a, T, R, E> {
@@ -206,3 +213,113 @@ where
         }
     }
 }
+
+/// An event yielded by [`EventStreamUpdates`], along with its metadata
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventUpdate<R> {
+    /// The event was emitted in a block of the canonical chain
+    Added(R, LogMeta),
+    /// The event was previously emitted, but its block has been reorganized out of the canonical
+    /// chain, so any state derived from it should be rolled back
+    Removed(R, LogMeta),
+}
+
+impl<R> EventUpdate<R> {
+    /// Returns true if the event was removed by a reorg
+    pub fn is_removed(&self) -> bool {
+        matches!(self, EventUpdate::Removed(..))
+    }
+
+    /// Returns the decoded event
+    pub fn event(&self) -> &R {
+        match self {
+            EventUpdate::Added(event, _) | EventUpdate::Removed(event, _) => event,
+        }
+    }
+
+    /// Returns the metadata of the event's log
+    pub fn meta(&self) -> &LogMeta {
+        match self {
+            EventUpdate::Added(_, meta) | EventUpdate::Removed(_, meta) => meta,
+        }
+    }
+}
+
+/// Wrapper around a `EventStream`, that yields [`EventUpdate`]s instead of the bare events.
+#[pin_project]
+pub struct EventStreamUpdates<'a, T, R, E>(pub EventStream<'a, T, R, E>);
+
+impl<'a, T, R, E> Stream for EventStreamUpdates<'a, T, R, E>
+where
+    T: Stream<Item = Log> + Unpin,
+{
+    type Item = Result<EventUpdate<R>, E>;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        match futures_util::ready!(this.0.stream.poll_next_unpin(ctx)) {
+            Some(item) => {
+                let meta = LogMeta::from(&item);
+                let removed = item.removed.unwrap_or_default();
+                let res = (this.0.parse)(item).map(|inner| {
+                    if removed {
+                        EventUpdate::Removed(inner, meta)
+                    } else {
+                        EventUpdate::Added(inner, meta)
+                    }
+                });
+                Poll::Ready(Some(res))
+            }
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Bytes, H256, U64};
+
+    fn log(data: u8, removed: Option<bool>) -> Log {
+        Log {
+            data: Bytes::from(vec![data]),
+            block_hash: Some(H256::from_low_u64_be(data as u64)),
+            block_number: Some(U64::from(data)),
+            transaction_hash: Some(H256::zero()),
+            transaction_index: Some(U64::zero()),
+            log_index: Some(U256::zero()),
+            removed,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn surfaces_removed_logs() {
+        let logs = futures_util::stream::iter(vec![
+            log(1, None),
+            log(2, Some(false)),
+            log(2, Some(true)),
+            log(3, Some(false)),
+        ]);
+        let stream = EventStream::new(
+            U256::zero(),
+            logs,
+            Box::new(|log: Log| Ok::<_, ()>(log.data.as_ref()[0])),
+        );
+
+        let updates = stream.with_updates().map(Result::unwrap).collect::<Vec<_>>().await;
+        let summary = updates
+            .iter()
+            .map(|update| (*update.event(), update.is_removed(), update.meta().block_number))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (1, false, 1.into()),
+                (2, false, 2.into()),
+                (2, true, 2.into()),
+                (3, false, 3.into())
+            ]
+        );
+    }
+}
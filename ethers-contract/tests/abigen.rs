@@ -654,3 +654,88 @@ fn can_generate_to_string_overload() {
         ToStringCalls::ToString5(_) => {}
     };
 }
+
+#[test]
+fn can_generate_custom_errors() {
+    abigen!(
+        Vault,
+        r#"[
+            error InsufficientBalance(uint256 available, uint256 required)
+            error Unauthorized()
+            function withdraw(uint256 amount)
+        ]"#
+    );
+
+    assert_eq!(InsufficientBalance::abi_signature(), "InsufficientBalance(uint256,uint256)");
+    assert_eq!(Unauthorized::abi_signature(), "Unauthorized()");
+
+    let err = InsufficientBalance { available: 7u64.into(), required: 42u64.into() };
+    let data = err.clone().encode();
+    assert_eq!(data[..4], InsufficientBalance::selector());
+    assert_eq!(VaultErrors::decode(&data).unwrap(), VaultErrors::InsufficientBalance(err));
+    assert_eq!(
+        VaultErrors::decode(Unauthorized().encode()).unwrap(),
+        VaultErrors::Unauthorized(Unauthorized())
+    );
+    assert!(VaultErrors::decode(WithdrawCall { amount: 1u64.into() }.encode()).is_err());
+
+    let decoded = ethers_contract::decode_revert(&data, &VAULT_ABI);
+    assert_eq!(decoded.decode_as::<VaultErrors>(), Some(VaultErrors::decode(&data).unwrap()));
+}
+
+#[tokio::test]
+async fn can_query_event_filters_by_block_range_and_topics() {
+    use ethers_core::{
+        abi::{self, Token},
+        types::{Filter, Log, H256, U64},
+    };
+
+    abigen!(
+        Erc20,
+        r#"[
+            event Transfer(address indexed from, address indexed to, uint256 value)
+        ]"#
+    );
+
+    let (provider, mock) = Provider::mocked();
+    let address = Address::repeat_byte(0xee);
+    let erc20 = Erc20::new(address, Arc::new(provider));
+
+    let from = Address::repeat_byte(1);
+    let to = Address::repeat_byte(2);
+    let tx_hash = H256::repeat_byte(3);
+    let filter = Filter::new()
+        .event("Transfer(address,address,uint256)")
+        .address(address)
+        .from_block(5u64)
+        .to_block(10u64)
+        .topic1(from);
+    let log = Log {
+        address,
+        topics: vec![TransferFilter::signature(), H256::from(from), H256::from(to)],
+        data: abi::encode(&[Token::Uint(100u64.into())]).into(),
+        block_hash: Some(H256::repeat_byte(4)),
+        block_number: Some(U64::from(7)),
+        transaction_hash: Some(tx_hash),
+        transaction_index: Some(U64::from(1)),
+        log_index: Some(U256::from(3)),
+        ..Default::default()
+    };
+
+    mock.push(vec![log]).unwrap();
+    let events = erc20
+        .transfer_filter()
+        .from_block(5u64)
+        .to_block(10u64)
+        .topic1(from)
+        .query_with_meta()
+        .await
+        .unwrap();
+    mock.assert_request("eth_getLogs", [&filter]).unwrap();
+
+    let (event, meta) = &events[0];
+    assert_eq!(event, &TransferFilter { from, to, value: 100u64.into() });
+    assert_eq!(meta.block_number, U64::from(7));
+    assert_eq!(meta.transaction_hash, tx_hash);
+    assert_eq!(meta.log_index, U256::from(3));
+}
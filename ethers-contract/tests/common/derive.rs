@@ -605,3 +605,60 @@ fn eth_display_works_on_ethers_bytes() {
     let s = format!("{}", call);
     assert_eq!(s, "0xaaaaaa");
 }
+
+#[test]
+fn can_filter_on_indexed_params() {
+    use ethers_core::{
+        abi::ParamType,
+        types::{Filter, ValueOrArray},
+    };
+    use ethers_providers::Provider;
+
+    #[derive(Debug, PartialEq, Eq, EthEvent)]
+    struct Transfer {
+        #[ethevent(indexed)]
+        from: Address,
+        #[ethevent(indexed)]
+        to: Address,
+        value: U256,
+    }
+    assert_eq!(Transfer::indexed_params(), vec![ParamType::Address, ParamType::Address]);
+
+    let (provider, _) = Provider::mocked();
+    let from = Address::repeat_byte(0x11);
+    let to = Address::repeat_byte(0x22);
+
+    let event = Transfer::new(Filter::new(), &provider)
+        .indexed_topic(0, Some(from))
+        .unwrap()
+        .indexed_topic(1, vec![to, from])
+        .unwrap();
+    assert_eq!(event.filter.topics[0], Some(Transfer::signature().into()));
+    assert_eq!(event.filter.topics[1], Some(ValueOrArray::Value(Some(from.into()))));
+    assert_eq!(
+        event.filter.topics[2],
+        Some(ValueOrArray::Array(vec![Some(to.into()), Some(from.into())]))
+    );
+
+    // no values is a wildcard
+    let event = event.indexed_topic(1, None::<Address>).unwrap();
+    assert_eq!(event.filter.topics[2], None);
+
+    // `value` is not indexed
+    assert!(event.indexed_topic(2, Some(U256::one())).is_err());
+    let event = Transfer::new(Filter::new(), &provider);
+    assert!(event.indexed_topic(0, Some(U256::one())).is_err());
+
+    #[derive(Debug, PartialEq, Eq, EthEvent)]
+    #[ethevent(anonymous)]
+    struct Message {
+        #[ethevent(indexed)]
+        message: String,
+    }
+    let event =
+        Message::new(Filter::new(), &provider).indexed_topic(0, Some("hello".to_string())).unwrap();
+    assert_eq!(
+        event.filter.topics[0],
+        Some(ValueOrArray::Value(Some(ethers_core::utils::keccak256("hello").into())))
+    );
+}
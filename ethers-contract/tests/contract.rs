@@ -93,6 +93,94 @@ mod eth_tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn call_reverts_with_decoded_custom_error() {
+        use ethers_contract::{Contract, ContractError, DecodedError};
+        use ethers_core::{abi::parse_abi, utils::id};
+
+        let (abi, bytecode) = compile_contract("CustomErrors", "CustomErrors.sol");
+        let anvil = Anvil::new().spawn();
+        let client = connect(&anvil, 0);
+        let factory = ContractFactory::new(abi, bytecode, client.clone());
+        let contract = factory.deploy(()).unwrap().legacy().send().await.unwrap();
+
+        // the errors of the contract's abi are decoded
+        let err =
+            contract.method::<_, ()>("withdraw", U256::from(42)).unwrap().call().await.unwrap_err();
+        let error = match err {
+            ContractError::Revert(DecodedError::Custom(error)) => error,
+            err => panic!("expected a custom error, got {:?}", err),
+        };
+        assert_eq!(error.name, "InsufficientBalance");
+        assert_eq!(error.param("available"), Some(&Token::Uint(7.into())));
+        assert_eq!(error.param("required"), Some(&Token::Uint(42.into())));
+
+        // reason strings don't need to be registered
+        let err =
+            contract.method::<_, ()>("fail", "nope".to_string()).unwrap().call().await.unwrap_err();
+        assert!(
+            matches!(err, ContractError::Revert(DecodedError::Reason(ref reason)) if reason == "nope"),
+            "{:?}",
+            err
+        );
+
+        // falls back to the raw revert data if the error is unknown
+        let abi = parse_abi(&["function withdraw(uint256)"]).unwrap();
+        let unknown = Contract::new(contract.address(), abi, client);
+        let call = unknown.method::<_, ()>("withdraw", U256::from(42)).unwrap();
+        match call.call().await.unwrap_err() {
+            ContractError::Revert(DecodedError::Raw(data)) => {
+                assert_eq!(data.as_ref(), error.data.as_ref());
+                assert_eq!(&data[..4], &id("InsufficientBalance(uint256,uint256)")[..]);
+            }
+            err => panic!("expected raw revert data, got {:?}", err),
+        }
+
+        // unless it is registered
+        let known = unknown.with_errors(contract.abi().errors.values().flatten().cloned());
+        let call = known.method::<_, ()>("withdraw", U256::from(42)).unwrap();
+        match call.call().await.unwrap_err() {
+            ContractError::Revert(DecodedError::Custom(decoded)) => assert_eq!(decoded, error),
+            err => panic!("expected a custom error, got {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_revert_data_behind_middlewares() {
+        use ethers_contract::{Contract, ContractError, DecodedError};
+        use ethers_core::{abi::parse_abi, utils::id};
+        use ethers_providers::JsonRpcError;
+
+        let abi = parse_abi(&[
+            "function withdraw(uint256)",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+        ])
+        .unwrap();
+        let (provider, mock) = Provider::mocked();
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let client = Arc::new(SignerMiddleware::new(provider, wallet.with_chain_id(1u64)));
+        let contract = Contract::new(Address::repeat_byte(1), abi, client);
+
+        // ganache prefixes the revert data
+        let mut data = id("InsufficientBalance(uint256,uint256)").to_vec();
+        data.extend(ethers_core::abi::encode(&[Token::Uint(7.into()), Token::Uint(42.into())]));
+        mock.push_json_rpc_error(JsonRpcError {
+            code: -32000,
+            message: "VM Exception while processing transaction: revert".to_string(),
+            data: Some(format!("Reverted 0x{}", hex::encode(&data)).into()),
+        });
+
+        let call = contract.method::<_, ()>("withdraw", U256::from(42)).unwrap();
+        match call.call().await.unwrap_err() {
+            ContractError::Revert(DecodedError::Custom(error)) => {
+                assert_eq!(error.name, "InsufficientBalance");
+                assert_eq!(error.data.as_ref(), &data[..]);
+            }
+            err => panic!("expected a custom error, got {:?}", err),
+        }
+    }
+
     #[tokio::test]
     #[cfg(feature = "abigen")]
     async fn get_past_events() {
@@ -574,8 +662,9 @@ mod eth_tests {
             .expect("failed to instantiate provider from anvil endpoint")
             .interval(Duration::from_millis(10u64));
 
-        let client =
-            SignerMiddleware::new_with_provider_chain(provider, wallet.clone()).await.unwrap();
+        let client = SignerMiddleware::new_with_provider_chain_unchecked(provider, wallet.clone())
+            .await
+            .unwrap();
         let client = Arc::new(client);
 
         let factory = ContractFactory::new(abi.clone(), bytecode.clone(), client.clone());
@@ -0,0 +1,14 @@
+pragma solidity >=0.8.4;
+
+contract CustomErrors {
+
+    error InsufficientBalance(uint256 available, uint256 required);
+
+    function withdraw(uint256 amount) external pure {
+        revert InsufficientBalance(7, amount);
+    }
+
+    function fail(string memory reason) external pure {
+        revert(reason);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::{BTreeMap, HashMap, VecDeque};
 
 use crate::abi::{
     error::{bail, format_err, ParseError, Result},
+    ethabi::AbiError,
     struct_def::{FieldType, StructFieldType},
     Abi, Constructor, Event, EventParam, Function, HumanReadableParser, Param, ParamType,
     SolStruct, StateMutability,
@@ -32,6 +33,7 @@ impl AbiParser {
     ///         function setValue(string)
     ///         function getValue() external view returns (string)
     ///         event ValueChanged(address indexed author, string oldValue, string newValue)
+    ///         error Unauthorized(address caller)
     ///     ]"#).unwrap();
     /// ```
     pub fn parse_str(&mut self, s: &str) -> Result<Abi> {
@@ -82,6 +84,9 @@ impl AbiParser {
             if line.starts_with("event") {
                 let event = self.parse_event(line)?;
                 abi.events.entry(event.name.clone()).or_default().push(event);
+            } else if line.starts_with("error ") {
+                let error = self.parse_error(line)?;
+                abi.errors.entry(error.name.clone()).or_default().push(error);
             } else if line.starts_with("constructor") {
                 let inputs = self
                     .constructor_inputs(line)?
@@ -207,6 +212,32 @@ impl AbiParser {
         }
     }
 
+    /// Parses a solidity error declaration from `error <name> (args*)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_core::abi::AbiParser;
+    /// let error = AbiParser::default()
+    ///     .parse_error("error InsufficientBalance(uint256 available, uint256 required)").unwrap();
+    /// assert_eq!(error.inputs.len(), 2);
+    /// ```
+    pub fn parse_error(&self, s: &str) -> Result<AbiError> {
+        let mut error =
+            s.trim().strip_prefix("error ").ok_or_else(|| format_err!("Not an error `{}`", s))?;
+
+        let name = parse_identifier(&mut error)?;
+
+        let params = error
+            .trim()
+            .strip_prefix('(')
+            .and_then(|params| params.strip_suffix(')'))
+            .ok_or_else(|| format_err!("Expected error args parentheses at `{}`", s))?;
+        let inputs = self.parse_params(params)?.into_iter().map(|(input, _)| input).collect();
+
+        Ok(AbiError { name, inputs })
+    }
+
     /// Parse a single event param
     fn parse_event_arg(&self, input: &str) -> Result<EventParam> {
         let mut iter = input.trim().rsplitn(3, is_whitespace);
@@ -686,6 +717,64 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parses_errors() {
+        let error = AbiParser::default()
+            .parse_error("error InsufficientBalance(uint256 available, uint256)")
+            .unwrap();
+        assert_eq!(error.name, "InsufficientBalance");
+        assert_eq!(error.inputs[0].name, "available");
+        assert_eq!(error.inputs[0].kind, ParamType::Uint(256));
+        assert_eq!(error.inputs[1].name, "");
+
+        let error = AbiParser::default().parse_error("error Paused()").unwrap();
+        assert_eq!(error.name, "Paused");
+        assert!(error.inputs.is_empty());
+
+        assert!(AbiParser::default().parse_error("error Paused").is_err());
+        assert!(AbiParser::default().parse_error("event Paused()").is_err());
+
+        // functions with names starting with `error` are not errors
+        let abi = parse(&["function errorCount() returns (uint256)"]).unwrap();
+        assert!(abi.errors.is_empty());
+        assert_eq!(abi.functions.len(), 1);
+    }
+
+    #[test]
+    fn human_readable_abi_matches_json() {
+        let abi = parse(&[
+            "struct Fill { address maker; bool partial; }",
+            "struct Order { uint256 amount; Fill fill; }",
+            "function settle(Order[] orders, uint256) external payable returns (bool)",
+            "event Settled(address indexed maker, uint256 amount)",
+            "error InsufficientFill(uint256 available, uint256 required)",
+            "error Paused()",
+        ])
+        .unwrap();
+
+        let json: Abi = serde_json::from_str(
+            r#"[
+            {"type":"function","name":"settle","stateMutability":"payable",
+             "inputs":[
+                {"name":"orders","type":"tuple[]","components":[
+                    {"name":"amount","type":"uint256"},
+                    {"name":"fill","type":"tuple","components":[
+                        {"name":"maker","type":"address"},{"name":"partial","type":"bool"}]}]},
+                {"name":"","type":"uint256"}],
+             "outputs":[{"name":"","type":"bool"}]},
+            {"type":"event","name":"Settled","anonymous":false,"inputs":[
+                {"name":"maker","type":"address","indexed":true},
+                {"name":"amount","type":"uint256","indexed":false}]},
+            {"type":"error","name":"InsufficientFill","inputs":[
+                {"name":"available","type":"uint256"},{"name":"required","type":"uint256"}]},
+            {"type":"error","name":"Paused","inputs":[]}
+        ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(abi, json);
+    }
+
     #[test]
     fn can_parse_structs_and_functions() {
         let abi = &[
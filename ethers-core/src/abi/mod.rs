@@ -2,7 +2,7 @@
 // Adapted from [Gnosis' ethcontract](https://github.com/gnosis/ethcontract-rs/blob/master/common/src/abiext.rs)
 use crate::{
     types::{Bytes, Selector},
-    utils::id,
+    utils::{id, keccak256},
 };
 
 pub use ethabi::{self, Contract as Abi, *};
@@ -189,6 +189,46 @@ impl_abi_type_tuple!(14, A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 impl_abi_type_tuple!(15, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 impl_abi_type_tuple!(16, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
 
+/// Encodes a value the way it is stored in a topic when emitted as an indexed event parameter.
+///
+/// Value types are ABI encoded into the 32 byte word, while `string`, `bytes`, arrays and structs
+/// are stored as the keccak256 hash of their encoding, see also [the Solidity docs](https://docs.soliditylang.org/en/latest/abi-spec.html#encoding-of-indexed-event-parameters)
+pub fn encode_topic(token: &Token) -> H256 {
+    match token {
+        Token::String(_) |
+        Token::Bytes(_) |
+        Token::Array(_) |
+        Token::FixedArray(_) |
+        Token::Tuple(_) => {
+            let mut data = Vec::new();
+            encode_topic_in_place(token, &mut data, false);
+            H256::from(keccak256(data))
+        }
+        token => H256::from_slice(&encode(&[token.clone()])),
+    }
+}
+
+/// Appends the in-place encoding of the token, `string` and `bytes` are only padded if nested
+fn encode_topic_in_place(token: &Token, out: &mut Vec<u8>, pad: bool) {
+    match token {
+        Token::String(s) => extend_padded(out, s.as_bytes(), pad),
+        Token::Bytes(b) => extend_padded(out, b, pad),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            for token in tokens {
+                encode_topic_in_place(token, out, true);
+            }
+        }
+        token => out.extend(encode(&[token.clone()])),
+    }
+}
+
+fn extend_padded(out: &mut Vec<u8>, data: &[u8], pad: bool) {
+    out.extend_from_slice(data);
+    if pad && data.len() % 32 != 0 {
+        out.resize(out.len() + 32 - data.len() % 32, 0);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,6 +279,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encodes_topics() {
+        let address = Address::repeat_byte(0x11);
+        assert_eq!(encode_topic(&Token::Address(address)), H256::from(address));
+        assert_eq!(encode_topic(&Token::Uint(1u64.into())), H256::from_low_u64_be(1));
+        assert_eq!(encode_topic(&Token::Bool(true)), H256::from_low_u64_be(1));
+
+        assert_eq!(encode_topic(&Token::String("hello".into())), H256::from(keccak256("hello")));
+        assert_eq!(encode_topic(&Token::Bytes(vec![1, 2, 3])), H256::from(keccak256([1, 2, 3])));
+
+        // arrays are hashed over their padded elements
+        let array = Token::Array(vec![Token::Uint(1u64.into()), Token::Uint(2u64.into())]);
+        let mut data = [0u8; 64];
+        data[31] = 1;
+        data[63] = 2;
+        assert_eq!(encode_topic(&array), H256::from(keccak256(data)));
+
+        let array = Token::Array(vec![Token::String("a".into())]);
+        let mut data = [0u8; 32];
+        data[0] = b'a';
+        assert_eq!(encode_topic(&array), H256::from(keccak256(data)));
+    }
+
     #[test]
     fn abi_type_works() {
         assert_eq!(ParamType::Bytes, Bytes::param_type());
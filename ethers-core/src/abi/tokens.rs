@@ -48,9 +48,11 @@ pub trait Tokenize {
     fn into_tokens(self) -> Vec<Token>;
 }
 
+/// A slice of tokens is the list of arguments as is, e.g. `&[Token::Tuple(..)]` is a single tuple
+/// argument.
 impl<'a> Tokenize for &'a [Token] {
     fn into_tokens(self) -> Vec<Token> {
-        flatten_tokens(self.to_vec())
+        self.to_vec()
     }
 }
 
@@ -496,6 +498,16 @@ mod tests {
         assert_eq!(tokens, vec![Token::Uint(1.into()), Token::Uint(2.into()),]);
     }
 
+    #[test]
+    fn slices_are_not_flattened() {
+        let tuple = Token::Tuple(vec![Token::Uint(1.into()), Token::Uint(2.into())]);
+        assert_eq!(
+            (&[tuple.clone()][..]).into_tokens(),
+            vec![Token::Tuple(vec![Token::Uint(1.into()), Token::Uint(2.into())])]
+        );
+        assert_eq!(tuple.into_tokens(), vec![Token::Uint(1.into()), Token::Uint(2.into())]);
+    }
+
     #[test]
     fn should_decode_array_of_fixed_bytes() {
         // byte[8][]
@@ -7,6 +7,8 @@ pub mod types;
 
 pub mod abi;
 
+pub mod serde_helpers;
+
 /// Various utilities
 pub mod utils;
 
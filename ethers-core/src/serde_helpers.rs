@@ -0,0 +1,86 @@
+//! Helpers to (de)serialize types with a different representation than their default one, for use
+//! with `#[serde(with = "...")]`
+
+/// (De)serializes a [`U256`](crate::types::U256) as a decimal string instead of the default `0x`
+/// prefixed hex string.
+///
+/// This matches the representation of big numbers that `ethers.js` expects, without the precision
+/// loss of JSON numbers for values larger than `2^53`.
+///
+/// ```
+/// use ethers_core::types::U256;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Balance {
+///     #[serde(with = "ethers_core::serde_helpers::u256_dec")]
+///     amount: U256,
+/// }
+///
+/// let balance = Balance { amount: U256::exp10(18) };
+/// let json = serde_json::to_string(&balance).unwrap();
+/// assert_eq!(json, r#"{"amount":"1000000000000000000"}"#);
+/// ```
+pub mod u256_dec {
+    use crate::types::U256;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(de::Error::custom(format!("invalid decimal string: {:?}", value)))
+        }
+        U256::from_dec_str(&value).map_err(|err| de::Error::custom(format!("{:?}", err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::U256;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Value {
+        #[serde(with = "super::u256_dec")]
+        value: U256,
+    }
+
+    #[test]
+    fn u256_dec_roundtrip() {
+        for value in [U256::zero(), U256::from(1u64 << 53) + 1, U256::max_value()] {
+            let json = serde_json::to_string(&Value { value }).unwrap();
+            assert_eq!(json, format!(r#"{{"value":"{}"}}"#, value));
+            assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), Value { value });
+        }
+        assert_eq!(
+            serde_json::to_string(&Value { value: U256::from(9007199254740993u64) }).unwrap(),
+            r#"{"value":"9007199254740993"}"#
+        );
+    }
+
+    #[test]
+    fn u256_dec_rejects_malformed() {
+        for json in [
+            r#"{"value":""}"#,
+            r#"{"value":"0x10"}"#,
+            r#"{"value":"-1"}"#,
+            r#"{"value":"1.5"}"#,
+            r#"{"value":" 1"}"#,
+            r#"{"value":1}"#,
+            // 2^256
+            r#"{"value":"115792089237316195423570985008687907853269984665640564039457584007913129639936"}"#,
+        ] {
+            assert!(serde_json::from_str::<Value>(json).is_err(), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::types::{H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The state of an account, as returned by `eth_getAccount`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    /// The account's balance
+    pub balance: U256,
+    /// The account's nonce
+    pub nonce: U256,
+    /// The keccak256 hash of the account's code
+    pub code_hash: H256,
+    /// The root of the account's storage trie.
+    ///
+    /// This is `None` if the node does not support `eth_getAccount` and the account info had to
+    /// be assembled from separate calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_root: Option<H256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_deserialize_account_info() {
+        let s = r#"{
+            "balance": "0xde0b6b3a7640000",
+            "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            "nonce": "0x9",
+            "storageRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        }"#;
+        let account: AccountInfo = serde_json::from_str(s).unwrap();
+        assert_eq!(account.balance, U256::exp10(18));
+        assert_eq!(account.nonce, 9u64.into());
+        assert_eq!(
+            account.code_hash,
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470".parse().unwrap()
+        );
+        assert_eq!(
+            account.storage_root,
+            Some(
+                "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+                    .parse()
+                    .unwrap()
+            )
+        );
+    }
+}
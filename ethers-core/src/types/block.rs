@@ -85,6 +85,14 @@ pub struct Block<TX> {
     /// Base fee per unit of gas (if past London)
     #[serde(rename = "baseFeePerGas")]
     pub base_fee_per_gas: Option<U256>,
+    /// Withdrawals of validators from the beacon chain (if past Shanghai)
+    #[cfg(not(feature = "celo"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Root of the withdrawals trie (if past Shanghai)
+    #[cfg(not(feature = "celo"))]
+    #[serde(default, rename = "withdrawalsRoot", skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
 
     #[cfg(feature = "celo")]
     #[cfg_attr(docsrs, doc(cfg(feature = "celo")))]
@@ -208,6 +216,8 @@ impl Block<TxHash> {
                 mix_hash,
                 nonce,
                 base_fee_per_gas,
+                withdrawals,
+                withdrawals_root,
                 other,
                 ..
             } = self;
@@ -233,6 +243,8 @@ impl Block<TxHash> {
                 mix_hash,
                 nonce,
                 base_fee_per_gas,
+                withdrawals,
+                withdrawals_root,
                 transactions,
                 other,
             }
@@ -312,6 +324,8 @@ impl From<Block<Transaction>> for Block<TxHash> {
                 mix_hash,
                 nonce,
                 base_fee_per_gas,
+                withdrawals,
+                withdrawals_root,
                 other,
             } = full;
             Block {
@@ -336,6 +350,8 @@ impl From<Block<Transaction>> for Block<TxHash> {
                 mix_hash,
                 nonce,
                 base_fee_per_gas,
+                withdrawals,
+                withdrawals_root,
                 transactions: transactions.iter().map(|tx| tx.hash).collect(),
                 other,
             }
@@ -388,6 +404,21 @@ impl From<Block<Transaction>> for Block<TxHash> {
     }
 }
 
+/// A withdrawal of a validator from the beacon chain, as included in blocks since Shanghai
+/// ([EIP-4895](https://eips.ethereum.org/EIPS/eip-4895))
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    /// Monotonically increasing identifier of the withdrawal
+    pub index: U64,
+    /// Index of the validator the withdrawal belongs to
+    pub validator_index: U64,
+    /// Recipient of the withdrawn ether
+    pub address: Address,
+    /// Withdrawn amount in Gwei
+    pub amount: U256,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg(feature = "celo")]
 /// Commit-reveal data for generating randomness in the
@@ -521,11 +552,15 @@ impl<'de> Deserialize<'de> for BlockId {
     }
 }
 
-/// A block Number (or tag - "latest", "earliest", "pending")
+/// A block Number (or tag - "latest", "earliest", "pending", "safe", "finalized")
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum BlockNumber {
     /// Latest block
     Latest,
+    /// Finalized block accepted as canonical
+    Finalized,
+    /// Safe head block
+    Safe,
     /// Earliest block (genesis)
     Earliest,
     /// Pending block (not yet part of the blockchain)
@@ -553,6 +588,16 @@ impl BlockNumber {
         matches!(self, BlockNumber::Latest)
     }
 
+    /// Returns `true` if it's "finalized"
+    pub fn is_finalized(&self) -> bool {
+        matches!(self, BlockNumber::Finalized)
+    }
+
+    /// Returns `true` if it's "safe"
+    pub fn is_safe(&self) -> bool {
+        matches!(self, BlockNumber::Safe)
+    }
+
     /// Returns `true` if it's "pending"
     pub fn is_pending(&self) -> bool {
         matches!(self, BlockNumber::Pending)
@@ -578,6 +623,8 @@ impl Serialize for BlockNumber {
         match *self {
             BlockNumber::Number(ref x) => serializer.serialize_str(&format!("0x{:x}", x)),
             BlockNumber::Latest => serializer.serialize_str("latest"),
+            BlockNumber::Finalized => serializer.serialize_str("finalized"),
+            BlockNumber::Safe => serializer.serialize_str("safe"),
             BlockNumber::Earliest => serializer.serialize_str("earliest"),
             BlockNumber::Pending => serializer.serialize_str("pending"),
         }
@@ -600,6 +647,8 @@ impl FromStr for BlockNumber {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let block = match s {
             "latest" => Self::Latest,
+            "finalized" => Self::Finalized,
+            "safe" => Self::Safe,
             "earliest" => Self::Earliest,
             "pending" => Self::Pending,
             n => BlockNumber::Number(n.parse::<U64>().map_err(|err| err.to_string())?),
@@ -613,6 +662,8 @@ impl fmt::Display for BlockNumber {
         match self {
             BlockNumber::Number(ref x) => format!("0x{:x}", x).fmt(f),
             BlockNumber::Latest => f.write_str("latest"),
+            BlockNumber::Finalized => f.write_str("finalized"),
+            BlockNumber::Safe => f.write_str("safe"),
             BlockNumber::Earliest => f.write_str("earliest"),
             BlockNumber::Pending => f.write_str("pending"),
         }
@@ -689,11 +740,20 @@ mod tests {
 
     #[test]
     fn serde_block_number() {
-        for b in &[BlockNumber::Latest, BlockNumber::Earliest, BlockNumber::Pending] {
+        for b in &[
+            BlockNumber::Latest,
+            BlockNumber::Finalized,
+            BlockNumber::Safe,
+            BlockNumber::Earliest,
+            BlockNumber::Pending,
+        ] {
             let b_ser = serde_json::to_string(&b).unwrap();
             let b_de: BlockNumber = serde_json::from_str(&b_ser).unwrap();
             assert_eq!(b_de, *b);
+            assert_eq!(b_ser, format!("\"{}\"", b));
         }
+        assert_eq!(serde_json::to_string(&BlockNumber::Finalized).unwrap(), "\"finalized\"");
+        assert_eq!(serde_json::to_string(&BlockNumber::Safe).unwrap(), "\"safe\"");
 
         let b = BlockNumber::Number(1042u64.into());
         let b_ser = serde_json::to_string(&b).unwrap();
@@ -708,6 +768,38 @@ mod tests {
         let _block: Block<TxHash> = serde_json::from_str(block).unwrap();
     }
 
+    #[test]
+    fn deserialize_new_heads_notification() {
+        // headers of `newHeads` subscriptions lack the transactions, uncles, size and total
+        // difficulty of full blocks
+        let header = serde_json::json!({
+            "parentHash": "0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "stateRoot": "0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b",
+            "transactionsRoot": "0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d",
+            "receiptsRoot": "0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2",
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "difficulty": "0x0",
+            "number": "0x3",
+            "gasLimit": "0x6691b7",
+            "gasUsed": "0x5208",
+            "timestamp": "0x5ecedbb9",
+            "extraData": "0x",
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce": "0x0000000000000000",
+            "baseFeePerGas": "0x7",
+            "hash": "0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972"
+        });
+        let block: Block<TxHash> = serde_json::from_value(header).unwrap();
+        assert_eq!(block.number, Some(3u64.into()));
+        assert_eq!(block.base_fee_per_gas, Some(7u64.into()));
+        assert!(block.transactions.is_empty());
+        assert!(block.uncles.is_empty());
+        assert!(block.size.is_none());
+        assert!(block.total_difficulty.is_none());
+    }
+
     #[test]
     fn deserialize_blk_with_txs() {
         let block = r#"{"number":"0x3","hash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","parentHash":"0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":"0x0000000000000000","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","transactionsRoot":"0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d","stateRoot":"0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b","receiptsRoot":"0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2","miner":"0x0000000000000000000000000000000000000000","difficulty":"0x0","totalDifficulty":"0x0","extraData":"0x","size":"0x3e8","gasLimit":"0x6691b7","gasUsed":"0x5208","timestamp":"0x5ecedbb9","transactions":[{"hash":"0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067","nonce":"0x2","blockHash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","blockNumber":"0x3","transactionIndex":"0x0","from":"0xfdcedc3bfca10ecb0890337fbdd1977aba84807a","to":"0xdca8ce283150ab773bcbeb8d38289bdb5661de1e","value":"0x0","gas":"0x15f90","gasPrice":"0x4a817c800","input":"0x","v":"0x25","r":"0x19f2694eb9113656dbea0b925e2e7ceb43df83e601c4116aee9c0dd99130be88","s":"0x73e5764b324a4f7679d890a198ba658ba1c8cd36983ff9797e10b1b89dbb448e"}],"uncles":[]}"#;
@@ -752,6 +844,58 @@ mod tests {
         assert_eq!(block.base_fee_per_gas, Some(U256::from(7)));
     }
 
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn pre_london_block_has_no_fee_or_withdrawals() {
+        let block = r#"{"number":"0x3","hash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","parentHash":"0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90","miner":"0x0000000000000000000000000000000000000000","difficulty":"0x0","totalDifficulty":"0x0","extraData":"0x","size":"0x3e8","gasLimit":"0x6691b7","gasUsed":"0x5208","timestamp":"0x5ecedbb9","transactions":[],"uncles":[]}"#;
+        let block: Block<TxHash> = serde_json::from_str(block).unwrap();
+        assert_eq!(block.base_fee_per_gas, None);
+        assert_eq!(block.withdrawals, None);
+        assert_eq!(block.withdrawals_root, None);
+        assert!(block.other.is_empty());
+
+        let json = serde_json::to_value(&block).unwrap();
+        assert!(json.get("withdrawals").is_none());
+        assert!(json.get("withdrawalsRoot").is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn post_shanghai_block() {
+        let json = serde_json::json!({
+            "baseFeePerGas": "0x3b9aca00",
+            "number": "0x1",
+            "hash": "0x0e670ec64341771606e55d6b4ca35a1a6b75ee3d5145a99d05921026d1527331",
+            "transactions": [],
+            "uncles": [],
+            "withdrawalsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "withdrawals": [{
+                "index": "0xf",
+                "validatorIndex": "0x1d3c",
+                "address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f",
+                "amount": "0xd3c5a4"
+            }]
+        });
+
+        let block: Block<TxHash> = serde_json::from_value(json).unwrap();
+        assert_eq!(block.base_fee_per_gas, Some(U256::from(1_000_000_000u64)));
+        assert_eq!(
+            block.withdrawals,
+            Some(vec![Withdrawal {
+                index: 15u64.into(),
+                validator_index: 7484u64.into(),
+                address: "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f".parse().unwrap(),
+                amount: 13878692u64.into(),
+            }])
+        );
+        assert!(block.withdrawals_root.is_some());
+        assert!(block.other.is_empty());
+
+        let full = block.clone().into_full_block(vec![]);
+        assert_eq!(full.withdrawals, block.withdrawals);
+        assert_eq!(Block::<TxHash>::from(full), block);
+    }
+
     #[test]
     fn test_next_block_base_fee() {
         // <https://etherscan.io/block/14402566>
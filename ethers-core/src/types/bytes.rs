@@ -10,7 +10,7 @@ use std::{
 use thiserror::Error;
 
 /// Wrapper type around Bytes to deserialize/serialize "0x" prefixed ethereum hex strings
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Ord, PartialOrd)]
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Ord, PartialOrd)]
 pub struct Bytes(
     #[serde(serialize_with = "serialize_bytes", deserialize_with = "deserialize_bytes")]
     pub  bytes::Bytes,
@@ -20,6 +20,12 @@ fn bytes_to_hex(b: &Bytes) -> String {
     hex::encode(b.0.as_ref())
 }
 
+impl Debug for Bytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Bytes(0x{})", bytes_to_hex(self))
+    }
+}
+
 impl Display for Bytes {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "0x{}", bytes_to_hex(self))
@@ -33,6 +39,11 @@ impl LowerHex for Bytes {
 }
 
 impl Bytes {
+    /// Creates bytes from a static slice without copying it
+    pub const fn from_static(bytes: &'static [u8]) -> Self {
+        Self(bytes::Bytes::from_static(bytes))
+    }
+
     pub fn to_vec(&self) -> Vec<u8> {
         self.as_ref().to_vec()
     }
@@ -154,14 +165,17 @@ impl FromStr for Bytes {
     type Err = ParseBytesError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if let Some(value) = value.strip_prefix("0x") {
-            hex::decode(value)
-        } else {
-            hex::decode(&value)
-        }
-        .map(Into::into)
-        .map_err(|e| ParseBytesError(format!("Invalid hex: {}", e)))
+        parse_hex(value).map(Into::into)
+    }
+}
+
+/// Decodes a hex string with or without `0x` prefix
+fn parse_hex(value: &str) -> Result<Vec<u8>, ParseBytesError> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if value.len() % 2 != 0 {
+        return Err(ParseBytesError(format!("Odd number of hex digits: {}", value.len())))
     }
+    hex::decode(value).map_err(|e| ParseBytesError(format!("Invalid hex: {}", e)))
 }
 
 pub fn serialize_bytes<S, T>(x: T, s: S) -> Result<S::Ok, S::Error>
@@ -177,13 +191,7 @@ where
     D: Deserializer<'de>,
 {
     let value = String::deserialize(d)?;
-    if let Some(value) = value.strip_prefix("0x") {
-        hex::decode(value)
-    } else {
-        hex::decode(&value)
-    }
-    .map(Into::into)
-    .map_err(|e| serde::de::Error::custom(e.to_string()))
+    parse_hex(&value).map(Into::into).map_err(serde::de::Error::custom)
 }
 
 #[cfg(test)]
@@ -208,5 +216,29 @@ mod tests {
         let b = Bytes::from_str("1213");
         let b = b.unwrap();
         assert_eq!(b.as_ref(), hex::decode("1213").unwrap());
+
+        assert!(Bytes::from_str("0x123").is_err());
+        assert!(Bytes::from_str("0xzz").is_err());
+    }
+
+    #[test]
+    fn debug_formatting() {
+        let b = Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(format!("{:?}", b), "Bytes(0xdeadbeef)");
+    }
+
+    #[test]
+    fn parse_and_serde_agree() {
+        let parsed = "0xdeadbeef".parse::<Bytes>().unwrap();
+        let deserialized: Bytes = serde_json::from_str(r#""0xdeadbeef""#).unwrap();
+        assert_eq!(parsed, deserialized);
+        assert_eq!(parsed, Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(serde_json::from_str::<Bytes>(r#""deadbeef""#).unwrap(), parsed);
+
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), r#""0xdeadbeef""#);
+        assert_eq!(parsed.to_string(), "0xdeadbeef");
+        assert_eq!(parsed.to_string().parse::<Bytes>().unwrap(), parsed);
+
+        assert!(serde_json::from_str::<Bytes>(r#""0xdeadbee""#).is_err());
     }
 }
@@ -0,0 +1,234 @@
+//! [CAIP-2](https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-2.md) chain and
+//! [CAIP-10](https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-10.md) account
+//! identifiers for the `eip155` namespace, e.g. `eip155:1` and
+//! `eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb`
+use crate::{types::Address, utils::to_checksum};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// The CAIP-2 namespace of EVM chains
+pub const EIP155_NAMESPACE: &str = "eip155";
+
+#[derive(Debug, Clone, Error)]
+#[error("Failed to parse CAIP identifier: {0}")]
+pub struct ParseCaipError(String);
+
+/// A CAIP-2 chain identifier in the `eip155` namespace, e.g. `eip155:1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Caip2 {
+    /// The EIP-155 chain id
+    pub chain_id: u64,
+}
+
+impl Caip2 {
+    /// Creates the identifier of the chain with the given id
+    pub fn new(chain_id: u64) -> Self {
+        Self { chain_id }
+    }
+}
+
+impl fmt::Display for Caip2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", EIP155_NAMESPACE, self.chain_id)
+    }
+}
+
+impl FromStr for Caip2 {
+    type Err = ParseCaipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, reference) = s
+            .split_once(':')
+            .ok_or_else(|| ParseCaipError(format!("missing chain reference in `{}`", s)))?;
+        if namespace != EIP155_NAMESPACE {
+            return Err(ParseCaipError(format!(
+                "unsupported namespace `{}`, expected `{}`",
+                namespace, EIP155_NAMESPACE
+            )))
+        }
+        parse_chain_id(reference).map(Self::new)
+    }
+}
+
+impl From<u64> for Caip2 {
+    fn from(chain_id: u64) -> Self {
+        Self::new(chain_id)
+    }
+}
+
+impl From<Caip2> for u64 {
+    fn from(caip: Caip2) -> Self {
+        caip.chain_id
+    }
+}
+
+/// A CAIP-10 account identifier in the `eip155` namespace, e.g.
+/// `eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb`
+///
+/// The address is formatted with its EIP-55 checksum, but parsed case-insensitively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Caip10 {
+    /// The EIP-155 chain id
+    pub chain_id: u64,
+    /// The account's address
+    pub address: Address,
+}
+
+impl Caip10 {
+    /// Creates the identifier of `address` on the chain with the given id
+    pub fn new(chain_id: u64, address: Address) -> Self {
+        Self { chain_id, address }
+    }
+
+    /// Returns the CAIP-2 identifier of the account's chain
+    pub fn chain(&self) -> Caip2 {
+        Caip2::new(self.chain_id)
+    }
+}
+
+impl fmt::Display for Caip10 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.chain(), to_checksum(&self.address, None))
+    }
+}
+
+impl FromStr for Caip10 {
+    type Err = ParseCaipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chain, address) = s
+            .rsplit_once(':')
+            .ok_or_else(|| ParseCaipError(format!("missing account address in `{}`", s)))?;
+        let chain: Caip2 = chain.parse()?;
+        let address = address
+            .strip_prefix("0x")
+            .filter(|hex| hex.len() == 40)
+            .and_then(|hex| hex.parse().ok())
+            .ok_or_else(|| ParseCaipError(format!("invalid account address `{}`", address)))?;
+        Ok(Self::new(chain.chain_id, address))
+    }
+}
+
+impl From<(u64, Address)> for Caip10 {
+    fn from((chain_id, address): (u64, Address)) -> Self {
+        Self::new(chain_id, address)
+    }
+}
+
+impl From<Caip10> for (u64, Address) {
+    fn from(caip: Caip10) -> Self {
+        (caip.chain_id, caip.address)
+    }
+}
+
+fn parse_chain_id(reference: &str) -> Result<u64, ParseCaipError> {
+    let err = || ParseCaipError(format!("invalid chain id `{}`", reference));
+    // the reference is the decimal chain id, without sign or leading zeros
+    if reference.is_empty() ||
+        !reference.bytes().all(|b| b.is_ascii_digit()) ||
+        (reference.len() > 1 && reference.starts_with('0'))
+    {
+        return Err(err())
+    }
+    reference.parse().map_err(|_| err())
+}
+
+macro_rules! impl_serde_via_str {
+    ($($ty:ty),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+            }
+        }
+    )*};
+}
+
+impl_serde_via_str!(Caip2, Caip10);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caip2_roundtrip() {
+        for (s, chain_id) in [("eip155:1", 1), ("eip155:137", 137), ("eip155:11155111", 11155111)] {
+            let caip: Caip2 = s.parse().unwrap();
+            assert_eq!(caip.chain_id, chain_id);
+            assert_eq!(caip.to_string(), s);
+            assert_eq!(u64::from(caip), chain_id);
+            assert_eq!(Caip2::from(chain_id), caip);
+        }
+    }
+
+    #[test]
+    fn caip10_roundtrip() {
+        for s in [
+            "eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb",
+            "eip155:137:0x0495EE61A6c19494Aa18326d08A961c446423cA2",
+            "eip155:10:0x0000000000000000000000000000000000000000",
+        ] {
+            let caip: Caip10 = s.parse().unwrap();
+            assert_eq!(caip.to_string(), s);
+
+            let (chain_id, address) = caip.into();
+            assert_eq!(Caip10::from((chain_id, address)), caip);
+            assert_eq!(caip.chain(), Caip2::new(chain_id));
+        }
+    }
+
+    #[test]
+    fn caip10_parses_addresses_case_insensitively() {
+        let caip: Caip10 = "eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb".parse().unwrap();
+        assert_eq!(caip.to_string(), "eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb");
+        assert_eq!(
+            caip.address,
+            "0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb".parse::<Address>().unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_identifiers() {
+        for s in [
+            "",
+            "eip155",
+            "eip155:",
+            "cosmos:cosmoshub-3",
+            "EIP155:1",
+            "eip155:0x1",
+            "eip155:+1",
+            "eip155:01",
+            "eip155:1:2",
+            "eip155:99999999999999999999",
+        ] {
+            assert!(s.parse::<Caip2>().is_err(), "{}", s);
+        }
+
+        for s in [
+            "eip155:1",
+            "eip155:1:",
+            "eip155:1:ab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb",
+            "eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcd",
+            "eip155:1:0xzb16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb",
+            "bip122:000000000019d6689c085ae165831e93:128Lkh3S7CkDTBZ8W7BbpsN3YYizJMp8p6",
+            "eip155::0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb",
+        ] {
+            assert!(s.parse::<Caip10>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn serde_as_strings() {
+        let caip = Caip10::new(5, Address::zero());
+        let json = serde_json::to_string(&caip).unwrap();
+        assert_eq!(json, r#""eip155:5:0x0000000000000000000000000000000000000000""#);
+        assert_eq!(serde_json::from_str::<Caip10>(&json).unwrap(), caip);
+        assert_eq!(serde_json::to_string(&caip.chain()).unwrap(), r#""eip155:5""#);
+    }
+}
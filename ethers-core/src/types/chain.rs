@@ -243,6 +243,55 @@ impl Chain {
     }
 }
 
+impl Chain {
+    /// Returns the number of confirmations after which a transaction on the chain can be
+    /// considered safe from reorgs.
+    ///
+    /// Rollups and chains with instant finality need a single confirmation, while chains with
+    /// probabilistic finality need more.
+    pub fn recommended_confirmations(&self) -> usize {
+        match self {
+            Chain::Mainnet | Chain::Rsk => 12,
+            Chain::Morden |
+            Chain::Ropsten |
+            Chain::Rinkeby |
+            Chain::Goerli |
+            Chain::Kovan |
+            Chain::Sepolia |
+            Chain::Poa |
+            Chain::Sokol |
+            Chain::XDai => 3,
+            Chain::Polygon | Chain::PolygonMumbai => 64,
+            Chain::BinanceSmartChain | Chain::BinanceSmartChainTestnet => 15,
+            Chain::Moonbeam | Chain::Moonbase | Chain::Moonriver => 2,
+            Chain::Dev |
+            Chain::AnvilHardhat |
+            Chain::MoonbeamDev |
+            Chain::Optimism |
+            Chain::OptimismKovan |
+            Chain::Arbitrum |
+            Chain::ArbitrumTestnet |
+            Chain::Fantom |
+            Chain::FantomTestnet |
+            Chain::Avalanche |
+            Chain::AvalancheFuji |
+            Chain::Cronos |
+            Chain::CronosTestnet |
+            Chain::Oasis |
+            Chain::Emerald |
+            Chain::EmeraldTestnet |
+            Chain::Evmos |
+            Chain::EvmosTestnet => 1,
+        }
+    }
+
+    /// Returns the [recommended confirmations](Self::recommended_confirmations) of the chain with
+    /// the given id, or those of mainnet if the chain is unknown
+    pub fn recommended_confirmations_of(chain_id: u64) -> usize {
+        Chain::try_from(chain_id).unwrap_or(Chain::Mainnet).recommended_confirmations()
+    }
+}
+
 impl Default for Chain {
     fn default() -> Self {
         Chain::Mainnet
@@ -253,3 +302,14 @@ impl Default for Chain {
 fn test_default_chain() {
     assert_eq!(Chain::default(), Chain::Mainnet);
 }
+
+#[test]
+fn test_recommended_confirmations() {
+    assert_eq!(Chain::Mainnet.recommended_confirmations(), 12);
+    assert_eq!(Chain::Optimism.recommended_confirmations(), 1);
+    assert_eq!(Chain::AnvilHardhat.recommended_confirmations(), 1);
+    assert_eq!(Chain::Polygon.recommended_confirmations(), 64);
+    assert_eq!(Chain::recommended_confirmations_of(56), 15);
+    // unknown chains fall back to mainnet
+    assert_eq!(Chain::recommended_confirmations_of(424242), 12);
+}
@@ -20,6 +20,41 @@ pub struct FeeHistory {
     pub reward: Vec<Vec<U256>>,
 }
 
+/// The safe, standard and fast tiers of a fee estimate
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasTiers<T> {
+    /// The fee of transactions which can wait a while to be included
+    pub safe: T,
+    /// The fee of transactions which should be included soon
+    pub standard: T,
+    /// The fee of transactions which should be included as soon as possible
+    pub fast: T,
+}
+
+impl<T> GasTiers<T> {
+    /// Maps the fee of every tier with `f`
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> GasTiers<U> {
+        GasTiers { safe: f(self.safe), standard: f(self.standard), fast: f(self.fast) }
+    }
+}
+
+/// The fees of an EIP-1559 transaction
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// The gas price tiers of a chain, either as legacy gas prices or as EIP-1559 fees
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasPriceTiers {
+    /// The gas prices of a chain without EIP-1559
+    Legacy(GasTiers<U256>),
+    /// The EIP-1559 fees of a chain with a base fee
+    Eip1559(GasTiers<Eip1559Fees>),
+}
+
 fn from_int_or_hex<'de, D>(deserializer: D) -> Result<U256, D::Error>
 where
     D: Deserializer<'de>,
@@ -11,6 +11,7 @@ pub mod transaction;
 pub use transaction::{
     eip1559::Eip1559TransactionRequest,
     eip2930::Eip2930TransactionRequest,
+    eip7702::Eip7702TransactionRequest,
     request::TransactionRequest,
     response::{Transaction, TransactionReceipt},
 };
@@ -31,7 +32,7 @@ mod bytes;
 pub use self::bytes::{deserialize_bytes, serialize_bytes, Bytes, ParseBytesError};
 
 mod block;
-pub use block::{Block, BlockId, BlockNumber, TimeError};
+pub use block::{Block, BlockId, BlockNumber, TimeError, Withdrawal};
 
 #[cfg(feature = "celo")]
 pub use block::Randomness;
@@ -57,10 +58,16 @@ pub use trace::*;
 mod chain;
 pub use chain::*;
 
+mod caip;
+pub use caip::{Caip10, Caip2, ParseCaipError, EIP155_NAMESPACE};
+
 mod proof;
 
 pub use proof::*;
 
+mod account;
+pub use account::AccountInfo;
+
 mod fee;
 pub use fee::*;
 
@@ -1,23 +1,51 @@
 use crate::types::{Address, Bytes, H256, U256};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
+/// The Merkle proof of a storage slot, see [`EIP1186ProofResponse`]
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct StorageProof {
+    /// The requested storage key
+    #[serde(deserialize_with = "deserialize_storage_key")]
     pub key: H256,
+    /// The RLP encoded nodes of the path from the storage root to the value
     pub proof: Vec<Bytes>,
+    /// The value stored in the slot
     pub value: U256,
 }
 
+/// The response of `eth_getProof`, as defined in [EIP-1186](https://eips.ethereum.org/EIPS/eip-1186)
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EIP1186ProofResponse {
-    address: Address,
-    balance: U256,
-    code_hash: H256,
-    nonce: U256,
-    storage_hash: H256,
-    account_proof: Vec<Bytes>,
-    storage_proof: Vec<StorageProof>,
+    /// The address of the account
+    pub address: Address,
+    /// The balance of the account
+    pub balance: U256,
+    /// The hash of the account's code
+    pub code_hash: H256,
+    /// The nonce of the account
+    pub nonce: U256,
+    /// The root of the account's storage trie
+    pub storage_hash: H256,
+    /// The RLP encoded nodes of the path from the state root to the account
+    pub account_proof: Vec<Bytes>,
+    /// The proofs of the requested storage slots
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// Deserializes a storage key, which some nodes return without leading zeros, e.g. `0x0`
+fn deserialize_storage_key<'de, D>(deserializer: D) -> Result<H256, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let key = String::deserialize(deserializer)?;
+    let key = key.strip_prefix("0x").unwrap_or(&key);
+    if key.len() > 64 {
+        return Err(serde::de::Error::custom(format!("storage key too long: 0x{}", key)))
+    }
+    let padded = format!("{:0>64}", key);
+    let bytes = hex::decode(padded).map_err(serde::de::Error::custom)?;
+    Ok(H256::from_slice(&bytes))
 }
 
 #[cfg(test)]
@@ -29,4 +57,19 @@ mod tests {
         serde_json::from_str::<EIP1186ProofResponse>(include_str!("../../testdata/proof.json"))
             .unwrap();
     }
+
+    #[test]
+    fn can_deserialize_short_storage_keys() {
+        let proof: StorageProof =
+            serde_json::from_str(r#"{"key":"0x1","proof":["0x80"],"value":"0x2a"}"#).unwrap();
+        assert_eq!(proof.key, H256::from_low_u64_be(1));
+        assert_eq!(proof.proof, vec![Bytes::from(vec![0x80])]);
+        assert_eq!(proof.value, 42u64.into());
+
+        let key = format!("0x{}", "ff".repeat(32));
+        let proof: StorageProof =
+            serde_json::from_str(&format!(r#"{{"key":"{}","proof":[],"value":"0x0"}}"#, key))
+                .unwrap();
+        assert_eq!(proof.key, H256::repeat_byte(0xff));
+    }
 }
@@ -0,0 +1,397 @@
+//! Types for the Geth debug tracing API
+//!
+//! <https://geth.ethereum.org/docs/rpc/ns-debug#debug_tracetransaction>
+use crate::types::{Address, Bytes, Selector, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, convert::TryInto};
+
+/// The name of geth's built-in call tracer
+pub const CALL_TRACER: &str = "callTracer";
+
+/// Options passed to `debug_traceTransaction` and `debug_traceCall`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethDebugTracingOptions {
+    /// Disables the capture of storage (struct logger only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    /// Disables the capture of the stack (struct logger only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    /// Enables the capture of memory (struct logger only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    /// Enables the capture of return data (struct logger only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+    /// The tracer to use instead of the default struct logger, e.g. [`CALL_TRACER`] or a
+    /// JavaScript expression
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<String>,
+    /// Overrides the default timeout of 5 seconds for JavaScript-based tracing calls, e.g. `"10s"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+impl GethDebugTracingOptions {
+    /// Returns options which trace the call tree with geth's built-in [`CALL_TRACER`]
+    pub fn call_tracer() -> Self {
+        Self { tracer: Some(CALL_TRACER.to_string()), ..Default::default() }
+    }
+}
+
+/// The result of a geth debug trace.
+///
+/// Which variant is returned depends on the tracer that was requested via
+/// [`GethDebugTracingOptions::tracer`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GethTrace {
+    /// Output of the default struct logger
+    Default(DefaultFrame),
+    /// Output of the [`CALL_TRACER`]
+    CallTracer(CallFrame),
+    /// Output of any other (e.g. JavaScript) tracer
+    Unknown(serde_json::Value),
+}
+
+/// Output of the default struct logger
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultFrame {
+    /// Whether the execution failed
+    pub failed: bool,
+    /// Total gas used by the execution
+    pub gas: u64,
+    /// Data returned by the execution
+    pub return_value: Bytes,
+    /// The executed opcodes
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// A single opcode executed by the EVM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// The call depth
+    pub depth: u64,
+    /// The error raised by this opcode, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Remaining gas before executing the opcode
+    pub gas: u64,
+    /// Gas cost of the opcode
+    pub gas_cost: u64,
+    /// The memory as 32 byte hex words, unless disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    /// The opcode name
+    pub op: String,
+    /// The program counter
+    pub pc: u64,
+    /// The refund counter
+    #[serde(default, rename = "refund", skip_serializing_if = "Option::is_none")]
+    pub refund_counter: Option<u64>,
+    /// The stack, unless disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    /// The storage slots accessed so far as hex words, unless disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<String, String>>,
+}
+
+/// A call frame produced by the [`CALL_TRACER`], including all of its nested calls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// The call type, e.g. `CALL`, `DELEGATECALL` or `CREATE`
+    #[serde(rename = "type")]
+    pub typ: String,
+    /// The caller
+    pub from: Address,
+    /// The callee, absent if the creation failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// The transferred value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// The gas provided to the call
+    pub gas: U256,
+    /// The gas used by the call
+    pub gas_used: U256,
+    /// The call data
+    pub input: Bytes,
+    /// The returned data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    /// The error, if the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The decoded revert reason, if the call reverted with `Error(string)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// The nested calls
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+/// The gas profile of a call and its nested calls, built from a [`CallFrame`] of the
+/// [`CALL_TRACER`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallProfile {
+    /// The call type, e.g. `CALL`, `DELEGATECALL` or `CREATE`
+    #[serde(rename = "type")]
+    pub typ: String,
+    /// The caller
+    pub from: Address,
+    /// The callee, absent if the creation failed
+    pub to: Option<Address>,
+    /// The function selector, if the call data contains one
+    pub selector: Option<Selector>,
+    /// The gas used by the call, including its nested calls
+    pub gas_used: U256,
+    /// The gas used by the call itself, excluding its nested calls
+    pub self_gas_used: U256,
+    /// The error, if the call failed
+    pub error: Option<String>,
+    /// The profiles of the nested calls
+    pub calls: Vec<CallProfile>,
+}
+
+impl CallProfile {
+    /// Returns the call and all of its nested calls in depth-first order, along with their call
+    /// depth starting at `0`
+    pub fn flatten(&self) -> Vec<(usize, &CallProfile)> {
+        fn visit<'a>(
+            profile: &'a CallProfile,
+            depth: usize,
+            out: &mut Vec<(usize, &'a CallProfile)>,
+        ) {
+            out.push((depth, profile));
+            for call in &profile.calls {
+                visit(call, depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        visit(self, 0, &mut out);
+        out
+    }
+}
+
+impl From<CallFrame> for CallProfile {
+    fn from(frame: CallFrame) -> Self {
+        let calls: Vec<CallProfile> = frame.calls.into_iter().map(Into::into).collect();
+        let nested_gas_used =
+            calls.iter().fold(U256::zero(), |acc, call| acc.saturating_add(call.gas_used));
+        let selector = frame.input.get(..4).map(|s| s.try_into().expect("4 bytes"));
+        Self {
+            typ: frame.typ,
+            from: frame.from,
+            to: frame.to,
+            selector,
+            self_gas_used: frame.gas_used.saturating_sub(nested_gas_used),
+            gas_used: frame.gas_used,
+            error: frame.error,
+            calls,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_tracing_options() {
+        let opts = GethDebugTracingOptions {
+            disable_storage: Some(true),
+            enable_memory: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_string(&opts).unwrap(),
+            r#"{"disableStorage":true,"enableMemory":false}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&GethDebugTracingOptions::call_tracer()).unwrap(),
+            r#"{"tracer":"callTracer"}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_struct_logger_trace() {
+        let s = r#"{
+            "failed": false,
+            "gas": 26809,
+            "returnValue": "",
+            "structLogs": [
+                {
+                    "pc": 0,
+                    "op": "PUSH1",
+                    "gas": 78978,
+                    "gasCost": 3,
+                    "depth": 1,
+                    "stack": []
+                },
+                {
+                    "pc": 2,
+                    "op": "SSTORE",
+                    "gas": 78975,
+                    "gasCost": 20000,
+                    "depth": 1,
+                    "refund": 0,
+                    "stack": ["0x80", "0x40"],
+                    "memory": ["0000000000000000000000000000000000000000000000000000000000000000"],
+                    "storage": {
+                        "0000000000000000000000000000000000000000000000000000000000000040": "0000000000000000000000000000000000000000000000000000000000000080"
+                    }
+                }
+            ]
+        }"#;
+
+        let trace: GethTrace = serde_json::from_str(s).unwrap();
+        let frame = match trace {
+            GethTrace::Default(frame) => frame,
+            _ => panic!("expected a struct logger trace"),
+        };
+        assert!(!frame.failed);
+        assert_eq!(frame.gas, 26809);
+        assert!(frame.return_value.is_empty());
+        assert_eq!(frame.struct_logs.len(), 2);
+
+        let sstore = &frame.struct_logs[1];
+        assert_eq!(sstore.op, "SSTORE");
+        assert_eq!(sstore.gas_cost, 20000);
+        assert_eq!(sstore.refund_counter, Some(0));
+        assert_eq!(sstore.stack, Some(vec![0x80.into(), 0x40.into()]));
+        assert_eq!(sstore.storage.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_call_tracer_trace() {
+        let s = r#"{
+            "type": "CALL",
+            "from": "0x5067c042e35881843f2b31dfc2db1f4f272ef48c",
+            "to": "0x3ee18b2214aff97000d974cf647e7c347e8fa585",
+            "value": "0xde0b6b3a7640000",
+            "gas": "0x30d40",
+            "gasUsed": "0x2c2a",
+            "input": "0xa9059cbb",
+            "output": "0x08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000a6e6f7420656e6f75676800000000000000000000000000000000000000000000",
+            "error": "execution reverted",
+            "revertReason": "not enough",
+            "calls": [
+                {
+                    "type": "DELEGATECALL",
+                    "from": "0x3ee18b2214aff97000d974cf647e7c347e8fa585",
+                    "to": "0x76264869a3eba9f1ad1d8a2e25ce6d3ac3d13d4a",
+                    "gas": "0x2e9a3",
+                    "gasUsed": "0x1b1e",
+                    "input": "0xa9059cbb",
+                    "calls": [
+                        {
+                            "type": "STATICCALL",
+                            "from": "0x76264869a3eba9f1ad1d8a2e25ce6d3ac3d13d4a",
+                            "to": "0x0000000000000000000000000000000000000001",
+                            "gas": "0x2d000",
+                            "gasUsed": "0xbb8",
+                            "input": "0x",
+                            "output": "0x"
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let trace: GethTrace = serde_json::from_str(s).unwrap();
+        let frame = match trace {
+            GethTrace::CallTracer(frame) => frame,
+            _ => panic!("expected a call tracer trace"),
+        };
+        assert_eq!(frame.typ, "CALL");
+        assert_eq!(frame.value, Some(U256::exp10(18)));
+        assert_eq!(frame.gas_used, 0x2c2a.into());
+        assert_eq!(frame.error.as_deref(), Some("execution reverted"));
+        assert_eq!(frame.revert_reason.as_deref(), Some("not enough"));
+
+        let delegate = &frame.calls[0];
+        assert_eq!(delegate.typ, "DELEGATECALL");
+        assert_eq!(delegate.value, None);
+        assert_eq!(delegate.output, None);
+
+        let nested = &delegate.calls[0];
+        assert_eq!(nested.typ, "STATICCALL");
+        assert_eq!(nested.to, Some(Address::from_low_u64_be(1)));
+        assert_eq!(nested.output, Some(Bytes::default()));
+        assert!(nested.calls.is_empty());
+
+        // round trips
+        let s = serde_json::to_string(&frame).unwrap();
+        assert_eq!(serde_json::from_str::<CallFrame>(&s).unwrap(), frame);
+    }
+
+    #[test]
+    fn profiles_call_tree() {
+        let s = r#"{
+            "type": "CALL",
+            "from": "0x5067c042e35881843f2b31dfc2db1f4f272ef48c",
+            "to": "0x3ee18b2214aff97000d974cf647e7c347e8fa585",
+            "gas": "0x30d40",
+            "gasUsed": "0x7530",
+            "input": "0xa9059cbb0000",
+            "calls": [
+                {
+                    "type": "DELEGATECALL",
+                    "from": "0x3ee18b2214aff97000d974cf647e7c347e8fa585",
+                    "to": "0x76264869a3eba9f1ad1d8a2e25ce6d3ac3d13d4a",
+                    "gas": "0x2e9a3",
+                    "gasUsed": "0x2710",
+                    "input": "0xa9059cbb0000",
+                    "calls": [
+                        {
+                            "type": "STATICCALL",
+                            "from": "0x76264869a3eba9f1ad1d8a2e25ce6d3ac3d13d4a",
+                            "to": "0x0000000000000000000000000000000000000001",
+                            "gas": "0x2d000",
+                            "gasUsed": "0xbb8",
+                            "input": "0x"
+                        }
+                    ]
+                },
+                {
+                    "type": "CALL",
+                    "from": "0x3ee18b2214aff97000d974cf647e7c347e8fa585",
+                    "to": "0x0000000000000000000000000000000000000002",
+                    "gas": "0x1388",
+                    "gasUsed": "0x3e8",
+                    "input": "0x70a08231",
+                    "error": "out of gas"
+                }
+            ]
+        }"#;
+        let frame: CallFrame = serde_json::from_str(s).unwrap();
+        let profile = CallProfile::from(frame);
+
+        assert_eq!(profile.selector, Some([0xa9, 0x05, 0x9c, 0xbb]));
+        assert_eq!(profile.gas_used, 30000u64.into());
+        // 30000 - 10000 - 1000
+        assert_eq!(profile.self_gas_used, 19000u64.into());
+        assert_eq!(profile.calls[0].self_gas_used, 7000u64.into());
+        assert_eq!(profile.calls[0].calls[0].selector, None);
+        assert_eq!(profile.calls[1].error.as_deref(), Some("out of gas"));
+
+        let flattened = profile.flatten();
+        let depths = flattened.iter().map(|(depth, _)| *depth).collect::<Vec<_>>();
+        assert_eq!(depths, vec![0, 1, 2, 1]);
+        // the gas used by all frames adds up to the gas used by the call
+        let total = flattened.iter().fold(U256::zero(), |acc, (_, p)| acc + p.self_gas_used);
+        assert_eq!(total, profile.gas_used);
+    }
+
+    #[test]
+    fn deserialize_custom_tracer_trace() {
+        let trace: GethTrace = serde_json::from_str(r#"{"opcodes": 42}"#).unwrap();
+        assert_eq!(trace, GethTrace::Unknown(serde_json::json!({"opcodes": 42})));
+    }
+}
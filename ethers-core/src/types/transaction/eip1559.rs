@@ -1,6 +1,6 @@
 use super::{decode_to, eip2718::TypedTransaction, eip2930::AccessList, normalize_v, rlp_opt};
 use crate::types::{
-    Address, Bytes, NameOrAddress, Signature, SignatureError, Transaction, U256, U64,
+    Address, Bytes, NameOrAddress, Signature, SignatureError, Transaction, H256, U256, U64,
 };
 use rlp::{Decodable, DecoderError, RlpStream};
 use thiserror::Error;
@@ -140,6 +140,18 @@ impl Eip1559TransactionRequest {
         self
     }
 
+    /// Adds the storage keys of the address to the `access_list` of the transaction, see
+    /// [`AccessList::add_item`]
+    #[must_use]
+    pub fn add_access_list_item<T: Into<Address>>(
+        mut self,
+        address: T,
+        storage_keys: Vec<H256>,
+    ) -> Self {
+        self.access_list.add_item(address, storage_keys);
+        self
+    }
+
     /// Sets the `nonce` field in the transaction to the provided value
     #[must_use]
     pub fn nonce<T: Into<U256>>(mut self, nonce: T) -> Self {
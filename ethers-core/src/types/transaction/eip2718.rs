@@ -1,6 +1,7 @@
 use super::{
     eip1559::{Eip1559RequestError, Eip1559TransactionRequest},
     eip2930::{AccessList, Eip2930RequestError, Eip2930TransactionRequest},
+    eip7702::{Eip7702TransactionRequest, EIP7702_TX_TYPE},
     request::RequestError,
 };
 use crate::{
@@ -10,7 +11,7 @@ use crate::{
     utils::keccak256,
 };
 use rlp::Decodable;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
 /// The TypedTransaction enum represents all Ethereum transaction types.
@@ -19,12 +20,17 @@ use thiserror::Error;
 /// 1. Legacy (pre-EIP2718) [`TransactionRequest`]
 /// 2. EIP2930 (state access lists) [`Eip2930TransactionRequest`]
 /// 3. EIP1559 [`Eip1559TransactionRequest`]
+/// 4. EIP7702 (set code) [`Eip7702TransactionRequest`]
 ///
 /// To support Kovan and other non-London-compatbile networks, please enable
 /// the `legacy` crate feature. This will disable the `type` flag in the
 /// serialized transaction, and cause contract calls and other common actions
 /// to default to using the legacy transaction type.
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+///
+/// Deserializing a transaction which sets the legacy `gasPrice` together with EIP-1559 fee fields
+/// fails with [`ValidationError::MixedFeeFields`], instead of dropping the fields of the other
+/// transaction type.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(not(feature = "legacy"), serde(tag = "type"))]
 #[cfg_attr(feature = "legacy", serde(untagged))]
 pub enum TypedTransaction {
@@ -37,6 +43,46 @@ pub enum TypedTransaction {
     // 0x02
     #[serde(rename = "0x02")]
     Eip1559(Eip1559TransactionRequest),
+    // 0x04
+    #[serde(rename = "0x04")]
+    Eip7702(Eip7702TransactionRequest),
+}
+
+/// The serde representation of [`TypedTransaction`]
+#[derive(Deserialize)]
+#[cfg_attr(not(feature = "legacy"), serde(tag = "type"))]
+#[cfg_attr(feature = "legacy", serde(untagged))]
+enum TypedTransactionRepr {
+    #[serde(rename = "0x00")]
+    Legacy(TransactionRequest),
+    #[serde(rename = "0x01")]
+    Eip2930(Eip2930TransactionRequest),
+    #[serde(rename = "0x02")]
+    Eip1559(Eip1559TransactionRequest),
+    // nodes return the type of set code transactions without the leading zero
+    #[serde(rename = "0x04", alias = "0x4")]
+    Eip7702(Eip7702TransactionRequest),
+}
+
+impl<'de> Deserialize<'de> for TypedTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let is_set = |field: &str| value.get(field).map_or(false, |value| !value.is_null());
+        if is_set("gasPrice") && (is_set("maxFeePerGas") || is_set("maxPriorityFeePerGas")) {
+            return Err(D::Error::custom(ValidationError::MixedFeeFields))
+        }
+
+        let tx = TypedTransactionRepr::deserialize(value).map_err(D::Error::custom)?;
+        Ok(match tx {
+            TypedTransactionRepr::Legacy(tx) => Legacy(tx),
+            TypedTransactionRepr::Eip2930(tx) => Eip2930(tx),
+            TypedTransactionRepr::Eip1559(tx) => Eip1559(tx),
+            TypedTransactionRepr::Eip7702(tx) => Eip7702(tx),
+        })
+    }
 }
 
 /// An error involving a typed transaction request.
@@ -60,8 +106,32 @@ pub enum TypedTransactionError {
     /// Missing transaction payload when decoding from RLP
     #[error("Missing transaction payload when decoding")]
     MissingTransactionPayload,
+    /// The transaction type is not supported when decoding from RLP
+    #[error("unsupported transaction type {0}")]
+    UnsupportedTransactionType(U64),
+}
+
+/// An obviously invalid transaction, see [`TypedTransaction::validate`]
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Calldata is sent to the zero address without any value, which is most likely a contract
+    /// deployment with `to` mistakenly set
+    #[error("calldata is sent to the zero address without value, omit `to` to deploy a contract")]
+    CalldataToZeroAddress,
+    /// The gas limit is below the intrinsic gas of any transaction
+    #[error("gas limit {0} is below the intrinsic gas of 21000")]
+    GasTooLow(U256),
+    /// The max priority fee per gas exceeds the max fee per gas
+    #[error("max priority fee per gas {max_priority_fee_per_gas} exceeds max fee per gas {max_fee_per_gas}")]
+    PriorityFeeTooHigh { max_fee_per_gas: U256, max_priority_fee_per_gas: U256 },
+    /// The legacy gas price is set together with EIP-1559 fee fields
+    #[error("legacy gas price is set together with EIP-1559 max fee or max priority fee per gas")]
+    MixedFeeFields,
 }
 
+/// The intrinsic gas of a transaction, which every gas limit needs to cover
+const INTRINSIC_GAS: u64 = 21_000;
+
 #[cfg(feature = "legacy")]
 impl Default for TypedTransaction {
     fn default() -> Self {
@@ -84,6 +154,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.from.as_ref(),
             Eip2930(inner) => inner.tx.from.as_ref(),
             Eip1559(inner) => inner.from.as_ref(),
+            Eip7702(inner) => inner.tx.from.as_ref(),
         }
     }
 
@@ -92,6 +163,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.from = Some(from),
             Eip2930(inner) => inner.tx.from = Some(from),
             Eip1559(inner) => inner.from = Some(from),
+            Eip7702(inner) => inner.tx.from = Some(from),
         };
         self
     }
@@ -101,6 +173,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.to.as_ref(),
             Eip2930(inner) => inner.tx.to.as_ref(),
             Eip1559(inner) => inner.to.as_ref(),
+            Eip7702(inner) => inner.tx.to.as_ref(),
         }
     }
 
@@ -110,6 +183,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.to = Some(to),
             Eip2930(inner) => inner.tx.to = Some(to),
             Eip1559(inner) => inner.to = Some(to),
+            Eip7702(inner) => inner.tx.to = Some(to),
         };
         self
     }
@@ -119,6 +193,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.nonce.as_ref(),
             Eip2930(inner) => inner.tx.nonce.as_ref(),
             Eip1559(inner) => inner.nonce.as_ref(),
+            Eip7702(inner) => inner.tx.nonce.as_ref(),
         }
     }
 
@@ -128,6 +203,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.nonce = Some(nonce),
             Eip2930(inner) => inner.tx.nonce = Some(nonce),
             Eip1559(inner) => inner.nonce = Some(nonce),
+            Eip7702(inner) => inner.tx.nonce = Some(nonce),
         };
         self
     }
@@ -137,6 +213,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.value.as_ref(),
             Eip2930(inner) => inner.tx.value.as_ref(),
             Eip1559(inner) => inner.value.as_ref(),
+            Eip7702(inner) => inner.tx.value.as_ref(),
         }
     }
 
@@ -146,6 +223,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.value = Some(value),
             Eip2930(inner) => inner.tx.value = Some(value),
             Eip1559(inner) => inner.value = Some(value),
+            Eip7702(inner) => inner.tx.value = Some(value),
         };
         self
     }
@@ -155,6 +233,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.gas.as_ref(),
             Eip2930(inner) => inner.tx.gas.as_ref(),
             Eip1559(inner) => inner.gas.as_ref(),
+            Eip7702(inner) => inner.tx.gas.as_ref(),
         }
     }
 
@@ -163,6 +242,7 @@ impl TypedTransaction {
             Legacy(inner) => &mut inner.gas,
             Eip2930(inner) => &mut inner.tx.gas,
             Eip1559(inner) => &mut inner.gas,
+            Eip7702(inner) => &mut inner.tx.gas,
         }
     }
 
@@ -172,6 +252,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.gas = Some(gas),
             Eip2930(inner) => inner.tx.gas = Some(gas),
             Eip1559(inner) => inner.gas = Some(gas),
+            Eip7702(inner) => inner.tx.gas = Some(gas),
         };
         self
     }
@@ -180,7 +261,7 @@ impl TypedTransaction {
         match self {
             Legacy(inner) => inner.gas_price,
             Eip2930(inner) => inner.tx.gas_price,
-            Eip1559(inner) => {
+            Eip1559(inner) | Eip7702(Eip7702TransactionRequest { tx: inner, .. }) => {
                 match (inner.max_fee_per_gas, inner.max_priority_fee_per_gas) {
                     (Some(max_fee), Some(_)) => Some(max_fee),
                     // this also covers the None, None case
@@ -196,7 +277,7 @@ impl TypedTransaction {
         match self {
             Legacy(inner) => inner.gas_price = Some(gas_price),
             Eip2930(inner) => inner.tx.gas_price = Some(gas_price),
-            Eip1559(inner) => {
+            Eip1559(inner) | Eip7702(Eip7702TransactionRequest { tx: inner, .. }) => {
                 inner.max_fee_per_gas = Some(gas_price);
                 inner.max_priority_fee_per_gas = Some(gas_price);
             }
@@ -209,6 +290,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.chain_id,
             Eip2930(inner) => inner.tx.chain_id,
             Eip1559(inner) => inner.chain_id,
+            Eip7702(inner) => inner.tx.chain_id,
         }
     }
 
@@ -218,6 +300,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.chain_id = Some(chain_id),
             Eip2930(inner) => inner.tx.chain_id = Some(chain_id),
             Eip1559(inner) => inner.chain_id = Some(chain_id),
+            Eip7702(inner) => inner.tx.chain_id = Some(chain_id),
         };
         self
     }
@@ -227,6 +310,7 @@ impl TypedTransaction {
             Legacy(inner) => inner.data.as_ref(),
             Eip2930(inner) => inner.tx.data.as_ref(),
             Eip1559(inner) => inner.data.as_ref(),
+            Eip7702(inner) => inner.tx.data.as_ref(),
         }
     }
 
@@ -235,6 +319,7 @@ impl TypedTransaction {
             Legacy(_) => None,
             Eip2930(inner) => Some(&inner.access_list),
             Eip1559(inner) => Some(&inner.access_list),
+            Eip7702(inner) => Some(&inner.tx.access_list),
         }
     }
 
@@ -243,15 +328,33 @@ impl TypedTransaction {
             Legacy(_) => {}
             Eip2930(inner) => inner.access_list = access_list,
             Eip1559(inner) => inner.access_list = access_list,
+            Eip7702(inner) => inner.tx.access_list = access_list,
         };
         self
     }
 
+    /// Sets the access list of the transaction, e.g. the one returned by `eth_createAccessList`.
+    ///
+    /// Unlike [`TypedTransaction::set_access_list`], this converts a legacy transaction into an
+    /// EIP-2930 transaction, since legacy transactions cannot carry an access list.
+    #[must_use]
+    pub fn with_access_list<T: Into<AccessList>>(self, access_list: T) -> Self {
+        let access_list = access_list.into();
+        match self {
+            Legacy(tx) => Eip2930(Eip2930TransactionRequest::new(tx, access_list)),
+            mut tx => {
+                tx.set_access_list(access_list);
+                tx
+            }
+        }
+    }
+
     pub fn set_data(&mut self, data: Bytes) -> &mut Self {
         match self {
             Legacy(inner) => inner.data = Some(data),
             Eip2930(inner) => inner.tx.data = Some(data),
             Eip1559(inner) => inner.data = Some(data),
+            Eip7702(inner) => inner.tx.data = Some(data),
         };
         self
     }
@@ -270,6 +373,10 @@ impl TypedTransaction {
                 encoded.extend_from_slice(&[0x2]);
                 encoded.extend_from_slice(inner.rlp_signed(signature).as_ref());
             }
+            Eip7702(inner) => {
+                encoded.extend_from_slice(&[EIP7702_TX_TYPE as u8]);
+                encoded.extend_from_slice(inner.rlp_signed(signature).as_ref());
+            }
         };
         encoded.into()
     }
@@ -288,15 +395,62 @@ impl TypedTransaction {
                 encoded.extend_from_slice(&[0x2]);
                 encoded.extend_from_slice(inner.rlp().as_ref());
             }
+            Eip7702(inner) => {
+                encoded.extend_from_slice(&[EIP7702_TX_TYPE as u8]);
+                encoded.extend_from_slice(inner.rlp().as_ref());
+            }
         };
 
         encoded.into()
     }
 
     /// Hashes the transaction's data. Does not double-RLP encode
+    ///
+    /// Legacy transactions without a chain id are hashed without the EIP-155 fields.
     pub fn sighash(&self) -> H256 {
-        let encoded = self.rlp();
-        keccak256(encoded).into()
+        match self {
+            Legacy(tx) => tx.sighash(),
+            _ => keccak256(self.rlp()).into(),
+        }
+    }
+
+    /// Checks the transaction for obviously invalid fields, which would waste a broadcast:
+    ///
+    /// - calldata sent to the zero address without value, instead of a contract deployment
+    /// - a gas limit below the intrinsic gas of 21000
+    /// - an EIP-1559 max priority fee per gas above the max fee per gas
+    ///
+    /// Fields which are not set are not validated. A legacy gas price mixed with EIP-1559 fee
+    /// fields is rejected on deserialization already, since the transaction types don't hold the
+    /// fields of each other.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let to_zero_address = matches!(self.to(), Some(NameOrAddress::Address(to)) if to.is_zero());
+        let has_calldata = self.data().map(|data| !data.is_empty()).unwrap_or_default();
+        let has_value = self.value().map(|value| !value.is_zero()).unwrap_or_default();
+        if to_zero_address && has_calldata && !has_value {
+            return Err(ValidationError::CalldataToZeroAddress)
+        }
+
+        if let Some(gas) = self.gas() {
+            if *gas < INTRINSIC_GAS.into() {
+                return Err(ValidationError::GasTooLow(*gas))
+            }
+        }
+
+        if let Eip1559(tx) | Eip7702(Eip7702TransactionRequest { tx, .. }) = self {
+            if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
+                (tx.max_fee_per_gas, tx.max_priority_fee_per_gas)
+            {
+                if max_priority_fee_per_gas > max_fee_per_gas {
+                    return Err(ValidationError::PriorityFeeTooHigh {
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                    })
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// Max cost of the transaction
@@ -315,7 +469,16 @@ impl TypedTransaction {
     }
 
     /// Decodes a signed TypedTransaction from a rlp encoded byte stream
+    ///
+    /// Legacy transactions are plain RLP lists, while typed transactions are prefixed with their
+    /// EIP-2718 type byte. The sender is recovered from the signature and set as `from`.
     pub fn decode_signed(rlp: &rlp::Rlp) -> Result<(Self, Signature), TypedTransactionError> {
+        if rlp.is_list() {
+            // Legacy (0x00)
+            let decoded_request = TransactionRequest::decode_signed_rlp(rlp)?;
+            return Ok((Self::Legacy(decoded_request.0), decoded_request.1))
+        }
+
         let tx_type: Option<U64> = match rlp.is_data() {
             true => Ok(Some(rlp.data()?.into())),
             false => Err(TypedTransactionError::MissingTransactionType),
@@ -336,12 +499,15 @@ impl TypedTransaction {
                 let decoded_request = Eip1559TransactionRequest::decode_signed_rlp(&rest)?;
                 Ok((Self::Eip1559(decoded_request.0), decoded_request.1))
             }
-            _ => {
+            Some(x) if x.is_zero() => {
                 // Legacy (0x00)
                 // use the original rlp
                 let decoded_request = TransactionRequest::decode_signed_rlp(&rest)?;
                 Ok((Self::Legacy(decoded_request.0), decoded_request.1))
             }
+            // decoding e.g. an EIP-4844 transaction as a legacy one would yield garbage
+            Some(x) => Err(TypedTransactionError::UnsupportedTransactionType(x)),
+            None => Err(TypedTransactionError::MissingTransactionType),
         }
     }
 }
@@ -393,6 +559,12 @@ impl From<Eip1559TransactionRequest> for TypedTransaction {
     }
 }
 
+impl From<Eip7702TransactionRequest> for TypedTransaction {
+    fn from(src: Eip7702TransactionRequest) -> TypedTransaction {
+        TypedTransaction::Eip7702(src)
+    }
+}
+
 impl From<&Transaction> for TypedTransaction {
     fn from(tx: &Transaction) -> TypedTransaction {
         match tx.transaction_type {
@@ -406,6 +578,11 @@ impl From<&Transaction> for TypedTransaction {
                 let request: Eip1559TransactionRequest = tx.into();
                 request.into()
             }
+            // EIP-7702 (0x04)
+            Some(x) if x == U64::from(EIP7702_TX_TYPE) => {
+                let request: Eip7702TransactionRequest = tx.into();
+                request.into()
+            }
             // Legacy (0x00)
             _ => {
                 let request: TransactionRequest = tx.into();
@@ -415,6 +592,12 @@ impl From<&Transaction> for TypedTransaction {
     }
 }
 
+impl From<Transaction> for TypedTransaction {
+    fn from(tx: Transaction) -> TypedTransaction {
+        (&tx).into()
+    }
+}
+
 impl TypedTransaction {
     pub fn as_legacy_ref(&self) -> Option<&TransactionRequest> {
         match self {
@@ -434,6 +617,12 @@ impl TypedTransaction {
             _ => None,
         }
     }
+    pub fn as_eip7702_ref(&self) -> Option<&Eip7702TransactionRequest> {
+        match self {
+            Eip7702(tx) => Some(tx),
+            _ => None,
+        }
+    }
 
     pub fn as_legacy_mut(&mut self) -> Option<&mut TransactionRequest> {
         match self {
@@ -453,12 +642,19 @@ impl TypedTransaction {
             _ => None,
         }
     }
+    pub fn as_eip7702_mut(&mut self) -> Option<&mut Eip7702TransactionRequest> {
+        match self {
+            Eip7702(tx) => Some(tx),
+            _ => None,
+        }
+    }
 }
 
 impl TypedTransaction {
     fn into_eip1559(self) -> Eip1559TransactionRequest {
         match self {
             Eip1559(tx) => tx,
+            Eip7702(tx) => tx.tx,
             _ => Eip1559TransactionRequest {
                 from: self.from().copied(),
                 to: self.to().cloned(),
@@ -468,7 +664,9 @@ impl TypedTransaction {
                 chain_id: self.chain_id(),
                 data: self.data().cloned(),
                 access_list: self.access_list().cloned().unwrap_or_default(),
-                ..Default::default()
+                // the gas price is the most the sender pays per gas
+                max_fee_per_gas: self.gas_price(),
+                max_priority_fee_per_gas: None,
             },
         }
     }
@@ -485,7 +683,7 @@ impl TypedTransaction {
         match self {
             Legacy(tx) => tx,
             Eip2930(tx) => tx.tx,
-            Eip1559(_) => TransactionRequest {
+            Eip1559(_) | Eip7702(_) => TransactionRequest {
                 from: self.from().copied(),
                 to: self.to().cloned(),
                 nonce: self.nonce().copied(),
@@ -521,7 +719,7 @@ impl TypedTransaction {
         match self {
             Eip2930(tx) => tx,
             Legacy(tx) => Eip2930TransactionRequest { tx, access_list },
-            Eip1559(_) => Eip2930TransactionRequest {
+            Eip1559(_) | Eip7702(_) => Eip2930TransactionRequest {
                 tx: TransactionRequest {
                     from: self.from().copied(),
                     to: self.to().cloned(),
@@ -559,9 +757,76 @@ mod tests {
     use rlp::Decodable;
 
     use super::*;
-    use crate::types::{Address, U256};
+    use crate::types::{
+        transaction::eip2930::{AccessListItem, AccessListWithGasUsed},
+        Address, U256,
+    };
     use std::str::FromStr;
 
+    #[test]
+    fn validates_transactions() {
+        let call: TypedTransaction = TransactionRequest::new()
+            .to(Address::repeat_byte(1))
+            .data(vec![1, 2, 3])
+            .gas(50_000)
+            .gas_price(1)
+            .into();
+        assert_eq!(call.validate(), Ok(()));
+
+        // contract deployments don't set `to`
+        let mut deploy = call.clone();
+        deploy.as_legacy_mut().unwrap().to = None;
+        assert_eq!(deploy.validate(), Ok(()));
+
+        let mut tx = call.clone();
+        tx.set_to(Address::zero());
+        assert_eq!(tx.validate(), Err(ValidationError::CalldataToZeroAddress));
+        // burning value with calldata attached is intentional
+        tx.set_value(1);
+        assert_eq!(tx.validate(), Ok(()));
+
+        let mut tx = call.clone();
+        tx.set_gas(20_999);
+        assert_eq!(tx.validate(), Err(ValidationError::GasTooLow(20_999.into())));
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::repeat_byte(1))
+            .gas(21_000)
+            .max_fee_per_gas(10)
+            .max_priority_fee_per_gas(11)
+            .into();
+        assert_eq!(
+            tx.validate(),
+            Err(ValidationError::PriorityFeeTooHigh {
+                max_fee_per_gas: 10.into(),
+                max_priority_fee_per_gas: 11.into()
+            })
+        );
+
+        // unset fields are not validated
+        assert_eq!(TypedTransaction::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_mixed_fee_fields() {
+        for tx in [
+            r#"{"type":"0x02","gasPrice":"0x1","maxFeePerGas":"0x2","maxPriorityFeePerGas":"0x1"}"#,
+            r#"{"type":"0x02","gasPrice":"0x1","maxPriorityFeePerGas":"0x1"}"#,
+            r#"{"type":"0x00","gasPrice":"0x1","maxFeePerGas":"0x2"}"#,
+        ] {
+            let err = serde_json::from_str::<TypedTransaction>(tx).unwrap_err();
+            assert!(
+                err.to_string().contains(&ValidationError::MixedFeeFields.to_string()),
+                "{}",
+                err
+            );
+        }
+
+        // unset fields don't mix
+        let tx = r#"{"type":"0x02","gasPrice":null,"maxFeePerGas":"0x2"}"#;
+        assert!(serde_json::from_str::<TypedTransaction>(tx).is_ok());
+    }
+
     #[test]
     fn serde_legacy_tx() {
         let tx = TransactionRequest::new().to(Address::zero()).value(U256::from(100));
@@ -576,6 +841,32 @@ mod tests {
         assert_eq!(tx, TypedTransaction::Legacy(de));
     }
 
+    #[test]
+    fn test_with_access_list() {
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::from_low_u64_be(1),
+            storage_keys: vec![H256::from_low_u64_be(2)],
+        }]);
+        let with_gas_used =
+            AccessListWithGasUsed { access_list: access_list.clone(), gas_used: 21000.into() };
+
+        // legacy transactions are upgraded to EIP-2930, keeping their gas price
+        let tx: TypedTransaction = TransactionRequest::new().gas_price(10).nonce(3).into();
+        let tx = tx.with_access_list(with_gas_used);
+        assert!(matches!(tx, TypedTransaction::Eip2930(_)));
+        assert_eq!(tx.access_list(), Some(&access_list));
+        assert_eq!(tx.gas_price(), Some(10.into()));
+        assert_eq!(tx.nonce(), Some(&3.into()));
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new().max_fee_per_gas(10).into();
+        let tx = tx.with_access_list(access_list.clone());
+        assert!(matches!(tx, TypedTransaction::Eip1559(_)));
+        assert_eq!(tx.access_list(), Some(&access_list));
+
+        let tx = tx.with_access_list(AccessList::default());
+        assert_eq!(tx.access_list(), Some(&AccessList::default()));
+    }
+
     #[test]
     fn test_typed_tx_without_access_list() {
         let tx: Eip1559TransactionRequest = serde_json::from_str(
@@ -770,6 +1061,104 @@ mod tests {
         assert_eq!(addr, tx.from.unwrap());
     }
 
+    #[cfg(not(feature = "celo"))]
+    #[test]
+    fn test_signed_tx_roundtrip_all_types() {
+        // all transactions are signed with the EIP-155 example key `0x4646..46`
+        let from = Address::from_str("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F").unwrap();
+        let vectors = [
+            (
+                // legacy (EIP-155)
+                "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+                "0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788",
+            ),
+            (
+                // EIP-2930
+                "01f8a701098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7940000000000000000000000000000000000000001e1a0010000000000000000000000000000000000000000000000000000000000000001a0996d057846bcdd1f37c474c82cbdf91602c9d050205f07470c47fe345bf10f7aa06f68c2a8fc360191aef86c59c2408d73c7dea7072702fbe6150f1e4edc2e85c3",
+                "0x9b8410c16054284236b5683c4e5d411d4b65b4cf45c90ce8cf14c6ceac6b554a",
+            ),
+            (
+                // EIP-1559
+                "02f8ac0109843b9aca008504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7940000000000000000000000000000000000000001e1a0010000000000000000000000000000000000000000000000000000000000000080a06cd061a916bc216a27e2ab1f26c84498074bbe22dd89c343daa4f5c6627d8b85a00717a93539b9b61749cd1b34f1acad55e5121346285ed7d5af6891d75ace1c0c",
+                "0xaf161e83db0603d104f279e5dba31b20e2741844452b7436a0cf0c7af511d092",
+            ),
+        ];
+
+        for (raw, hash) in vectors {
+            let raw = hex::decode(raw).unwrap();
+            let (tx, sig) = TypedTransaction::decode_signed(&rlp::Rlp::new(&raw)).unwrap();
+
+            assert_eq!(tx.from(), Some(&from));
+            assert_eq!(tx.chain_id(), Some(U64::one()));
+            assert_eq!(tx.nonce(), Some(&U256::from(9u64)));
+            assert_eq!(sig.recover(tx.sighash()).unwrap(), from);
+
+            // re-encoding must produce the exact same envelope
+            assert_eq!(tx.rlp_signed(&sig).as_ref(), raw.as_slice());
+            assert_eq!(tx.hash(&sig), H256::from_str(hash).unwrap());
+        }
+
+        // an unknown type must not be decoded as a legacy transaction
+        let mut raw = hex::decode(vectors[2].0).unwrap();
+        raw[0] = 0x03;
+        let err = TypedTransaction::decode_signed(&rlp::Rlp::new(&raw)).unwrap_err();
+        assert!(
+            matches!(err, TypedTransactionError::UnsupportedTransactionType(x) if x == 3u64.into())
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn converts_fetched_transactions() {
+        let json = serde_json::json!({
+            "hash": "0x929ff27a5c7833953df23103c4eb55ebdfb698678139d751c51932163877fada",
+            "nonce": "0x0",
+            "from": "0xc26ad91f4e7a0cad84c4b9315f420ca9217e315d",
+            "to": "0xdac17f958d2ee523a2206206994597c13d831ec7",
+            "value": "0x0",
+            "gas": "0x10e2b",
+            "gasPrice": "0x12ec276caf",
+            "input": "0xa9059cbb000000000000000000000000fdae129ecc2c27d166a3131098bc05d143fa258e0000000000000000000000000000000000000000000000000000000002faf080",
+            "type": "0x0",
+            "v": "0x25",
+            "r": "0xc81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141",
+            "s": "0x1dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274"
+        });
+        let legacy: Transaction = serde_json::from_value(json).unwrap();
+
+        let json = serde_json::json!({
+            "hash": "0x781d57642f4e3277fe01d370bd45ba1361b475bea6a35f26814e02a0a2b26549",
+            "nonce": "0x1df",
+            "from": "0x057f8d0f6fb2703197363f75c002f766f1c4287a",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "value": "0x2b40d6d551c8970c",
+            "gas": "0x6d22",
+            "maxFeePerGas": "0x1344ead983",
+            "maxPriorityFeePerGas": "0x1344ead983",
+            "input": "0xd0e30db0",
+            "type": "0x2",
+            "chainId": "0x1",
+            "accessList": [],
+            "v": "0x1",
+            "r": "0x5616cdaec839ca14d209b59eafb706e623169dc9d0fa58fbf13931cef5b5e3b0",
+            "s": "0x3e708f8044bd158d29c2e250b6a98ea637c3bc460beeea63a8f00f7cebac432a"
+        });
+        let london: Transaction = serde_json::from_value(json).unwrap();
+
+        for tx in [legacy, london] {
+            let signature = Signature { r: tx.r, s: tx.s, v: tx.v.as_u64() };
+            let request = TypedTransaction::from(tx.clone());
+
+            assert_eq!(request.from(), Some(&tx.from));
+            assert_eq!(request.nonce(), Some(&tx.nonce));
+            assert_eq!(request.data(), Some(&tx.input));
+            // the chain id of legacy transactions is recovered from their EIP-155 `v`
+            assert_eq!(request.chain_id(), Some(1u64.into()));
+            // re-signing the request reproduces the fetched transaction
+            assert_eq!(request.hash(&signature), tx.hash);
+        }
+    }
+
     #[test]
     fn test_tx_casts() {
         // eip1559 tx
@@ -806,4 +1195,12 @@ mod tests {
             assert_eq!(tx0, tx1);
         }
     }
+
+    #[test]
+    fn keeps_gas_price_as_max_fee() {
+        let tx: TypedTransaction = TransactionRequest::new().gas_price(7).into();
+        let tx = Eip1559TransactionRequest::from(tx).max_priority_fee_per_gas(2);
+        assert_eq!(tx.max_fee_per_gas, Some(7u64.into()));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(2u64.into()));
+    }
 }
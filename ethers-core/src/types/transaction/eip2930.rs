@@ -39,12 +39,38 @@ pub struct AccessListWithGasUsed {
     pub gas_used: U256,
 }
 
+impl AccessList {
+    /// Adds the storage keys of the address to the access list.
+    ///
+    /// If the address is already part of the list, the keys which are not yet accessed are added
+    /// to its item. The items are RLP encoded in the order they were first added.
+    pub fn add_item<T: Into<Address>>(&mut self, address: T, storage_keys: Vec<H256>) {
+        let address = address.into();
+        match self.0.iter_mut().find(|item| item.address == address) {
+            Some(item) => {
+                for key in storage_keys {
+                    if !item.storage_keys.contains(&key) {
+                        item.storage_keys.push(key);
+                    }
+                }
+            }
+            None => self.0.push(AccessListItem { address, storage_keys }),
+        }
+    }
+}
+
 impl From<Vec<AccessListItem>> for AccessList {
     fn from(src: Vec<AccessListItem>) -> AccessList {
         AccessList(src)
     }
 }
 
+impl From<AccessListWithGasUsed> for AccessList {
+    fn from(src: AccessListWithGasUsed) -> AccessList {
+        src.access_list
+    }
+}
+
 impl TransactionRequest {
     /// Sets the `access_list` field in the transaction (converts the [`TransactionRequest`] to
     /// an [`Eip2930TransactionRequest`])
@@ -103,6 +129,25 @@ impl Eip2930TransactionRequest {
         Self { tx, access_list }
     }
 
+    /// Sets the `access_list` field in the transaction to the provided value
+    #[must_use]
+    pub fn access_list<T: Into<AccessList>>(mut self, access_list: T) -> Self {
+        self.access_list = access_list.into();
+        self
+    }
+
+    /// Adds the storage keys of the address to the `access_list` of the transaction, see
+    /// [`AccessList::add_item`]
+    #[must_use]
+    pub fn add_access_list_item<T: Into<Address>>(
+        mut self,
+        address: T,
+        storage_keys: Vec<H256>,
+    ) -> Self {
+        self.access_list.add_item(address, storage_keys);
+        self
+    }
+
     pub fn rlp(&self) -> Bytes {
         let mut rlp = RlpStream::new();
         rlp.begin_list(NUM_EIP2930_FIELDS);
@@ -219,6 +264,48 @@ mod tests {
         assert_eq!(hex::encode(&enc), expected);
     }
 
+    #[test]
+    fn access_list_builder() {
+        let tx = Eip2930TransactionRequest::new(TransactionRequest::new(), AccessList::default())
+            .add_access_list_item(Address::repeat_byte(2), vec![H256::from_low_u64_be(1)])
+            .add_access_list_item(Address::repeat_byte(1), vec![])
+            .add_access_list_item(
+                Address::repeat_byte(2),
+                vec![H256::from_low_u64_be(2), H256::from_low_u64_be(1)],
+            );
+        let access_list = tx.access_list;
+        assert_eq!(access_list.0.len(), 2);
+
+        // items and keys are encoded in the order they were added
+        assert_eq!(
+            hex::encode(rlp::encode(&access_list)),
+            "f872f859940202020202020202020202020202020202020202f842a00000000000000000000000000000000000000000000000000000000000000001a00000000000000000000000000000000000000000000000000000000000000002d6940101010101010101010101010101010101010101c0"
+        );
+
+        assert_eq!(
+            serde_json::to_value(&access_list).unwrap(),
+            serde_json::json!([
+                {
+                    "address": "0x0202020202020202020202020202020202020202",
+                    "storageKeys": [
+                        "0x0000000000000000000000000000000000000000000000000000000000000001",
+                        "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    ]
+                },
+                {
+                    "address": "0x0101010101010101010101010101010101010101",
+                    "storageKeys": []
+                }
+            ])
+        );
+
+        let tx = crate::types::Eip1559TransactionRequest::new()
+            .access_list(access_list.clone())
+            .add_access_list_item(Address::repeat_byte(1), vec![H256::from_low_u64_be(3)]);
+        assert_eq!(tx.access_list.0[0], access_list.0[0]);
+        assert_eq!(tx.access_list.0[1].storage_keys, vec![H256::from_low_u64_be(3)]);
+    }
+
     #[test]
     #[cfg_attr(feature = "legacy", ignore)]
     fn serde_eip2930_tx() {
@@ -88,10 +88,21 @@ pub trait Eip712 {
     /// EIP-712 encoded payload. This method relies on the aforementioned methods for computing
     /// the final encoded payload.
     fn encode_eip712(&self) -> Result<[u8; 32], Self::Error> {
+        self.encode_eip712_with_domain_separator(self.domain_separator()?)
+    }
+
+    /// Computes the final EIP-712 encoded payload like [`encode_eip712`](Self::encode_eip712),
+    /// but with a precomputed domain separator.
+    ///
+    /// When signing many payloads of the same domain, this allows computing the domain separator
+    /// once and reusing it for every payload.
+    fn encode_eip712_with_domain_separator(
+        &self,
+        domain_separator: [u8; 32],
+    ) -> Result<[u8; 32], Self::Error> {
         // encode the digest to be compatible with solidity abi.encodePacked()
         // See: https://github.com/gakonst/ethers-rs/blob/master/examples/permit_hash.rs#L72
 
-        let domain_separator = self.domain_separator()?;
         let struct_hash = self.struct_hash()?;
 
         let digest_input = [&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat();
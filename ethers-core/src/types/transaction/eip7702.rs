@@ -0,0 +1,131 @@
+use super::{eip1559::Eip1559TransactionRequest, normalize_v};
+use crate::{
+    types::{Address, Bytes, Signature, SignatureError, Transaction, H256, U256, U64},
+    utils::keccak256,
+};
+use rlp::RlpStream;
+use rlp_derive::{RlpDecodable, RlpEncodable};
+use serde::{Deserialize, Serialize};
+
+/// The transaction type of EIP-7702 set code transactions
+pub const EIP7702_TX_TYPE: u64 = 4;
+
+/// EIP-7702 transactions have 10 fields
+const NUM_TX_FIELDS: usize = 10;
+
+/// The magic byte prefixed to the RLP encoded authorization before it is signed
+const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// A signed authorization of an EIP-7702 set code transaction.
+///
+/// The signer of the authorization (the authority) delegates the code of its account to the code
+/// at `address`.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, RlpEncodable, RlpDecodable,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAuthorization {
+    /// The chain the authorization is valid on, or 0 if it is valid on all chains
+    pub chain_id: U256,
+    /// The address of the code the authority delegates to
+    pub address: Address,
+    /// The nonce of the authority
+    pub nonce: U64,
+    /// The y parity of the signature
+    pub y_parity: U64,
+    /// The r value of the signature
+    pub r: U256,
+    /// The s value of the signature
+    pub s: U256,
+}
+
+impl SignedAuthorization {
+    /// Returns the hash signed by the authority, i.e.
+    /// `keccak256(0x05 || rlp([chain_id, address, nonce]))`
+    pub fn signature_hash(&self) -> H256 {
+        let mut rlp = RlpStream::new_list(3);
+        rlp.append(&self.chain_id);
+        rlp.append(&self.address);
+        rlp.append(&self.nonce);
+
+        let mut encoded = vec![AUTHORIZATION_MAGIC];
+        encoded.extend_from_slice(rlp.out().as_ref());
+        keccak256(encoded).into()
+    }
+
+    /// Returns the signature of the authorization
+    pub fn signature(&self) -> Signature {
+        Signature { r: self.r, s: self.s, v: self.y_parity.as_u64() }
+    }
+
+    /// Recovers the address of the authority which signed the authorization
+    pub fn recover_authority(&self) -> Result<Address, SignatureError> {
+        self.signature().recover(self.signature_hash())
+    }
+}
+
+/// An EIP-7702 set code transaction is an EIP-1559 transaction including a list of
+/// [`SignedAuthorization`]s.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct Eip7702TransactionRequest {
+    #[serde(flatten)]
+    pub tx: Eip1559TransactionRequest,
+    #[serde(rename = "authorizationList", default)]
+    pub authorization_list: Vec<SignedAuthorization>,
+}
+
+impl Eip7702TransactionRequest {
+    pub fn new(
+        tx: Eip1559TransactionRequest,
+        authorization_list: Vec<SignedAuthorization>,
+    ) -> Self {
+        Self { tx, authorization_list }
+    }
+
+    /// Sets the `authorization_list` field in the transaction to the provided value
+    #[must_use]
+    pub fn authorization_list(mut self, authorization_list: Vec<SignedAuthorization>) -> Self {
+        self.authorization_list = authorization_list;
+        self
+    }
+
+    /// Gets the unsigned transaction's RLP encoding
+    pub fn rlp(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(NUM_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        rlp.out().freeze().into()
+    }
+
+    /// Produces the RLP encoding of the transaction with the provided signature
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(NUM_TX_FIELDS + 3);
+        self.rlp_base(&mut rlp);
+
+        // if the chain_id is none we assume mainnet and choose one
+        let chain_id = self.tx.chain_id.unwrap_or_else(U64::one);
+
+        // append the signature
+        let v = normalize_v(signature.v, chain_id);
+        rlp.append(&v);
+        rlp.append(&signature.r);
+        rlp.append(&signature.s);
+        rlp.out().freeze().into()
+    }
+
+    fn rlp_base(&self, rlp: &mut RlpStream) {
+        self.tx.rlp_base(rlp);
+        // append the authorization list in addition to the EIP-1559 rlp encoding
+        rlp.append_list::<SignedAuthorization, SignedAuthorization>(&self.authorization_list);
+    }
+}
+
+impl From<&Transaction> for Eip7702TransactionRequest {
+    fn from(tx: &Transaction) -> Eip7702TransactionRequest {
+        Eip7702TransactionRequest {
+            tx: tx.into(),
+            authorization_list: tx.authorization_list.clone().unwrap_or_default(),
+        }
+    }
+}
@@ -4,6 +4,7 @@ pub mod response;
 pub mod eip1559;
 pub mod eip2718;
 pub mod eip2930;
+pub mod eip7702;
 
 #[cfg(feature = "eip712")]
 pub mod eip712;
@@ -93,6 +94,20 @@ fn decode_to(
     Ok(to)
 }
 
+/// Decodes an optional field encoded with [`rlp_opt`] based on the RLP offset passed, where an
+/// empty string is `None`. Increments the offset by one.
+#[cfg(feature = "celo")]
+#[inline]
+fn decode_opt<T: rlp::Decodable>(
+    rlp: &rlp::Rlp,
+    offset: &mut usize,
+) -> Result<Option<T>, rlp::DecoderError> {
+    let item = rlp.at(*offset)?;
+    let value = if item.is_empty() { None } else { Some(item.as_val()?) };
+    *offset += 1;
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::{transaction::rlp_opt, U64};
@@ -223,12 +223,10 @@ impl TransactionRequest {
 
         #[cfg(feature = "celo")]
         {
-            txn.fee_currency = Some(rlp.at(*offset)?.as_val()?);
-            *offset += 1;
-            txn.gateway_fee_recipient = Some(rlp.at(*offset)?.as_val()?);
-            *offset += 1;
-            txn.gateway_fee = Some(rlp.at(*offset)?.as_val()?);
-            *offset += 1;
+            // the fields are optional and encoded as empty strings if unset
+            txn.fee_currency = super::decode_opt(rlp, offset)?;
+            txn.gateway_fee_recipient = super::decode_opt(rlp, offset)?;
+            txn.gateway_fee = super::decode_opt(rlp, offset)?;
         }
 
         txn.to = decode_to(rlp, offset)?;
@@ -298,7 +296,11 @@ impl From<&Transaction> for TransactionRequest {
             value: Some(tx.value),
             data: Some(Bytes(tx.input.0.clone())),
             nonce: Some(tx.nonce),
-            chain_id: tx.chain_id.map(|x| U64::from(x.as_u64())),
+            // nodes may omit the chain id of legacy transactions, but EIP-155 encodes it in `v`
+            chain_id: tx.chain_id.map(|x| U64::from(x.as_u64())).or_else(|| {
+                let v = tx.v.as_u64();
+                (v >= 35).then(|| U64::from((v - 35) / 2))
+            }),
 
             #[cfg(feature = "celo")]
             fee_currency: tx.fee_currency,
@@ -312,6 +314,12 @@ impl From<&Transaction> for TransactionRequest {
     }
 }
 
+impl From<Transaction> for TransactionRequest {
+    fn from(tx: Transaction) -> TransactionRequest {
+        (&tx).into()
+    }
+}
+
 // Separate impl block for the celo-specific fields
 #[cfg(feature = "celo")]
 impl TransactionRequest {
@@ -561,3 +569,36 @@ mod tests {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "celo")]
+mod celo_tests {
+    use super::*;
+    use rlp::Rlp;
+
+    #[test]
+    fn encode_decode_celo_fields() {
+        let base = TransactionRequest::new()
+            .nonce(3)
+            .gas_price(1)
+            .gas(25_000)
+            .to(Address::repeat_byte(1))
+            .value(7)
+            .data(vec![1, 2])
+            .chain_id(42220);
+
+        let txs = vec![
+            base.clone(),
+            // pays the gas in cUSD
+            base.clone().fee_currency(Address::repeat_byte(2)),
+            base.fee_currency(Address::repeat_byte(2))
+                .gateway_fee_recipient(Address::repeat_byte(3))
+                .gateway_fee(5),
+        ];
+        for tx in txs {
+            let encoded = tx.rlp();
+            let decoded = TransactionRequest::decode_unsigned_rlp(&Rlp::new(&encoded)).unwrap();
+            assert_eq!(decoded, tx);
+        }
+    }
+}
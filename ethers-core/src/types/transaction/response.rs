@@ -1,7 +1,10 @@
 //! Transaction types
 use super::{
-    decode_signature, eip2718::TypedTransaction, eip2930::AccessList, normalize_v, rlp_opt,
-    rlp_opt_list,
+    decode_signature,
+    eip2718::TypedTransaction,
+    eip2930::AccessList,
+    eip7702::{SignedAuthorization, EIP7702_TX_TYPE},
+    normalize_v, rlp_opt, rlp_opt_list,
 };
 use crate::{
     types::{Address, Bloom, Bytes, Log, Signature, SignatureError, H256, U256, U64},
@@ -85,7 +88,7 @@ pub struct Transaction {
     pub gateway_fee: Option<U256>,
 
     // EIP2718
-    /// Transaction type, Some(2) for EIP-1559 transaction,
+    /// Transaction type, Some(4) for EIP-7702 transaction, Some(2) for EIP-1559 transaction,
     /// Some(1) for AccessList transaction, None for Legacy
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
     pub transaction_type: Option<U64>,
@@ -115,6 +118,11 @@ pub struct Transaction {
     #[serde(rename = "chainId", default, skip_serializing_if = "Option::is_none")]
     pub chain_id: Option<U256>,
 
+    // EIP7702
+    /// The authorizations of a set code transaction, None for other transaction types
+    #[serde(rename = "authorizationList", default, skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<SignedAuthorization>>,
+
     /// Captures unknown fields such as additional fields used by L2s
     #[cfg(not(feature = "celo"))]
     #[serde(flatten)]
@@ -132,10 +140,16 @@ impl Transaction {
         rlp_opt(rlp, &self.gateway_fee);
     }
 
+    /// Recomputes the hash of the transaction from its signed RLP encoding.
+    ///
+    /// For transactions returned by a node this equals the `hash` field, unless the node lied
+    /// about the transaction.
     pub fn hash(&self) -> H256 {
         keccak256(&self.rlp().as_ref()).into()
     }
 
+    /// Returns the signed RLP encoding of the transaction, prefixed with the type byte for typed
+    /// transactions
     pub fn rlp(&self) -> Bytes {
         let mut rlp = RlpStream::new();
         rlp.begin_unbounded_list();
@@ -174,6 +188,24 @@ impl Transaction {
                     rlp.append(&normalize_v(self.v.as_u64(), U64::from(chain_id.as_u64())));
                 }
             }
+            // EIP-7702 (0x04)
+            Some(x) if x == U64::from(EIP7702_TX_TYPE) => {
+                rlp_opt(&mut rlp, &self.chain_id);
+                rlp.append(&self.nonce);
+                rlp_opt(&mut rlp, &self.max_priority_fee_per_gas);
+                rlp_opt(&mut rlp, &self.max_fee_per_gas);
+                rlp.append(&self.gas);
+                rlp_opt(&mut rlp, &self.to);
+                rlp.append(&self.value);
+                rlp.append(&self.input.as_ref());
+                rlp_opt_list(&mut rlp, &self.access_list);
+                rlp.append_list::<SignedAuthorization, SignedAuthorization>(
+                    self.authorization_list.as_deref().unwrap_or_default(),
+                );
+                if let Some(chain_id) = self.chain_id {
+                    rlp.append(&normalize_v(self.v.as_u64(), U64::from(chain_id.as_u64())));
+                }
+            }
             // Legacy (0x00)
             _ => {
                 rlp.append(&self.nonce);
@@ -208,6 +240,11 @@ impl Transaction {
                 encoded.extend_from_slice(rlp_bytes.as_ref());
                 encoded.into()
             }
+            Some(x) if x == U64::from(EIP7702_TX_TYPE) => {
+                encoded.extend_from_slice(&[EIP7702_TX_TYPE as u8]);
+                encoded.extend_from_slice(rlp_bytes.as_ref());
+                encoded.into()
+            }
             _ => rlp_bytes,
         }
     }
@@ -221,12 +258,9 @@ impl Transaction {
         rlp: &rlp::Rlp,
         offset: &mut usize,
     ) -> Result<(), DecoderError> {
-        self.fee_currency = Some(rlp.val_at(*offset)?);
-        *offset += 1;
-        self.gateway_fee_recipient = Some(rlp.val_at(*offset)?);
-        *offset += 1;
-        self.gateway_fee = Some(rlp.val_at(*offset)?);
-        *offset += 1;
+        self.fee_currency = super::decode_opt(rlp, offset)?;
+        self.gateway_fee_recipient = super::decode_opt(rlp, offset)?;
+        self.gateway_fee = super::decode_opt(rlp, offset)?;
         Ok(())
     }
 
@@ -260,6 +294,20 @@ impl Transaction {
         Ok(())
     }
 
+    /// Decodes fields of the type 4 transaction response starting at the RLP offset passed.
+    /// Increments the offset for each element parsed.
+    #[inline]
+    fn decode_base_eip7702(
+        &mut self,
+        rlp: &rlp::Rlp,
+        offset: &mut usize,
+    ) -> Result<(), DecoderError> {
+        self.decode_base_eip1559(rlp, offset)?;
+        self.authorization_list = Some(rlp.list_at(*offset)?);
+        *offset += 1;
+        Ok(())
+    }
+
     /// Decodes fields of the type 1 transaction response based on the RLP offset passed.
     /// Increments the offset for each element parsed.
     fn decode_base_eip2930(
@@ -360,6 +408,10 @@ impl Decodable for Transaction {
                 // EIP-1559 (0x02)
                 txn.decode_base_eip1559(&rest, &mut offset)?;
             }
+            Some(x) if x == U64::from(EIP7702_TX_TYPE) => {
+                // EIP-7702 (0x04)
+                txn.decode_base_eip7702(&rest, &mut offset)?;
+            }
             _ => {
                 // Legacy (0x00)
                 txn.decode_base_legacy(&rest, &mut offset)?;
@@ -578,6 +630,7 @@ mod tests {
                 16,
             )
             .unwrap(),
+            authorization_list: None,
             other: Default::default(),
         };
         println!("0x{}", hex::encode(&tx.rlp()));
@@ -589,6 +642,78 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn recomputes_hash() {
+        // legacy
+        let tx: Transaction = serde_json::from_value(serde_json::json!({
+            "hash": "0x929ff27a5c7833953df23103c4eb55ebdfb698678139d751c51932163877fada",
+            "nonce": "0x0",
+            "from": "0xc26ad91f4e7a0cad84c4b9315f420ca9217e315d",
+            "to": "0xdac17f958d2ee523a2206206994597c13d831ec7",
+            "value": "0x0",
+            "gas": "0x10e2b",
+            "gasPrice": "0x12ec276caf",
+            "input": "0xa9059cbb000000000000000000000000fdae129ecc2c27d166a3131098bc05d143fa258e0000000000000000000000000000000000000000000000000000000002faf080",
+            "type": "0x0",
+            "chainId": "0x1",
+            "v": "0x25",
+            "r": "0xc81e70f9e49e0d3b854720143e86d172fecc9e76ef8a8666f2fdc017017c5141",
+            "s": "0x1dd3410180f6a6ca3e25ad3058789cd0df3321ed76b5b4dbe0a2bb2dc28ae274"
+        }))
+        .unwrap();
+        assert_eq!(tx.hash(), tx.hash);
+
+        // EIP-2930
+        let tx: Transaction = serde_json::from_value(serde_json::json!({
+            "hash": "0x3a4a2e04e949f26f234ad5da01c39f394a0c4b26be08a088622a96355afce98e",
+            "nonce": "0x1",
+            "from": "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+            "to": "0x1111111111111111111111111111111111111111",
+            "value": "0x5",
+            "gas": "0x7530",
+            "gasPrice": "0x3b9aca00",
+            "input": "0x",
+            "type": "0x1",
+            "chainId": "0x1",
+            "accessList": [{
+                "address": "0x2222222222222222222222222222222222222222",
+                "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+            }],
+            "v": "0x1",
+            "r": "0x89ecb47f08226896027c5b54e8414f4f99b998fcc0ef4f3e1d1c109e0c297e08",
+            "s": "0x5834cfc5fd2cf5322c9af5e0b283f75952df27aed50da1da18b920dbb98cbf1a"
+        }))
+        .unwrap();
+        assert_eq!(tx.hash(), tx.hash);
+
+        // EIP-1559
+        let tx: Transaction = serde_json::from_value(serde_json::json!({
+            "hash": "0x781d57642f4e3277fe01d370bd45ba1361b475bea6a35f26814e02a0a2b26549",
+            "nonce": "0x1df",
+            "from": "0x057f8d0f6fb2703197363f75c002f766f1c4287a",
+            "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "value": "0x2b40d6d551c8970c",
+            "gas": "0x6d22",
+            "maxFeePerGas": "0x1344ead983",
+            "maxPriorityFeePerGas": "0x1344ead983",
+            "input": "0xd0e30db0",
+            "type": "0x2",
+            "chainId": "0x1",
+            "accessList": [],
+            "v": "0x1",
+            "r": "0x5616cdaec839ca14d209b59eafb706e623169dc9d0fa58fbf13931cef5b5e3b0",
+            "s": "0x3e708f8044bd158d29c2e250b6a98ea637c3bc460beeea63a8f00f7cebac432a"
+        }))
+        .unwrap();
+        assert_eq!(tx.hash(), tx.hash);
+
+        // a node lying about the hash is detected
+        let mut tx = tx;
+        tx.hash = H256::zero();
+        assert_ne!(tx.hash(), tx.hash);
+    }
+
     #[test]
     fn rlp_london_no_access_list() {
         let tx = Transaction {
@@ -622,6 +747,7 @@ mod tests {
                 16,
             )
             .unwrap(),
+            authorization_list: None,
             other: Default::default(),
         };
         println!("0x{}", hex::encode(&tx.rlp()));
@@ -657,6 +783,7 @@ mod tests {
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None,
+            authorization_list: None,
             other: Default::default()
         };
         assert_eq!(
@@ -703,6 +830,7 @@ mod tests {
             max_priority_fee_per_gas: Some(1500000000.into()),
             max_fee_per_gas: Some(1500000009.into()),
             chain_id: Some(5.into()),
+            authorization_list: None,
             other: Default::default(),
         };
         assert_eq!(
@@ -749,6 +877,7 @@ mod tests {
             max_priority_fee_per_gas: Some(1500000000.into()),
             max_fee_per_gas: Some(1500000009.into()),
             chain_id: Some(5.into()),
+            authorization_list: None,
             other: Default::default(),
         };
 
@@ -759,6 +888,158 @@ mod tests {
         assert_eq!(decoded_transaction.hash(), tx.hash());
     }
 
+    #[test]
+    fn decode_eip7702_tx() {
+        let tx: Transaction = serde_json::from_str(
+            r#"{
+            "blockHash": "0x1d59ff54b1eb26b013ce3cb5fc9dab3705b415a67127a003c3e61eb445bb8df2",
+            "blockNumber": "0x14b4a7e",
+            "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "gas": "0x186a0",
+            "gasPrice": "0x4a817c800",
+            "maxFeePerGas": "0x4a817c800",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "hash": "0xcb3c360d51b25176c4ad2ed0a7ca849e54e0c4d81a3c0f6bc1a19bb78e8f1168",
+            "input": "0x",
+            "nonce": "0x5",
+            "to": "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+            "transactionIndex": "0x3",
+            "value": "0x0",
+            "type": "0x4",
+            "accessList": [],
+            "chainId": "0x1",
+            "authorizationList": [
+                {
+                    "chainId": "0x1",
+                    "address": "0x63c0c19a282a1b52b07dd5a65b58948a07dae32b",
+                    "nonce": "0x0",
+                    "yParity": "0x0",
+                    "r": "0x58df39f656b445507aa971c5e0896b0fd65a2d39b1c9ec74da1763f31053d4aa",
+                    "s": "0xe41c8998b17b160ef3ca4b5760174c692c2f74a9b8bff9d3759f2a00bc92944"
+                }
+            ],
+            "v": "0x1",
+            "r": "0x1b7bfd3b4de3ea7307e911d7e48879e203a210ebae17d920a4699f8841b55889",
+            "s": "0x34b8fa2dc108107ad5fa7c7722ee35591e47dec3ae92bcbd69f66bb4ee089c20",
+            "yParity": "0x1"
+        }"#,
+        )
+        .unwrap();
+
+        assert_eq!(tx.transaction_type, Some(4u64.into()));
+        let authorization_list = tx.authorization_list.as_ref().unwrap();
+        assert_eq!(authorization_list.len(), 1);
+        let authorization = &authorization_list[0];
+        assert_eq!(authorization.chain_id, 1u64.into());
+        assert_eq!(
+            authorization.address,
+            Address::from_str("63c0c19a282a1b52b07dd5a65b58948a07dae32b").unwrap()
+        );
+        assert_eq!(
+            authorization.recover_authority().unwrap(),
+            Address::from_str("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap()
+        );
+
+        // the hash depends on the rlp encoding
+        assert_eq!(tx.hash(), tx.hash);
+        let rlp_bytes = tx.rlp();
+        assert_eq!(rlp_bytes.as_ref(), hex::decode("04f8ca0105843b9aca008504a817c800830186a0942c7536e3605d9c16a7a3d7b1898e529396a65c238080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b8080a058df39f656b445507aa971c5e0896b0fd65a2d39b1c9ec74da1763f31053d4aaa00e41c8998b17b160ef3ca4b5760174c692c2f74a9b8bff9d3759f2a00bc9294401a01b7bfd3b4de3ea7307e911d7e48879e203a210ebae17d920a4699f8841b55889a034b8fa2dc108107ad5fa7c7722ee35591e47dec3ae92bcbd69f66bb4ee089c20").unwrap());
+
+        let decoded = Transaction::decode(&rlp::Rlp::new(rlp_bytes.as_ref())).unwrap();
+        assert_eq!(decoded.authorization_list, tx.authorization_list);
+        assert_eq!(decoded.hash(), tx.hash);
+    }
+
+    #[test]
+    fn typed_eip7702_tx_roundtrip() {
+        let typed: TypedTransaction = serde_json::from_str(
+            r#"{
+            "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "to": "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+            "gas": "0x186a0",
+            "value": "0x0",
+            "nonce": "0x5",
+            "maxFeePerGas": "0x4a817c800",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "type": "0x4",
+            "accessList": [],
+            "authorizationList": [
+                {
+                    "chainId": "0x1",
+                    "address": "0x63c0c19a282a1b52b07dd5a65b58948a07dae32b",
+                    "nonce": "0x0",
+                    "yParity": "0x0",
+                    "r": "0x58df39f656b445507aa971c5e0896b0fd65a2d39b1c9ec74da1763f31053d4aa",
+                    "s": "0xe41c8998b17b160ef3ca4b5760174c692c2f74a9b8bff9d3759f2a00bc92944"
+                }
+            ]
+        }"#,
+        )
+        .unwrap();
+
+        let tx = typed.as_eip7702_ref().unwrap();
+        assert_eq!(tx.tx.nonce, Some(5u64.into()));
+        assert_eq!(tx.tx.max_priority_fee_per_gas, Some(1_000_000_000u64.into()));
+        assert_eq!(tx.authorization_list.len(), 1);
+        assert_eq!(
+            tx.authorization_list[0].address,
+            Address::from_str("63c0c19a282a1b52b07dd5a65b58948a07dae32b").unwrap()
+        );
+
+        let serialized = serde_json::to_string(&typed).unwrap();
+        let decoded: TypedTransaction = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(decoded, typed);
+    }
+
+    #[test]
+    fn hashes_typed_eip7702_tx() {
+        let tx: Transaction = serde_json::from_str(
+            r#"{
+            "blockHash": "0x1d59ff54b1eb26b013ce3cb5fc9dab3705b415a67127a003c3e61eb445bb8df2",
+            "blockNumber": "0x14b4a7e",
+            "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "gas": "0x186a0",
+            "gasPrice": "0x4a817c800",
+            "maxFeePerGas": "0x4a817c800",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "hash": "0xcb3c360d51b25176c4ad2ed0a7ca849e54e0c4d81a3c0f6bc1a19bb78e8f1168",
+            "input": "0x",
+            "nonce": "0x5",
+            "to": "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+            "transactionIndex": "0x3",
+            "value": "0x0",
+            "type": "0x4",
+            "accessList": [],
+            "chainId": "0x1",
+            "authorizationList": [
+                {
+                    "chainId": "0x1",
+                    "address": "0x63c0c19a282a1b52b07dd5a65b58948a07dae32b",
+                    "nonce": "0x0",
+                    "yParity": "0x0",
+                    "r": "0x58df39f656b445507aa971c5e0896b0fd65a2d39b1c9ec74da1763f31053d4aa",
+                    "s": "0xe41c8998b17b160ef3ca4b5760174c692c2f74a9b8bff9d3759f2a00bc92944"
+                }
+            ],
+            "v": "0x1",
+            "r": "0x1b7bfd3b4de3ea7307e911d7e48879e203a210ebae17d920a4699f8841b55889",
+            "s": "0x34b8fa2dc108107ad5fa7c7722ee35591e47dec3ae92bcbd69f66bb4ee089c20",
+            "yParity": "0x1"
+        }"#,
+        )
+        .unwrap();
+
+        // the typed request encodes the same payload as the signed response
+        let typed = TypedTransaction::from(&tx);
+        assert_eq!(
+            typed.as_eip7702_ref().unwrap().authorization_list,
+            tx.authorization_list.clone().unwrap()
+        );
+        let signature = Signature { r: tx.r, s: tx.s, v: tx.v.as_u64() };
+        assert_eq!(typed.rlp_signed(&signature).as_ref(), tx.rlp().as_ref());
+        assert_eq!(typed.hash(&signature), tx.hash);
+    }
+
     #[test]
     fn recover_from() {
         let tx = Transaction {
@@ -795,6 +1076,7 @@ mod tests {
             max_priority_fee_per_gas: Some(1500000000.into()),
             max_fee_per_gas: Some(1500000009.into()),
             chain_id: Some(5.into()),
+            authorization_list: None,
             other: Default::default(),
         };
 
@@ -110,10 +110,10 @@ impl Serialize for TxpoolInspectSummary {
 /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TxpoolContent {
-    /// pending tx
-    pub pending: BTreeMap<Address, BTreeMap<String, TxpoolTransaction>>,
-    /// queued tx
-    pub queued: BTreeMap<Address, BTreeMap<String, TxpoolTransaction>>,
+    /// pending tx, keyed by sender and nonce
+    pub pending: BTreeMap<Address, BTreeMap<u64, TxpoolTransaction>>,
+    /// queued tx, keyed by sender and nonce
+    pub queued: BTreeMap<Address, BTreeMap<u64, TxpoolTransaction>>,
 }
 
 /// Transaction Pool Inspect
@@ -127,10 +127,10 @@ pub struct TxpoolContent {
 /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxpoolInspect {
-    /// pending tx
-    pub pending: BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
-    /// queued tx
-    pub queued: BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
+    /// pending tx, keyed by sender and nonce
+    pub pending: BTreeMap<Address, BTreeMap<u64, TxpoolInspectSummary>>,
+    /// queued tx, keyed by sender and nonce
+    pub queued: BTreeMap<Address, BTreeMap<u64, TxpoolInspectSummary>>,
 }
 
 /// Transaction Pool Status
@@ -263,6 +263,14 @@ mod tests {
         let deserialized: TxpoolContent = serde_json::from_str(txpool_content_json).unwrap();
         let serialized: String = serde_json::to_string(&deserialized).unwrap();
         assert_eq!(deserialized, serde_json::from_str::<TxpoolContent>(&serialized).unwrap());
+
+        // the decimal nonce keys are parsed and ordered numerically
+        let sender = Address::from_str("07e80128c7a35d0d43ddcc67fa8b1495871e08bf").unwrap();
+        let nonces = deserialized.pending[&sender].keys().copied().collect::<Vec<_>>();
+        assert_eq!(nonces, vec![41588, 41589, 41590]);
+        assert_eq!(deserialized.pending[&sender][&41588].nonce, Some(0xa274.into()));
+        let sender = Address::from_str("0f87ffcd71859233eb259f42b236c8e9873444e3").unwrap();
+        assert_eq!(deserialized.queued[&sender][&8].nonce, Some(8.into()));
     }
 
     #[test]
@@ -314,7 +322,7 @@ mod tests {
         let mut pending_map = BTreeMap::new();
         let mut pending_map_inner = BTreeMap::new();
         pending_map_inner.insert(
-            "124930".to_string(),
+            124930,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("000000000000000000000000000000000000007E").unwrap()),
                 value: U256::from(0u64),
@@ -328,7 +336,7 @@ mod tests {
         );
         pending_map_inner.clear();
         pending_map_inner.insert(
-            "252350".to_string(),
+            252350,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("d10e3Be2bc8f959Bc8C41CF65F60dE721cF89ADF").unwrap()),
                 value: U256::from(0u64),
@@ -337,7 +345,7 @@ mod tests {
             },
         );
         pending_map_inner.insert(
-            "252351".to_string(),
+            252351,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("d10e3Be2bc8f959Bc8C41CF65F60dE721cF89ADF").unwrap()),
                 value: U256::from(0u64),
@@ -346,7 +354,7 @@ mod tests {
             },
         );
         pending_map_inner.insert(
-            "252352".to_string(),
+            252352,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("d10e3Be2bc8f959Bc8C41CF65F60dE721cF89ADF").unwrap()),
                 value: U256::from(0u64),
@@ -355,7 +363,7 @@ mod tests {
             },
         );
         pending_map_inner.insert(
-            "252353".to_string(),
+            252353,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("d10e3Be2bc8f959Bc8C41CF65F60dE721cF89ADF").unwrap()),
                 value: U256::from(0u64),
@@ -370,7 +378,7 @@ mod tests {
         let mut queued_map = BTreeMap::new();
         let mut queued_map_inner = BTreeMap::new();
         queued_map_inner.insert(
-            "7".to_string(),
+            7,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("3479BE69e07E838D9738a301Bb0c89e8EA2Bef4a").unwrap()),
                 value: U256::from(1000000000000000u64),
@@ -379,7 +387,7 @@ mod tests {
             },
         );
         queued_map_inner.insert(
-            "8".to_string(),
+            8,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("73Aaf691bc33fe38f86260338EF88f9897eCaa4F").unwrap()),
                 value: U256::from(1000000000000000u64),
@@ -393,7 +401,7 @@ mod tests {
         );
         queued_map_inner.clear();
         queued_map_inner.insert(
-            "3".to_string(),
+            3,
             TxpoolInspectSummary {
                 to: Some(Address::from_str("73Aaf691bc33fe38f86260338EF88f9897eCaa4F").unwrap()),
                 value: U256::from(10000000000000000u64),
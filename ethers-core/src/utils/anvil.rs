@@ -12,6 +12,9 @@ use std::{
 /// How long we will wait for anvil to indicate that it is ready.
 const ANVIL_STARTUP_TIMEOUT_MILLIS: u64 = 5_000;
 
+/// The chain id anvil uses if none is configured
+const DEFAULT_CHAIN_ID: u64 = 31337;
+
 /// An anvil CLI instance. Will close the instance when dropped.
 ///
 /// Construct this using [`Anvil`](crate::utils::Anvil)
@@ -20,6 +23,7 @@ pub struct AnvilInstance {
     private_keys: Vec<K256SecretKey>,
     addresses: Vec<Address>,
     port: u16,
+    chain_id: Option<u64>,
 }
 
 impl AnvilInstance {
@@ -38,6 +42,13 @@ impl AnvilInstance {
         self.port
     }
 
+    /// Returns the chain id of this instance as reported by anvil on startup, which is the chain id
+    /// of the forked chain when forking. Defaults to anvil's chain id `31337` if anvil reported
+    /// none.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id.unwrap_or(DEFAULT_CHAIN_ID)
+    }
+
     /// Returns the HTTP endpoint of this instance
     pub fn endpoint(&self) -> String {
         format!("http://localhost:{}", self.port)
@@ -83,6 +94,7 @@ pub struct Anvil {
     mnemonic: Option<String>,
     fork: Option<String>,
     fork_block_number: Option<u64>,
+    chain_id: Option<u64>,
     args: Vec<String>,
 }
 
@@ -94,6 +106,9 @@ impl Anvil {
     }
 
     /// Sets the port which will be used when the `anvil` instance is launched.
+    ///
+    /// With port `0`, anvil binds to a random free port, which is available via
+    /// [`AnvilInstance::port`] once spawned.
     #[must_use]
     pub fn port<T: Into<u16>>(mut self, port: T) -> Self {
         self.port = Some(port.into());
@@ -114,6 +129,13 @@ impl Anvil {
         self
     }
 
+    /// Sets the chain id which will be used when the `anvil` instance is launched.
+    #[must_use]
+    pub fn chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
     /// Sets the `fork-block-number` which will be used in addition to [`Self::fork`].
     ///
     /// **Note:** if set, then this requires `fork` to be set as well
@@ -158,7 +180,7 @@ impl Anvil {
     pub fn spawn(self) -> AnvilInstance {
         let mut cmd = Command::new("anvil");
         cmd.stdout(std::process::Stdio::piped());
-        let port = if let Some(port) = self.port { port } else { unused_port() };
+        let mut port = if let Some(port) = self.port { port } else { unused_port() };
         cmd.arg("-p").arg(port.to_string());
 
         if let Some(mnemonic) = self.mnemonic {
@@ -177,6 +199,10 @@ impl Anvil {
             cmd.arg("--fork-block-number").arg(fork_block_number.to_string());
         }
 
+        if let Some(chain_id) = self.chain_id {
+            cmd.arg("--chain-id").arg(chain_id.to_string());
+        }
+
         cmd.args(self.args);
 
         let mut child = cmd.spawn().expect("couldnt start anvil");
@@ -189,6 +215,8 @@ impl Anvil {
         let mut private_keys = Vec::new();
         let mut addresses = Vec::new();
         let mut is_private_key = false;
+        let mut chain_id = self.chain_id;
+        let mut is_chain_id = false;
         loop {
             if start + Duration::from_millis(ANVIL_STARTUP_TIMEOUT_MILLIS) <= Instant::now() {
                 panic!("Timed out waiting for anvil to start. Is anvil installed?")
@@ -197,9 +225,24 @@ impl Anvil {
             let mut line = String::new();
             reader.read_line(&mut line).expect("Failed to read line from anvil process");
             if line.contains("Listening on") {
+                // the actual port, which differs from the configured one if that was `0`
+                if let Some(listening) = parse_listening_port(&line) {
+                    port = listening;
+                }
                 break
             }
 
+            // the configured chain id, or the one of the forked chain, is printed either below a
+            // `Chain ID` header or as `Chain ID: 1`
+            if line.trim() == "Chain ID" {
+                is_chain_id = true;
+            } else if is_chain_id || line.contains("Chain ID:") {
+                if let Some(id) = parse_chain_id(&line) {
+                    chain_id = Some(id);
+                    is_chain_id = false;
+                }
+            }
+
             if line.starts_with("Private Keys") {
                 is_private_key = true;
             }
@@ -215,10 +258,20 @@ impl Anvil {
 
         child.stdout = Some(reader.into_inner());
 
-        AnvilInstance { pid: child, private_keys, addresses, port }
+        AnvilInstance { pid: child, private_keys, addresses, port, chain_id }
     }
 }
 
+/// Parses the port from anvil's `Listening on 127.0.0.1:8545` line
+fn parse_listening_port(line: &str) -> Option<u16> {
+    line.trim().rsplit(':').next()?.parse().ok()
+}
+
+/// Parses the chain id from anvil's `Chain ID: 1` line, or the line below the `Chain ID` header
+fn parse_chain_id(line: &str) -> Option<u64> {
+    line.trim().trim_start_matches("Chain ID:").trim().parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,4 +280,25 @@ mod tests {
     fn can_launch_anvil() {
         let _ = Anvil::new().spawn();
     }
+
+    #[test]
+    fn reports_chain_id() {
+        assert_eq!(Anvil::new().spawn().chain_id(), 31337);
+        assert_eq!(Anvil::new().chain_id(1337u64).spawn().chain_id(), 1337);
+    }
+
+    #[test]
+    fn parses_listening_port() {
+        assert_eq!(parse_listening_port("Listening on 127.0.0.1:43127\n"), Some(43127));
+        assert_eq!(parse_listening_port("Listening on [::]:8545"), Some(8545));
+        assert_eq!(parse_listening_port("Listening on"), None);
+    }
+
+    #[test]
+    fn parses_chain_id() {
+        assert_eq!(parse_chain_id("31337\n"), Some(31337));
+        assert_eq!(parse_chain_id("Chain ID: 1\n"), Some(1));
+        assert_eq!(parse_chain_id("=================="), None);
+        assert_eq!(parse_chain_id(""), None);
+    }
 }
@@ -0,0 +1,88 @@
+//! [ENS](https://docs.ens.domains/) name hashing as specified in
+//! [EIP-137](https://eips.ethereum.org/EIPS/eip-137)
+use super::keccak256;
+use crate::types::H256;
+
+/// Returns the ENS namehash of the name.
+///
+/// The name is [normalized](normalize) first, so `Vitalik.ETH` and `vitalik.eth` produce the same
+/// node.
+///
+/// ```
+/// use ethers_core::utils::namehash;
+///
+/// assert_eq!(namehash("Vitalik.ETH"), namehash("vitalik.eth"));
+/// ```
+pub fn namehash(name: &str) -> H256 {
+    let name = normalize(name);
+    if name.is_empty() {
+        return H256::zero()
+    }
+
+    // iterate in reverse
+    name.rsplit('.')
+        .fold([0u8; 32], |node, label| keccak256([node, keccak256(label.as_bytes())].concat()))
+        .into()
+}
+
+/// Returns the hash of a single, [normalized](normalize) label of an ENS name, which is used as
+/// the token id of `.eth` names.
+pub fn labelhash(label: &str) -> H256 {
+    keccak256(normalize(label).as_bytes()).into()
+}
+
+/// Normalizes an ENS name with UTS-46, i.e. case folds it and maps compatibility characters to
+/// their canonical form.
+///
+/// Names which are invalid per UTS-46 can never be registered, they are only lowercased.
+pub fn normalize(name: &str) -> String {
+    let (normalized, res) = idna::Config::default()
+        .use_std3_ascii_rules(true)
+        .transitional_processing(false)
+        .check_hyphens(false)
+        .to_unicode(name);
+    match res {
+        Ok(()) => normalized,
+        Err(_) => name.to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_hex(hash: H256, val: &str) {
+        let v = if let Some(stripped) = val.strip_prefix("0x") { stripped } else { val };
+
+        assert_eq!(hash.0.to_vec(), hex::decode(v).unwrap());
+    }
+
+    #[test]
+    fn test_namehash() {
+        for (name, expected) in &[
+            ("", "0000000000000000000000000000000000000000000000000000000000000000"),
+            ("eth", "0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"),
+            ("foo.eth", "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"),
+            ("alice.eth", "0x787192fc5378cc32aa956ddfdedbf26b24e8d78e40109add0eea2c1a012c3dec"),
+        ] {
+            assert_hex(namehash(name), expected);
+        }
+    }
+
+    #[test]
+    fn test_labelhash() {
+        assert_hex(
+            labelhash("eth"),
+            "4f5b812789fc606be1b3b16908db13fc7a9adf7ca72641f84d75b47069d3d7f0",
+        );
+        assert_eq!(labelhash("ETH"), labelhash("eth"));
+    }
+
+    #[test]
+    fn normalizes_names() {
+        assert_eq!(normalize("Vitalik.ETH"), "vitalik.eth");
+        assert_eq!(namehash("Vitalik.ETH"), namehash("vitalik.eth"));
+        // full width characters are mapped to their ascii form
+        assert_eq!(normalize("ｆｏｏ.eth"), "foo.eth");
+    }
+}
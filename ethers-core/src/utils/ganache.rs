@@ -4,14 +4,19 @@ use crate::{
 };
 use k256::{ecdsa::SigningKey, SecretKey as K256SecretKey};
 use std::{
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
     process::{Child, Command},
+    thread,
     time::{Duration, Instant},
 };
 
 /// Default amount of time we will wait for ganache to indicate that it is ready.
 const GANACHE_STARTUP_TIMEOUT_MILLIS: u64 = 10_000;
 
+/// How often to check whether ganache responds to requests once it is listening
+const GANACHE_READY_POLL_INTERVAL_MILLIS: u64 = 50;
+
 /// A ganache CLI instance. Will close the instance when dropped.
 ///
 /// Construct this using [`Ganache`](crate::utils::Ganache)
@@ -82,6 +87,9 @@ pub struct Ganache {
     block_time: Option<u64>,
     mnemonic: Option<String>,
     fork: Option<String>,
+    fork_block_number: Option<u64>,
+    gas_limit: Option<u64>,
+    chain_id: Option<u64>,
     args: Vec<String>,
     startup_timeout: Option<u64>,
 }
@@ -108,6 +116,9 @@ impl Ganache {
     }
 
     /// Sets the mnemonic which will be used when the `ganache-cli` instance is launched.
+    ///
+    /// The accounts are derived from the mnemonic, so [`GanacheInstance::keys`] are the same
+    /// across launches.
     #[must_use]
     pub fn mnemonic<T: Into<String>>(mut self, mnemonic: T) -> Self {
         self.mnemonic = Some(mnemonic.into());
@@ -131,6 +142,29 @@ impl Ganache {
         self
     }
 
+    /// Sets the block to fork from, in addition to [`Self::fork`].
+    ///
+    /// **Note:** if set, then this requires `fork` to be set as well
+    #[must_use]
+    pub fn fork_block_number<T: Into<u64>>(mut self, fork_block_number: T) -> Self {
+        self.fork_block_number = Some(fork_block_number.into());
+        self
+    }
+
+    /// Sets the block gas limit which will be used when the `ganache-cli` instance is launched.
+    #[must_use]
+    pub fn gas_limit<T: Into<u64>>(mut self, gas_limit: T) -> Self {
+        self.gas_limit = Some(gas_limit.into());
+        self
+    }
+
+    /// Sets the chain id which will be used when the `ganache-cli` instance is launched.
+    #[must_use]
+    pub fn chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
     /// Adds an argument to pass to the `ganache-cli`.
     #[must_use]
     pub fn arg<T: Into<String>>(mut self, arg: T) -> Self {
@@ -169,7 +203,18 @@ impl Ganache {
         }
 
         if let Some(fork) = self.fork {
-            cmd.arg("-f").arg(fork);
+            match self.fork_block_number {
+                Some(number) => cmd.arg("-f").arg(format!("{}@{}", fork, number)),
+                None => cmd.arg("-f").arg(fork),
+            };
+        }
+
+        if let Some(gas_limit) = self.gas_limit {
+            cmd.arg("-l").arg(gas_limit.to_string());
+        }
+
+        if let Some(chain_id) = self.chain_id {
+            cmd.arg("--chainId").arg(chain_id.to_string());
         }
 
         cmd.args(self.args);
@@ -203,8 +248,10 @@ impl Ganache {
             }
 
             if is_private_key && line.starts_with('(') {
-                let key_str = &line[6..line.len() - 1];
-                let key_hex = hex::decode(key_str).expect("could not parse as hex");
+                // e.g. `(0) 0x4f3edf983ac636a65a842ce7c78d9aa706d3b113bce9c46f30d7d21715b23b1d`
+                let key_str = line.split_whitespace().last().expect("private key");
+                let key_hex =
+                    hex::decode(key_str.trim_start_matches("0x")).expect("could not parse as hex");
                 let key = K256SecretKey::from_be_bytes(&key_hex).expect("did not get private key");
                 addresses.push(secret_key_to_address(&SigningKey::from(&key)));
                 private_keys.push(key);
@@ -213,10 +260,40 @@ impl Ganache {
 
         child.stdout = Some(reader.into_inner());
 
+        // ganache may still be setting up, e.g. fetching the forked state, so wait until it
+        // actually responds to requests
+        while !responds_to_requests(port) {
+            if start + startup_timeout <= Instant::now() {
+                panic!("Timed out waiting for ganache to respond to requests")
+            }
+            thread::sleep(Duration::from_millis(GANACHE_READY_POLL_INTERVAL_MILLIS));
+        }
+
         GanacheInstance { pid: child, private_keys, addresses, port }
     }
 }
 
+/// Returns true if the node listening on `port` answers an `eth_blockNumber` request
+fn responds_to_requests(port: u16) -> bool {
+    let body = r#"{"jsonrpc":"2.0","id":1,"method":"eth_blockNumber","params":[]}"#;
+    let request = format!(
+        "POST / HTTP/1.1\r\nHost: localhost:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        port,
+        body.len(),
+        body
+    );
+
+    let mut stream = match TcpStream::connect(("127.0.0.1", port)) {
+        Ok(stream) => stream,
+        Err(_) => return false,
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+    let mut response = String::new();
+    stream.write_all(request.as_bytes()).is_ok() &&
+        stream.read_to_string(&mut response).is_ok() &&
+        response.contains("\"result\"")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,4 +309,31 @@ mod tests {
     fn default_startup_works() {
         Ganache::new().spawn();
     }
+
+    #[test]
+    fn polls_readiness() {
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).unwrap();
+            let body = r#"{"jsonrpc":"2.0","id":1,"result":"0x0"}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        });
+
+        assert!(responds_to_requests(port));
+        server.join().unwrap();
+
+        // nothing listens anymore
+        assert!(!responds_to_requests(port));
+    }
 }
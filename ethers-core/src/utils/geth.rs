@@ -1,6 +1,8 @@
-use super::unused_port;
+use super::{secret_key_to_address, unused_port};
+use crate::types::Address;
+use k256::{ecdsa::SigningKey, SecretKey as K256SecretKey};
 use std::{
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, ErrorKind},
     path::PathBuf,
     process::{Child, Command},
     time::{Duration, Instant},
@@ -15,6 +17,10 @@ const API: &str = "eth,net,web3,txpool";
 /// The geth command
 const GETH: &str = "geth";
 
+/// The well-known private key of the prefunded developer account, used by `geth --dev` (since
+/// v1.13) if there is no account in the keystore
+const DEV_KEY: &str = "b71c71a67e1177ad4e901695e1b4b9ee17ae16c6668d313eac2f96dbcda3f291";
+
 /// A geth instance. Will close the instance when dropped.
 ///
 /// Construct this using [`Geth`](crate::utils::Geth)
@@ -22,6 +28,9 @@ pub struct GethInstance {
     pid: Child,
     port: u16,
     ipc: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    private_keys: Vec<K256SecretKey>,
+    addresses: Vec<Address>,
 }
 
 impl GethInstance {
@@ -43,6 +52,24 @@ impl GethInstance {
     pub fn ipc_path(&self) -> &Option<PathBuf> {
         &self.ipc
     }
+
+    /// Returns the data directory of this instance, if one was set
+    pub fn data_dir(&self) -> &Option<PathBuf> {
+        &self.data_dir
+    }
+
+    /// Returns the private key of the prefunded developer account.
+    ///
+    /// This is empty if geth uses an account of the keystore in the data directory, or if the
+    /// geth version generates a random developer account.
+    pub fn keys(&self) -> &[K256SecretKey] {
+        &self.private_keys
+    }
+
+    /// Returns the address of the prefunded developer account
+    pub fn addresses(&self) -> &[Address] {
+        &self.addresses
+    }
 }
 
 impl Drop for GethInstance {
@@ -67,9 +94,11 @@ impl Drop for GethInstance {
 ///
 /// let geth = Geth::new()
 ///     .port(port)
-///     .block_time(5000u64)
+///     .block_time(5u64)
 ///     .spawn();
 ///
+/// let dev_account = geth.addresses()[0];
+///
 /// drop(geth); // this will kill the instance
 /// ```
 #[derive(Clone, Default)]
@@ -77,6 +106,7 @@ pub struct Geth {
     port: Option<u16>,
     block_time: Option<u64>,
     ipc_path: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
 }
 
 impl Geth {
@@ -93,7 +123,8 @@ impl Geth {
         self
     }
 
-    /// Sets the block-time which will be used when the `geth-cli` instance is launched.
+    /// Sets the block-time in seconds which will be used when the `geth-cli` instance is
+    /// launched.
     #[must_use]
     pub fn block_time<T: Into<u64>>(mut self, block_time: T) -> Self {
         self.block_time = Some(block_time.into());
@@ -107,6 +138,14 @@ impl Geth {
         self
     }
 
+    /// Sets the data directory of the instance, so the chain persists between launches. By
+    /// default, geth keeps the dev chain in memory.
+    #[must_use]
+    pub fn data_dir<T: Into<PathBuf>>(mut self, path: T) -> Self {
+        self.data_dir = Some(path.into());
+        self
+    }
+
     /// Consumes the builder and spawns `geth` with stdout redirected
     /// to /dev/null.
     pub fn spawn(self) -> GethInstance {
@@ -135,20 +174,38 @@ impl Geth {
             cmd.arg("--ipcpath").arg(ipc);
         }
 
-        let mut child = cmd.spawn().expect("couldnt start geth");
+        if let Some(ref data_dir) = self.data_dir {
+            cmd.arg("--datadir").arg(data_dir);
+        }
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                panic!("Could not find `{}` in $PATH. Is geth installed?", GETH)
+            }
+            Err(err) => panic!("couldnt start geth: {}", err),
+        };
 
         let stdout = child.stderr.expect("Unable to get stderr for geth child process");
 
         let start = Instant::now();
         let mut reader = BufReader::new(stdout);
 
+        let mut addresses = Vec::new();
         loop {
             if start + Duration::from_millis(GETH_STARTUP_TIMEOUT_MILLIS) <= Instant::now() {
                 panic!("Timed out waiting for geth to start. Is geth installed?")
             }
 
             let mut line = String::new();
-            reader.read_line(&mut line).expect("Failed to read line from geth process");
+            let read = reader.read_line(&mut line).expect("Failed to read line from geth process");
+            if read == 0 {
+                panic!("geth exited before its HTTP endpoint was opened")
+            }
+
+            if let Some(address) = parse_dev_account(&line) {
+                addresses.push(address);
+            }
 
             // geth 1.9.23 uses "server started" while 1.9.18 uses "endpoint opened"
             if line.contains("HTTP endpoint opened") || line.contains("HTTP server started") {
@@ -158,6 +215,55 @@ impl Geth {
 
         child.stderr = Some(reader.into_inner());
 
-        GethInstance { pid: child, port, ipc: self.ipc_path }
+        // the key of the developer account is only known if geth uses the well-known dev key
+        let dev_key = dev_key();
+        let private_keys = if addresses == [secret_key_to_address(&SigningKey::from(&dev_key))] {
+            vec![dev_key]
+        } else {
+            Vec::new()
+        };
+
+        GethInstance {
+            pid: child,
+            port,
+            ipc: self.ipc_path,
+            data_dir: self.data_dir,
+            private_keys,
+            addresses,
+        }
+    }
+}
+
+/// Returns the well-known private key of the developer account
+fn dev_key() -> K256SecretKey {
+    K256SecretKey::from_be_bytes(&hex::decode(DEV_KEY).expect("valid hex"))
+        .expect("valid private key")
+}
+
+/// Parses the address of the developer account from geth's log line, e.g.
+/// `INFO [..] Using developer account    address=0x71562b71999873DB5b286dF957af199Ec94617F7`
+fn parse_dev_account(line: &str) -> Option<Address> {
+    if !line.contains("Using developer account") {
+        return None
+    }
+    let address = &line[line.find("address=")? + "address=".len()..];
+    address.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dev_account() {
+        let line = "INFO [10-16|12:00:00.000] Using developer account                 address=0x71562b71999873DB5b286dF957af199Ec94617F7\n";
+        let address = parse_dev_account(line).unwrap();
+        assert_eq!(address, secret_key_to_address(&SigningKey::from(&dev_key())));
+        assert_eq!(
+            address,
+            "0x71562b71999873DB5b286dF957af199Ec94617F7".parse::<Address>().unwrap()
+        );
+
+        assert!(parse_dev_account("INFO [10-16|12:00:00.000] HTTP server started").is_none());
     }
 }
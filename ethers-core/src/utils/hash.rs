@@ -1,4 +1,4 @@
-//! Various utilities for manipulating Ethereum related dat
+//! Various utilities for manipulating Ethereum related data
 use ethabi::ethereum_types::H256;
 use tiny_keccak::{Hasher, Keccak};
 
@@ -9,6 +9,16 @@ const PREFIX: &str = "\x19Ethereum Signed Message:\n";
 /// The data is a UTF-8 encoded string and will enveloped as follows:
 /// `"\x19Ethereum Signed Message:\n" + message.length + message` and hashed
 /// using keccak256.
+///
+/// ```
+/// use ethers_core::utils::hash_message;
+///
+/// let hash = hash_message("Hello World");
+/// assert_eq!(
+///     hash,
+///     "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2".parse().unwrap()
+/// );
+/// ```
 pub fn hash_message<S>(message: S) -> H256
 where
     S: AsRef<[u8]>,
@@ -22,6 +32,13 @@ where
 }
 
 /// Compute the Keccak-256 hash of input bytes.
+///
+/// ```
+/// use ethers_core::utils::{hex, keccak256};
+///
+/// let hash = keccak256(b"hello");
+/// assert_eq!(hex::encode(hash), "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8");
+/// ```
 // TODO: Add Solidity Keccak256 packing support
 pub fn keccak256<S>(bytes: S) -> [u8; 32]
 where
@@ -37,6 +54,14 @@ where
 /// Calculate the function selector as per the contract ABI specification. This
 /// is defined as the first 4 bytes of the Keccak256 hash of the function
 /// signature.
+///
+/// The signature must be in its canonical form, i.e. without spaces or argument names.
+///
+/// ```
+/// use ethers_core::utils::id;
+///
+/// assert_eq!(id("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+/// ```
 pub fn id<S: AsRef<str>>(signature: S) -> [u8; 4] {
     let mut output = [0u8; 4];
 
@@ -88,6 +113,13 @@ mod tests {
         assert_eq!(id("myMethod(uint256,string)"), [0x24, 0xee, 0x00, 0x97],);
     }
 
+    #[test]
+    fn erc20_function_signatures() {
+        assert_eq!(hex::encode(id("transfer(address,uint256)")), "a9059cbb");
+        assert_eq!(hex::encode(id("approve(address,uint256)")), "095ea7b3");
+        assert_eq!(hex::encode(id("balanceOf(address)")), "70a08231");
+    }
+
     #[test]
     fn revert_function_signature() {
         assert_eq!(id("Error(string)"), [0x08, 0xc3, 0x79, 0xa0]);
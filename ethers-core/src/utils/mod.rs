@@ -22,16 +22,23 @@ pub mod moonbeam;
 mod hash;
 pub use hash::{hash_message, id, keccak256, serialize};
 
+/// ENS name hashing
+pub mod ens;
+pub use ens::{labelhash, namehash};
+
 mod units;
 pub use units::Units;
 
+mod verify;
+pub use verify::{verify_all, verify_signatures, VerificationMode};
+
 /// Re-export RLP
 pub use rlp;
 
 /// Re-export hex
 pub use hex;
 
-use crate::types::{Address, Bytes, I256, U256};
+use crate::types::{Address, Bytes, GasTiers, H160, I256, U256};
 use elliptic_curve::sec1::ToEncodedPoint;
 use ethabi::ethereum_types::FromDecStrErr;
 use k256::{ecdsa::SigningKey, PublicKey as K256PublicKey};
@@ -52,6 +59,20 @@ pub enum ConversionError {
     FromDecStrError(#[from] FromDecStrErr),
     #[error(transparent)]
     DecimalError(#[from] rust_decimal::Error),
+    #[error(transparent)]
+    FromHexError(#[from] hex::FromHexError),
+    #[error("invalid address checksum")]
+    InvalidAddressChecksum,
+    #[error("invalid decimal number: {0}")]
+    InvalidDecimal(String),
+    #[error("{0} has more fractional digits than the {1} decimals of the unit")]
+    TooManyDecimals(String, u32),
+}
+
+impl From<std::convert::Infallible> for ConversionError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
 }
 
 /// 1 Ether = 1e18 Wei == 0x0de0b6b3a7640000 Wei
@@ -69,6 +90,23 @@ pub const EIP1559_FEE_ESTIMATION_PRIORITY_FEE_TRIGGER: u64 = 100_000_000_000;
 /// The threshold max change/difference (in %) at which we will ignore the fee history values
 /// under it.
 pub const EIP1559_FEE_ESTIMATION_THRESHOLD_MAX_CHANGE: i64 = 200;
+/// The reward percentiles fetched for estimating fees for a target probability of inclusion, see
+/// [`eip1559_target_estimator`].
+pub const EIP1559_FEE_ESTIMATION_TARGET_PERCENTILES: [f64; 10] =
+    [10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0];
+/// The reward percentiles fetched for the safe, standard and fast gas price tiers, see
+/// [`gas_price_tiers_estimator`].
+pub const GAS_PRICE_TIER_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+/// The number of recent block hashes available to the `BLOCKHASH` opcode
+pub const BLOCKHASH_WINDOW: u64 = 256;
+/// The address of the EIP-2935 system contract serving historical block hashes
+pub const EIP2935_HISTORY_STORAGE_ADDRESS: Address = H160([
+    0x00, 0x00, 0xf9, 0x08, 0x27, 0xf1, 0xc5, 0x3a, 0x10, 0xcb, 0x7a, 0x02, 0x33, 0x5b, 0x17, 0x53,
+    0x20, 0x00, 0x29, 0x35,
+]);
+/// The number of recent block hashes served by the EIP-2935 system contract
+pub const EIP2935_HISTORY_SERVE_WINDOW: u64 = 8191;
 
 /// Format the output for the user which prefer to see values
 /// in ether (instead of wei)
@@ -80,33 +118,37 @@ pub fn format_ether<T: Into<U256>>(amount: T) -> U256 {
 
 /// Divides the provided amount with 10^{units} provided.
 ///
+/// The conversion is exact and trailing zeros of the fractional part are trimmed.
+///
 /// ```
-/// use ethers_core::{types::U256, utils::format_units};
+/// use ethers_core::{types::U256, utils::{format_units, Units}};
 ///
 /// let eth = format_units(1395633240123456000_u128, "ether").unwrap();
-/// assert_eq!(eth.parse::<f64>().unwrap(), 1.395633240123456);
+/// assert_eq!(eth, "1.395633240123456");
 ///
-/// let eth = format_units(U256::from_dec_str("1395633240123456000").unwrap(), "ether").unwrap();
-/// assert_eq!(eth.parse::<f64>().unwrap(), 1.395633240123456);
-///
-/// let eth = format_units(U256::from_dec_str("1395633240123456789").unwrap(), "ether").unwrap();
+/// let eth = format_units(U256::from_dec_str("1395633240123456789").unwrap(), Units::Ether).unwrap();
 /// assert_eq!(eth, "1.395633240123456789");
+///
+/// let gwei = format_units(1_500_000_000u64, 9).unwrap();
+/// assert_eq!(gwei, "1.5");
 /// ```
 pub fn format_units<T, K>(amount: T, units: K) -> Result<String, ConversionError>
 where
     T: Into<U256>,
-    K: TryInto<Units, Error = ConversionError>,
+    K: TryInto<Units>,
+    ConversionError: From<K::Error>,
 {
-    let units = units.try_into()?;
-    let amount = amount.into();
-    let amount_decimals = amount % U256::from(10_u128.pow(units.as_num()));
-    let amount_integer = amount / U256::from(10_u128.pow(units.as_num()));
-    Ok(format!(
-        "{}.{:0width$}",
-        amount_integer,
-        amount_decimals.as_u128(),
-        width = units.as_num() as usize
-    ))
+    let decimals = units.try_into()?.as_num() as usize;
+    let digits = amount.into().to_string();
+    if decimals == 0 {
+        return Ok(digits)
+    }
+
+    // pad with zeros so there is at least one integer digit
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    Ok(format!("{}.{}", integer, if fraction.is_empty() { "0" } else { fraction }))
 }
 
 /// Converts the input to a U256 and converts from Ether to Wei.
@@ -147,16 +189,28 @@ where
 pub fn parse_units<K, S>(amount: S, units: K) -> Result<U256, ConversionError>
 where
     S: ToString,
-    K: TryInto<Units, Error = ConversionError> + Copy,
+    K: TryInto<Units>,
+    ConversionError: From<K::Error>,
 {
-    use rust_decimal::Decimal;
-    let num: Decimal = amount.to_string().parse()?;
-    let multiplier: Decimal = 10u64.pow(units.try_into()?.as_num()).into();
-    let val =
-        num.checked_mul(multiplier).ok_or(rust_decimal::Error::ExceedsMaximumPossibleValue)?;
-    let u256_n: U256 = U256::from_dec_str(&val.round().to_string())?;
-    Ok(u256_n)
+    let decimals = units.try_into()?.as_num() as usize;
+    let amount = amount.to_string();
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount.as_str(), ""));
+
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(integer) || !is_digits(fraction) || (integer.is_empty() && fraction.is_empty()) {
+        return Err(ConversionError::InvalidDecimal(amount))
+    }
+
+    // trailing zeros do not add precision
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals {
+        return Err(ConversionError::TooManyDecimals(amount, decimals as u32))
+    }
+
+    let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+    Ok(U256::from_dec_str(&digits)?)
 }
+
 /// The address for an Ethereum contract is deterministically computed from the
 /// address of its creator (sender) and how many transactions the creator has
 /// sent (nonce). The sender and nonce are RLP encoded and then hashed with Keccak-256.
@@ -264,7 +318,21 @@ pub fn secret_key_to_address(secret_key: &SigningKey) -> Address {
 
 /// Converts an Ethereum address to the checksum encoding
 /// Ref: <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-55.md>
+///
+/// If a chain id is provided, the chain specific checksum of
+/// [EIP-1191](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1191.md) is used instead, see
+/// [`to_checksum_with_chain_id`] for chain ids which don't fit into a `u8`.
 pub fn to_checksum(addr: &Address, chain_id: Option<u8>) -> String {
+    checksum(addr, chain_id.map(u64::from))
+}
+
+/// Converts an Ethereum address to the chain specific checksum encoding of
+/// [EIP-1191](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1191.md)
+pub fn to_checksum_with_chain_id(addr: &Address, chain_id: u64) -> String {
+    checksum(addr, Some(chain_id))
+}
+
+fn checksum(addr: &Address, chain_id: Option<u64>) -> String {
     let prefixed_addr = match chain_id {
         Some(chain_id) => format!("{}0x{:x}", chain_id, addr),
         None => format!("{:x}", addr),
@@ -285,6 +353,35 @@ pub fn to_checksum(addr: &Address, chain_id: Option<u8>) -> String {
     })
 }
 
+/// Parses an address, with or without `0x` prefix, and validates its checksum.
+///
+/// All lowercase and all uppercase addresses carry no checksum and are accepted, mixed case
+/// addresses must match [`to_checksum`], or [`to_checksum_with_chain_id`] for the given chain id.
+///
+/// ```
+/// use ethers_core::utils::parse_checksummed;
+///
+/// assert!(parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", None).is_ok());
+/// assert!(parse_checksummed("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", None).is_ok());
+/// assert!(parse_checksummed("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED", None).is_ok());
+/// assert!(parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeaED", None).is_err());
+/// ```
+pub fn parse_checksummed(addr: &str, chain_id: Option<u64>) -> Result<Address, ConversionError> {
+    let addr_hex = addr.strip_prefix("0x").unwrap_or(addr);
+    if addr_hex.len() != 40 {
+        return Err(hex::FromHexError::InvalidStringLength.into())
+    }
+    let address = Address::from_slice(&hex::decode(addr_hex)?);
+
+    let is_lowercase = !addr_hex.bytes().any(|b| b.is_ascii_uppercase());
+    let is_uppercase = !addr_hex.bytes().any(|b| b.is_ascii_lowercase());
+    if is_lowercase || is_uppercase || checksum(&address, chain_id)[2..] == *addr_hex {
+        Ok(address)
+    } else {
+        Err(ConversionError::InvalidAddressChecksum)
+    }
+}
+
 /// Returns a bytes32 string representation of text. If the length of text exceeds 32 bytes,
 /// an error is returned.
 pub fn format_bytes32_string(text: &str) -> Result<[u8; 32], ConversionError> {
@@ -329,6 +426,84 @@ pub fn eip1559_default_estimator(base_fee_per_gas: U256, rewards: Vec<Vec<U256>>
     (max_fee_per_gas, max_priority_fee_per_gas)
 }
 
+/// Estimates the EIP-1559 fees for a transaction to be included within the next `blocks` blocks
+/// with the given `probability` (between 0 and 1), returning
+/// `(max_fee_per_gas, max_priority_fee_per_gas)`.
+///
+/// `base_fee_per_gas` is the base fee of the next block, and `rewards` holds the priority fees
+/// paid in recent blocks at the [`EIP1559_FEE_ESTIMATION_TARGET_PERCENTILES`], as returned by
+/// `eth_feeHistory`.
+///
+/// The heuristic treats the inclusion in each of the blocks as independent events:
+///
+/// 1. A transaction is included within `blocks` blocks with `probability` if it is included in a
+///    single block with probability `p = 1 - (1 - probability)^(1 / blocks)`.
+/// 2. A transaction paying the priority fee at the `p`-th percentile of a recent block would have
+///    outbid a share `p` of its transactions, so it is assumed to be included in a block with
+///    probability `p`. The priority fee is the median over the recent blocks of the smallest
+///    fetched percentile at or above `p`, ignoring empty blocks. Without any non-empty block the
+///    default priority fee is used.
+/// 3. The base fee can rise by 12.5% per block, so the max fee covers the base fee rising in each
+///    of the `blocks` blocks, plus the priority fee.
+///
+/// A higher probability or a shorter deadline thus result in a higher priority fee, while a
+/// longer deadline results in a higher max fee.
+pub fn eip1559_target_estimator(
+    base_fee_per_gas: U256,
+    rewards: &[Vec<U256>],
+    blocks: u64,
+    probability: f64,
+) -> (U256, U256) {
+    let blocks = blocks.max(1);
+    let probability = if probability.is_nan() { 1.0 } else { probability.clamp(0.0, 1.0) };
+    let percentile = (1.0 - (1.0 - probability).powf(1.0 / blocks as f64)) * 100.0;
+
+    // tolerate rounding errors, e.g. a probability of 0.9 within a single block is the 90th
+    // percentile
+    let index = EIP1559_FEE_ESTIMATION_TARGET_PERCENTILES
+        .iter()
+        .position(|p| *p >= percentile - 1e-6)
+        .unwrap_or(EIP1559_FEE_ESTIMATION_TARGET_PERCENTILES.len() - 1);
+    let mut fees: Vec<U256> = rewards
+        .iter()
+        .filter_map(|block| block.get(index).copied())
+        .filter(|fee| !fee.is_zero())
+        .collect();
+    fees.sort();
+    let max_priority_fee_per_gas = if fees.is_empty() {
+        U256::from(EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE)
+    } else {
+        fees[fees.len() / 2]
+    };
+
+    let max_base_fee_per_gas =
+        (0..blocks).fold(base_fee_per_gas, |fee, _| fee.saturating_mul(9u64.into()) / 8u64);
+    (max_base_fee_per_gas.saturating_add(max_priority_fee_per_gas), max_priority_fee_per_gas)
+}
+
+/// Estimates the safe, standard and fast tiers from the `rewards` paid in recent blocks at the
+/// [`GAS_PRICE_TIER_PERCENTILES`], as returned by `eth_feeHistory`.
+///
+/// The fee of each tier is the median over the recent blocks of the reward at its percentile,
+/// ignoring empty blocks, and at least the fee of the tier below it. Returns `None` if there are
+/// no non-empty blocks.
+pub fn gas_price_tiers_estimator(rewards: &[Vec<U256>]) -> Option<GasTiers<U256>> {
+    let median = |index: usize| {
+        let mut fees: Vec<U256> = rewards
+            .iter()
+            .filter_map(|block| block.get(index).copied())
+            .filter(|fee| !fee.is_zero())
+            .collect();
+        fees.sort();
+        fees.get(fees.len() / 2).copied()
+    };
+
+    let safe = median(0)?;
+    let standard = median(1).unwrap_or_default().max(safe);
+    let fast = median(2).unwrap_or_default().max(standard);
+    Some(GasTiers { safe, standard, fast })
+}
+
 fn estimate_priority_fee(rewards: Vec<Vec<U256>>) -> U256 {
     let mut rewards: Vec<U256> =
         rewards.iter().map(|r| r[0]).filter(|r| *r > U256::zero()).collect();
@@ -433,6 +608,19 @@ mod tests {
         let eth =
             format_units(U256::from_dec_str("1005633240123456789").unwrap(), "ether").unwrap();
         assert_eq!(eth, "1.005633240123456789");
+
+        assert_eq!(format_units(WEI_IN_ETHER, Units::Ether).unwrap(), "1.0");
+        assert_eq!(format_units(1u8, Units::Ether).unwrap(), "0.000000000000000001");
+        assert_eq!(format_units(1_234_000_000u64, Units::Gwei).unwrap(), "1.234");
+        assert_eq!(format_units(1234u64, Units::Wei).unwrap(), "1234");
+        assert_eq!(
+            format_units(U256::MAX, 18).unwrap(),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+        assert_eq!(
+            format_units(12345u64, Units::Other(40)).unwrap(),
+            "0.0000000000000000000000000000000000012345"
+        );
     }
 
     #[test]
@@ -454,6 +642,46 @@ mod tests {
 
         let val = parse_units("2.3", "ether").unwrap();
         assert_eq!(val, U256::from_dec_str("2300000000000000000").unwrap());
+
+        let val = parse_units(".5", Units::Gwei).unwrap();
+        assert_eq!(val, U256::from(500_000_000u64));
+
+        // more digits than fit into 96 bits must not lose precision
+        let val =
+            parse_units("123456789012345678901234567890.123456789012345678", Units::Ether).unwrap();
+        assert_eq!(
+            val,
+            U256::from_dec_str("123456789012345678901234567890123456789012345678").unwrap()
+        );
+
+        // trailing zeros beyond the decimals of the unit are fine
+        assert_eq!(parse_units("1.5000", 1).unwrap(), U256::from(15u64));
+    }
+
+    #[test]
+    fn test_parse_units_errors() {
+        assert!(matches!(
+            parse_units("1.0000000001", Units::Gwei).unwrap_err(),
+            ConversionError::TooManyDecimals(_, 9)
+        ));
+        assert!(matches!(
+            parse_units("1.5", Units::Wei).unwrap_err(),
+            ConversionError::TooManyDecimals(_, 0)
+        ));
+        for invalid in ["", ".", "-1", "1.2.3", "1e18", "abc"] {
+            assert!(matches!(
+                parse_units(invalid, Units::Ether).unwrap_err(),
+                ConversionError::InvalidDecimal(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_units_roundtrip() {
+        let amount = U256::from_dec_str("1395633240123456789").unwrap();
+        for units in [Units::Wei, Units::Gwei, Units::Ether, Units::Other(6)] {
+            assert_eq!(parse_units(format_units(amount, units).unwrap(), units).unwrap(), amount);
+        }
     }
 
     #[test]
@@ -540,10 +768,56 @@ mod tests {
 
         for (chain_id, addr, checksummed_addr) in addr_list {
             let addr = addr.parse::<Address>().unwrap();
-            assert_eq!(to_checksum(&addr, chain_id), String::from(checksummed_addr));
+            let checksum = match chain_id {
+                Some(chain_id) => to_checksum_with_chain_id(&addr, chain_id),
+                None => to_checksum(&addr, None),
+            };
+            assert_eq!(checksum, String::from(checksummed_addr));
+            assert_eq!(to_checksum(&addr, chain_id.map(|id| id as u8)), checksum);
+            assert_eq!(parse_checksummed(checksummed_addr, chain_id).unwrap(), addr);
         }
     }
 
+    #[test]
+    fn parse_checksummed_addr() {
+        // the examples of EIP-55
+        let addr_list = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+        ];
+
+        for checksummed_addr in addr_list {
+            let addr = parse_checksummed(checksummed_addr, None).unwrap();
+            assert_eq!(to_checksum(&addr, None), checksummed_addr);
+            assert_eq!(parse_checksummed(&checksummed_addr[2..], None).unwrap(), addr);
+            assert_eq!(parse_checksummed(&checksummed_addr.to_lowercase(), None).unwrap(), addr);
+            assert_eq!(
+                parse_checksummed(&format!("0x{}", checksummed_addr[2..].to_uppercase()), None)
+                    .unwrap(),
+                addr
+            );
+        }
+
+        // flipping the case of a single letter invalidates the checksum
+        assert!(matches!(
+            parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD", None),
+            Err(ConversionError::InvalidAddressChecksum)
+        ));
+        // the EIP-55 checksum is not valid on RSK
+        assert!(matches!(
+            parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", Some(30)),
+            Err(ConversionError::InvalidAddressChecksum)
+        ));
+        assert!(parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA", None).is_err());
+        assert!(parse_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg", None).is_err());
+    }
+
     #[test]
     fn contract_address() {
         // http://ethereum.stackexchange.com/questions/760/how-is-the-address-of-an-ethereum-contract-computed
@@ -701,4 +975,74 @@ mod tests {
         let rewards_overflow: Vec<Vec<U256>> = vec![vec![overflow], vec![overflow]];
         assert_eq!(estimate_priority_fee(rewards_overflow), overflow);
     }
+
+    #[test]
+    fn eip2935_history_storage_address() {
+        assert_eq!(
+            to_checksum(&EIP2935_HISTORY_STORAGE_ADDRESS, None),
+            "0x0000F90827F1C53a10cb7A02335B175320002935"
+        );
+    }
+
+    #[test]
+    fn test_gas_price_tiers_estimator() {
+        let gwei = |n: u64| U256::from(n) * 1_000_000_000u64;
+        let rewards = vec![
+            vec![gwei(1), gwei(2), gwei(5)],
+            vec![U256::zero(); 3],
+            vec![gwei(2), gwei(3), gwei(3)],
+            vec![gwei(1), gwei(1), gwei(8)],
+        ];
+        let tiers = gas_price_tiers_estimator(&rewards).unwrap();
+        assert_eq!(tiers, GasTiers { safe: gwei(1), standard: gwei(2), fast: gwei(5) });
+
+        // the tiers never decrease, even if the rewards of a block do
+        let tiers = gas_price_tiers_estimator(&[vec![gwei(3), gwei(2), gwei(1)]]).unwrap();
+        assert_eq!(tiers, GasTiers { safe: gwei(3), standard: gwei(3), fast: gwei(3) });
+
+        assert_eq!(gas_price_tiers_estimator(&[vec![U256::zero(); 3]]), None);
+        assert_eq!(gas_price_tiers_estimator(&[]), None);
+    }
+
+    #[test]
+    fn test_eip1559_target_estimator() {
+        let gwei = |n: u64| U256::from(n) * 1_000_000_000u64;
+        let base_fee_per_gas = gwei(80);
+        // the rewards at the 10th, 20th, ..., 100th percentile increase by 1 gwei each, with
+        // slightly different levels per block and an empty block
+        let rewards: Vec<Vec<U256>> = vec![
+            (1..=10).map(gwei).collect(),
+            (2..=11).map(gwei).collect(),
+            vec![U256::zero(); 10],
+            (1..=10).map(|n| gwei(n) + gwei(1) / 2).collect(),
+        ];
+
+        let estimate = |blocks, probability| {
+            eip1559_target_estimator(base_fee_per_gas, &rewards, blocks, probability)
+        };
+
+        // a higher probability yields higher fees
+        let mut last = (U256::zero(), U256::zero());
+        for probability in [0.1, 0.5, 0.75, 0.9, 0.99] {
+            let (max_fee, priority_fee) = estimate(3, probability);
+            assert!(priority_fee > last.1, "{} {} {}", probability, priority_fee, last.1);
+            assert!(max_fee > last.0);
+            assert_eq!(max_fee, base_fee_per_gas * 729 / 512 + priority_fee);
+            last = (max_fee, priority_fee);
+        }
+
+        // 90% within a single block is the median of the 90th percentile
+        assert_eq!(estimate(1, 0.9), (gwei(90) + gwei(9) + gwei(1) / 2, gwei(9) + gwei(1) / 2));
+        // 90% within 3 blocks needs ~54% per block, i.e. the 60th percentile
+        assert_eq!(estimate(3, 0.9).1, gwei(6) + gwei(1) / 2);
+
+        // a longer deadline lowers the priority fee, but allows for a higher base fee
+        let (max_fee, priority_fee) = estimate(10, 0.9);
+        assert!(priority_fee < estimate(3, 0.9).1);
+        assert!(max_fee > estimate(3, 0.9).0);
+
+        // without any fee history, the default priority fee is used
+        let (_, priority_fee) = eip1559_target_estimator(base_fee_per_gas, &[], 3, 0.9);
+        assert_eq!(priority_fee, U256::from(EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE));
+    }
 }
@@ -5,11 +5,16 @@ use std::collections::BTreeMap;
 use k256::SecretKey;
 
 /// Returns the private developer keys <https://docs.moonbeam.network/builders/get-started/networks/moonbeam-dev/#pre-funded-development-accounts>
+///
+/// The same accounts are pre-funded on development nodes of Moonbeam, Moonriver and Moonbase
+/// Alpha, ordered by name from `Alith` to `Judith`.
 pub fn dev_keys() -> Vec<SecretKey> {
     MoonbeamDev::default().into_keys().collect()
 }
 
-/// Holds private developer keys with their names
+/// Holds private developer keys with their names.
+///
+/// The keys are shared by the development nodes of Moonbeam, Moonriver and Moonbase Alpha.
 #[derive(Debug, Clone)]
 pub struct MoonbeamDev {
     keys: BTreeMap<&'static str, SecretKey>,
@@ -29,6 +34,11 @@ impl MoonbeamDev {
         self.keys.get(name.as_ref())
     }
 
+    /// Get a key by its index in the alphabetical order of the names, `0` being `Alith`
+    pub fn get_by_index(&self, index: usize) -> Option<&SecretKey> {
+        self.keys.values().nth(index)
+    }
+
     pub fn alith(&self) -> &SecretKey {
         self.get("Alith").unwrap()
     }
@@ -41,9 +51,21 @@ impl MoonbeamDev {
         self.get("Charleth").unwrap()
     }
 
+    pub fn dorothy(&self) -> &SecretKey {
+        self.get("Dorothy").unwrap()
+    }
+
     pub fn ethan(&self) -> &SecretKey {
         self.get("Ethan").unwrap()
     }
+
+    pub fn faith(&self) -> &SecretKey {
+        self.get("Faith").unwrap()
+    }
+
+    pub fn goliath(&self) -> &SecretKey {
+        self.get("Goliath").unwrap()
+    }
 }
 
 fn to_secret_key(s: &str) -> SecretKey {
@@ -78,6 +100,12 @@ impl Default for MoonbeamDev {
                         "39539ab1876910bbf3a223d84a29e28f1cb4e2e456503e7e91ed39b2e7223d68",
                     ),
                 ),
+                (
+                    "Ethan",
+                    to_secret_key(
+                        "7dce9bc8babb68fec1409be38c8e1a52650206a7ed90ff956ae8a6d15eeaaef4",
+                    ),
+                ),
                 (
                     "Faith",
                     to_secret_key(
@@ -112,3 +140,37 @@ impl Default for MoonbeamDev {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::Address, utils::secret_key_to_address};
+    use k256::ecdsa::SigningKey;
+
+    fn address(key: &SecretKey) -> Address {
+        secret_key_to_address(&SigningKey::from(key.clone()))
+    }
+
+    #[test]
+    fn dev_accounts() {
+        let dev = MoonbeamDev::default();
+        assert_eq!(dev_keys().len(), 10);
+        assert_eq!(
+            address(dev.alith()),
+            "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac".parse::<Address>().unwrap()
+        );
+        assert_eq!(
+            address(dev.dorothy()),
+            "0x773539d4Ac0e786233D90A233654ccEE26a613D9".parse::<Address>().unwrap()
+        );
+        assert_eq!(
+            address(dev.ethan()),
+            "0xFf64d3F6efE2317EE2807d223a0Bdc4c0c49dfDB".parse::<Address>().unwrap()
+        );
+
+        assert_eq!(dev.get_by_index(0), Some(dev.alith()));
+        assert_eq!(dev.get_by_index(4), Some(dev.ethan()));
+        assert_eq!(dev.get_by_index(9), dev.get("Judith"));
+        assert!(dev.get_by_index(10).is_none());
+    }
+}
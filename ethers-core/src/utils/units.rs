@@ -1,6 +1,7 @@
 use super::ConversionError;
 
 /// Common Ethereum unit types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Units {
     /// Ether corresponds to 1e18 Wei
     Ether,
@@ -13,6 +14,7 @@ pub enum Units {
 }
 
 impl Units {
+    /// Returns the number of decimals of the unit
     pub fn as_num(&self) -> u32 {
         match self {
             Units::Ether => 18,
@@ -41,6 +43,14 @@ impl TryFrom<i32> for Units {
     }
 }
 
+impl TryFrom<u8> for Units {
+    type Error = ConversionError;
+
+    fn try_from(src: u8) -> Result<Self, Self::Error> {
+        Ok(Units::Other(src as u32))
+    }
+}
+
 impl TryFrom<usize> for Units {
     type Error = ConversionError;
 
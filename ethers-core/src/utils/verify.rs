@@ -0,0 +1,136 @@
+//! Batch verification of signatures against their expected signers
+use crate::types::{Address, RecoveryMessage, Signature, H256};
+
+/// How the message of each item is turned into the digest that was signed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationMode {
+    /// The message is hashed according to EIP-191 (`personal_sign`) before recovery
+    Prefixed,
+    /// The message is the 32 byte digest that was signed
+    Hash,
+}
+
+/// Recovers the signer of each `(message, signature, expected_signer)` item and returns whether
+/// it matches the expected signer.
+///
+/// Items whose signature cannot be recovered, or whose message is not a 32 byte digest in
+/// [`VerificationMode::Hash`] mode, are reported as `false`.
+///
+/// ```
+/// use ethers_core::{
+///     types::{Address, Signature},
+///     utils::{verify_signatures, VerificationMode},
+/// };
+///
+/// let signer: Address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse().unwrap();
+/// let signature: Signature = "49220e11e116d45cc5d6925af76e8c447892b154546d7fdd2374a028a89e800a7ac903b1605d2a5137b32e7fa976696ee329762f0e3a94702844298fc8838dd51b".parse().unwrap();
+///
+/// let items = [("attestation 1", signature, signer), ("attestation 2", signature, signer)];
+/// assert_eq!(verify_signatures(&items, VerificationMode::Prefixed), vec![true, false]);
+/// ```
+pub fn verify_signatures<M: AsRef<[u8]>>(
+    items: &[(M, Signature, Address)],
+    mode: VerificationMode,
+) -> Vec<bool> {
+    items
+        .iter()
+        .map(|(message, signature, expected)| verify_signature(message, signature, *expected, mode))
+        .collect()
+}
+
+/// Returns `true` if the signatures of all items were produced by their expected signers.
+///
+/// See [`verify_signatures`].
+pub fn verify_all<M: AsRef<[u8]>>(
+    items: &[(M, Signature, Address)],
+    mode: VerificationMode,
+) -> bool {
+    items
+        .iter()
+        .all(|(message, signature, expected)| verify_signature(message, signature, *expected, mode))
+}
+
+fn verify_signature<M: AsRef<[u8]>>(
+    message: M,
+    signature: &Signature,
+    expected: Address,
+    mode: VerificationMode,
+) -> bool {
+    let message = message.as_ref();
+    let message = match mode {
+        VerificationMode::Prefixed => RecoveryMessage::Data(message.to_vec()),
+        VerificationMode::Hash if message.len() == 32 => {
+            RecoveryMessage::Hash(H256::from_slice(message))
+        }
+        VerificationMode::Hash => return false,
+    };
+    signature.verify(message, expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer1() -> Address {
+        "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse().unwrap()
+    }
+
+    fn signer2() -> Address {
+        "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".parse().unwrap()
+    }
+
+    fn sig(s: &str) -> Signature {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn verifies_prefixed_signatures() {
+        let sig1 = sig("49220e11e116d45cc5d6925af76e8c447892b154546d7fdd2374a028a89e800a7ac903b1605d2a5137b32e7fa976696ee329762f0e3a94702844298fc8838dd51b");
+        let sig2 = sig("3f8fd717cac3098e94483e73153dfa1cbc8351ddec55ffaa14b52ab016a7a64b5418e0b35a0b85365aa44b7570e465a28a9e5da678a1d130773e4a36d78a04b21b");
+
+        let items = [
+            // valid
+            ("attestation 1", sig1, signer1()),
+            // valid
+            ("attestation 2", sig2, signer2()),
+            // signed by someone else
+            ("attestation 1", sig1, signer2()),
+            // different message
+            ("attestation 2", sig1, signer1()),
+        ];
+        assert_eq!(
+            verify_signatures(&items, VerificationMode::Prefixed),
+            vec![true, true, false, false]
+        );
+        assert!(!verify_all(&items, VerificationMode::Prefixed));
+        assert!(verify_all(&items[..2], VerificationMode::Prefixed));
+
+        // the prefixed signatures are not valid for the raw messages
+        assert_eq!(verify_signatures(&items[..2], VerificationMode::Hash), vec![false, false]);
+    }
+
+    #[test]
+    fn verifies_hash_signatures() {
+        let hash1 = hex::decode("75e3d606c89738d8196e9b5ec75d887df33f7c5b711b82fb3539d274a1a12d6b")
+            .unwrap();
+        let hash2 = hex::decode("8092e36fb24472277c083fa4c4375e185a7020e0ece746a97ea5d1bbe5c69aff")
+            .unwrap();
+        let sig1 = sig("309f9306a9472cbd50771d3fb91553d676686ad557b997a235bc96b1b1510ef429a514527b53533ae66ea72219947c32540655c594021a2d361d8b9adb680b231b");
+        let sig2 = sig("a76e46ae1dc5e56b5a917ff4c7f0a32844f569291af9cfd4e819494ebcf294c2327df723947a96d1087c4fe70a29327d64b03393a1572fd3b46f8a49326995a21c");
+
+        let items = [
+            (hash1.clone(), sig1, signer1()),
+            (hash2.clone(), sig2, signer2()),
+            (hash2, sig1, signer1()),
+            // not a 32 byte digest
+            (hash1[..31].to_vec(), sig1, signer1()),
+        ];
+        assert_eq!(
+            verify_signatures(&items, VerificationMode::Hash),
+            vec![true, true, false, false]
+        );
+        assert!(!verify_all(&items, VerificationMode::Hash));
+        assert!(verify_all(&items[..2], VerificationMode::Hash));
+        assert!(verify_all::<Vec<u8>>(&[], VerificationMode::Hash));
+    }
+}
@@ -116,6 +116,46 @@ impl VerifyContract {
     }
 }
 
+/// The state of a source code verification, as reported by etherscan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationStatus {
+    /// The verification request is still queued
+    Pending,
+    /// The source code was verified
+    Verified,
+    /// The source code of the contract had already been verified
+    AlreadyVerified,
+    /// The verification failed for the given reason
+    Failed(String),
+}
+
+impl VerificationStatus {
+    /// Parses the `result` of a `checkverifystatus` response, or of a rejected
+    /// `verifysourcecode` request
+    pub fn from_result(result: &str) -> Self {
+        let lowercase = result.to_lowercase();
+        if lowercase.starts_with("pending") {
+            VerificationStatus::Pending
+        } else if lowercase.starts_with("pass") {
+            VerificationStatus::Verified
+        } else if lowercase.contains("already verified") {
+            VerificationStatus::AlreadyVerified
+        } else {
+            VerificationStatus::Failed(result.to_string())
+        }
+    }
+
+    /// Whether the verification is still pending
+    pub fn is_pending(&self) -> bool {
+        matches!(self, VerificationStatus::Pending)
+    }
+
+    /// Whether the source code is verified, either by this or by an earlier request
+    pub fn is_verified(&self) -> bool {
+        matches!(self, VerificationStatus::Verified | VerificationStatus::AlreadyVerified)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CodeFormat {
     #[serde(rename = "solidity-single-file")]
@@ -247,6 +287,17 @@ pub struct Metadata {
     pub swarm_source: String,
 }
 
+/// A verified contract, with the ABI and metadata of its source code
+#[derive(Debug)]
+pub struct VerifiedContract {
+    /// The address of the contract
+    pub address: Address,
+    /// The ABI of the contract
+    pub abi: Abi,
+    /// The metadata of the contract's source code
+    pub metadata: Metadata,
+}
+
 impl Client {
     /// Submit Source Code for Verification
     pub async fn submit_contract_verification(
@@ -271,6 +322,16 @@ impl Client {
         self.post_form(&body).await
     }
 
+    /// Returns the [`VerificationStatus`] of the verification request with the receipt `guid`
+    /// received from `[Self::submit_contract_verification]`
+    pub async fn contract_verification_status(
+        &self,
+        guid: impl AsRef<str>,
+    ) -> Result<VerificationStatus> {
+        let resp = self.check_contract_verification_status(guid).await?;
+        Ok(VerificationStatus::from_result(&resp.result))
+    }
+
     /// Returns the contract ABI of a verified contract
     ///
     /// ```no_run
@@ -362,11 +423,42 @@ impl Client {
 
         Ok(res)
     }
+
+    /// Returns the ABI and source code metadata of a verified contract
+    ///
+    /// ```no_run
+    /// # use ethers_etherscan::Client;
+    /// # use ethers_core::types::Chain;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///     let client = Client::new(Chain::Mainnet, "API_KEY").unwrap();
+    ///     let contract = client
+    ///         .get_verified_contract("0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413".parse().unwrap())
+    ///         .await.unwrap();
+    ///     println!("{} has {} functions", contract.metadata.contract_name, contract.abi.functions.len());
+    /// # }
+    /// ```
+    pub async fn get_verified_contract(&self, address: Address) -> Result<VerifiedContract> {
+        let metadata = self
+            .contract_source_code(address)
+            .await?
+            .items
+            .into_iter()
+            .next()
+            .ok_or(EtherscanError::ContractCodeNotVerified(address))?;
+        let abi = serde_json::from_str(&metadata.abi)?;
+        Ok(VerifiedContract { address, abi, metadata })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{contract::VerifyContract, tests::run_at_least_duration, Client, EtherscanError};
+    use crate::{
+        contract::{VerificationStatus, VerifyContract},
+        tests::run_at_least_duration,
+        Client, EtherscanError,
+    };
     use ethers_core::types::Chain;
     use ethers_solc::{Project, ProjectPathsConfig};
     use serial_test::serial;
@@ -410,6 +502,25 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    #[serial]
+    #[ignore]
+    async fn can_fetch_verified_contract() {
+        run_at_least_duration(Duration::from_millis(250), async {
+            let client = Client::new_from_env(Chain::Mainnet).unwrap();
+
+            let contract = client
+                .get_verified_contract(
+                    "0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413".parse().unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(contract.metadata.contract_name, "DAO");
+            assert!(!contract.abi.functions.is_empty());
+        })
+        .await;
+    }
+
     #[tokio::test]
     #[serial]
     #[ignore]
@@ -485,6 +596,29 @@ mod tests {
         .await
     }
 
+    #[test]
+    fn can_parse_verification_status() {
+        assert_eq!(
+            VerificationStatus::from_result("Pending in queue"),
+            VerificationStatus::Pending
+        );
+        assert_eq!(
+            VerificationStatus::from_result("Pass - Verified"),
+            VerificationStatus::Verified
+        );
+        assert_eq!(
+            VerificationStatus::from_result("Contract source code already verified"),
+            VerificationStatus::AlreadyVerified
+        );
+        assert_eq!(
+            VerificationStatus::from_result("Already Verified"),
+            VerificationStatus::AlreadyVerified
+        );
+        let failed = VerificationStatus::from_result("Fail - Unable to verify");
+        assert_eq!(failed, VerificationStatus::Failed("Fail - Unable to verify".to_string()));
+        assert!(!failed.is_pending() && !failed.is_verified());
+    }
+
     #[tokio::test]
     #[serial]
     async fn can_flatten_and_verify_contract() {
@@ -242,6 +242,13 @@ impl<M: Middleware> FromErr<M::Error> for GasEscalatorError<M> {
     fn from(src: M::Error) -> GasEscalatorError<M> {
         GasEscalatorError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            GasEscalatorError::MiddlewareError(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
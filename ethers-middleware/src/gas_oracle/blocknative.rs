@@ -24,7 +24,7 @@ pub struct BlockNative {
     client: Client,
     url: Url,
     api_key: String,
-    gas_category: GasCategory,
+    confidence: u64,
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -43,7 +43,15 @@ impl BlockNativeGasResponse {
         &self,
         gas_category: &GasCategory,
     ) -> Result<EstimatedPrice, GasOracleError> {
-        let confidence = gas_category_to_confidence(gas_category);
+        self.get_estimation_for_confidence(gas_category_to_confidence(gas_category))
+    }
+
+    /// Returns the estimated prices of the next block for the `confidence` percentage, i.e. the
+    /// probability of a transaction paying these prices to be included in the next block
+    pub fn get_estimation_for_confidence(
+        &self,
+        confidence: u64,
+    ) -> Result<EstimatedPrice, GasOracleError> {
         Ok(self
             .block_prices
             .first()
@@ -93,14 +101,25 @@ impl BlockNative {
             client,
             api_key,
             url: BLOCKNATIVE_GAS_PRICE_ENDPOINT.try_into().unwrap(),
-            gas_category: GasCategory::Standard,
+            confidence: gas_category_to_confidence(&GasCategory::Standard),
         }
     }
 
     /// Sets the gas price category to be used when fetching the gas price.
     #[must_use]
     pub fn category(mut self, gas_category: GasCategory) -> Self {
-        self.gas_category = gas_category;
+        self.confidence = gas_category_to_confidence(&gas_category);
+        self
+    }
+
+    /// Sets the confidence percentage of the estimated prices to be used, i.e. the probability of
+    /// the transaction to be included in the next block, which overrides the gas price category.
+    ///
+    /// BlockNative estimates prices for the confidence levels 70, 80, 90, 95 and 99, fetching
+    /// prices for any other level fails with [`GasOracleError::GasCategoryNotSupported`].
+    #[must_use]
+    pub fn confidence(mut self, percent: u64) -> Self {
+        self.confidence = percent;
         self
     }
 
@@ -122,12 +141,12 @@ impl BlockNative {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl GasOracle for BlockNative {
     async fn fetch(&self) -> Result<U256, GasOracleError> {
-        let prices = self.request().await?.get_estimation_for(&self.gas_category)?;
+        let prices = self.request().await?.get_estimation_for_confidence(self.confidence)?;
         Ok(U256::from(prices.price * 100_u64) * U256::from(GWEI_TO_WEI) / U256::from(100))
     }
 
     async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
-        let prices = self.request().await?.get_estimation_for(&self.gas_category)?;
+        let prices = self.request().await?.get_estimation_for_confidence(self.confidence)?;
         let base_fee = U256::from((prices.max_fee_per_gas * 100.0) as u64) *
             U256::from(GWEI_TO_WEI) /
             U256::from(100);
@@ -19,6 +19,35 @@ where
     pub fn new(inner: M, gas_oracle: G) -> Self {
         Self { inner, gas_oracle }
     }
+
+    /// Sets the fees of `tx` which are unset to the estimates of the gas oracle
+    async fn fill_gas_fees(&self, tx: &mut TypedTransaction) -> Result<(), MiddlewareError<M>> {
+        match tx {
+            TypedTransaction::Legacy(ref mut tx) => {
+                if tx.gas_price.is_none() {
+                    tx.gas_price = Some(self.gas_oracle.fetch().await?);
+                }
+            }
+            TypedTransaction::Eip2930(ref mut inner) => {
+                if inner.tx.gas_price.is_none() {
+                    inner.tx.gas_price = Some(self.gas_oracle.fetch().await?);
+                }
+            }
+            TypedTransaction::Eip1559(ref mut inner) |
+            TypedTransaction::Eip7702(Eip7702TransactionRequest { tx: ref mut inner, .. }) => {
+                if inner.max_priority_fee_per_gas.is_none() || inner.max_fee_per_gas.is_none() {
+                    let (max_fee_per_gas, max_priority_fee_per_gas) =
+                        self.gas_oracle.estimate_eip1559_fees().await?;
+                    let max_fee_per_gas = *inner.max_fee_per_gas.get_or_insert(max_fee_per_gas);
+                    // the priority fee must not exceed the max fee
+                    inner
+                        .max_priority_fee_per_gas
+                        .get_or_insert(max_priority_fee_per_gas.min(max_fee_per_gas));
+                }
+            }
+        };
+        Ok(())
+    }
 }
 
 #[derive(Error, Debug)]
@@ -37,6 +66,13 @@ impl<M: Middleware> FromErr<M::Error> for MiddlewareError<M> {
     fn from(src: M::Error) -> MiddlewareError<M> {
         MiddlewareError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            MiddlewareError::MiddlewareError(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -67,37 +103,24 @@ where
         Ok(self.gas_oracle.estimate_eip1559_fees().await?)
     }
 
+    /// Fills the gas price, or the EIP-1559 fees of EIP-1559 transactions, with the estimates of
+    /// the gas oracle before filling the remaining fields with the inner middleware
+    async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<(), Self::Error> {
+        self.fill_gas_fees(tx).await?;
+        self.inner.fill_transaction(tx, block).await.map_err(MiddlewareError::MiddlewareError)
+    }
+
     async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
         tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
         let mut tx = tx.into();
-
-        match tx {
-            TypedTransaction::Legacy(ref mut tx) => {
-                if tx.gas_price.is_none() {
-                    tx.gas_price = Some(self.get_gas_price().await?);
-                }
-            }
-            TypedTransaction::Eip2930(ref mut inner) => {
-                if inner.tx.gas_price.is_none() {
-                    inner.tx.gas_price = Some(self.get_gas_price().await?);
-                }
-            }
-            TypedTransaction::Eip1559(ref mut inner) => {
-                if inner.max_priority_fee_per_gas.is_none() || inner.max_fee_per_gas.is_none() {
-                    let (max_fee_per_gas, max_priority_fee_per_gas) =
-                        self.estimate_eip1559_fees(None).await?;
-                    if inner.max_priority_fee_per_gas.is_none() {
-                        inner.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
-                    }
-                    if inner.max_fee_per_gas.is_none() {
-                        inner.max_fee_per_gas = Some(max_fee_per_gas);
-                    }
-                }
-            }
-        };
+        self.fill_gas_fees(&mut tx).await?;
         self.inner.send_transaction(tx, block).await.map_err(MiddlewareError::MiddlewareError)
     }
 }
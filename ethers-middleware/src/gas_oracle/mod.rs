@@ -22,6 +22,9 @@ pub use cache::Cache;
 mod polygon;
 pub use polygon::Polygon;
 
+mod provider_oracle;
+pub use provider_oracle::ProviderOracle;
+
 use ethers_core::types::U256;
 
 use async_trait::async_trait;
@@ -73,6 +76,10 @@ pub enum GasOracleError {
 
     #[error("Chain is not supported by the oracle")]
     UnsupportedChain,
+
+    /// An error of the provider queried by a [`ProviderOracle`]
+    #[error(transparent)]
+    ProviderError(Box<dyn std::error::Error + Send + Sync>),
 }
 
 /// `GasOracle` is a trait that an underlying gas oracle needs to implement.
@@ -113,5 +120,24 @@ pub trait GasOracle: Send + Sync + std::fmt::Debug {
     /// ```
     async fn fetch(&self) -> Result<U256, GasOracleError>;
 
+    /// Estimates the EIP-1559 fees, returned as `(max_fee_per_gas, max_priority_fee_per_gas)`.
+    ///
+    /// Oracles which only provide legacy gas prices fail with
+    /// [`GasOracleError::Eip1559EstimationNotSupported`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_middleware::{
+    ///     gas_oracle::{BlockNative, GasOracle},
+    /// };
+    ///
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let blocknative_oracle = BlockNative::new("my-api-key".to_string()).confidence(70);
+    /// let (max_fee_per_gas, max_priority_fee_per_gas) =
+    ///     blocknative_oracle.estimate_eip1559_fees().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError>;
 }
@@ -0,0 +1,43 @@
+use crate::gas_oracle::{GasOracle, GasOracleError};
+use async_trait::async_trait;
+use ethers_core::types::U256;
+use ethers_providers::Middleware;
+use std::fmt::Debug;
+
+/// Gas oracle which queries the node: `eth_gasPrice` for legacy gas prices and the fee history
+/// for EIP-1559 fees, see [`Middleware::estimate_eip1559_fees`].
+///
+/// This allows combining the node's estimates with the estimates of the API based oracles, e.g.
+/// in a [`Median`](crate::gas_oracle::Median).
+#[derive(Clone, Debug)]
+pub struct ProviderOracle<M: Middleware> {
+    provider: M,
+}
+
+impl<M: Middleware> ProviderOracle<M> {
+    pub fn new(provider: M) -> Self {
+        Self { provider }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> GasOracle for ProviderOracle<M>
+where
+    M: Middleware,
+    M::Error: 'static,
+{
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        self.provider
+            .get_gas_price()
+            .await
+            .map_err(|err| GasOracleError::ProviderError(Box::new(err)))
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        self.provider
+            .estimate_eip1559_fees(None)
+            .await
+            .map_err(|err| GasOracleError::ProviderError(Box::new(err)))
+    }
+}
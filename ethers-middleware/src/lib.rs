@@ -30,7 +30,12 @@ pub use signer::SignerMiddleware;
 /// The [Policy](crate::PolicyMiddleware) is used to ensure transactions comply with the rules
 /// configured in the `PolicyMiddleware` before sending them.
 pub mod policy;
-pub use policy::PolicyMiddleware;
+pub use policy::{AllowedRecipients, PolicyError, PolicyMiddleware, ValueCap};
+
+/// The [Tracing middleware](crate::TracingMiddleware) emits a `tracing` event for every
+/// transaction that is sent
+pub mod logging;
+pub use logging::TracingMiddleware;
 
 /// The [TimeLag](crate::TimeLag) provides safety against reorgs by querying state N blocks
 /// before the chain tip
@@ -0,0 +1,272 @@
+use async_trait::async_trait;
+use ethers_core::{
+    types::{transaction::eip2718::TypedTransaction, BlockId, Bytes, Transaction, H256},
+    utils::{keccak256, rlp},
+};
+use ethers_providers::{FromErr, Middleware, PendingTransaction};
+use thiserror::Error;
+use tracing_futures::Instrument;
+
+/// Middleware which emits a [`tracing`] event for every transaction that is sent.
+///
+/// Both `send_transaction` and `send_raw_transaction` are instrumented with a span, and an `INFO`
+/// event carrying the `tx_hash` and `nonce` is emitted once the node accepted the transaction. For
+/// `send_transaction`, the nonce is only known if it was set before the transaction was passed to
+/// this middleware.
+///
+/// Placed beneath a [`SignerMiddleware`](crate::SignerMiddleware), this records every signed
+/// transaction that hits the node.
+///
+/// To additionally trace every JSON-RPC request, wrap the transport of the provider in a
+/// [`TracingClient`](ethers_providers::TracingClient).
+#[derive(Debug, Clone)]
+pub struct TracingMiddleware<M> {
+    inner: M,
+}
+
+impl<M> TracingMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Instruments the inner middleware
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown when the client interacts with the tracing middleware
+pub enum TracingMiddlewareError<M: Middleware> {
+    /// Thrown when an internal middleware errors
+    #[error(transparent)]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> FromErr<M::Error> for TracingMiddlewareError<M> {
+    fn from(src: M::Error) -> TracingMiddlewareError<M> {
+        TracingMiddlewareError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            TracingMiddlewareError::MiddlewareError(err) => Some(err),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for TracingMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = TracingMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let tx = tx.into();
+        let span = tracing::info_span!("send_transaction", from = ?tx.from(), to = ?tx.to());
+        if span.is_disabled() {
+            return self
+                .inner
+                .send_transaction(tx, block)
+                .await
+                .map_err(TracingMiddlewareError::MiddlewareError)
+        }
+
+        let nonce = tx.nonce().copied();
+        async move {
+            let pending = self.inner.send_transaction(tx, block).await;
+            match &pending {
+                Ok(pending) => {
+                    tracing::info!(tx_hash = ?**pending, nonce = ?nonce, "sent transaction")
+                }
+                Err(err) => {
+                    tracing::warn!(nonce = ?nonce, error = %err, "failed to send transaction")
+                }
+            }
+            pending.map_err(TracingMiddlewareError::MiddlewareError)
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        let span = tracing::info_span!("send_raw_transaction", size = tx.len());
+        if span.is_disabled() {
+            return self
+                .inner
+                .send_raw_transaction(tx)
+                .await
+                .map_err(TracingMiddlewareError::MiddlewareError)
+        }
+
+        async move {
+            // the nonce is only decoded if the event is enabled
+            let nonce = || rlp::decode::<Transaction>(&tx).ok().map(|tx| tx.nonce);
+            let hash = H256(keccak256(&tx));
+            let pending = self.inner.send_raw_transaction(tx.clone()).await;
+            match &pending {
+                Ok(_) => tracing::info!(tx_hash = ?hash, nonce = ?nonce(), "sent transaction"),
+                Err(err) => {
+                    tracing::warn!(tx_hash = ?hash, error = %err, "failed to send transaction")
+                }
+            }
+            pending.map_err(TracingMiddlewareError::MiddlewareError)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(all(test, not(feature = "celo"), not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, TransactionRequest, TxHash, U256};
+    use ethers_providers::Provider;
+    use ethers_signers::LocalWallet;
+    use std::{
+        collections::BTreeMap,
+        fmt,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{field::Field, span, Event, Metadata, Subscriber};
+
+    type Fields = BTreeMap<String, String>;
+
+    /// Collects the fields of all events
+    #[derive(Clone, Default)]
+    struct EventCollector(Arc<Mutex<Vec<Fields>>>);
+
+    struct FieldVisitor<'a>(&'a mut Fields);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl Subscriber for EventCollector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[tokio::test]
+    async fn passes_transactions_through() {
+        let events = EventCollector::default();
+        let _guard = tracing::subscriber::set_default(events.clone());
+
+        let (provider, mock) = Provider::mocked();
+        let client = TracingMiddleware::new(provider);
+
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let tx: TypedTransaction = TransactionRequest::pay(Address::repeat_byte(1), 1u64)
+            .gas(21_000u64)
+            .gas_price(1u64)
+            .nonce(3u64)
+            .chain_id(1u64)
+            .into();
+        let raw = tx.rlp_signed(&wallet.sign_transaction_sync(&tx));
+        let hash = TxHash(keccak256(&raw));
+
+        mock.push(hash).unwrap();
+        let pending = client.send_raw_transaction(raw.clone()).await.unwrap();
+        assert_eq!(*pending, hash);
+        mock.assert_request("eth_sendRawTransaction", [raw.clone()]).unwrap();
+
+        let sent = events.0.lock().unwrap().pop().unwrap();
+        assert_eq!(sent["message"], "sent transaction");
+        assert_eq!(sent["tx_hash"], format!("{:?}", hash));
+        assert_eq!(sent["nonce"], format!("{:?}", Some(U256::from(3u64))));
+
+        // failed sends are reported with their error
+        mock.push_error(-32000, "nonce too low");
+        client.send_transaction(tx, None).await.unwrap_err();
+
+        let failed = events.0.lock().unwrap().pop().unwrap();
+        assert_eq!(failed["message"], "failed to send transaction");
+        assert_eq!(failed["nonce"], format!("{:?}", Some(U256::from(3u64))));
+        assert!(failed["error"].contains("nonce too low"), "{:?}", failed);
+    }
+
+    /// Disables all spans and events
+    struct Disabled;
+
+    impl Subscriber for Disabled {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            false
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[tokio::test]
+    async fn forwards_transactions_if_disabled() {
+        let _guard = tracing::subscriber::set_default(Disabled);
+
+        let (provider, mock) = Provider::mocked();
+        let client = TracingMiddleware::new(provider);
+
+        let raw = Bytes::from(vec![1, 2, 3]);
+        mock.push(TxHash::repeat_byte(1)).unwrap();
+        let pending = client.send_raw_transaction(raw.clone()).await.unwrap();
+        assert_eq!(*pending, TxHash::repeat_byte(1));
+        mock.assert_request("eth_sendRawTransaction", [raw]).unwrap();
+
+        mock.push_error(-32000, "nonce too low");
+        let tx = TransactionRequest::pay(Address::repeat_byte(1), 1u64)
+            .from(Address::repeat_byte(2))
+            .gas(21_000u64)
+            .gas_price(1u64)
+            .nonce(3u64)
+            .chain_id(1u64);
+        assert!(matches!(
+            client.send_transaction(tx, None).await.unwrap_err(),
+            TracingMiddlewareError::MiddlewareError(_)
+        ));
+    }
+}
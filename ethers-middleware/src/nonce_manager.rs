@@ -1,15 +1,24 @@
 use async_trait::async_trait;
 use ethers_core::types::{transaction::eip2718::TypedTransaction, *};
 use ethers_providers::{FromErr, Middleware, PendingTransaction};
+use futures_locks::Mutex;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use thiserror::Error;
 
 #[derive(Debug)]
 /// Middleware used for calculating nonces locally, useful for signing multiple
 /// consecutive transactions without waiting for them to hit the mempool
+///
+/// Nonces are handed out atomically, so concurrent senders always get distinct nonces. The nonce
+/// only ever moves forward: if a transaction fails because its nonce was already used, e.g. by a
+/// transaction sent without the manager, the nonce skips ahead to the transaction count of the
+/// address, but it is never reset below nonces that were already handed out. A transaction which
+/// fails for another reason leaves a gap at its nonce.
 pub struct NonceManagerMiddleware<M> {
     inner: M,
     initialized: AtomicBool,
+    /// Held while the nonce is fetched, so it is only initialized once
+    init_guard: Mutex<()>,
     nonce: AtomicU64,
     address: Address,
 }
@@ -21,7 +30,13 @@ where
     /// Instantiates the nonce manager with a 0 nonce. The `address` should be the
     /// address which you'll be sending transactions from
     pub fn new(inner: M, address: Address) -> Self {
-        Self { initialized: false.into(), nonce: 0.into(), inner, address }
+        Self {
+            initialized: false.into(),
+            init_guard: Mutex::new(()),
+            nonce: 0.into(),
+            inner,
+            address,
+        }
     }
 
     /// Returns the next nonce to be used
@@ -30,19 +45,26 @@ where
         nonce.into()
     }
 
+    /// Initializes the nonce with the transaction count of the address at `block` (default:
+    /// pending) and returns it. The transaction count is only fetched once, also if multiple
+    /// callers race to initialize the nonce.
     pub async fn initialize_nonce(
         &self,
         block: Option<BlockId>,
     ) -> Result<U256, NonceManagerError<M>> {
-        // initialize the nonce the first time the manager is called
         if !self.initialized.load(Ordering::SeqCst) {
-            let nonce = self
-                .inner
-                .get_transaction_count(self.address, block)
-                .await
-                .map_err(FromErr::from)?;
-            self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
-            self.initialized.store(true, Ordering::SeqCst);
+            let _guard = self.init_guard.lock().await;
+            // another caller may have initialized the nonce while we were waiting for the lock
+            if !self.initialized.load(Ordering::SeqCst) {
+                let block = block.or_else(|| Some(BlockNumber::Pending.into()));
+                let nonce = self
+                    .inner
+                    .get_transaction_count(self.address, block)
+                    .await
+                    .map_err(FromErr::from)?;
+                self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
+                self.initialized.store(true, Ordering::SeqCst);
+            }
         }
         // return current nonce
         Ok(self.nonce.load(Ordering::SeqCst).into())
@@ -53,16 +75,7 @@ where
         block: Option<BlockId>,
     ) -> Result<U256, NonceManagerError<M>> {
         // initialize the nonce the first time the manager is called
-        if !self.initialized.load(Ordering::SeqCst) {
-            let nonce = self
-                .inner
-                .get_transaction_count(self.address, block)
-                .await
-                .map_err(FromErr::from)?;
-            self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
-            self.initialized.store(true, Ordering::SeqCst);
-        }
-
+        self.initialize_nonce(block).await?;
         Ok(self.next())
     }
 }
@@ -79,6 +92,12 @@ impl<M: Middleware> FromErr<M::Error> for NonceManagerError<M> {
     fn from(src: M::Error) -> Self {
         NonceManagerError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            NonceManagerError::MiddlewareError(err) => Some(err),
+        }
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -125,11 +144,12 @@ where
             Ok(tx_hash) => Ok(tx_hash),
             Err(err) => {
                 let nonce = self.get_transaction_count(self.address, block).await?;
-                if nonce != self.nonce.load(Ordering::SeqCst).into() {
-                    // try re-submitting the transaction with the correct nonce if there
-                    // was a nonce mismatch
-                    self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
-                    tx.set_nonce(nonce);
+                if tx.nonce().map_or(false, |used| *used < nonce) {
+                    // the nonce was already used, skip ahead to the transaction count and
+                    // re-submit the transaction with a fresh nonce. The nonce is never moved
+                    // backwards, as lower nonces may be in use by concurrent senders
+                    self.nonce.fetch_max(nonce.as_u64(), Ordering::SeqCst);
+                    tx.set_nonce(self.next());
                     self.inner.send_transaction(tx, block).await.map_err(FromErr::from)
                 } else {
                     // propagate the error otherwise
@@ -139,3 +159,86 @@ where
         }
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::utils::serialize;
+    use ethers_providers::Provider;
+    use std::collections::HashSet;
+
+    #[tokio::test]
+    async fn initializes_nonce_once() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        // only a single response, a second initialization would fail
+        mock.push(U256::from(7u64)).unwrap();
+        let nonce_manager = NonceManagerMiddleware::new(provider, address);
+
+        let nonces = futures_util::future::join_all(
+            (0..50).map(|_| nonce_manager.get_transaction_count_with_manager(None)),
+        )
+        .await
+        .into_iter()
+        .map(|nonce| nonce.unwrap().as_u64())
+        .collect::<HashSet<_>>();
+        assert_eq!(nonces, (7..57).collect());
+
+        mock.assert_request(
+            "eth_getTransactionCount",
+            [serialize(&address), serialize(&BlockNumber::Pending)],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn never_reuses_nonces_after_failed_sends() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let nonce_manager = NonceManagerMiddleware::new(provider, address);
+        mock.push(U256::from(7u64)).unwrap();
+        nonce_manager.initialize_nonce(None).await.unwrap();
+        mock.assert_request(
+            "eth_getTransactionCount",
+            [serialize(&address), serialize(&BlockNumber::Pending)],
+        )
+        .unwrap();
+
+        // nonces 7 and 8 are in flight with concurrent senders
+        assert_eq!(nonce_manager.next(), 7.into());
+        assert_eq!(nonce_manager.next(), 8.into());
+
+        let tx = TransactionRequest::pay(Address::zero(), 1).from(address).gas(21000).gas_price(1);
+
+        // the send with nonce 9 fails, while the node has only seen nonce 7 so far
+        mock.push(U256::from(8u64)).unwrap();
+        mock.push_error(-32000, "insufficient funds");
+        nonce_manager.send_transaction(tx.clone(), None).await.unwrap_err();
+        let failed: TypedTransaction = tx.clone().nonce(9).into();
+        mock.assert_request("eth_sendTransaction", [failed]).unwrap();
+        mock.assert_request(
+            "eth_getTransactionCount",
+            [serialize(&address), serialize(&BlockNumber::Latest)],
+        )
+        .unwrap();
+        assert_eq!(nonce_manager.next(), 10.into());
+
+        // the send with nonce 11 fails as transactions were sent without the manager
+        let hash = H256::repeat_byte(2);
+        mock.push(hash).unwrap();
+        mock.push(U256::from(12u64)).unwrap();
+        mock.push_error(-32000, "nonce too low");
+        let pending = nonce_manager.send_transaction(tx.clone(), None).await.unwrap();
+        assert_eq!(*pending, hash);
+        let failed: TypedTransaction = tx.clone().nonce(11).into();
+        mock.assert_request("eth_sendTransaction", [failed]).unwrap();
+        mock.assert_request(
+            "eth_getTransactionCount",
+            [serialize(&address), serialize(&BlockNumber::Latest)],
+        )
+        .unwrap();
+        let resent: TypedTransaction = tx.nonce(12).into();
+        mock.assert_request("eth_sendTransaction", [resent]).unwrap();
+        assert_eq!(nonce_manager.next(), 13.into());
+    }
+}
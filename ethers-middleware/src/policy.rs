@@ -1,8 +1,10 @@
-use ethers_core::types::{transaction::eip2718::TypedTransaction, BlockId};
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Address, BlockId, NameOrAddress, U256,
+};
 use ethers_providers::{FromErr, Middleware, PendingTransaction};
 
 use async_trait::async_trait;
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug};
 use thiserror::Error;
 
 /// Basic trait to ensure that transactions about to be sent follow certain rules.
@@ -45,7 +47,84 @@ impl Policy for RejectEverything {
     }
 }
 
+/// The reason a transaction was rejected by one of the built-in policies
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// The recipient, or the deployment if `None`, is not allowed
+    #[error("recipient {0:?} is not allowed")]
+    RecipientNotAllowed(Option<NameOrAddress>),
+    /// The value of the transaction exceeds the cap
+    #[error("value {value} exceeds the cap of {cap}")]
+    ValueTooHigh { value: U256, cap: U256 },
+}
+
+/// A policy that only allows transactions to a set of addresses.
+///
+/// Contract deployments and recipients given as ENS names are rejected.
+#[derive(Debug, Clone, Default)]
+pub struct AllowedRecipients {
+    recipients: HashSet<Address>,
+}
+
+impl AllowedRecipients {
+    /// Creates a policy allowing transactions to the given addresses
+    pub fn new(recipients: impl IntoIterator<Item = Address>) -> Self {
+        Self { recipients: recipients.into_iter().collect() }
+    }
+
+    /// Allows transactions to the address
+    #[must_use]
+    pub fn allow(mut self, recipient: Address) -> Self {
+        self.recipients.insert(recipient);
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Policy for AllowedRecipients {
+    type Error = PolicyError;
+
+    async fn ensure_can_send(&self, tx: TypedTransaction) -> Result<TypedTransaction, Self::Error> {
+        match tx.to() {
+            Some(NameOrAddress::Address(to)) if self.recipients.contains(to) => Ok(tx),
+            to => Err(PolicyError::RecipientNotAllowed(to.cloned())),
+        }
+    }
+}
+
+/// A policy that rejects transactions transferring more than a fixed value.
+#[derive(Debug, Clone, Copy)]
+pub struct ValueCap {
+    cap: U256,
+}
+
+impl ValueCap {
+    /// Creates a policy rejecting transactions with a value above `cap`
+    pub fn new(cap: impl Into<U256>) -> Self {
+        Self { cap: cap.into() }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Policy for ValueCap {
+    type Error = PolicyError;
+
+    async fn ensure_can_send(&self, tx: TypedTransaction) -> Result<TypedTransaction, Self::Error> {
+        match tx.value() {
+            Some(value) if *value > self.cap => {
+                Err(PolicyError::ValueTooHigh { value: *value, cap: self.cap })
+            }
+            _ => Ok(tx),
+        }
+    }
+}
+
 /// Middleware used to enforce certain policies for transactions.
+///
+/// The policy sees transactions as they were passed to `send_transaction`, before any of their
+/// fields are filled. Multiple policies can be enforced by stacking multiple `PolicyMiddleware`s.
 #[derive(Clone, Debug)]
 pub struct PolicyMiddleware<M, P> {
     pub(crate) inner: M,
@@ -56,6 +135,13 @@ impl<M: Middleware, P: Policy> FromErr<M::Error> for PolicyMiddlewareError<M, P>
     fn from(src: M::Error) -> PolicyMiddlewareError<M, P> {
         PolicyMiddlewareError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            PolicyMiddlewareError::MiddlewareError(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl<M, P> PolicyMiddleware<M, P>
@@ -110,3 +196,47 @@ where
         self.inner.send_transaction(tx, block).await.map_err(PolicyMiddlewareError::MiddlewareError)
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::TransactionRequest;
+
+    #[tokio::test]
+    async fn allows_recipients() {
+        let allowed = Address::repeat_byte(1);
+        let policy = AllowedRecipients::default().allow(allowed);
+
+        let tx: TypedTransaction = TransactionRequest::pay(allowed, 1u64).into();
+        assert_eq!(policy.ensure_can_send(tx.clone()).await.unwrap(), tx);
+
+        let other = Address::repeat_byte(2);
+        let tx = TransactionRequest::pay(other, 1u64).into();
+        assert_eq!(
+            policy.ensure_can_send(tx).await.unwrap_err(),
+            PolicyError::RecipientNotAllowed(Some(other.into()))
+        );
+
+        let deployment = TransactionRequest::new().data(vec![0x60]).into();
+        assert_eq!(
+            policy.ensure_can_send(deployment).await.unwrap_err(),
+            PolicyError::RecipientNotAllowed(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn caps_value() {
+        let policy = ValueCap::new(100u64);
+
+        let tx: TypedTransaction = TransactionRequest::pay(Address::zero(), 100u64).into();
+        policy.ensure_can_send(tx).await.unwrap();
+        let tx: TypedTransaction = TransactionRequest::new().into();
+        policy.ensure_can_send(tx).await.unwrap();
+
+        let tx = TransactionRequest::pay(Address::zero(), 101u64).into();
+        assert_eq!(
+            policy.ensure_can_send(tx).await.unwrap_err(),
+            PolicyError::ValueTooHigh { value: 101u64.into(), cap: 100u64.into() }
+        );
+    }
+}
@@ -1,8 +1,12 @@
+use ethers_contract::{DecodedError, RevertDecoder};
 use ethers_core::types::{
-    transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
-    Address, BlockId, Bytes, Signature, U256,
+    transaction::{
+        eip2718::{TypedTransaction, ValidationError},
+        eip2930::AccessListWithGasUsed,
+    },
+    Address, BlockId, Bytes, Chain, Signature, TransactionReceipt, U256,
 };
-use ethers_providers::{maybe, FromErr, Middleware, PendingTransaction};
+use ethers_providers::{maybe, FromErr, Middleware, PendingTransaction, ProviderError};
 use ethers_signers::Signer;
 
 use async_trait::async_trait;
@@ -64,12 +68,29 @@ pub struct SignerMiddleware<M, S> {
     pub(crate) inner: M,
     pub(crate) signer: S,
     pub(crate) address: Address,
+    /// Whether transactions are validated before they're signed
+    pub(crate) validate: bool,
+    /// Decodes the revert data of transactions which fail their `eth_call` simulation, if
+    /// transactions are simulated before they're sent
+    pub(crate) preflight: Option<RevertDecoder>,
+    /// The percentage gas estimates are scaled by
+    pub(crate) gas_estimate_multiplier: Option<u64>,
+    /// The minimum gas estimate
+    pub(crate) gas_estimate_floor: Option<U256>,
 }
 
 impl<M: Middleware, S: Signer> FromErr<M::Error> for SignerMiddlewareError<M, S> {
     fn from(src: M::Error) -> SignerMiddlewareError<M, S> {
         SignerMiddlewareError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            SignerMiddlewareError::MiddlewareError(err) |
+            SignerMiddlewareError::UnknownSender { error: err, .. } => Some(err),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -98,6 +119,22 @@ pub enum SignerMiddlewareError<M: Middleware, S: Signer> {
     /// Thrown if the signer's chain_id is different than the chain_id of the transaction
     #[error("specified chain_id is different than the signer's chain_id")]
     DifferentChainID,
+    /// Thrown if the transaction to sign is invalid
+    #[error(transparent)]
+    InvalidTransaction(#[from] ValidationError),
+    /// Thrown if the signer is configured for a different chain than the provider is connected to
+    #[error("signer chain id {signer} does not match the provider chain id {provider}")]
+    ChainIdMismatch { signer: u64, provider: u64 },
+    /// Thrown if the transaction reverted when it was simulated before sending it
+    #[error("transaction reverted: {0}")]
+    PreflightReverted(DecodedError),
+    /// Thrown if waiting for the confirmations of a sent transaction fails
+    #[error(transparent)]
+    PendingTransactionError(ProviderError),
+    /// Thrown if a transaction is sent from an address which is neither the signer's address nor
+    /// an account of the node, so that nobody can sign it
+    #[error("cannot sign for {from:?}, which is neither the signer nor a node account: {error}")]
+    UnknownSender { from: Address, error: M::Error },
 }
 
 // Helper functions for locally signing transactions
@@ -111,13 +148,22 @@ where
     /// The chain_id of the signer will not be set to the chain id of the provider. If the signer
     /// passed here is initialized with a different chain id, then the client may throw errors, or
     /// methods like `sign_transaction` may error.
-    /// To automatically set the signer's chain id, see `new_with_provider_chain`.
+    /// To check or automatically set the signer's chain id, see `new_with_provider_chain` and
+    /// `new_with_provider_chain_unchecked`.
     ///
     /// [`Middleware`] ethers_providers::Middleware
     /// [`Signer`] ethers_signers::Signer
     pub fn new(inner: M, signer: S) -> Self {
         let address = signer.address();
-        SignerMiddleware { inner, signer, address }
+        SignerMiddleware {
+            inner,
+            signer,
+            address,
+            validate: true,
+            preflight: None,
+            gas_estimate_multiplier: None,
+            gas_estimate_floor: None,
+        }
     }
 
     /// Signs and returns the RLP encoding of the signed transaction.
@@ -141,6 +187,10 @@ where
             _ => {}
         }
 
+        if self.validate {
+            tx.validate()?;
+        }
+
         let signature =
             self.signer.sign_transaction(&tx).await.map_err(SignerMiddlewareError::SignerError)?;
 
@@ -148,6 +198,87 @@ where
         Ok(tx.rlp_signed(&signature))
     }
 
+    /// Disables the validation of transactions before they're signed, see
+    /// [`TypedTransaction::validate`]
+    #[must_use]
+    pub fn skip_validation(mut self) -> Self {
+        self.validate = false;
+        self
+    }
+
+    /// Simulates transactions with `eth_call` before they're signed and sent, which costs an
+    /// extra request per transaction.
+    ///
+    /// If the simulation, or the gas estimation while filling the transaction, reverts, the
+    /// transaction is not sent and [`SignerMiddlewareError::PreflightReverted`] is returned with
+    /// the revert data decoded by `decoder`.
+    #[must_use]
+    pub fn with_preflight(mut self, decoder: RevertDecoder) -> Self {
+        self.preflight = Some(decoder);
+        self
+    }
+
+    /// Scales gas estimates by `percent`, e.g. `120` adds a 20% buffer, to avoid running out of
+    /// gas when the gas used depends on state which changes until the transaction is mined.
+    ///
+    /// This applies to [`Middleware::estimate_gas`] and to the gas limit estimated while filling
+    /// transactions which don't set one.
+    ///
+    /// # Panics
+    ///
+    /// If `percent` is below `100`, which would make transactions run out of gas.
+    #[must_use]
+    pub fn gas_estimate_multiplier(mut self, percent: u64) -> Self {
+        assert!(percent >= 100, "gas estimate multiplier must be at least 100%");
+        self.gas_estimate_multiplier = Some(percent);
+        self
+    }
+
+    /// Sets the minimum gas estimate, which applies after the
+    /// [`SignerMiddleware::gas_estimate_multiplier`]
+    #[must_use]
+    pub fn gas_estimate_floor<T: Into<U256>>(mut self, floor: T) -> Self {
+        self.gas_estimate_floor = Some(floor.into());
+        self
+    }
+
+    /// Applies the gas estimate multiplier and floor to `estimate`
+    fn buffer_gas_estimate(&self, estimate: U256) -> U256 {
+        let estimate = match self.gas_estimate_multiplier {
+            Some(percent) => estimate * percent / 100,
+            None => estimate,
+        };
+        self.gas_estimate_floor.map_or(estimate, |floor| estimate.max(floor))
+    }
+
+    /// Replaces the error of a failed preflight with the decoded revert data, if it contains any
+    fn preflight_error(&self, err: SignerMiddlewareError<M, S>) -> SignerMiddlewareError<M, S> {
+        let reason = self
+            .preflight
+            .as_ref()
+            .zip(self.as_error_response(&err))
+            .and_then(|(decoder, response)| decoder.decode_error(response));
+        match reason {
+            Some(reason) => SignerMiddlewareError::PreflightReverted(reason),
+            None => err,
+        }
+    }
+
+    /// Explains why the inner middleware failed to send a transaction from `from`, which the signer
+    /// cannot sign for
+    async fn delegation_error(
+        &self,
+        from: Address,
+        error: M::Error,
+    ) -> SignerMiddlewareError<M, S> {
+        match self.inner.get_accounts().await {
+            Ok(accounts) if !accounts.contains(&from) => {
+                SignerMiddlewareError::UnknownSender { from, error }
+            }
+            _ => SignerMiddlewareError::MiddlewareError(error),
+        }
+    }
+
     /// Returns the client's address
     pub fn address(&self) -> Address {
         self.address
@@ -171,22 +302,70 @@ where
         this
     }
 
+    /// Creates a new client from the provider and signer, after checking that the signer is
+    /// configured for the chain id of the inner [`Middleware`].
+    ///
+    /// Returns [`SignerMiddlewareError::ChainIdMismatch`] if the signer is configured for a
+    /// different chain, including signers left at their default chain id `1`. To sign for the
+    /// chain of the inner [`Middleware`] regardless, see
+    /// [`SignerMiddleware::new_with_provider_chain_unchecked`].
+    ///
+    /// [`Middleware`] ethers_providers::Middleware
+    pub async fn new_with_provider_chain(
+        inner: M,
+        signer: S,
+    ) -> Result<Self, SignerMiddlewareError<M, S>> {
+        let chain_id =
+            inner.get_chainid().await.map_err(|e| SignerMiddlewareError::MiddlewareError(e))?;
+        let chain_id = chain_id.as_u64();
+
+        let signer_chain_id = signer.chain_id();
+        if signer_chain_id != chain_id {
+            return Err(SignerMiddlewareError::ChainIdMismatch {
+                signer: signer_chain_id,
+                provider: chain_id,
+            })
+        }
+
+        Ok(SignerMiddleware::new(inner, signer))
+    }
+
     /// Creates a new client from the provider and signer.
     /// Sets the address of this middleware to the address of the signer.
     /// Sets the chain id of the signer to the chain id of the inner [`Middleware`] passed in,
-    /// using the [`Signer`]'s implementation of with_chain_id.
+    /// using the [`Signer`]'s implementation of with_chain_id, whichever chain id the signer was
+    /// configured for.
     ///
     /// [`Middleware`] ethers_providers::Middleware
     /// [`Signer`] ethers_signers::Signer
-    pub async fn new_with_provider_chain(
+    pub async fn new_with_provider_chain_unchecked(
         inner: M,
         signer: S,
     ) -> Result<Self, SignerMiddlewareError<M, S>> {
-        let address = signer.address();
         let chain_id =
             inner.get_chainid().await.map_err(|e| SignerMiddlewareError::MiddlewareError(e))?;
         let signer = signer.with_chain_id(chain_id.as_u64());
-        Ok(SignerMiddleware { inner, signer, address })
+        Ok(SignerMiddleware::new(inner, signer))
+    }
+
+    /// Signs and broadcasts the transaction, then waits until it has `confirmations`
+    /// confirmations.
+    ///
+    /// If no number of confirmations is given, the
+    /// [recommended confirmations](Chain::recommended_confirmations) of the signer's chain are
+    /// used.
+    pub async fn send_and_confirm<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        confirmations: Option<usize>,
+    ) -> Result<Option<TransactionReceipt>, SignerMiddlewareError<M, S>> {
+        let confirmations = confirmations
+            .unwrap_or_else(|| Chain::recommended_confirmations_of(self.signer.chain_id()));
+        self.send_transaction(tx, None)
+            .await?
+            .confirmations(confirmations)
+            .await
+            .map_err(SignerMiddlewareError::PendingTransactionError)
     }
 
     fn set_tx_from_if_none(&self, tx: &TypedTransaction) -> TypedTransaction {
@@ -253,16 +432,33 @@ where
 
         let nonce = maybe(tx.nonce().cloned(), self.get_transaction_count(from, block)).await?;
         tx.set_nonce(nonce);
+        let estimate_gas = tx.gas().is_none();
         self.inner()
             .fill_transaction(tx, block)
             .await
             .map_err(SignerMiddlewareError::MiddlewareError)?;
+
+        if estimate_gas {
+            if let Some(gas) = tx.gas().copied() {
+                tx.set_gas(self.buffer_gas_estimate(gas));
+            }
+        }
         Ok(())
     }
 
     /// Signs and broadcasts the transaction. The optional parameter `block` can be passed so that
     /// gas cost and nonce calculations take it into account. For simple transactions this can be
     /// left to `None`.
+    ///
+    /// The path the transaction takes depends on its `from` field:
+    ///
+    /// - unset or the signer's address: the transaction is signed by the signer and broadcast with
+    ///   `eth_sendRawTransaction`
+    /// - any other address: the signer cannot sign it, so it is sent with the inner middleware,
+    ///   i.e. signed by an inner `SignerMiddleware` for that address or by the node with
+    ///   `eth_sendTransaction`, which requires the account to be unlocked on the node. If that
+    ///   fails and the address is not one of the node's accounts,
+    ///   [`SignerMiddlewareError::UnknownSender`] is returned.
     async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
         tx: T,
@@ -271,15 +467,21 @@ where
         let mut tx = tx.into();
 
         // fill any missing fields
-        self.fill_transaction(&mut tx, block).await?;
+        self.fill_transaction(&mut tx, block).await.map_err(|err| self.preflight_error(err))?;
 
         // If the from address is set and is not our signer, delegate to inner
-        if tx.from().is_some() && tx.from() != Some(&self.address()) {
-            return self
-                .inner
-                .send_transaction(tx, block)
+        if let Some(from) = tx.from().copied().filter(|from| *from != self.address) {
+            return match self.inner.send_transaction(tx, block).await {
+                Ok(pending) => Ok(pending),
+                Err(error) => Err(self.delegation_error(from, error).await),
+            }
+        }
+
+        if self.preflight.is_some() {
+            self.inner
+                .call(&tx, block)
                 .await
-                .map_err(SignerMiddlewareError::MiddlewareError)
+                .map_err(|err| self.preflight_error(SignerMiddlewareError::MiddlewareError(err)))?;
         }
 
         // if we have a nonce manager set, we should try handling the result in
@@ -305,7 +507,9 @@ where
 
     async fn estimate_gas(&self, tx: &TypedTransaction) -> Result<U256, Self::Error> {
         let tx = self.set_tx_from_if_none(tx);
-        self.inner.estimate_gas(&tx).await.map_err(SignerMiddlewareError::MiddlewareError)
+        let estimate =
+            self.inner.estimate_gas(&tx).await.map_err(SignerMiddlewareError::MiddlewareError)?;
+        Ok(self.buffer_gas_estimate(estimate))
     }
 
     async fn create_access_list(
@@ -334,8 +538,8 @@ where
 mod tests {
     use super::*;
     use ethers_core::{
-        types::TransactionRequest,
-        utils::{self, keccak256, Anvil},
+        types::{Eip1559TransactionRequest, Transaction, TransactionRequest, H256},
+        utils::{self, keccak256, rlp, Anvil},
     };
     use ethers_providers::Provider;
     use ethers_signers::LocalWallet;
@@ -381,6 +585,80 @@ mod tests {
         assert_eq!(tx, expected_rlp);
     }
 
+    #[tokio::test]
+    async fn fills_missing_fields() {
+        let anvil = Anvil::new().spawn();
+        let provider = Provider::try_from(anvil.endpoint()).unwrap();
+        let key: LocalWallet = anvil.keys()[0].clone().into();
+        let key = key.with_chain_id(anvil.chain_id());
+        let address = key.address();
+        let client = SignerMiddleware::new(provider, key);
+        let to = Address::repeat_byte(1);
+
+        // a bare legacy transaction is filled completely
+        let mut tx = TransactionRequest::new().to(to).value(100u64).into();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.from(), Some(&address));
+        assert_eq!(tx.nonce(), Some(&0u64.into()));
+        assert_eq!(tx.gas(), Some(&21_000u64.into()));
+        assert!(tx.gas_price().is_some());
+        assert_eq!(tx.chain_id(), Some(anvil.chain_id().into()));
+
+        let raw = client.sign_transaction(tx.clone()).await.unwrap();
+        let signed: Transaction = rlp::decode(&raw).unwrap();
+        assert_eq!(signed.recover_from().unwrap(), address);
+        assert_eq!(signed.to, Some(to));
+        assert_eq!(signed.value, 100u64.into());
+        assert_eq!(signed.nonce, 0u64.into());
+        assert_eq!(signed.gas, 21_000u64.into());
+        assert_eq!(signed.gas_price, tx.gas_price());
+
+        // fields set by the caller are kept
+        let mut tx = Eip1559TransactionRequest::new()
+            .to(to)
+            .value(100u64)
+            .nonce(5u64)
+            .gas(30_000u64)
+            .max_priority_fee_per_gas(1u64)
+            .into();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.nonce(), Some(&5u64.into()));
+        assert_eq!(tx.gas(), Some(&30_000u64.into()));
+        match tx {
+            TypedTransaction::Eip1559(tx) => {
+                assert_eq!(tx.max_priority_fee_per_gas, Some(1u64.into()));
+                assert!(tx.max_fee_per_gas.is_some());
+            }
+            _ => panic!("expected an EIP-1559 transaction"),
+        }
+    }
+
+    #[tokio::test]
+    async fn validates_tx_before_signing() {
+        let tx: TypedTransaction = TransactionRequest::pay(Address::repeat_byte(1), 1)
+            .gas(20_000)
+            .gas_price(1)
+            .nonce(0)
+            .chain_id(1)
+            .into();
+        let (provider, _) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_chain_id(1u64);
+        let client = SignerMiddleware::new(provider, key);
+
+        let err = client.sign_transaction(tx.clone()).await.unwrap_err();
+        assert!(
+            matches!(err, SignerMiddlewareError::InvalidTransaction(ValidationError::GasTooLow(_))),
+            "{:?}",
+            err
+        );
+
+        let client = client.skip_validation();
+        client.sign_transaction(tx).await.unwrap();
+    }
+
     #[tokio::test]
     async fn signs_tx_none_chainid() {
         // retrieved test vector from:
@@ -430,7 +708,8 @@ mod tests {
 
         // combine the provider and wallet and test that the chain id is the same for both the
         // signer returned by the middleware and through the middleware itself.
-        let client = SignerMiddleware::new_with_provider_chain(provider, key).await.unwrap();
+        let client =
+            SignerMiddleware::new_with_provider_chain_unchecked(provider, key).await.unwrap();
         let middleware_chainid = client.get_chainid().await.unwrap();
         assert_eq!(chain_id, middleware_chainid);
 
@@ -452,7 +731,8 @@ mod tests {
 
         // combine the provider and wallet and test that the chain id is the same for both the
         // signer returned by the middleware and through the middleware itself.
-        let client = SignerMiddleware::new_with_provider_chain(provider, key).await.unwrap();
+        let client =
+            SignerMiddleware::new_with_provider_chain_unchecked(provider, key).await.unwrap();
         let middleware_chainid = client.get_chainid().await.unwrap();
         assert_eq!(chain_id, middleware_chainid);
 
@@ -461,6 +741,174 @@ mod tests {
         assert_eq!(chain_id.as_u64(), signer_chainid);
     }
 
+    #[tokio::test]
+    async fn buffers_gas_estimates() {
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let (provider, mock) = Provider::mocked();
+        let client = SignerMiddleware::new(provider, key).gas_estimate_multiplier(150);
+        let tx = TransactionRequest::pay(Address::zero(), 1).into();
+
+        mock.push(U256::from(40_000u64)).unwrap();
+        assert_eq!(client.estimate_gas(&tx).await.unwrap(), 60_000u64.into());
+
+        let client = client.gas_estimate_floor(100_000u64);
+        mock.push(U256::from(40_000u64)).unwrap();
+        assert_eq!(client.estimate_gas(&tx).await.unwrap(), 100_000u64.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 100%")]
+    fn rejects_gas_estimate_multipliers_below_100() {
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let (provider, _) = Provider::mocked();
+        let _ = SignerMiddleware::new(provider, key).gas_estimate_multiplier(90);
+    }
+
+    #[tokio::test]
+    async fn sends_transactions_from_other_addresses_with_the_node() {
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let (provider, mock) = Provider::mocked();
+        let client = SignerMiddleware::new(provider, key);
+
+        // fully filled, so that only the transaction is sent
+        let other = Address::repeat_byte(1);
+        let tx = TransactionRequest::pay(Address::zero(), 1)
+            .from(other)
+            .nonce(0)
+            .gas(21_000)
+            .gas_price(1)
+            .chain_id(1);
+
+        let hash = H256::repeat_byte(2);
+        mock.push(hash).unwrap();
+        assert_eq!(*client.send_transaction(tx.clone(), None).await.unwrap(), hash);
+        let sent: TypedTransaction = tx.clone().into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+
+        // the node has no account for the address
+        mock.push(Vec::<Address>::new()).unwrap();
+        mock.push_error(-32000, "unknown account");
+        let err = client.send_transaction(tx.clone(), None).await.unwrap_err();
+        assert!(
+            matches!(err, SignerMiddlewareError::UnknownSender { from, .. } if from == other),
+            "{:?}",
+            err
+        );
+
+        // the node has the account, but fails to send the transaction
+        mock.push(vec![other]).unwrap();
+        mock.push_error(-32000, "authentication needed: password or unlock");
+        let err = client.send_transaction(tx, None).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::MiddlewareError(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn rejects_signer_for_other_chain() {
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap();
+
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(5u64)).unwrap();
+        let err =
+            SignerMiddleware::new_with_provider_chain(provider, key.clone().with_chain_id(10u64))
+                .await
+                .unwrap_err();
+        assert!(
+            matches!(err, SignerMiddlewareError::ChainIdMismatch { signer: 10, provider: 5 }),
+            "{:?}",
+            err
+        );
+
+        // signers for mainnet are not retargeted either
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(5u64)).unwrap();
+        let err =
+            SignerMiddleware::new_with_provider_chain(provider, key.clone().with_chain_id(1u64))
+                .await
+                .unwrap_err();
+        assert!(
+            matches!(err, SignerMiddlewareError::ChainIdMismatch { signer: 1, provider: 5 }),
+            "{:?}",
+            err
+        );
+
+        // unless explicitly asked to
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(5u64)).unwrap();
+        let client = SignerMiddleware::new_with_provider_chain_unchecked(
+            provider,
+            key.clone().with_chain_id(1u64),
+        )
+        .await
+        .unwrap();
+        assert_eq!(client.signer().chain_id(), 5);
+
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(5u64)).unwrap();
+        let client = SignerMiddleware::new_with_provider_chain(provider, key.with_chain_id(5u64))
+            .await
+            .unwrap();
+        assert_eq!(client.signer().chain_id(), 5);
+    }
+
+    #[tokio::test]
+    async fn preflight_rejects_reverting_tx() {
+        let anvil = Anvil::new().spawn();
+        let provider = Provider::try_from(anvil.endpoint()).unwrap();
+        // deploys a contract which always reverts with `Error("nope")`
+        let init_code = Bytes::from(hex::decode("6070600c60003960706000f36064600c60003960646000fd08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000046e6f706500000000000000000000000000000000000000000000000000000000").unwrap());
+        let deployer = provider.get_accounts().await.unwrap()[0];
+        let tx = TransactionRequest::new().from(deployer).data(init_code);
+        let receipt = provider.send_transaction(tx, None).await.unwrap().await.unwrap().unwrap();
+        let reverter = receipt.contract_address.unwrap();
+
+        let key: LocalWallet = anvil.keys()[1].clone().into();
+        let client = SignerMiddleware::new_with_provider_chain_unchecked(provider, key)
+            .await
+            .unwrap()
+            .with_preflight(RevertDecoder::default());
+
+        // reverts in the `eth_call` simulation
+        let tx = TransactionRequest::new().to(reverter).gas(100_000);
+        let err = client.send_transaction(tx, None).await.unwrap_err();
+        assert!(
+            matches!(&err, SignerMiddlewareError::PreflightReverted(DecodedError::Reason(reason)) if reason == "nope"),
+            "{:?}",
+            err
+        );
+
+        // reverts while estimating the gas
+        let tx = TransactionRequest::new().to(reverter);
+        let err = client.send_transaction(tx, None).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::PreflightReverted(_)), "{:?}", err);
+
+        // nothing was sent
+        let nonce = client.get_transaction_count(client.address(), None).await.unwrap();
+        assert_eq!(nonce, U256::zero());
+    }
+
+    #[tokio::test]
+    async fn sends_and_confirms_with_chain_default() {
+        let anvil = Anvil::new().spawn();
+        let provider = Provider::try_from(anvil.endpoint()).unwrap();
+        let key: LocalWallet = anvil.keys()[0].clone().into();
+        let client =
+            SignerMiddleware::new_with_provider_chain_unchecked(provider, key).await.unwrap();
+
+        // anvil mines a block per transaction, so this only resolves because a single
+        // confirmation is recommended for the anvil chain id
+        let tx = TransactionRequest::pay(Address::repeat_byte(1), 100);
+        let receipt = client.send_and_confirm(tx, None).await.unwrap().unwrap();
+        assert_eq!(receipt.status, Some(1u64.into()));
+    }
+
     #[tokio::test]
     async fn handles_tx_from_field() {
         let anvil = Anvil::new().spawn();
@@ -474,7 +922,8 @@ mod tests {
             )
             .await
             .unwrap();
-        let client = SignerMiddleware::new_with_provider_chain(provider, key).await.unwrap();
+        let client =
+            SignerMiddleware::new_with_provider_chain_unchecked(provider, key).await.unwrap();
 
         let request = TransactionRequest::new();
 
@@ -29,6 +29,13 @@ impl<M: Middleware> FromErr<M::Error> for TimeLagError<M> {
     fn from(src: M::Error) -> TimeLagError<M> {
         TimeLagError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            TimeLagError::MiddlewareError(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 /// TimeLag Provider
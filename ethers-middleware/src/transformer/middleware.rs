@@ -37,6 +37,13 @@ impl<M: Middleware> FromErr<M::Error> for TransformerMiddlewareError<M> {
     fn from(src: M::Error) -> TransformerMiddlewareError<M> {
         TransformerMiddlewareError::MiddlewareError(src)
     }
+
+    fn as_inner(&self) -> Option<&M::Error> {
+        match self {
+            TransformerMiddlewareError::MiddlewareError(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -4,10 +4,16 @@ use std::convert::TryFrom;
 
 use async_trait::async_trait;
 
-use ethers_core::{types::*, utils::Anvil};
+use ethers_core::{
+    types::{
+        transaction::{eip2718::TypedTransaction, eip2930::AccessListItem},
+        *,
+    },
+    utils::Anvil,
+};
 use ethers_middleware::gas_oracle::{
     EthGasStation, Etherchain, Etherscan, GasCategory, GasOracle, GasOracleError,
-    GasOracleMiddleware,
+    GasOracleMiddleware, ProviderOracle,
 };
 use ethers_providers::{Http, Middleware, Provider};
 use serial_test::serial;
@@ -29,6 +35,24 @@ impl GasOracle for FakeGasOracle {
     }
 }
 
+#[derive(Debug)]
+struct FakeEip1559GasOracle {
+    gas_price: U256,
+    fees: (U256, U256),
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasOracle for FakeEip1559GasOracle {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        Ok(self.gas_price)
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        Ok(self.fees)
+    }
+}
+
 #[tokio::test]
 async fn using_gas_oracle() {
     let anvil = Anvil::new().spawn();
@@ -54,6 +78,66 @@ async fn using_gas_oracle() {
     assert_eq!(tx.gas_price, Some(expected_gas_price));
 }
 
+#[tokio::test]
+async fn fills_fees_by_transaction_type() {
+    let (provider, mock) = Provider::mocked();
+    let gas_oracle = FakeEip1559GasOracle { gas_price: 100.into(), fees: (200.into(), 300.into()) };
+    let provider = GasOracleMiddleware::new(provider, gas_oracle);
+
+    // fully specified apart from the fees, so that the provider does not make any requests
+    let mut legacy: TypedTransaction =
+        TransactionRequest::pay(Address::zero(), 1).gas(21000).into();
+    provider.fill_transaction(&mut legacy, None).await.unwrap();
+    assert_eq!(legacy.gas_price(), Some(100.into()));
+
+    let access_list = vec![AccessListItem { address: Address::zero(), storage_keys: vec![] }];
+    let mut eip1559: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(Address::zero())
+        .gas(21000)
+        .access_list(access_list.clone())
+        .into();
+    provider.fill_transaction(&mut eip1559, None).await.unwrap();
+    match eip1559 {
+        TypedTransaction::Eip1559(ref tx) => {
+            assert_eq!(tx.max_fee_per_gas, Some(200.into()));
+            // capped at the max fee
+            assert_eq!(tx.max_priority_fee_per_gas, Some(200.into()));
+        }
+        _ => unreachable!(),
+    }
+
+    // fees set by the caller are kept
+    let mut eip1559: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(Address::zero())
+        .gas(21000)
+        .access_list(access_list)
+        .max_fee_per_gas(1000)
+        .into();
+    provider.fill_transaction(&mut eip1559, None).await.unwrap();
+    match eip1559 {
+        TypedTransaction::Eip1559(ref tx) => {
+            assert_eq!(tx.max_fee_per_gas, Some(1000.into()));
+            assert_eq!(tx.max_priority_fee_per_gas, Some(300.into()));
+        }
+        _ => unreachable!(),
+    }
+
+    assert!(mock.assert_request("eth_gasPrice", ()).is_err());
+}
+
+#[tokio::test]
+async fn provider_oracle() {
+    let (provider, mock) = Provider::mocked();
+    mock.push(U256::from(42)).unwrap();
+
+    let oracle = ProviderOracle::new(provider);
+    assert_eq!(oracle.fetch().await.unwrap(), 42.into());
+    mock.assert_request("eth_gasPrice", ()).unwrap();
+
+    mock.push_error(-32000, "unavailable");
+    assert!(matches!(oracle.fetch().await, Err(GasOracleError::ProviderError(_))));
+}
+
 #[tokio::test]
 async fn eth_gas_station() {
     // initialize and fetch gas estimates from EthGasStation
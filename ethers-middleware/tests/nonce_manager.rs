@@ -53,3 +53,38 @@ async fn nonce_manager() {
 
     assert_eq!(nonces, (nonce..nonce + (num_tx as u64)).collect::<Vec<_>>())
 }
+
+#[tokio::test]
+#[cfg(not(feature = "celo"))]
+async fn nonce_manager_concurrent_sends() {
+    use ethers_core::{types::*, utils::Anvil};
+    use ethers_middleware::{nonce_manager::NonceManagerMiddleware, signer::SignerMiddleware};
+    use ethers_providers::{Http, Middleware, Provider};
+    use ethers_signers::{LocalWallet, Signer};
+    use std::{collections::HashSet, convert::TryFrom};
+
+    let anvil = Anvil::new().spawn();
+    let wallet: LocalWallet = anvil.keys()[0].clone().into();
+    let wallet = wallet.with_chain_id(anvil.chain_id());
+    let address = wallet.address();
+
+    let provider = Provider::<Http>::try_from(anvil.endpoint()).unwrap();
+    let provider = SignerMiddleware::new(provider, wallet);
+    let provider = NonceManagerMiddleware::new(provider, address);
+
+    let num_tx = 50;
+    let txs = (0..num_tx).map(|_| {
+        provider.send_transaction(TransactionRequest::pay(Address::repeat_byte(1), 100u64), None)
+    });
+    let tx_hashes = futures_util::future::join_all(txs)
+        .await
+        .into_iter()
+        .map(|tx| *tx.unwrap())
+        .collect::<Vec<_>>();
+
+    let mut nonces = HashSet::new();
+    for tx_hash in tx_hashes {
+        nonces.insert(provider.get_transaction(tx_hash).await.unwrap().unwrap().nonce.as_u64());
+    }
+    assert_eq!(nonces, (0..num_tx).collect());
+}
@@ -35,7 +35,8 @@ async fn send_eth() {
         .unwrap()
         .interval(Duration::from_millis(10u64));
     let chain_id = provider.get_chainid().await.unwrap().as_u64();
-    let provider = SignerMiddleware::new_with_provider_chain(provider, wallet).await.unwrap();
+    let provider =
+        SignerMiddleware::new_with_provider_chain_unchecked(provider, wallet).await.unwrap();
 
     // craft the transaction
     let tx = TransactionRequest::new().to(wallet2.address()).value(10000).chain_id(chain_id);
@@ -167,8 +168,9 @@ async fn send_transaction_handles_tx_from_field() {
 
     // connect to the network
     let provider = Provider::try_from(anvil.endpoint()).unwrap();
-    let provider =
-        SignerMiddleware::new_with_provider_chain(provider, signer.clone()).await.unwrap();
+    let provider = SignerMiddleware::new_with_provider_chain_unchecked(provider, signer.clone())
+        .await
+        .unwrap();
 
     // sending a TransactionRequest with a from field of None should result
     // in a transaction from the signer address
@@ -267,6 +267,16 @@ pub mod spoof {
             self.storage.get_or_insert_with(Default::default).insert(key, val);
             self
         }
+        /// Replace the entire storage of the account, so that all slots which are not overridden
+        /// read as zero (`state`). By default only the overridden slots are patched (`stateDiff`).
+        pub fn replace_storage(&mut self) -> &mut Self {
+            let slots = match self.storage.take() {
+                Some(Storage::Diff(slots)) | Some(Storage::Replace(slots)) => slots,
+                None => Default::default(),
+            };
+            self.storage = Some(Storage::Replace(slots));
+            self
+        }
     }
 
     /// Wraps a map from storage slot to the overriden value.
@@ -562,6 +572,74 @@ mod tests {
         test_encode(call);
     }
 
+    #[test]
+    fn test_serialize_overrides_like_geth() {
+        let adr: Address = "0x6fC21092DA55B392b045eD78F4732bff3C580e2c".parse().unwrap();
+        let key = H256::from_low_u64_be(1);
+        let val = H256::from_low_u64_be(17);
+
+        let mut state = spoof::state();
+        state.account(adr).balance(parse_ether(1u64).unwrap()).nonce(2.into()).store(key, val);
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::json!({
+                "0x6fc21092da55b392b045ed78f4732bff3c580e2c": {
+                    "balance": "0xde0b6b3a7640000",
+                    "nonce": "0x2",
+                    "stateDiff": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000001":
+                            "0x0000000000000000000000000000000000000000000000000000000000000011"
+                    }
+                }
+            })
+        );
+
+        state.account(adr).replace_storage();
+        assert_eq!(
+            serde_json::to_value(&state).unwrap()["0x6fc21092da55b392b045ed78f4732bff3c580e2c"],
+            serde_json::json!({
+                "balance": "0xde0b6b3a7640000",
+                "nonce": "0x2",
+                "state": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x0000000000000000000000000000000000000000000000000000000000000011"
+                }
+            })
+        );
+
+        // replacing the storage of an account without storage overrides clears all its slots
+        let mut state = spoof::state();
+        state.account(adr).code(Bytes::from(vec![0x00])).replace_storage();
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::json!({
+                "0x6fc21092da55b392b045ed78f4732bff3c580e2c": { "code": "0x00", "state": {} }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_with_overrides() {
+        let (provider, mock) = Provider::mocked();
+        let adr: Address = "0x6fC21092DA55B392b045eD78F4732bff3C580e2c".parse().unwrap();
+        let tx = TransactionRequest::default().to(adr).into();
+        let state = spoof::balance(adr, 100.into());
+
+        mock.push(Bytes::from(vec![0x01])).unwrap();
+        let res = provider.call_with_overrides(&tx, Some(7.into()), &state).await.unwrap();
+        assert_eq!(res, Bytes::from(vec![0x01]));
+
+        mock.assert_request(
+            "eth_call",
+            [
+                utils::serialize(&tx),
+                serde_json::json!("0x7"),
+                serde_json::json!({ "0x6fc21092da55b392b045ed78f4732bff3c580e2c": { "balance": "0x64" } }),
+            ],
+        )
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_state_overrides() {
         let geth = Geth::new().spawn();
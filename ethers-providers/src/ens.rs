@@ -1,11 +1,8 @@
 //! [Ethereum Name Service](https://docs.ens.domains/) support
 //! Adapted from <https://github.com/hhatto/rust-ens/blob/master/src/lib.rs>
-use ethers_core::{
-    types::{Address, NameOrAddress, Selector, TransactionRequest, H160, H256},
-    utils::keccak256,
-};
+use ethers_core::types::{Address, Bytes, NameOrAddress, Selector, TransactionRequest, H160};
 
-use std::convert::TryInto;
+use std::{convert::TryInto, fmt};
 
 /// ENS registry address (`0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e`)
 pub const ENS_ADDRESS: Address = H160([
@@ -28,6 +25,15 @@ pub const NAME_SELECTOR: Selector = [105, 31, 52, 49];
 /// text(bytes32, string)
 pub const FIELD_SELECTOR: Selector = [89, 209, 212, 60];
 
+/// contenthash(bytes32)
+pub const CONTENTHASH_SELECTOR: Selector = [188, 28, 88, 209];
+
+// Multicodecs of the content hash namespaces
+const IPFS_NS: u64 = 0xe3;
+const SWARM_NS: u64 = 0xe4;
+const IPNS_NS: u64 = 0xe5;
+const ARWEAVE_NS: u64 = 0xb29910;
+
 /// Returns a transaction request for calling the `resolver` method on the ENS server
 pub fn get_resolver<T: Into<Address>>(ens_address: T, name: &str) -> TransactionRequest {
     // keccak256('resolver(bytes32)')
@@ -59,16 +65,148 @@ pub fn reverse_address(addr: Address) -> String {
     format!("{:?}.{}", addr, ENS_REVERSE_REGISTRAR_DOMAIN)[2..].to_string()
 }
 
-/// Returns the ENS namehash as specified in [EIP-137](https://eips.ethereum.org/EIPS/eip-137)
-pub fn namehash(name: &str) -> H256 {
-    if name.is_empty() {
-        return H256::zero()
+pub use ethers_core::utils::namehash;
+
+/// A content hash record as specified by [EIP-1577](https://eips.ethereum.org/EIPS/eip-1577),
+/// e.g. the IPFS CID of a website.
+///
+/// Each variant holds the content address following the multicodec of its namespace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContentHash {
+    /// The CID of an IPFS object
+    Ipfs(Bytes),
+    /// The CID of an IPNS name
+    Ipns(Bytes),
+    /// The CID of a Swarm manifest
+    Swarm(Bytes),
+    /// The id of an Arweave transaction
+    Arweave(Bytes),
+    /// A content hash of an unknown namespace, including its multicodec
+    Raw(Bytes),
+}
+
+impl ContentHash {
+    /// Decodes the bytes returned by a resolver's `contenthash(bytes32)`, returning `None` if the
+    /// record is not set.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() {
+            return None
+        }
+
+        let raw = || ContentHash::Raw(bytes.to_vec().into());
+        let (codec, len) = match decode_varint(bytes) {
+            Some(codec) => codec,
+            None => return Some(raw()),
+        };
+        let content = Bytes::from(bytes[len..].to_vec());
+        Some(match codec {
+            IPFS_NS => ContentHash::Ipfs(content),
+            IPNS_NS => ContentHash::Ipns(content),
+            SWARM_NS => ContentHash::Swarm(content),
+            ARWEAVE_NS => ContentHash::Arweave(content),
+            _ => raw(),
+        })
+    }
+
+    /// Returns the textual content identifier: the CID for IPFS and IPNS (as a base58 CIDv0 if
+    /// possible and as a base32 CIDv1 otherwise), the hex encoded hash for Swarm, the base64url
+    /// encoded transaction id for Arweave and the hex encoded bytes for unknown namespaces.
+    pub fn content_id(&self) -> String {
+        match self {
+            ContentHash::Ipfs(cid) | ContentHash::Ipns(cid) => match cid.as_ref() {
+                // CIDv1 of a dag-pb object with a sha2-256 multihash, which is usually shown as
+                // the equivalent CIDv0 of its multihash
+                [0x01, 0x70, multihash @ ..] if multihash.starts_with(&[0x12, 0x20]) => {
+                    base58(multihash)
+                }
+                // CIDv1
+                [0x01, ..] => format!("b{}", base32(cid)),
+                // a bare multihash is a CIDv0
+                _ => base58(cid),
+            },
+            ContentHash::Swarm(cid) => match cid.as_ref() {
+                // CIDv1 of a swarm manifest with a keccak-256 multihash
+                [0x01, 0xfa, 0x01, 0x1b, 0x20, hash @ ..] => hex::encode(hash),
+                _ => hex::encode(cid),
+            },
+            ContentHash::Arweave(id) => base64::encode_config(id, base64::URL_SAFE_NO_PAD),
+            ContentHash::Raw(bytes) => hex::encode(bytes),
+        }
+    }
+}
+
+impl fmt::Display for ContentHash {
+    /// Formats the content hash as a URL, e.g. `ipfs://Qm...`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scheme = match self {
+            ContentHash::Ipfs(_) => "ipfs://",
+            ContentHash::Ipns(_) => "ipns://",
+            ContentHash::Swarm(_) => "bzz://",
+            ContentHash::Arweave(_) => "ar://",
+            ContentHash::Raw(_) => "0x",
+        };
+        write!(f, "{}{}", scheme, self.content_id())
+    }
+}
+
+/// Decodes an unsigned varint, returning its value and length
+fn decode_varint(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1))
+        }
+    }
+    None
+}
+
+/// Encodes the bytes with the bitcoin base58 alphabet
+fn base58(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    // little endian base58 digits
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = u32::from(*byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
     }
 
-    // iterate in reverse
-    name.rsplit('.')
-        .fold([0u8; 32], |node, label| keccak256(&[node, keccak256(label.as_bytes())].concat()))
-        .into()
+    // leading zeros are encoded as leading ones
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    std::iter::repeat('1')
+        .take(zeros)
+        .chain(digits.iter().rev().map(|digit| ALPHABET[*digit as usize] as char))
+        .collect()
+}
+
+/// Encodes the bytes with the lowercase RFC 4648 base32 alphabet, without padding
+fn base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+    let mut encoded = String::with_capacity((bytes.len() * 8 + 4) / 5);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = ((buffer << 8) | u32::from(*byte)) & 0xffff;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    encoded
 }
 
 /// Returns a number in bytes form with padding to fit in 32 bytes.
@@ -91,6 +229,7 @@ pub fn parameterhash(name: &str) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ethers_core::types::H256;
 
     fn assert_hex(hash: H256, val: &str) {
         let v = if let Some(stripped) = val.strip_prefix("0x") { stripped } else { val };
@@ -110,6 +249,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decodes_content_hashes() {
+        // test vectors from EIP-1577
+        let ipfs = hex::decode(
+            "e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f",
+        )
+        .unwrap();
+        let ipfs = ContentHash::decode(&ipfs).unwrap();
+        assert!(matches!(ipfs, ContentHash::Ipfs(_)));
+        assert_eq!(ipfs.to_string(), "ipfs://QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4");
+
+        let swarm = hex::decode(
+            "e40101fa011b20d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162",
+        )
+        .unwrap();
+        assert_eq!(
+            ContentHash::decode(&swarm).unwrap().to_string(),
+            "bzz://d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162"
+        );
+
+        // a CIDv1 with a raw codec
+        let ipns = hex::decode("e50101551220").unwrap();
+        let ipns = ContentHash::decode(&ipns).unwrap();
+        assert_eq!(ipns, ContentHash::Ipns(vec![0x01, 0x55, 0x12, 0x20].into()));
+        assert_eq!(ipns.content_id(), "bafkreia");
+
+        let arweave = ContentHash::decode(&[0x90, 0xb2, 0xca, 0x05, 0xfb, 0xff]).unwrap();
+        assert_eq!(arweave.to_string(), "ar://-_8");
+
+        assert_eq!(
+            ContentHash::decode(&[0x01, 0x02]).unwrap(),
+            ContentHash::Raw(vec![1, 2].into())
+        );
+        assert_eq!(ContentHash::decode(&[]), None);
+    }
+
     #[test]
     fn test_parametershash() {
         assert_eq!(
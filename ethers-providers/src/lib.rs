@@ -13,7 +13,9 @@ mod provider;
 pub mod ens;
 
 mod pending_transaction;
-pub use pending_transaction::PendingTransaction;
+pub use pending_transaction::{
+    PendingTransaction, PendingTransactionWithContext, ReplacementError,
+};
 
 mod pending_escalator;
 pub use pending_escalator::EscalatingPending;
@@ -73,6 +75,11 @@ pub trait JsonRpcClient: Debug + Send + Sync {
 use ethers_core::types::*;
 pub trait FromErr<T> {
     fn from(src: T) -> Self;
+
+    /// Returns the error of the inner middleware, if this error was caused by it
+    fn as_inner(&self) -> Option<&T> {
+        None
+    }
 }
 
 /// Calls the future if `item` is None, otherwise returns a `futures::ok`
@@ -124,6 +131,12 @@ pub enum SyncingStatus {
 ///     fn from(src: M::Error) -> MyError<M> {
 ///         MyError::MiddlewareError(src)
 ///     }
+///
+///     fn as_inner(&self) -> Option<&M::Error> {
+///         match self {
+///             MyError::MiddlewareError(err) => Some(err),
+///         }
+///     }
 /// }
 ///
 /// #[async_trait]
@@ -172,6 +185,15 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().default_sender()
     }
 
+    /// Returns the JSON-RPC error the node responded with, if `err` was caused by one, e.g. to get
+    /// the revert data of a failed call.
+    ///
+    /// The error is looked up in the error of the inner middleware, see [`FromErr::as_inner`].
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        let inner = FromErr::<<Self::Inner as Middleware>::Error>::as_inner(err)?;
+        self.inner().as_error_response(inner)
+    }
+
     async fn client_version(&self) -> Result<String, Self::Error> {
         self.inner().client_version().await.map_err(FromErr::from)
     }
@@ -283,6 +305,16 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().resolve_field(ens_name, field).await.map_err(FromErr::from)
     }
 
+    /// Returns the [EIP-1577](https://eips.ethereum.org/EIPS/eip-1577) content hash record of
+    /// `ens_name`, e.g. the IPFS CID of a website, or `None` if the name has no resolver or no
+    /// content hash.
+    async fn resolve_contenthash(
+        &self,
+        ens_name: &str,
+    ) -> Result<Option<ens::ContentHash>, Self::Error> {
+        self.inner().resolve_contenthash(ens_name).await.map_err(FromErr::from)
+    }
+
     async fn get_block<T: Into<BlockId> + Send + Sync>(
         &self,
         block_hash_or_number: T,
@@ -290,6 +322,18 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().get_block(block_hash_or_number).await.map_err(FromErr::from)
     }
 
+    /// Returns the hash of the block with the given `number`.
+    ///
+    /// The hash is taken from the block header. If the node does not serve the header, the hash is
+    /// read from the EIP-2935 history contract instead, which serves the hashes of the last
+    /// [`EIP2935_HISTORY_SERVE_WINDOW`](ethers_core::utils::EIP2935_HISTORY_SERVE_WINDOW) blocks.
+    async fn get_block_hash<T: Into<U64> + Send + Sync>(
+        &self,
+        number: T,
+    ) -> Result<H256, Self::Error> {
+        self.inner().get_block_hash(number).await.map_err(FromErr::from)
+    }
+
     async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
         &self,
         block_hash_or_number: T,
@@ -384,6 +428,34 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().estimate_eip1559_fees(estimator).await.map_err(FromErr::from)
     }
 
+    /// Estimates the EIP-1559 fees for a transaction to be included within the next `blocks`
+    /// blocks with the given `probability` (between 0 and 1), returning
+    /// `(max_fee_per_gas, max_priority_fee_per_gas)`.
+    ///
+    /// The fees are estimated from the fee history of recent blocks, see
+    /// [`eip1559_target_estimator`](ethers_core::utils::eip1559_target_estimator) for the
+    /// heuristic.
+    async fn estimate_eip1559_fees_for_target(
+        &self,
+        blocks: u64,
+        probability: f64,
+    ) -> Result<(U256, U256), Self::Error> {
+        self.inner()
+            .estimate_eip1559_fees_for_target(blocks, probability)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Estimates the safe, standard and fast gas price tiers: EIP-1559 fees on chains with a
+    /// base fee, and legacy gas prices otherwise.
+    ///
+    /// The tiers are estimated from the fee history of recent blocks, see
+    /// [`gas_price_tiers_estimator`](ethers_core::utils::gas_price_tiers_estimator). Chains which
+    /// do not support `eth_feeHistory` use the node's gas price for all tiers.
+    async fn gas_price_tiers(&self) -> Result<GasPriceTiers, Self::Error> {
+        self.inner().gas_price_tiers().await.map_err(FromErr::from)
+    }
+
     async fn get_accounts(&self) -> Result<Vec<Address>, Self::Error> {
         self.inner().get_accounts().await.map_err(FromErr::from)
     }
@@ -426,6 +498,10 @@ pub trait Middleware: Sync + Send + Debug {
     }
 
     /// Returns a stream of logs are loaded in pages of given page size
+    ///
+    /// The next page is only requested once the previous one was consumed, waiting for the
+    /// polling interval of the provider if one was set. See [LogQuery] for resuming the stream
+    /// after an error.
     fn get_logs_paginated<'a>(
         &'a self,
         filter: &Filter,
@@ -496,6 +572,14 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().get_proof(from, locations, block).await.map_err(FromErr::from)
     }
 
+    async fn get_account<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<AccountInfo, Self::Error> {
+        self.inner().get_account(from, block).await.map_err(FromErr::from)
+    }
+
     // Mempool inspection for Geth's API
 
     async fn txpool_content(&self) -> Result<TxpoolContent, Self::Error> {
@@ -510,6 +594,25 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().txpool_status().await.map_err(FromErr::from)
     }
 
+    // Geth `debug` support
+
+    async fn debug_trace_transaction(
+        &self,
+        tx_hash: TxHash,
+        trace_options: GethDebugTracingOptions,
+    ) -> Result<GethTrace, Self::Error> {
+        self.inner().debug_trace_transaction(tx_hash, trace_options).await.map_err(FromErr::from)
+    }
+
+    async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        req: T,
+        block: Option<BlockId>,
+        trace_options: GethDebugTracingOptions,
+    ) -> Result<GethTrace, Self::Error> {
+        self.inner().debug_trace_call(req, block, trace_options).await.map_err(FromErr::from)
+    }
+
     // Parity `trace` support
 
     /// Executes the given call and returns a number of possible traces for it
@@ -611,6 +714,10 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().unsubscribe(id).await.map_err(FromErr::from)
     }
 
+    /// Subscribes to the headers of new blocks via `eth_subscribe("newHeads")`
+    ///
+    /// The notifications only contain the block headers, so the `transactions`, `uncles` and
+    /// `size` of the yielded blocks are not set.
     async fn subscribe_blocks(
         &self,
     ) -> Result<SubscriptionStream<'_, Self::Provider, Block<TxHash>>, Self::Error>
@@ -629,6 +736,23 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().subscribe_pending_txs().await.map_err(FromErr::from)
     }
 
+    /// Subscribes to full pending transactions via `eth_subscribe("newPendingTransactions",
+    /// true)`
+    ///
+    /// Fails with [`ProviderError::UnsupportedRPC`] if the node rejects the subscription. Nodes
+    /// which ignore the flag only notify transaction hashes, which are skipped. In both cases,
+    /// [`subscribe_pending_txs`](Self::subscribe_pending_txs) combined with
+    /// [`transactions_unordered`](SubscriptionStream::transactions_unordered) fetches the full
+    /// transactions instead.
+    async fn subscribe_full_pending_txs(
+        &self,
+    ) -> Result<SubscriptionStream<'_, Self::Provider, Transaction>, Self::Error>
+    where
+        <Self as Middleware>::Provider: PubsubClient,
+    {
+        self.inner().subscribe_full_pending_txs().await.map_err(FromErr::from)
+    }
+
     async fn subscribe_logs<'a>(
         &'a self,
         filter: &Filter,
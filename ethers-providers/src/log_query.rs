@@ -1,18 +1,39 @@
-use super::{JsonRpcClient, Middleware, PinBoxFut, Provider, ProviderError};
+use super::{JsonRpcClient, JsonRpcError, Middleware, PinBoxFut, Provider, ProviderError};
 use ethers_core::types::{Filter, Log, U64};
 use futures_core::stream::Stream;
 use std::{
     collections::VecDeque,
+    future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 use thiserror::Error;
 
+#[cfg(not(target_arch = "wasm32"))]
+use futures_timer::Delay;
+#[cfg(target_arch = "wasm32")]
+use wasm_timer::Delay;
+
+/// A stream of the logs matching a filter.
+///
+/// If the filter has a start block, the logs are fetched page by page up to the current block, and
+/// the next page is only requested once the consumer has drained the current one. The speed of the
+/// consumer therefore controls the rate of `eth_getLogs` requests, and at most one page of logs is
+/// held in memory at a time.
+///
+/// A failed request is yielded as an error and ends the stream, so a node which keeps failing is
+/// not hammered with retries. [LogQuery::resume_block] returns the block from which a new query can
+/// pick up.
 pub struct LogQuery<'a, P> {
     provider: &'a Provider<P>,
     filter: Filter,
     from_block: Option<U64>,
+    /// The start block of the page that is currently being loaded
+    page_start: Option<U64>,
     page_size: u64,
+    adaptive: bool,
+    interval: Option<Duration>,
     current_logs: VecDeque<Log>,
     last_block: Option<U64>,
     state: LogQueryState<'a>,
@@ -22,7 +43,11 @@ enum LogQueryState<'a> {
     Initial,
     LoadLastBlock(PinBoxFut<'a, U64>),
     LoadLogs(PinBoxFut<'a, Vec<Log>>),
+    /// Waits for the interval to pass before loading the page starting at the given block
+    Sleeping(Pin<Box<Delay>>, U64),
     Consume,
+    /// A request failed, the stream has ended
+    Failed,
 }
 
 impl<'a, P> LogQuery<'a, P>
@@ -34,7 +59,10 @@ where
             provider,
             filter: filter.clone(),
             from_block: filter.get_from_block(),
+            page_start: None,
             page_size: 10000,
+            adaptive: false,
+            interval: None,
             current_logs: VecDeque::new(),
             last_block: None,
             state: LogQueryState::Initial,
@@ -46,6 +74,48 @@ where
         self.page_size = page_size;
         self
     }
+
+    /// If enabled, a page the node rejects as too large, e.g. with `query returned more than
+    /// 10000 results`, is retried with half the page size until it succeeds or spans a single
+    /// block. The reduced page size is kept for the following pages.
+    pub fn with_adaptive_page_size(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// Waits for the given interval before requesting the next page, e.g. to stay below the rate
+    /// limit of the node.
+    pub fn with_interval<T: Into<Duration>>(mut self, interval: T) -> Self {
+        self.interval = Some(interval.into());
+        self
+    }
+
+    /// Returns the block from which a new query can resume without missing any logs, e.g. after
+    /// the stream yielded an error.
+    ///
+    /// This is the first block of the page that is currently being loaded or consumed, so logs of
+    /// this page which were already yielded are yielded again by the resumed query. Returns the
+    /// start block of the filter if no page was requested yet.
+    pub fn resume_block(&self) -> Option<U64> {
+        self.page_start.or_else(|| self.filter.get_from_block())
+    }
+
+    /// Returns the state which loads the page starting at `from_block`
+    fn load_page(&mut self, from_block: U64) -> LogQueryState<'a> {
+        let to_block = from_block + self.page_size;
+        self.page_start = Some(from_block);
+        self.from_block = Some(to_block + 1);
+
+        let filter = self.filter.clone().from_block(from_block).to_block(to_block);
+        let provider = self.provider;
+        LogQueryState::LoadLogs(Box::pin(async move { provider.get_logs(&filter).await }))
+    }
+}
+
+/// Returns `true` if the node rejected an `eth_getLogs` request because its block range or result
+/// set is too large
+fn is_page_too_large(err: &ProviderError) -> bool {
+    err.as_error_response().map_or(false, JsonRpcError::is_log_query_too_large)
 }
 
 macro_rules! rewake_with_new_state {
@@ -93,16 +163,14 @@ where
                         // this is okay because we will only enter this state when the filter is
                         // paginatable i.e. from block is set
                         let from_block = self.filter.get_from_block().unwrap();
-                        let to_block = from_block + self.page_size;
-                        self.from_block = Some(to_block + 1);
-
-                        let filter = self.filter.clone().from_block(from_block).to_block(to_block);
-                        let provider = self.provider;
                         // load first page of logs
-                        let fut = Box::pin(async move { provider.get_logs(&filter).await });
-                        rewake_with_new_state!(ctx, self, LogQueryState::LoadLogs(fut));
+                        let state = self.load_page(from_block);
+                        rewake_with_new_state!(ctx, self, state);
+                    }
+                    Err(err) => {
+                        self.state = LogQueryState::Failed;
+                        Poll::Ready(Some(Err(LogQueryError::LoadLastBlockError(err))))
                     }
-                    Err(err) => Poll::Ready(Some(Err(LogQueryError::LoadLastBlockError(err)))),
                 }
             }
             LogQueryState::LoadLogs(fut) => match futures_util::ready!(fut.as_mut().poll(ctx)) {
@@ -110,8 +178,26 @@ where
                     self.current_logs = VecDeque::from(logs);
                     rewake_with_new_state!(ctx, self, LogQueryState::Consume);
                 }
-                Err(err) => Poll::Ready(Some(Err(LogQueryError::LoadLogsError(err)))),
+                Err(err) => {
+                    if let Some(page_start) = self.page_start {
+                        if self.adaptive && self.page_size > 0 && is_page_too_large(&err) {
+                            // retry the same page with half the block range
+                            self.page_size /= 2;
+                            let state = self.load_page(page_start);
+                            rewake_with_new_state!(ctx, self, state);
+                        }
+                    }
+                    // the query can be resumed from the failed page
+                    self.state = LogQueryState::Failed;
+                    Poll::Ready(Some(Err(LogQueryError::LoadLogsError(err))))
+                }
             },
+            LogQueryState::Sleeping(delay, from_block) => {
+                let from_block = *from_block;
+                futures_util::ready!(delay.as_mut().poll(ctx));
+                let state = self.load_page(from_block);
+                rewake_with_new_state!(ctx, self, state);
+            }
             LogQueryState::Consume => {
                 let log = self.current_logs.pop_front();
                 if log.is_none() {
@@ -122,7 +208,6 @@ where
                         // load new logs if there are still more pages to go through
                         // can safely assume this will always be set in this state
                         let from_block = self.from_block.unwrap();
-                        let to_block = from_block + self.page_size;
 
                         // no more pages to load, and everything is consumed
                         // can safely assume this will always be set in this state
@@ -130,17 +215,189 @@ where
                             return Poll::Ready(None)
                         }
                         // load next page
-                        self.from_block = Some(to_block + 1);
-
-                        let filter = self.filter.clone().from_block(from_block).to_block(to_block);
-                        let provider = self.provider;
-                        let fut = Box::pin(async move { provider.get_logs(&filter).await });
-                        rewake_with_new_state!(ctx, self, LogQueryState::LoadLogs(fut));
+                        let state = match self.interval {
+                            Some(interval) => {
+                                LogQueryState::Sleeping(Box::pin(Delay::new(interval)), from_block)
+                            }
+                            None => self.load_page(from_block),
+                        };
+                        rewake_with_new_state!(ctx, self, state);
                     }
                 } else {
                     Poll::Ready(log.map(Ok))
                 }
             }
+            LogQueryState::Failed => Poll::Ready(None),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockError;
+    use futures_util::StreamExt;
+
+    fn log(index: u64) -> Log {
+        Log { log_index: Some(index.into()), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn fetches_next_page_once_drained() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0u64);
+
+        // responses are popped in reverse order
+        mock.push::<Vec<Log>, _>(vec![log(2)]).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log(0), log(1)]).unwrap();
+        mock.push(U64::from(15u64)).unwrap();
+
+        let mut logs = LogQuery::new(&provider, &filter).with_page_size(9);
+
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(0));
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(0u64).to_block(9u64)])
+            .unwrap();
+        // the second page is not requested while the first one is not drained
+        assert!(matches!(mock.assert_request("eth_getLogs", ()), Err(MockError::EmptyRequests)));
+
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(1));
+        assert!(matches!(mock.assert_request("eth_getLogs", ()), Err(MockError::EmptyRequests)));
+
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(2));
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(10u64).to_block(19u64)])
+            .unwrap();
+
+        assert!(logs.next().await.is_none());
+        assert!(matches!(mock.assert_request("eth_getLogs", ()), Err(MockError::EmptyRequests)));
+    }
+
+    #[tokio::test]
+    async fn ends_after_failed_page() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0u64);
+
+        mock.push_error(-32000, "header not found");
+        mock.push::<Vec<Log>, _>(vec![log(0)]).unwrap();
+        mock.push(U64::from(15u64)).unwrap();
+
+        let mut logs = LogQuery::new(&provider, &filter).with_page_size(9);
+        assert_eq!(logs.resume_block(), Some(0u64.into()));
+
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(0));
+        assert!(logs.next().await.unwrap().is_err());
+        assert!(logs.next().await.is_none());
+        assert_eq!(logs.resume_block(), Some(10u64.into()));
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(0u64).to_block(9u64)])
+            .unwrap();
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(10u64).to_block(19u64)])
+            .unwrap();
+        // the failed page is not requested again
+        assert!(matches!(mock.assert_request("eth_getLogs", ()), Err(MockError::EmptyRequests)));
+
+        // a new query resumes from the failed page
+        mock.push::<Vec<Log>, _>(vec![log(1)]).unwrap();
+        mock.push(U64::from(15u64)).unwrap();
+        let filter = filter.from_block(logs.resume_block().unwrap());
+        let mut logs = LogQuery::new(&provider, &filter).with_page_size(9);
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(1));
+        assert!(logs.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn ends_on_persistent_errors() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0u64);
+
+        // the node keeps failing, but only a single request is made
+        mock.push_error(-32000, "internal error");
+        let mut logs = LogQuery::new(&provider, &filter);
+        assert!(matches!(
+            logs.next().await,
+            Some(Err(LogQueryError::LoadLastBlockError(ProviderError::JsonRpcClientError(_))))
+        ));
+        assert!(logs.next().await.is_none());
+        assert!(logs.next().await.is_none());
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        assert!(matches!(
+            mock.assert_request("eth_blockNumber", ()),
+            Err(MockError::EmptyRequests)
+        ));
+        assert_eq!(logs.resume_block(), Some(0u64.into()));
+
+        // also if the filter is not paginated
+        mock.push_error(-32000, "internal error");
+        let mut logs = LogQuery::new(&provider, &Filter::new());
+        assert!(logs.next().await.unwrap().is_err());
+        assert!(logs.next().await.is_none());
+        mock.assert_request("eth_getLogs", [Filter::new()]).unwrap();
+        assert!(matches!(mock.assert_request("eth_getLogs", ()), Err(MockError::EmptyRequests)));
+    }
+
+    #[tokio::test]
+    async fn waits_between_pages() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0u64);
+
+        mock.push::<Vec<Log>, _>(vec![log(1)]).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log(0)]).unwrap();
+        mock.push(U64::from(15u64)).unwrap();
+
+        let interval = Duration::from_millis(50);
+        let mut logs = LogQuery::new(&provider, &filter).with_page_size(9).with_interval(interval);
+
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(0));
+        let start = std::time::Instant::now();
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(1));
+        assert!(start.elapsed() >= interval);
+        assert!(logs.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn halves_pages_too_large() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0u64);
+
+        mock.push::<Vec<Log>, _>(vec![log(1)]).unwrap();
+        mock.push::<Vec<Log>, _>(vec![log(0)]).unwrap();
+        mock.push_error(-32005, "query returned more than 10000 results");
+        mock.push(U64::from(9u64)).unwrap();
+
+        let mut logs =
+            LogQuery::new(&provider, &filter).with_page_size(8).with_adaptive_page_size(true);
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(0));
+        assert_eq!(logs.next().await.unwrap().unwrap(), log(1));
+        assert!(logs.next().await.is_none());
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(0u64).to_block(8u64)])
+            .unwrap();
+        // the page is retried with half the size, which is kept for the next page
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(0u64).to_block(4u64)])
+            .unwrap();
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(5u64).to_block(9u64)])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_not_halve_pages_on_other_errors() {
+        let (provider, mock) = Provider::mocked();
+        let filter = Filter::new().from_block(0u64);
+
+        // rate limits are not mistaken for too large pages
+        mock.push_error(-32005, "daily request count exceeded, request rate limited");
+        mock.push(U64::from(9u64)).unwrap();
+
+        let mut logs =
+            LogQuery::new(&provider, &filter).with_page_size(8).with_adaptive_page_size(true);
+        assert!(logs.next().await.unwrap().is_err());
+        assert!(logs.next().await.is_none());
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getLogs", [filter.clone().from_block(0u64).to_block(8u64)])
+            .unwrap();
+        assert!(matches!(mock.assert_request("eth_getLogs", ()), Err(MockError::EmptyRequests)));
+    }
+}
@@ -2,7 +2,10 @@ use crate::{
     stream::{interval, DEFAULT_POLL_INTERVAL},
     JsonRpcClient, Middleware, PinBoxFut, Provider, ProviderError,
 };
-use ethers_core::types::{Transaction, TransactionReceipt, TxHash, U64};
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Bytes, Eip7702TransactionRequest, Transaction,
+    TransactionReceipt, TxHash, U256, U64,
+};
 use futures_core::stream::Stream;
 use futures_util::stream::StreamExt;
 use pin_project::pin_project;
@@ -14,6 +17,7 @@ use std::{
     task::{Context, Poll},
     time::Duration,
 };
+use thiserror::Error;
 
 #[cfg(not(target_arch = "wasm32"))]
 use futures_timer::Delay;
@@ -26,6 +30,11 @@ use wasm_timer::Delay;
 /// is 1, but may be adjusted with the `confirmations` method. If the transaction does not
 /// have enough confirmations or is not mined, the future will stay in the pending state.
 ///
+/// If the transaction cannot be found (after the configured number of `retries`), the future
+/// resolves to `None`. With [`error_on_eviction`](Self::error_on_eviction), a transaction which
+/// disappears after it had been pending resolves to [`ProviderError::TransactionEvicted`]
+/// instead. To stop waiting after a deadline, set a [`timeout`](Self::timeout).
+///
 /// # Example
 ///
 ///```
@@ -63,10 +72,19 @@ pub struct PendingTransaction<'a, P> {
     state: PendingTxState<'a>,
     interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
     retries_remaining: usize,
+    /// Resolves once the transaction timed out
+    deadline: Option<Pin<Box<Delay>>>,
+    /// Whether to fail if the transaction disappears after it had been pending
+    error_on_eviction: bool,
+    /// Whether the transaction has been found
+    seen: bool,
 }
 
 const DEFAULT_RETRIES: usize = 3;
 
+/// The minimum fee increase in percent nodes require to replace a pending transaction
+const MIN_REPLACEMENT_BUMP: u64 = 10;
+
 impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
     /// Creates a new pending transaction poller from a hash and a provider
     pub fn new(tx_hash: TxHash, provider: &'a Provider<P>) -> Self {
@@ -78,6 +96,9 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
             state: PendingTxState::InitialDelay(delay),
             interval: Box::new(interval(DEFAULT_POLL_INTERVAL)),
             retries_remaining: DEFAULT_RETRIES,
+            deadline: None,
+            error_on_eviction: false,
+            seen: false,
         }
     }
 
@@ -122,6 +143,26 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
         self.retries_remaining = retries;
         self
     }
+
+    /// Sets the overall deadline: if the transaction has not been mined with the requested number
+    /// of confirmations within `timeout`, the future resolves to
+    /// [`ProviderError::PendingTransactionTimeout`].
+    ///
+    /// The deadline starts when this is called, independent of the polling `interval`.
+    #[must_use]
+    pub fn timeout<T: Into<Duration>>(mut self, timeout: T) -> Self {
+        self.deadline = Some(Box::pin(Delay::new(timeout.into())));
+        self
+    }
+
+    /// Resolves to [`ProviderError::TransactionEvicted`] if the transaction can no longer be found
+    /// (after the configured number of `retries`) after it had been pending, e.g. because it was
+    /// evicted from the mempool, instead of resolving to `None`
+    #[must_use]
+    pub fn error_on_eviction(mut self) -> Self {
+        self.error_on_eviction = true;
+        self
+    }
 }
 
 impl<'a, P> PendingTransaction<'a, P> {
@@ -146,6 +187,141 @@ impl<'a, P> PendingTransaction<'a, P> {
     pub fn log(self) -> Self {
         self.inspect(|s| println!("Pending hash: {:?}", **s))
     }
+
+    /// Tags the pending transaction with a user-defined context, e.g. an id, which is returned
+    /// alongside the result once the transaction resolved.
+    ///
+    /// This allows correlating the results of many in-flight transactions without keeping track
+    /// of their hashes.
+    pub fn with_context<T>(self, context: T) -> PendingTransactionWithContext<'a, P, T> {
+        PendingTransactionWithContext { pending: self, context: Some(context) }
+    }
+
+    /// Replaces the pending transaction with a copy which pays fees increased by `bump_percent`,
+    /// i.e. its gas price or both EIP-1559 fees, and returns the replacement.
+    ///
+    /// The replacement is sent through `client`, which therefore must be able to sign for the
+    /// sender of the transaction, e.g. a `SignerMiddleware`. Nodes only accept replacements
+    /// paying at least 10% more, a lower `bump_percent` results in
+    /// [`ReplacementError::BumpTooLow`].
+    pub async fn speed_up<'c, M: Middleware>(
+        &self,
+        client: &'c M,
+        bump_percent: u64,
+    ) -> Result<PendingTransaction<'c, M::Provider>, ReplacementError<M::Error>> {
+        let tx = self.replacement(client, bump_percent).await?;
+        client.send_transaction(tx, None).await.map_err(ReplacementError::MiddlewareError)
+    }
+
+    /// Cancels the pending transaction by replacing it with a transfer of 0 ether from its
+    /// sender to itself which pays fees increased by `bump_percent`, see
+    /// [`speed_up`](Self::speed_up).
+    pub async fn cancel<'c, M: Middleware>(
+        &self,
+        client: &'c M,
+        bump_percent: u64,
+    ) -> Result<PendingTransaction<'c, M::Provider>, ReplacementError<M::Error>> {
+        let mut tx = self.replacement(client, bump_percent).await?;
+        let from = *tx.from().expect("transactions have a sender");
+        tx.set_to(from).set_value(0).set_data(Bytes::default()).set_gas(21_000);
+        if tx.access_list().is_some() {
+            tx.set_access_list(Default::default());
+        }
+        client.send_transaction(tx, None).await.map_err(ReplacementError::MiddlewareError)
+    }
+
+    /// Returns a copy of the pending transaction with its fees increased by `bump_percent`
+    async fn replacement<M: Middleware>(
+        &self,
+        client: &M,
+        bump_percent: u64,
+    ) -> Result<TypedTransaction, ReplacementError<M::Error>> {
+        if bump_percent < MIN_REPLACEMENT_BUMP {
+            return Err(ReplacementError::BumpTooLow(bump_percent))
+        }
+        let tx = client
+            .get_transaction(self.tx_hash)
+            .await
+            .map_err(ReplacementError::MiddlewareError)?
+            .ok_or(ReplacementError::NotFound(self.tx_hash))?;
+        if tx.block_number.is_some() {
+            return Err(ReplacementError::AlreadyMined(self.tx_hash))
+        }
+
+        let bump = |fee: U256| (fee * (100 + bump_percent) + 99u64) / 100u64;
+        let mut replacement = TypedTransaction::from(&tx);
+        match replacement {
+            TypedTransaction::Eip1559(ref mut inner) |
+            TypedTransaction::Eip7702(Eip7702TransactionRequest { tx: ref mut inner, .. }) => {
+                inner.max_fee_per_gas = inner.max_fee_per_gas.map(bump);
+                inner.max_priority_fee_per_gas = inner.max_priority_fee_per_gas.map(bump);
+            }
+            _ => {
+                if let Some(gas_price) = replacement.gas_price() {
+                    replacement.set_gas_price(bump(gas_price));
+                }
+            }
+        }
+        Ok(replacement)
+    }
+}
+
+/// Errors when replacing a [`PendingTransaction`]
+#[derive(Debug, Error)]
+pub enum ReplacementError<E> {
+    /// Thrown when the internal middleware errors
+    #[error(transparent)]
+    MiddlewareError(E),
+    /// The transaction to replace could not be found
+    #[error("transaction {0:?} not found")]
+    NotFound(TxHash),
+    /// The transaction to replace has already been mined
+    #[error("transaction {0:?} has already been mined")]
+    AlreadyMined(TxHash),
+    /// The fee increase is too small for nodes to accept the replacement
+    #[error(
+        "fee bump of {0}% is below the {}% required to replace a transaction",
+        MIN_REPLACEMENT_BUMP
+    )]
+    BumpTooLow(u64),
+}
+
+/// A [`PendingTransaction`] tagged with a user-defined context, see
+/// [`PendingTransaction::with_context`].
+///
+/// `await`'ing resolves to the context and the result of the pending transaction, so the context
+/// is returned even if waiting for the transaction failed.
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct PendingTransactionWithContext<'a, P, T> {
+    #[pin]
+    pending: PendingTransaction<'a, P>,
+    context: Option<T>,
+}
+
+impl<'a, P, T> PendingTransactionWithContext<'a, P, T> {
+    /// Returns the transaction hash of the pending transaction
+    pub fn tx_hash(&self) -> TxHash {
+        self.pending.tx_hash
+    }
+
+    /// Returns the context of the pending transaction
+    pub fn context(&self) -> &T {
+        self.context.as_ref().expect("context is only taken once the transaction resolved")
+    }
+}
+
+impl<'a, P: JsonRpcClient, T> Future for PendingTransactionWithContext<'a, P, T> {
+    type Output = (T, Result<Option<TransactionReceipt>, ProviderError>);
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = futures_util::ready!(this.pending.poll(ctx));
+        let context =
+            this.context.take().expect("PendingTransactionWithContext polled after completion");
+        Poll::Ready((context, res))
+    }
 }
 
 macro_rules! rewake_with_new_state {
@@ -171,6 +347,16 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
     fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
         let this = self.project();
 
+        if let Some(deadline) = this.deadline.as_mut() {
+            if !matches!(this.state, PendingTxState::Completed) &&
+                deadline.as_mut().poll(ctx).is_ready()
+            {
+                tracing::debug!("Timed out waiting for pending tx {:?}", *this.tx_hash);
+                *this.state = PendingTxState::Completed;
+                return Poll::Ready(Err(ProviderError::PendingTransactionTimeout(*this.tx_hash)))
+            }
+        }
+
         match this.state {
             PendingTxState::InitialDelay(fut) => {
                 futures_util::ready!(fut.as_mut().poll(ctx));
@@ -203,6 +389,11 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                     if *this.retries_remaining == 0 {
                         tracing::debug!("Dropped from mempool, pending tx {:?}", *this.tx_hash);
                         *this.state = PendingTxState::Completed;
+                        if *this.seen && *this.error_on_eviction {
+                            return Poll::Ready(Err(ProviderError::TransactionEvicted(
+                                *this.tx_hash,
+                            )))
+                        }
                         return Poll::Ready(Ok(None))
                     }
 
@@ -212,6 +403,7 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
 
                 // If it hasn't confirmed yet, poll again later
                 let tx = tx_opt.unwrap();
+                *this.seen = true;
                 rewake_with_new_state_if!(
                     tx.block_number.is_none(),
                     ctx,
@@ -390,3 +582,160 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
         f.debug_struct("PendingTxState").field("state", &state).finish()
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, TransactionRequest, H256, U64};
+
+    #[tokio::test]
+    async fn times_out() {
+        let (provider, mock) = Provider::mocked();
+        // the transaction stays pending
+        for _ in 0..100 {
+            mock.push(Transaction::default()).unwrap();
+        }
+
+        let err = PendingTransaction::new(H256::repeat_byte(1), &provider)
+            .interval(Duration::from_millis(10))
+            .timeout(Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ProviderError::PendingTransactionTimeout(hash) if hash == H256::repeat_byte(1)),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn detects_evictions() {
+        let evicted = || {
+            let (provider, mock) = Provider::mocked();
+            // found while pending, then no longer found
+            mock.push(Option::<Transaction>::None).unwrap();
+            mock.push(Option::<Transaction>::None).unwrap();
+            mock.push(Transaction::default()).unwrap();
+            provider
+        };
+
+        let provider = evicted();
+        let pending =
+            PendingTransaction::new(H256::zero(), &provider).interval(Duration::from_millis(10));
+        assert_eq!(pending.retries(1).await.unwrap(), None);
+
+        let provider = evicted();
+        let err = PendingTransaction::new(H256::zero(), &provider)
+            .interval(Duration::from_millis(10))
+            .retries(1)
+            .error_on_eviction()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::TransactionEvicted(_)), "{:?}", err);
+
+        // transactions which were never found are dropped
+        let (provider, mock) = Provider::mocked();
+        mock.push(Option::<Transaction>::None).unwrap();
+        let pending = PendingTransaction::new(H256::zero(), &provider)
+            .interval(Duration::from_millis(10))
+            .retries(0)
+            .error_on_eviction();
+        assert_eq!(pending.await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn returns_context() {
+        let (provider, mock) = Provider::mocked();
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            block_number: Some(U64::one()),
+            ..Default::default()
+        };
+        mock.push(receipt.clone()).unwrap();
+        mock.push(Transaction { block_number: Some(U64::one()), ..Default::default() }).unwrap();
+
+        let pending = PendingTransaction::new(H256::repeat_byte(1), &provider)
+            .interval(Duration::from_millis(10))
+            .with_context("transfer #1");
+        assert_eq!(pending.tx_hash(), H256::repeat_byte(1));
+        assert_eq!(*pending.context(), "transfer #1");
+
+        let (context, res) = pending.await;
+        assert_eq!(context, "transfer #1");
+        assert_eq!(res.unwrap(), Some(receipt));
+
+        // the context is returned if waiting for the transaction fails
+        let (provider, mock) = Provider::mocked();
+        mock.push(Transaction::default()).unwrap();
+        let (context, res) = PendingTransaction::new(H256::repeat_byte(1), &provider)
+            .interval(Duration::from_millis(10))
+            .timeout(Duration::from_millis(50))
+            .with_context(42)
+            .await;
+        assert_eq!(context, 42);
+        assert!(matches!(res, Err(ProviderError::PendingTransactionTimeout(_))), "{:?}", res);
+    }
+
+    fn pending_transfer() -> Transaction {
+        Transaction {
+            hash: H256::repeat_byte(1),
+            nonce: 3u64.into(),
+            from: Address::repeat_byte(2),
+            to: Some(Address::repeat_byte(3)),
+            value: 1000u64.into(),
+            gas: 30_000u64.into(),
+            gas_price: Some(100u64.into()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn speeds_up_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let tx = pending_transfer();
+        let pending = PendingTransaction::new(tx.hash, &provider);
+
+        mock.push(H256::repeat_byte(4)).unwrap();
+        mock.push(tx.clone()).unwrap();
+        let replacement = pending.speed_up(&provider, 15).await.unwrap();
+        assert_eq!(*replacement, H256::repeat_byte(4));
+
+        let expected: TypedTransaction = TransactionRequest::from(&tx).gas_price(115u64).into();
+        mock.assert_request("eth_getTransactionByHash", [tx.hash]).unwrap();
+        mock.assert_request("eth_sendTransaction", [expected]).unwrap();
+
+        mock.push(H256::repeat_byte(5)).unwrap();
+        mock.push(tx.clone()).unwrap();
+        pending.cancel(&provider, 10).await.unwrap();
+
+        let expected: TypedTransaction = TransactionRequest::new()
+            .from(tx.from)
+            .to(tx.from)
+            .value(0u64)
+            .data(Bytes::default())
+            .gas(21_000u64)
+            .gas_price(110u64)
+            .nonce(tx.nonce)
+            .into();
+        mock.assert_request("eth_getTransactionByHash", [tx.hash]).unwrap();
+        mock.assert_request("eth_sendTransaction", [expected]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_replacements() {
+        let (provider, mock) = Provider::mocked();
+        let tx = pending_transfer();
+        let pending = PendingTransaction::new(tx.hash, &provider);
+
+        let err = pending.speed_up(&provider, 5).await.unwrap_err();
+        assert!(matches!(err, ReplacementError::BumpTooLow(5)));
+
+        mock.push(Transaction { block_number: Some(1u64.into()), ..tx.clone() }).unwrap();
+        let err = pending.speed_up(&provider, 10).await.unwrap_err();
+        assert!(matches!(err, ReplacementError::AlreadyMined(_)));
+
+        mock.push(Option::<Transaction>::None).unwrap();
+        let err = pending.cancel(&provider, 10).await.unwrap_err();
+        assert!(matches!(err, ReplacementError::NotFound(_)));
+    }
+}
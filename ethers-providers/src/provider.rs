@@ -1,10 +1,11 @@
 use crate::{
-    call_raw::CallBuilder,
+    call_raw::{spoof, CallBuilder, RawCall},
     ens, erc, maybe,
     pubsub::{PubsubClient, SubscriptionStream},
     stream::{FilterWatcher, DEFAULT_POLL_INTERVAL},
-    FromErr, Http as HttpProvider, JsonRpcClient, JsonRpcClientWrapper, LogQuery, MockProvider,
-    PendingTransaction, QuorumProvider, RwClient, SyncingStatus,
+    FromErr, Http as HttpProvider, HttpClientError, JsonRpcClient, JsonRpcClientWrapper,
+    JsonRpcError, LogQuery, MockError, MockProvider, PendingTransaction, QuorumProvider,
+    RevertReason, RwClient, SyncingStatus,
 };
 
 #[cfg(all(not(target_arch = "wasm32"), feature = "ws"))]
@@ -21,10 +22,11 @@ use ethers_core::{
     abi::{self, Detokenize, ParamType},
     types::{
         transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
-        Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, EIP1186ProofResponse, FeeHistory,
-        Filter, FilterBlockOption, Log, NameOrAddress, Selector, Signature, Trace, TraceFilter,
-        TraceType, Transaction, TransactionReceipt, TransactionRequest, TxHash, TxpoolContent,
-        TxpoolInspect, TxpoolStatus, H256, U256, U64,
+        AccountInfo, Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, EIP1186ProofResponse,
+        Eip1559Fees, Eip7702TransactionRequest, FeeHistory, Filter, FilterBlockOption,
+        GasPriceTiers, GasTiers, GethDebugTracingOptions, GethTrace, Log, NameOrAddress, Selector,
+        Signature, Trace, TraceFilter, TraceType, Transaction, TransactionReceipt,
+        TransactionRequest, TxHash, TxpoolContent, TxpoolInspect, TxpoolStatus, H256, U256, U64,
     },
     utils,
 };
@@ -33,7 +35,11 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use url::{ParseError, Url};
 
-use futures_util::{lock::Mutex, try_join};
+use futures_util::{
+    lock::Mutex,
+    stream::{Stream, StreamExt},
+    try_join,
+};
 use std::{
     collections::VecDeque, convert::TryFrom, fmt::Debug, str::FromStr, sync::Arc, time::Duration,
 };
@@ -95,6 +101,10 @@ pub struct Provider<P> {
     /// Unsupported node client = `Some(None)`
     /// Supported node client = `Some(Some(NodeClient))`
     _node_client: Arc<Mutex<Option<NodeClient>>>,
+    /// Ids of the filters of dropped watchers, uninstalled before the next filter is installed
+    dropped_filters: Arc<std::sync::Mutex<Vec<U256>>>,
+    /// The chain id, cached after the first successful `eth_chainId` request
+    chain_id: Arc<std::sync::Mutex<Option<U256>>>,
 }
 
 impl<P> AsRef<P> for Provider<P> {
@@ -103,6 +113,52 @@ impl<P> AsRef<P> for Provider<P> {
     }
 }
 
+impl<P> Provider<P> {
+    /// Queues the filter to be uninstalled before the next filter is installed
+    pub(crate) fn uninstall_filter_later(&self, id: U256) {
+        if let Ok(mut filters) = self.dropped_filters.lock() {
+            filters.push(id);
+        }
+    }
+}
+
+impl ProviderError {
+    /// Returns the JSON-RPC error the node responded with, if the request failed with one
+    pub fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            ProviderError::JsonRpcClientError(source) => json_rpc_error(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the JSON-RPC error the node responded with, if `err` is the error of a transport that
+/// failed with one
+pub(crate) fn json_rpc_error(err: &(dyn std::error::Error + 'static)) -> Option<&JsonRpcError> {
+    if let Some(HttpClientError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err)
+    }
+    if let Some(MockError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err)
+    }
+    #[cfg(feature = "ws")]
+    if let Some(crate::WsClientError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err)
+    }
+    #[cfg(all(target_family = "unix", feature = "ipc"))]
+    if let Some(crate::IpcError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err)
+    }
+    if let Some(crate::QuorumError::NoQuorumReached { errors, .. }) = err.downcast_ref() {
+        return errors.iter().find_map(ProviderError::as_error_response)
+    }
+    // e.g. the error of a `QuorumProvider`
+    if let Some(err) = err.downcast_ref::<ProviderError>() {
+        return err.as_error_response()
+    }
+    None
+}
+
 impl FromErr<ProviderError> for ProviderError {
     fn from(src: ProviderError) -> Self {
         src
@@ -144,6 +200,26 @@ pub enum ProviderError {
 
     #[error("Attempted to sign a transaction with no available signer. Hint: did you mean to use a SignerMiddleware?")]
     SignerUnavailable,
+
+    /// A JSON-RPC request did not complete before the timeout of the transport elapsed. Wrapping
+    /// transports such as [`RetryClient`](crate::RetryClient) and [`RwClient`](crate::RwClient)
+    /// pass timeouts of their inner client through, a [`QuorumProvider`](crate::QuorumProvider)
+    /// reports a timeout if the requests to all of its providers timed out.
+    #[error("request timed out")]
+    Timeout,
+
+    /// A pending transaction was not mined before its timeout elapsed
+    #[error("transaction {0:?} was not mined before the timeout")]
+    PendingTransactionTimeout(TxHash),
+
+    /// A pending transaction was no longer found after it had been pending
+    #[error("transaction {0:?} was evicted from the mempool")]
+    TransactionEvicted(TxHash),
+
+    /// The hash of a block is neither available from its header nor from the EIP-2935 history
+    /// contract
+    #[error("the hash of block {0} is unavailable")]
+    BlockHashUnavailable(U64),
 }
 
 /// Types of filters supported by the JSON-RPC.
@@ -159,6 +235,10 @@ pub enum FilterKind<'a> {
     PendingTransactions,
 }
 
+/// The maximum number of requests [`Provider::get_balances`] and
+/// [`Provider::get_transaction_counts`] keep in flight at the same time
+const MAX_CONCURRENT_ACCOUNT_REQUESTS: usize = 16;
+
 // JSON RPC bindings
 impl<P: JsonRpcClient> Provider<P> {
     /// Instantiate a new provider with a backend.
@@ -169,6 +249,8 @@ impl<P: JsonRpcClient> Provider<P> {
             interval: None,
             from: None,
             _node_client: Arc::new(Mutex::new(None)),
+            dropped_filters: Default::default(),
+            chain_id: Default::default(),
         }
     }
 
@@ -215,6 +297,94 @@ impl<P: JsonRpcClient> Provider<P> {
         Ok(res)
     }
 
+    /// Checks that the node responds by requesting the latest block number, which is returned.
+    ///
+    /// The request is subject to the timeout of the transport, e.g. [`Http::with_timeout`], and
+    /// fails with [`ProviderError::Timeout`] when the node does not respond in time.
+    ///
+    /// [`Http::with_timeout`]: crate::Http::with_timeout
+    pub async fn health(&self) -> Result<U64, ProviderError> {
+        self.request("eth_blockNumber", ()).await
+    }
+
+    /// Returns whether the node responds, see [`Provider::health`]
+    pub async fn is_connected(&self) -> bool {
+        self.health().await.is_ok()
+    }
+
+    /// Returns why the transaction `tx_hash` failed, like block explorers show it, or `None` if
+    /// it did not fail or is not mined yet.
+    ///
+    /// The receipt does not contain the revert data, so the transaction is replayed with
+    /// `eth_call` at the block it was mined in and the revert data of the replay is decoded. As the
+    /// replay runs on the state at the end of the block, it may succeed or revert differently when
+    /// the state the transaction depends on changed within the block. A replay which does not
+    /// revert, e.g. because the transaction ran out of gas, returns `None`.
+    ///
+    /// Nodes which do not keep the state of the block, e.g. full nodes for transactions older than
+    /// 128 blocks, fail the replay with an error like `missing trie node`, which is returned as is.
+    pub async fn get_revert_reason<T: Send + Sync + Into<TxHash>>(
+        &self,
+        tx_hash: T,
+    ) -> Result<Option<RevertReason>, ProviderError> {
+        let tx_hash = tx_hash.into();
+        let receipt = match self.get_transaction_receipt(tx_hash).await? {
+            Some(receipt) => receipt,
+            None => return Ok(None),
+        };
+        // receipts of transactions before Byzantium have no status and are replayed
+        if receipt.status == Some(1u64.into()) {
+            return Ok(None)
+        }
+        let (tx, block) = match (self.get_transaction(tx_hash).await?, receipt.block_number) {
+            (Some(tx), Some(block)) => (tx, block),
+            _ => return Ok(None),
+        };
+
+        let tx = TypedTransaction::from(tx);
+        match self.call(&tx, Some(block.into())).await {
+            Ok(_) => Ok(None),
+            Err(err) => match err.as_error_response() {
+                Some(rpc_err) if rpc_err.is_revert() => Ok(Some(RevertReason::decode(
+                    rpc_err.as_revert_data().as_deref().unwrap_or_default(),
+                ))),
+                _ => Err(err),
+            },
+        }
+    }
+
+    /// Returns the balances of all `addresses` at `block`, in the same order as `addresses`.
+    ///
+    /// The transports do not support JSON-RPC batches, so the individual `eth_getBalance`
+    /// requests are sent concurrently instead, with a bounded number of requests in flight.
+    /// A failed request only fails the result for its own address.
+    pub async fn get_balances(
+        &self,
+        addresses: &[Address],
+        block: Option<BlockId>,
+    ) -> Vec<Result<U256, ProviderError>> {
+        futures_util::stream::iter(addresses)
+            .map(|address| self.get_balance(*address, block))
+            .buffered(MAX_CONCURRENT_ACCOUNT_REQUESTS)
+            .collect()
+            .await
+    }
+
+    /// Returns the nonces of all `addresses` at `block`, in the same order as `addresses`.
+    ///
+    /// See [`Provider::get_balances`] for how the requests are sent.
+    pub async fn get_transaction_counts(
+        &self,
+        addresses: &[Address],
+        block: Option<BlockId>,
+    ) -> Vec<Result<U256, ProviderError>> {
+        futures_util::stream::iter(addresses)
+            .map(|address| self.get_transaction_count(*address, block))
+            .buffered(MAX_CONCURRENT_ACCOUNT_REQUESTS)
+            .collect()
+            .await
+    }
+
     async fn get_block_gen<Tx: Default + Serialize + DeserializeOwned + Debug>(
         &self,
         id: BlockId,
@@ -273,6 +443,98 @@ impl<P: JsonRpcClient> Provider<P> {
     pub fn call_raw<'a>(&'a self, tx: &'a TypedTransaction) -> CallBuilder<'a, P> {
         CallBuilder::new(self, tx)
     }
+
+    /// Executes `tx` via `eth_call` against the state at `block` (defaults to latest), with the
+    /// accounts in `overrides` replaced by the given balance, nonce, code and storage.
+    ///
+    /// This is shorthand for `.await`ing [`Provider::call_raw`] with the [`RawCall::block`] and
+    /// [`RawCall::state`] parameters set. See [`call_raw::spoof`] for constructing the override
+    /// set.
+    ///
+    /// [`call_raw::spoof`]: crate::call_raw::spoof
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ethers_core::types::{Address, Bytes, TransactionRequest};
+    /// # use ethers_providers::{Provider, Http, call_raw::spoof};
+    /// # use std::convert::TryFrom;
+    /// #
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let token: Address = "0x6B175474E89094C44Da98b954EedeAC495271d0F".parse()?;
+    /// // balanceOf(0x6fC21092DA55B392b045eD78F4732bff3C580e2c)
+    /// let calldata: Bytes =
+    ///     "0x70a082310000000000000000000000006fc21092da55b392b045ed78f4732bff3c580e2c".parse()?;
+    /// let tx = TransactionRequest::new().to(token).data(calldata).into();
+    ///
+    /// // simulate the call as if the token's balance mapping was empty
+    /// let mut overrides = spoof::state();
+    /// overrides.account(token).replace_storage();
+    /// let balance = provider.call_with_overrides(&tx, None, &overrides).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &spoof::State,
+    ) -> Result<Bytes, ProviderError> {
+        let call = self.call_raw(tx).state(overrides);
+        match block {
+            Some(block) => call.block(block).await,
+            None => call.await,
+        }
+    }
+
+    /// Streams the value of the storage slot `slot` of `address` whenever it changes.
+    ///
+    /// The slot is read via `eth_getStorageAt` at every new block (see
+    /// [`Middleware::watch_blocks`]) and a value is only emitted if it differs from the
+    /// previously seen one, starting with the value at the latest block when the watcher was
+    /// created.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ethers_core::types::{Address, H256};
+    /// # use ethers_providers::{Provider, Http};
+    /// # use futures_util::StreamExt;
+    /// # use std::convert::TryFrom;
+    /// #
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let contract: Address = "0x6B175474E89094C44Da98b954EedeAC495271d0F".parse()?;
+    ///
+    /// let mut changes = Box::pin(provider.watch_storage(contract, H256::zero()).await?);
+    /// while let Some(value) = changes.next().await {
+    ///     println!("slot 0 changed to {:?}", value?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn watch_storage(
+        &self,
+        address: Address,
+        slot: H256,
+    ) -> Result<impl Stream<Item = Result<H256, ProviderError>> + '_, ProviderError> {
+        // install the filter first so that no change after the initial read is missed
+        let blocks = self.watch_blocks().await?;
+        let mut last = self.get_storage_at(address, slot, None).await?;
+
+        Ok(blocks
+            .then(move |block| self.get_storage_at(address, slot, Some(block.into())))
+            .filter_map(move |value| {
+                let change = match value {
+                    Ok(value) if value == last => None,
+                    Ok(value) => {
+                        last = value;
+                        Some(Ok(value))
+                    }
+                    Err(err) => Some(Err(err)),
+                };
+                futures_util::future::ready(change)
+            }))
+    }
 }
 
 #[cfg(feature = "celo")]
@@ -307,6 +569,10 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.from
     }
 
+    fn as_error_response<'a>(&self, err: &'a ProviderError) -> Option<&'a JsonRpcError> {
+        err.as_error_response()
+    }
+
     ////// Blockchain Status
     //
     // Functions for querying the state of the blockchain
@@ -341,12 +607,31 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
                 let gas_price = maybe(tx.gas_price(), self.get_gas_price()).await?;
                 tx.set_gas_price(gas_price);
             }
-            TypedTransaction::Eip1559(ref mut inner) => {
+            TypedTransaction::Eip1559(ref mut inner) |
+            TypedTransaction::Eip7702(Eip7702TransactionRequest { tx: ref mut inner, .. }) => {
                 if inner.max_fee_per_gas.is_none() || inner.max_priority_fee_per_gas.is_none() {
                     let (max_fee_per_gas, max_priority_fee_per_gas) =
                         self.estimate_eip1559_fees(None).await?;
+                    // only fill the fees the caller did not set
+                    let max_fee_per_gas =
+                        match (inner.max_fee_per_gas, inner.max_priority_fee_per_gas) {
+                            (Some(max_fee_per_gas), _) => max_fee_per_gas,
+                            // the max fee must cover the base fee on top of the priority fee
+                            (None, Some(priority_fee)) if priority_fee > max_fee_per_gas => {
+                                let base_fee_per_gas = self
+                                    .get_block(BlockNumber::Latest)
+                                    .await?
+                                    .and_then(|block| block.base_fee_per_gas)
+                                    .unwrap_or_default();
+                                max_fee_per_gas.max(base_fee_per_gas + priority_fee)
+                            }
+                            (None, _) => max_fee_per_gas,
+                        };
                     inner.max_fee_per_gas = Some(max_fee_per_gas);
-                    inner.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+                    // the priority fee must not exceed the max fee
+                    inner
+                        .max_priority_fee_per_gas
+                        .get_or_insert(max_priority_fee_per_gas.min(max_fee_per_gas));
                 };
             }
         }
@@ -444,6 +729,35 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         })
     }
 
+    async fn get_block_hash<T: Into<U64> + Send + Sync>(
+        &self,
+        number: T,
+    ) -> Result<H256, ProviderError> {
+        let number = number.into();
+        if let Some(hash) = self.get_block(BlockNumber::Number(number)).await?.and_then(|b| b.hash)
+        {
+            return Ok(hash)
+        }
+
+        // nodes that pruned the header may still serve the hash from the history contract
+        let latest = self.get_block_number().await?;
+        if number > latest || (latest - number).as_u64() > utils::EIP2935_HISTORY_SERVE_WINDOW {
+            return Err(ProviderError::BlockHashUnavailable(number))
+        }
+
+        // the history contract takes the block number as its 32 byte calldata
+        let tx = TransactionRequest::new()
+            .to(utils::EIP2935_HISTORY_STORAGE_ADDRESS)
+            .data(H256::from_low_u64_be(number.as_u64()).as_bytes().to_vec())
+            .into();
+        let hash = self.call(&tx, Some(BlockNumber::Number(latest).into())).await?;
+        // chains without the history contract return no data
+        if hash.len() != 32 || hash.iter().all(|byte| *byte == 0) {
+            return Err(ProviderError::BlockHashUnavailable(number))
+        }
+        Ok(H256::from_slice(&hash))
+    }
+
     /// Gets the transaction with `transaction_hash`
     async fn get_transaction<T: Send + Sync + Into<TxHash>>(
         &self,
@@ -517,6 +831,81 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok((max_fee_per_gas, max_priority_fee_per_gas))
     }
 
+    async fn estimate_eip1559_fees_for_target(
+        &self,
+        blocks: u64,
+        probability: f64,
+    ) -> Result<(U256, U256), Self::Error> {
+        if blocks == 0 || !(probability > 0.0 && probability <= 1.0) {
+            return Err(ProviderError::CustomError(format!(
+                "Invalid inclusion target: probability {} within {} blocks",
+                probability, blocks
+            )))
+        }
+
+        let fee_history = self
+            .fee_history(
+                utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                BlockNumber::Latest,
+                &utils::EIP1559_FEE_ESTIMATION_TARGET_PERCENTILES,
+            )
+            .await?;
+        // the last base fee is the one of the next block
+        let base_fee_per_gas = fee_history
+            .base_fee_per_gas
+            .last()
+            .copied()
+            .filter(|base_fee| !base_fee.is_zero())
+            .ok_or_else(|| ProviderError::CustomError("EIP-1559 not activated".into()))?;
+
+        Ok(utils::eip1559_target_estimator(
+            base_fee_per_gas,
+            &fee_history.reward,
+            blocks,
+            probability,
+        ))
+    }
+
+    async fn gas_price_tiers(&self) -> Result<GasPriceTiers, ProviderError> {
+        if let Ok(fee_history) = self
+            .fee_history(
+                utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                BlockNumber::Latest,
+                &utils::GAS_PRICE_TIER_PERCENTILES,
+            )
+            .await
+        {
+            // the last base fee is the one of the next block
+            let base_fee_per_gas = fee_history.base_fee_per_gas.last().copied().unwrap_or_default();
+            let tiers = utils::gas_price_tiers_estimator(&fee_history.reward);
+
+            if !base_fee_per_gas.is_zero() {
+                let tiers = tiers.unwrap_or_else(|| {
+                    let fee = U256::from(utils::EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE);
+                    GasTiers { safe: fee, standard: fee, fast: fee }
+                });
+                // leave room for the base fee to double
+                return Ok(GasPriceTiers::Eip1559(tiers.map(|max_priority_fee_per_gas| {
+                    Eip1559Fees {
+                        max_fee_per_gas: base_fee_per_gas * 2 + max_priority_fee_per_gas,
+                        max_priority_fee_per_gas,
+                    }
+                })))
+            }
+            // without a base fee, the rewards are the full gas prices
+            if let Some(tiers) = tiers {
+                return Ok(GasPriceTiers::Legacy(tiers))
+            }
+        }
+
+        let gas_price = self.get_gas_price().await?;
+        Ok(GasPriceTiers::Legacy(GasTiers {
+            safe: gas_price,
+            standard: gas_price,
+            fast: gas_price,
+        }))
+    }
+
     /// Gets the accounts on the node
     async fn get_accounts(&self) -> Result<Vec<Address>, ProviderError> {
         self.request("eth_accounts", ()).await
@@ -556,8 +945,18 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
 
     /// Returns the currently configured chain id, a value used in replay-protected
     /// transaction signing as introduced by EIP-155.
+    ///
+    /// The chain id of a connection never changes, so it is only requested once and then served
+    /// from a cache shared by all clones of the provider, see [`Provider::clear_chain_id_cache`].
     async fn get_chainid(&self) -> Result<U256, ProviderError> {
-        self.request("eth_chainId", ()).await
+        if let Some(chain_id) = self.cached_chain_id() {
+            return Ok(chain_id)
+        }
+        let chain_id: U256 = self.request("eth_chainId", ()).await?;
+        if let Ok(mut cached) = self.chain_id.lock() {
+            *cached = Some(chain_id);
+        }
+        Ok(chain_id)
     }
 
     /// Return current client syncing status. If IsFalse sync is over.
@@ -614,6 +1013,11 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("eth_estimateGas", [tx]).await
     }
 
+    /// Creates an EIP-2930 access list for the transaction and returns it along with the gas
+    /// used when executing the transaction with it, see [`TypedTransaction::with_access_list`].
+    ///
+    /// Fails with [`ProviderError::UnsupportedRPC`] if the node does not implement
+    /// `eth_createAccessList`.
     async fn create_access_list(
         &self,
         tx: &TypedTransaction,
@@ -621,7 +1025,13 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     ) -> Result<AccessListWithGasUsed, ProviderError> {
         let tx = utils::serialize(tx);
         let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
-        self.request("eth_createAccessList", [tx, block]).await
+        self.request("eth_createAccessList", [tx, block]).await.map_err(|err| {
+            if is_method_not_found(&err) {
+                ProviderError::UnsupportedRPC
+            } else {
+                err
+            }
+        })
     }
 
     /// Sends the transaction to the entire Ethereum network and returns the transaction's hash
@@ -694,7 +1104,11 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     }
 
     fn get_logs_paginated<'a>(&'a self, filter: &Filter, page_size: u64) -> LogQuery<'a, P> {
-        LogQuery::new(self, filter).with_page_size(page_size)
+        let query = LogQuery::new(self, filter).with_page_size(page_size);
+        match self.interval {
+            Some(interval) => query.with_interval(interval),
+            None => query,
+        }
     }
 
     /// Streams matching filter logs
@@ -726,6 +1140,16 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// Creates a filter object, based on filter options, to notify when the state changes (logs).
     /// To check if the state has changed, call `get_filter_changes` with the filter id.
     async fn new_filter(&self, filter: FilterKind<'_>) -> Result<U256, ProviderError> {
+        // clean up the filters of dropped watchers, they may have expired already
+        let dropped = self
+            .dropped_filters
+            .lock()
+            .map(|mut ids| std::mem::take(&mut *ids))
+            .unwrap_or_default();
+        for id in dropped {
+            let _ = self.uninstall_filter(id).await;
+        }
+
         let (method, args) = match filter {
             FilterKind::NewBlocks => ("eth_newBlockFilter", vec![]),
             FilterKind::PendingTransactions => ("eth_newPendingTransactionFilter", vec![]),
@@ -1000,6 +1424,86 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(field)
     }
 
+    /// Returns the [EIP-1577](https://eips.ethereum.org/EIPS/eip-1577) content hash record of
+    /// `ens_name`, or `None` if the name has no resolver or no content hash.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ethers_providers::{Provider, Http as HttpProvider, Middleware};
+    /// # use std::convert::TryFrom;
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let provider = Provider::<HttpProvider>::try_from("http://localhost:8545")?;
+    /// if let Some(content) = provider.resolve_contenthash("vitalik.eth").await? {
+    ///     // e.g. `ipfs://Qm...`
+    ///     println!("{}", content);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn resolve_contenthash(
+        &self,
+        ens_name: &str,
+    ) -> Result<Option<ens::ContentHash>, ProviderError> {
+        let content: Bytes = match self
+            .query_resolver(ParamType::Bytes, ens_name, ens::CONTENTHASH_SELECTOR)
+            .await
+        {
+            Ok(content) => content,
+            Err(ProviderError::EnsError(_)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        Ok(ens::ContentHash::decode(&content))
+    }
+
+    /// Returns the balance, nonce, code hash and storage root of an account via `eth_getAccount`.
+    ///
+    /// If the node does not support `eth_getAccount`, the account info is assembled from
+    /// `eth_getBalance`, `eth_getTransactionCount` and `eth_getCode` instead, in which case the
+    /// storage root is not available.
+    async fn get_account<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<AccountInfo, ProviderError> {
+        let from = match from.into() {
+            NameOrAddress::Name(ens_name) => self.resolve_name(&ens_name).await?,
+            NameOrAddress::Address(addr) => addr,
+        };
+        let block = block.unwrap_or_else(|| BlockNumber::Latest.into());
+
+        match self
+            .request::<_, AccountInfo>(
+                "eth_getAccount",
+                [utils::serialize(&from), utils::serialize(&block)],
+            )
+            .await
+        {
+            success @ Ok(_) => success,
+            err @ Err(_) => {
+                let fallback = async {
+                    let balance = self.get_balance(from, Some(block)).await?;
+                    let nonce = self.get_transaction_count(from, Some(block)).await?;
+                    let code = self.get_code(from, Some(block)).await?;
+                    let code_hash = H256::from(utils::keccak256(&code));
+                    Ok::<_, ProviderError>(AccountInfo {
+                        balance,
+                        nonce,
+                        code_hash,
+                        storage_root: None,
+                    })
+                }
+                .await;
+
+                if fallback.is_err() {
+                    // if the composed fallback also failed, return the error from the initial
+                    // attempt
+                    return err
+                }
+                fallback
+            }
+        }
+    }
+
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content)
@@ -1021,6 +1525,37 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("txpool_status", ()).await
     }
 
+    /// Replays the transaction with the given hash and returns its geth-style trace.
+    ///
+    /// Without a tracer the struct logger output is returned, see [`GethTrace`] for the
+    /// possible results.
+    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-debug#debug_tracetransaction)
+    async fn debug_trace_transaction(
+        &self,
+        tx_hash: TxHash,
+        trace_options: GethDebugTracingOptions,
+    ) -> Result<GethTrace, ProviderError> {
+        let tx_hash = utils::serialize(&tx_hash);
+        let trace_options = utils::serialize(&trace_options);
+        self.request("debug_traceTransaction", [tx_hash, trace_options]).await
+    }
+
+    /// Executes the given call on top of the state of `block` (defaults to latest) and returns
+    /// its geth-style trace.
+    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-debug#debug_tracecall)
+    async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        req: T,
+        block: Option<BlockId>,
+        trace_options: GethDebugTracingOptions,
+    ) -> Result<GethTrace, ProviderError> {
+        let req = req.into();
+        let req = utils::serialize(&req);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        let trace_options = utils::serialize(&trace_options);
+        self.request("debug_traceCall", [req, block, trace_options]).await
+    }
+
     /// Executes the given call and returns a number of possible traces for it
     async fn trace_call<T: Into<TypedTransaction> + Send + Sync>(
         &self,
@@ -1150,6 +1685,21 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.subscribe(["newPendingTransactions"]).await
     }
 
+    async fn subscribe_full_pending_txs(
+        &self,
+    ) -> Result<SubscriptionStream<'_, P, Transaction>, ProviderError>
+    where
+        P: PubsubClient,
+    {
+        self.subscribe(("newPendingTransactions", true)).await.map_err(|err| {
+            if is_method_not_found(&err) || is_invalid_params(&err) {
+                ProviderError::UnsupportedRPC
+            } else {
+                err
+            }
+        })
+    }
+
     async fn subscribe_logs<'a>(
         &'a self,
         filter: &Filter,
@@ -1256,6 +1806,11 @@ impl<P: JsonRpcClient> Provider<P> {
             .call(&ens::resolve(resolver_address, selector, ens_name, parameters).into(), None)
             .await?;
 
+        // the resolver does not implement the selector
+        if data.0.is_empty() {
+            return Err(ProviderError::EnsError(ens_name.to_owned()))
+        }
+
         Ok(decode_bytes(param, data))
     }
 
@@ -1268,7 +1823,12 @@ impl<P: JsonRpcClient> Provider<P> {
         Ok(())
     }
 
-    /// Sets the ENS Address (default: mainnet)
+    /// Sets the address of the ENS registry used by all ENS calls, e.g. `resolve_name`,
+    /// `lookup_address` and the ENS names in transactions, to resolve names on a chain with its own
+    /// registry deployment, like a local devnet.
+    ///
+    /// Defaults to [`ens::ENS_ADDRESS`], the address of the official registry on mainnet and on
+    /// all testnets ENS is deployed to.
     #[must_use]
     pub fn ens<T: Into<Address>>(mut self, ens: T) -> Self {
         self.ens = Some(ens.into());
@@ -1277,6 +1837,8 @@ impl<P: JsonRpcClient> Provider<P> {
 
     /// Sets the default polling interval for event filters and pending transactions
     /// (default: 7 seconds)
+    ///
+    /// If set, this is also the delay between the pages of [Middleware::get_logs_paginated].
     #[must_use]
     pub fn interval<T: Into<Duration>>(mut self, interval: T) -> Self {
         self.interval = Some(interval.into());
@@ -1288,6 +1850,22 @@ impl<P: JsonRpcClient> Provider<P> {
     pub fn get_interval(&self) -> Duration {
         self.interval.unwrap_or(DEFAULT_POLL_INTERVAL)
     }
+
+    /// Returns the chain id cached by [`Middleware::get_chainid`], if it was requested before
+    pub fn cached_chain_id(&self) -> Option<U256> {
+        self.chain_id.lock().ok().and_then(|chain_id| *chain_id)
+    }
+
+    /// Clears the cached chain id, so that the next [`Middleware::get_chainid`] requests it again.
+    ///
+    /// This must be called when the transport reconnected to a possibly different node, e.g. a
+    /// reconnecting WebSocket transport. Connecting with `Provider::connect` always returns a
+    /// provider without a cached chain id.
+    pub fn clear_chain_id_cache(&self) {
+        if let Ok(mut chain_id) = self.chain_id.lock() {
+            *chain_id = None;
+        }
+    }
 }
 
 #[cfg(feature = "ws")]
@@ -1387,6 +1965,30 @@ fn decode_bytes<T: Detokenize>(param: ParamType, bytes: Bytes) -> T {
     T::from_tokens(tokens).expect("could not parse tokens as address")
 }
 
+/// Returns `true` if the node rejected the request because it does not implement the method, i.e.
+/// responded with the JSON-RPC error code `-32601`
+fn is_method_not_found(err: &ProviderError) -> bool {
+    has_error_code(err, -32601)
+}
+
+/// Returns `true` if the node responded with the JSON-RPC error `code`. Checks the typed error
+/// response first, and only falls back to the displayed error for transports which don't expose it
+fn has_error_code(err: &ProviderError, code: i64) -> bool {
+    match (err.as_error_response(), err) {
+        (Some(response), _) => response.code == code,
+        (None, ProviderError::JsonRpcClientError(err)) => {
+            err.to_string().contains(&format!("code: {}", code))
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if the node rejected the parameters of a request, i.e. responded with the
+/// JSON-RPC error code `-32602`
+fn is_invalid_params(err: &ProviderError) -> bool {
+    has_error_code(err, -32602)
+}
+
 impl TryFrom<&str> for Provider<HttpProvider> {
     type Error = ParseError;
 
@@ -1499,6 +2101,13 @@ pub mod dev_rpc {
         fn from(src: M::Error) -> DevRpcMiddlewareError<M> {
             DevRpcMiddlewareError::MiddlewareError(src)
         }
+
+        fn as_inner(&self) -> Option<&M::Error> {
+            match self {
+                DevRpcMiddlewareError::MiddlewareError(err) => Some(err),
+                _ => None,
+            }
+        }
     }
 
     impl<M> From<ProviderError> for DevRpcMiddlewareError<M>
@@ -1603,7 +2212,7 @@ pub mod dev_rpc {
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
     use super::*;
-    use crate::Http;
+    use crate::{Http, HttpClientError};
     use ethers_core::{
         types::{
             transaction::eip2930::AccessList, Eip1559TransactionRequest, TransactionRequest, H256,
@@ -1811,6 +2420,152 @@ mod tests {
         dbg!(&history);
     }
 
+    #[tokio::test]
+    async fn estimate_eip1559_fees_for_target() {
+        let (provider, mock) = Provider::mocked();
+        let rewards: Vec<Vec<U256>> = (0..5u64)
+            .map(|block| (1..=10u64).map(|p| U256::from(p * 1_000_000_000 + block)).collect())
+            .collect();
+        let history = FeeHistory {
+            base_fee_per_gas: vec![U256::from(100_000_000_000u64); 6],
+            gas_used_ratio: vec![0.5; 5],
+            oldest_block: 100u64.into(),
+            reward: rewards.clone(),
+        };
+
+        let mut last = U256::zero();
+        for probability in [0.5, 0.9, 0.99] {
+            mock.push(history.clone()).unwrap();
+            let (max_fee, priority_fee) =
+                provider.estimate_eip1559_fees_for_target(2, probability).await.unwrap();
+            mock.assert_request(
+                "eth_feeHistory",
+                serde_json::json!([
+                    "0xa",
+                    "latest",
+                    utils::EIP1559_FEE_ESTIMATION_TARGET_PERCENTILES
+                ]),
+            )
+            .unwrap();
+            assert_eq!(
+                (max_fee, priority_fee),
+                utils::eip1559_target_estimator(
+                    100_000_000_000u64.into(),
+                    &rewards,
+                    2,
+                    probability
+                )
+            );
+            assert!(priority_fee > last);
+            last = priority_fee;
+        }
+
+        for (blocks, probability) in [(0, 0.9), (3, 0.0), (3, 1.5), (3, f64::NAN)] {
+            assert!(provider.estimate_eip1559_fees_for_target(blocks, probability).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn get_block_hash() {
+        let (provider, mock) = Provider::mocked();
+
+        // blocks are read from their headers
+        let block = Block::<TxHash> { hash: Some(H256::repeat_byte(1)), ..Default::default() };
+        mock.push(block).unwrap();
+        assert_eq!(provider.get_block_hash(1_000u64).await.unwrap(), H256::repeat_byte(1));
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["0x3e8", false])).unwrap();
+
+        // headers unavailable to the node are read from the EIP-2935 history contract
+        mock.push(Bytes::from(H256::repeat_byte(2).as_bytes().to_vec())).unwrap();
+        mock.push(U64::from(10_000)).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+        assert_eq!(provider.get_block_hash(5_000u64).await.unwrap(), H256::repeat_byte(2));
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["0x1388", false])).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(utils::EIP2935_HISTORY_STORAGE_ADDRESS)
+            .data(H256::from_low_u64_be(5_000).as_bytes().to_vec())
+            .into();
+        mock.assert_request(
+            "eth_call",
+            [utils::serialize(&tx), utils::serialize(&U64::from(10_000))],
+        )
+        .unwrap();
+
+        // chains without the history contract return no data
+        mock.push(Bytes::default()).unwrap();
+        mock.push(U64::from(10_000)).unwrap();
+        mock.push(serde_json::Value::Null).unwrap();
+        assert!(matches!(
+            provider.get_block_hash(5_000u64).await.unwrap_err(),
+            ProviderError::BlockHashUnavailable(number) if number == 5_000u64.into()
+        ));
+
+        // blocks outside of the history window and future blocks are unavailable
+        for number in [1_000u64, 10_001] {
+            mock.push(U64::from(10_000)).unwrap();
+            mock.push(serde_json::Value::Null).unwrap();
+            assert!(matches!(
+                provider.get_block_hash(number).await.unwrap_err(),
+                ProviderError::BlockHashUnavailable(_)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn gas_price_tiers() {
+        let gwei = |n: u64| U256::from(n) * 1_000_000_000u64;
+        let (provider, mock) = Provider::mocked();
+
+        let history = FeeHistory {
+            base_fee_per_gas: vec![gwei(30); 4],
+            gas_used_ratio: vec![0.5; 3],
+            oldest_block: 100u64.into(),
+            reward: vec![
+                vec![gwei(1), gwei(2), gwei(4)],
+                vec![gwei(2), gwei(2), gwei(3)],
+                vec![gwei(1), gwei(3), gwei(6)],
+            ],
+        };
+        mock.push(history.clone()).unwrap();
+        let tiers = match provider.gas_price_tiers().await.unwrap() {
+            GasPriceTiers::Eip1559(tiers) => tiers,
+            tiers => panic!("expected EIP-1559 tiers, got {:?}", tiers),
+        };
+        mock.assert_request(
+            "eth_feeHistory",
+            serde_json::json!(["0xa", "latest", utils::GAS_PRICE_TIER_PERCENTILES]),
+        )
+        .unwrap();
+        assert_eq!(
+            tiers.standard,
+            Eip1559Fees { max_fee_per_gas: gwei(62), max_priority_fee_per_gas: gwei(2) }
+        );
+        assert!(tiers.safe.max_priority_fee_per_gas <= tiers.standard.max_priority_fee_per_gas);
+        assert!(tiers.standard.max_priority_fee_per_gas <= tiers.fast.max_priority_fee_per_gas);
+        assert!(tiers.safe.max_fee_per_gas <= tiers.standard.max_fee_per_gas);
+        assert!(tiers.standard.max_fee_per_gas <= tiers.fast.max_fee_per_gas);
+
+        // without a base fee, the rewards are legacy gas prices
+        mock.push(FeeHistory { base_fee_per_gas: vec![U256::zero(); 4], ..history }).unwrap();
+        let tiers = provider.gas_price_tiers().await.unwrap();
+        assert_eq!(
+            tiers,
+            GasPriceTiers::Legacy(GasTiers { safe: gwei(1), standard: gwei(2), fast: gwei(4) })
+        );
+
+        // nodes without `eth_feeHistory` fall back to the gas price
+        let (provider, mock) = Provider::mocked();
+        mock.push(gwei(7)).unwrap();
+        mock.push::<String, _>("unsupported".to_string()).unwrap();
+        mock.push::<String, _>("unsupported".to_string()).unwrap();
+        let tiers = provider.gas_price_tiers().await.unwrap();
+        assert_eq!(
+            tiers,
+            GasPriceTiers::Legacy(GasTiers { safe: gwei(7), standard: gwei(7), fast: gwei(7) })
+        );
+    }
+
     #[tokio::test]
     #[ignore]
     #[cfg(feature = "ws")]
@@ -1955,6 +2710,35 @@ mod tests {
         assert!(matches!(res, Err(ProviderError::JsonRpcClientError(_))));
     }
 
+    #[tokio::test]
+    async fn fills_max_fee_above_the_priority_fee() {
+        let gwei = |n: u64| U256::from(n) * 1_000_000_000u64;
+        let (provider, mock) = Provider::mocked();
+
+        let block = Block::<TxHash> { base_fee_per_gas: Some(gwei(10)), ..Default::default() };
+        let history = FeeHistory {
+            base_fee_per_gas: vec![gwei(10); 11],
+            gas_used_ratio: vec![0.5; 10],
+            oldest_block: 100u64.into(),
+            reward: vec![vec![gwei(1)]; 10],
+        };
+        mock.push(block.clone()).unwrap();
+        mock.push(history).unwrap();
+        mock.push(block).unwrap();
+
+        let mut tx = Eip1559TransactionRequest::new()
+            .gas(21_000)
+            .max_priority_fee_per_gas(gwei(100))
+            .access_list(vec![Default::default()].into())
+            .into();
+        provider.fill_transaction(&mut tx, None).await.unwrap();
+
+        let tx = tx.as_eip1559_ref().unwrap();
+        assert_eq!(tx.max_priority_fee_per_gas, Some(gwei(100)));
+        assert_eq!(tx.max_fee_per_gas, Some(gwei(110)));
+        assert!(TypedTransaction::Eip1559(tx.clone()).validate().is_ok());
+    }
+
     #[tokio::test]
     async fn test_fill_transaction_legacy() {
         let (mut provider, mock) = Provider::mocked();
@@ -1988,4 +2772,383 @@ mod tests {
         assert_eq!(tx.gas_price(), Some(gas_price));
         assert!(tx.access_list().is_none());
     }
+
+    #[tokio::test]
+    async fn get_block_with_txs_includes_full_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let tx = Transaction { hash: H256::repeat_byte(1), nonce: 2.into(), ..Default::default() };
+        let block =
+            Block { number: Some(3u64.into()), transactions: vec![tx], ..Default::default() };
+        mock.push(block).unwrap();
+
+        let block = provider.get_block_with_txs(3u64).await.unwrap().unwrap();
+        assert_eq!(block.transactions.len(), 1);
+        assert_eq!(block.transactions[0].hash, H256::repeat_byte(1));
+        assert_eq!(block.transactions[0].nonce, 2.into());
+        mock.assert_request(
+            "eth_getBlockByNumber",
+            [utils::serialize(&BlockNumber::from(3u64)), utils::serialize(&true)],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn debug_trace_call_request() {
+        let (provider, mock) = Provider::mocked();
+        let to: Address = "0x0000000000000000000000000000000000000002".parse().unwrap();
+        let tx = TransactionRequest::new().to(to).data(vec![0xa9, 0x05, 0x9c, 0xbb]);
+        let frame = serde_json::json!({
+            "type": "CALL",
+            "from": "0x0000000000000000000000000000000000000001",
+            "to": "0x0000000000000000000000000000000000000002",
+            "gas": "0x5208",
+            "gasUsed": "0x5208",
+            "input": "0xa9059cbb",
+            "error": "execution reverted",
+            "revertReason": "paused"
+        });
+        mock.push(frame).unwrap();
+
+        let trace = provider
+            .debug_trace_call(tx.clone(), None, GethDebugTracingOptions::call_tracer())
+            .await
+            .unwrap();
+        match trace {
+            GethTrace::CallTracer(frame) => {
+                assert_eq!(frame.to, Some(to));
+                assert_eq!(frame.revert_reason.as_deref(), Some("paused"));
+            }
+            _ => panic!("expected a call tracer trace"),
+        }
+
+        let tx: TypedTransaction = tx.into();
+        mock.assert_request(
+            "debug_traceCall",
+            [
+                utils::serialize(&tx),
+                utils::serialize(&BlockNumber::Latest),
+                serde_json::json!({ "tracer": "callTracer" }),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_account_falls_back_to_separate_calls() {
+        let (provider, mock) = Provider::mocked();
+        let addr: Address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse().unwrap();
+        let block = utils::serialize(&BlockNumber::Latest);
+
+        // responses are popped from the back
+        mock.push(Bytes::from(vec![0x60, 0x00])).unwrap();
+        mock.push(U256::from(9)).unwrap();
+        mock.push(U256::exp10(18)).unwrap();
+        // an unparseable `eth_getAccount` response triggers the fallback
+        mock.push(()).unwrap();
+
+        let account = provider.get_account(addr, None).await.unwrap();
+        assert_eq!(account.balance, U256::exp10(18));
+        assert_eq!(account.nonce, 9.into());
+        assert_eq!(account.code_hash, H256::from(utils::keccak256([0x60u8, 0x00])));
+        assert_eq!(account.storage_root, None);
+
+        let params = [utils::serialize(&addr), block];
+        mock.assert_request("eth_getAccount", &params).unwrap();
+        mock.assert_request("eth_getBalance", &params).unwrap();
+        mock.assert_request("eth_getTransactionCount", &params).unwrap();
+        mock.assert_request("eth_getCode", &params).unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_balances_and_nonces_keep_input_order() {
+        let (provider, mock) = Provider::mocked();
+        let addrs: Vec<Address> = (1u64..=3).map(Address::from_low_u64_be).collect();
+        let block = utils::serialize(&BlockNumber::Latest);
+
+        // responses are popped from the back
+        mock.push(U256::from(3)).unwrap();
+        mock.push_error(-32000, "header not found");
+        mock.push(U256::from(1)).unwrap();
+
+        let balances = provider.get_balances(&addrs, None).await;
+        assert_eq!(balances.len(), 3);
+        assert_eq!(balances[0].as_ref().unwrap(), &U256::from(1));
+        assert!(balances[1].is_err());
+        assert_eq!(balances[2].as_ref().unwrap(), &U256::from(3));
+        for addr in &addrs {
+            mock.assert_request("eth_getBalance", [utils::serialize(addr), block.clone()]).unwrap();
+        }
+
+        mock.push(U256::from(30)).unwrap();
+        mock.push(U256::from(20)).unwrap();
+        mock.push(U256::from(10)).unwrap();
+
+        let nonces = provider.get_transaction_counts(&addrs, None).await;
+        let nonces: Vec<U256> = nonces.into_iter().map(Result::unwrap).collect();
+        assert_eq!(nonces, vec![10.into(), 20.into(), 30.into()]);
+        for addr in &addrs {
+            mock.assert_request("eth_getTransactionCount", [utils::serialize(addr), block.clone()])
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_chain_id_and_checks_health() {
+        let (provider, mock) = Provider::mocked();
+        assert_eq!(provider.cached_chain_id(), None);
+
+        mock.push(U256::from(5)).unwrap();
+        assert_eq!(provider.get_chainid().await.unwrap(), 5.into());
+        // clones share the cache and do not request the chain id again
+        assert_eq!(provider.clone().get_chainid().await.unwrap(), 5.into());
+        assert_eq!(provider.cached_chain_id(), Some(5.into()));
+        mock.assert_request("eth_chainId", ()).unwrap();
+        assert!(mock.assert_request("eth_chainId", ()).is_err());
+
+        provider.clear_chain_id_cache();
+        mock.push(U256::from(137)).unwrap();
+        assert_eq!(provider.get_chainid().await.unwrap(), 137.into());
+        mock.assert_request("eth_chainId", ()).unwrap();
+
+        mock.push(U64::from(10)).unwrap();
+        assert_eq!(provider.health().await.unwrap(), 10.into());
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+
+        mock.push_error(-32000, "node is shutting down");
+        assert!(!provider.is_connected().await);
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolves_contenthash() {
+        use ethers_core::abi::Token;
+
+        let (provider, mock) = Provider::mocked();
+        let resolver = Token::Address(Address::repeat_byte(1));
+        let content = hex::decode(
+            "e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f",
+        )
+        .unwrap();
+
+        // responses are popped from the back
+        mock.push(Bytes::from(abi::encode(&[Token::Bytes(content)]))).unwrap();
+        mock.push(Bytes::from(abi::encode(&[resolver.clone()]))).unwrap();
+        let content = provider.resolve_contenthash("vitalik.eth").await.unwrap().unwrap();
+        assert_eq!(content.to_string(), "ipfs://QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4");
+
+        // the record is not set
+        mock.push(Bytes::from(abi::encode(&[Token::Bytes(vec![])]))).unwrap();
+        mock.push(Bytes::from(abi::encode(&[resolver]))).unwrap();
+        assert_eq!(provider.resolve_contenthash("vitalik.eth").await.unwrap(), None);
+
+        // the name has no resolver
+        mock.push(Bytes::from(abi::encode(&[Token::Address(Address::zero())]))).unwrap();
+        assert_eq!(provider.resolve_contenthash("vitalik.eth").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn ens_calls_use_registry_override() {
+        use ethers_core::abi::Token;
+
+        let (provider, mock) = Provider::mocked();
+        let registry = Address::repeat_byte(0xee);
+        let provider = provider.ens(registry);
+        let resolver = Address::repeat_byte(1);
+        let owner = Address::repeat_byte(2);
+
+        // responses are popped from the back
+        mock.push(Bytes::from(abi::encode(&[Token::Address(owner)]))).unwrap();
+        mock.push(Bytes::from(abi::encode(&[Token::Address(resolver)]))).unwrap();
+        mock.push(Bytes::from(abi::encode(&[Token::String("local.eth".to_string())]))).unwrap();
+        mock.push(Bytes::from(abi::encode(&[Token::Address(resolver)]))).unwrap();
+        assert_eq!(provider.lookup_address(owner).await.unwrap(), "local.eth");
+
+        let block = utils::serialize(&BlockNumber::Latest);
+        let call =
+            |tx: TransactionRequest| [utils::serialize(&TypedTransaction::from(tx)), block.clone()];
+        let reverse_name = ens::reverse_address(owner);
+        mock.assert_request("eth_call", call(ens::get_resolver(registry, &reverse_name))).unwrap();
+        mock.assert_request(
+            "eth_call",
+            call(ens::resolve(resolver, ens::NAME_SELECTOR, &reverse_name, None)),
+        )
+        .unwrap();
+        mock.assert_request("eth_call", call(ens::get_resolver(registry, "local.eth"))).unwrap();
+        mock.assert_request(
+            "eth_call",
+            call(ens::resolve(resolver, ens::ADDR_SELECTOR, "local.eth", None)),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn broadcasts_decoded_raw_transactions() {
+        // signed offline with the EIP-155 example key `0x4646..46`
+        let raw = Bytes::from(hex::decode("02f8ac0109843b9aca008504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7940000000000000000000000000000000000000001e1a0010000000000000000000000000000000000000000000000000000000000000080a06cd061a916bc216a27e2ab1f26c84498074bbe22dd89c343daa4f5c6627d8b85a00717a93539b9b61749cd1b34f1acad55e5121346285ed7d5af6891d75ace1c0c").unwrap());
+        let (tx, signature) =
+            TypedTransaction::decode_signed(&utils::rlp::Rlp::new(raw.as_ref())).unwrap();
+        let from: Address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".parse().unwrap();
+        assert_eq!(tx.from(), Some(&from));
+        assert_eq!(signature.recover(tx.sighash()).unwrap(), from);
+
+        let (provider, mock) = Provider::mocked();
+        mock.push(tx.hash(&signature)).unwrap();
+        let pending = provider.send_raw_transaction(raw.clone()).await.unwrap();
+        assert_eq!(pending.tx_hash(), tx.hash(&signature));
+        mock.assert_request("eth_sendRawTransaction", [raw]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn gets_revert_reason_of_failed_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let hash = H256::repeat_byte(1);
+        let block = U64::from(7);
+        let tx = Transaction {
+            hash,
+            from: Address::repeat_byte(2),
+            to: Some(Address::repeat_byte(3)),
+            block_number: Some(block),
+            gas: 50_000.into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(block),
+            status: Some(0u64.into()),
+            ..Default::default()
+        };
+        let mut revert_data = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert_data.extend(abi::encode(&[abi::Token::String("insufficient allowance".into())]));
+        let revert = JsonRpcError {
+            code: 3,
+            message: "execution reverted: insufficient allowance".to_string(),
+            data: Some(serde_json::json!(Bytes::from(revert_data))),
+        };
+
+        // responses are popped from the back
+        mock.push_json_rpc_error(revert);
+        mock.push(tx.clone()).unwrap();
+        mock.push(receipt.clone()).unwrap();
+        let reason = provider.get_revert_reason(hash).await.unwrap().unwrap();
+        assert_eq!(reason, RevertReason::Message("insufficient allowance".to_string()));
+        assert_eq!(reason.to_string(), "reverted: insufficient allowance");
+        mock.assert_request("eth_getTransactionReceipt", [hash]).unwrap();
+        mock.assert_request("eth_getTransactionByHash", [hash]).unwrap();
+        let call = TypedTransaction::from(tx.clone());
+        mock.assert_request(
+            "eth_call",
+            [utils::serialize(&call), utils::serialize(&BlockId::from(block))],
+        )
+        .unwrap();
+
+        // nodes without the state of the block fail the replay
+        mock.push_error(-32000, "missing trie node");
+        mock.push(tx).unwrap();
+        mock.push(receipt.clone()).unwrap();
+        let err = provider.get_revert_reason(hash).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().message, "missing trie node");
+
+        // successful transactions are not replayed
+        mock.push(TransactionReceipt { status: Some(1u64.into()), ..receipt }).unwrap();
+        assert_eq!(provider.get_revert_reason(hash).await.unwrap(), None);
+    }
+
+    /// A transport for a node which implements no methods at all
+    #[derive(Debug)]
+    struct MethodNotFoundClient;
+
+    #[async_trait]
+    impl JsonRpcClient for MethodNotFoundClient {
+        type Error = HttpClientError;
+
+        async fn request<T, R>(&self, method: &str, _params: T) -> Result<R, HttpClientError>
+        where
+            T: std::fmt::Debug + Serialize + Send + Sync,
+            R: DeserializeOwned,
+        {
+            let err = serde_json::json!({
+                "code": -32601,
+                "message": format!("the method {} does not exist/is not available", method),
+            });
+            Err(HttpClientError::JsonRpcError(serde_json::from_value(err).unwrap()))
+        }
+    }
+
+    #[tokio::test]
+    async fn create_access_list_unsupported() {
+        let provider = Provider::new(MethodNotFoundClient);
+        let tx = TransactionRequest::new().into();
+
+        let err = provider.create_access_list(&tx, None).await.unwrap_err();
+        assert!(matches!(err, ProviderError::UnsupportedRPC), "{:?}", err);
+
+        // also if the error of the node is only part of the error of the transport
+        let quorum = crate::QuorumProvider::builder()
+            .add_providers(
+                [MethodNotFoundClient, MethodNotFoundClient].map(crate::WeightedProvider::new),
+            )
+            .build();
+        let err = Provider::quorum(quorum).create_access_list(&tx, None).await.unwrap_err();
+        assert!(matches!(err, ProviderError::UnsupportedRPC), "{:?}", err);
+
+        // other errors are passed through
+        let (provider, _) = Provider::mocked();
+        let err = provider.create_access_list(&tx, None).await.unwrap_err();
+        assert!(matches!(err, ProviderError::JsonRpcClientError(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn classifies_errors_by_json_rpc_error_code() {
+        let response =
+            JsonRpcError { code: -32602, message: "invalid argument".into(), data: None };
+        let err: ProviderError = MockError::JsonRpcError(response).into();
+        assert!(is_invalid_params(&err));
+        assert!(!is_method_not_found(&err));
+
+        // the displayed error of a quorum does not contain the error code
+        let err: ProviderError =
+            crate::QuorumError::NoQuorumReached { values: vec![], errors: vec![err] }.into();
+        assert!(is_invalid_params(&err));
+
+        // transports which don't expose the JSON-RPC error
+        let err = ProviderError::JsonRpcClientError("(code: -32602, message: invalid)".into());
+        assert!(is_invalid_params(&err));
+        assert!(!is_invalid_params(&ProviderError::CustomError("code: -32602".to_string())));
+    }
+
+    #[tokio::test]
+    async fn watch_storage_changes() {
+        let anvil = Anvil::new().spawn();
+        let provider = Provider::<Http>::try_from(anvil.endpoint())
+            .unwrap()
+            .interval(Duration::from_millis(10u64));
+        let from = provider.get_accounts().await.unwrap()[0];
+
+        // deploys a contract which stores its calldata in slot 0
+        let code: Bytes = "0x6007600c60003960076000f360003560005500".parse().unwrap();
+        let tx = TransactionRequest::new().from(from).data(code);
+        let receipt = provider.send_transaction(tx, None).await.unwrap().await.unwrap().unwrap();
+        let contract = receipt.contract_address.unwrap();
+
+        let store = |value: u64| {
+            let tx = TransactionRequest::new()
+                .from(from)
+                .to(contract)
+                .data(H256::from_low_u64_be(value).as_bytes().to_vec());
+            let provider = &provider;
+            async move {
+                provider.send_transaction(tx, None).await.unwrap().await.unwrap().unwrap();
+            }
+        };
+
+        let changes = provider.watch_storage(contract, H256::zero()).await.unwrap();
+        store(5).await;
+        // unchanged values are not emitted
+        store(5).await;
+        store(7).await;
+        store(0).await;
+
+        let values = changes.take(3).map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(values, vec![H256::from_low_u64_be(5), H256::from_low_u64_be(7), H256::zero()]);
+    }
 }
@@ -86,13 +86,17 @@ where
             return Poll::Ready(next_element)
         }
 
-        let this = self.project();
-        match futures_util::ready!(this.rx.poll_next(ctx)) {
-            Some(item) => match serde_json::from_str(item.get()) {
-                Ok(res) => Poll::Ready(Some(res)),
-                _ => Poll::Pending,
-            },
-            None => Poll::Ready(None),
+        let mut this = self.project();
+        loop {
+            match futures_util::ready!(this.rx.as_mut().poll_next(ctx)) {
+                Some(item) => {
+                    if let Ok(res) = serde_json::from_str(item.get()) {
+                        return Poll::Ready(Some(res))
+                    }
+                    // skip notifications which can't be decoded
+                }
+                None => return Poll::Ready(None),
+            }
         }
     }
 }
@@ -4,7 +4,7 @@ use crate::{JsonRpcClient, Middleware, PinBoxFut, Provider, ProviderError};
 use ethers_core::types::{Transaction, TxHash, U256};
 use futures_core::{stream::Stream, Future};
 use futures_util::{stream, stream::FuturesUnordered, FutureExt, StreamExt};
-use pin_project::pin_project;
+use pin_project::{pin_project, pinned_drop};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     collections::VecDeque,
@@ -36,7 +36,10 @@ enum FilterWatcherState<'a, R> {
 
 #[must_use = "filters do nothing unless you stream them"]
 /// Streams data from an installed filter via `eth_getFilterChanges`
-#[pin_project]
+///
+/// When the watcher is dropped, its filter is uninstalled with the next filter the provider
+/// installs. Use [`FilterWatcher::uninstall`] to uninstall it right away.
+#[pin_project(PinnedDrop)]
 pub struct FilterWatcher<'a, P, R> {
     /// The filter's installed id on the ethereum node
     pub id: U256,
@@ -47,6 +50,8 @@ pub struct FilterWatcher<'a, P, R> {
     interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
     /// statemachine driven by the Stream impl
     state: FilterWatcherState<'a, R>,
+    /// Whether the filter still needs to be uninstalled when the watcher is dropped
+    installed: bool,
 }
 
 impl<'a, P, R> FilterWatcher<'a, P, R>
@@ -61,6 +66,7 @@ where
             interval: Box::new(interval(DEFAULT_POLL_INTERVAL)),
             state: FilterWatcherState::WaitForInterval,
             provider,
+            installed: true,
         }
     }
 
@@ -75,6 +81,23 @@ where
     pub fn stream(self) -> Pin<Box<Self>> {
         Box::pin(self)
     }
+
+    /// Uninstalls the filter from the node via `eth_uninstallFilter`
+    pub async fn uninstall(mut self) -> Result<bool, ProviderError> {
+        self.installed = false;
+        self.provider.uninstall_filter(self.id).await
+    }
+}
+
+#[pinned_drop]
+impl<P, R> PinnedDrop for FilterWatcher<'_, P, R> {
+    fn drop(self: Pin<&mut Self>) {
+        // `eth_uninstallFilter` can't be awaited here, so the provider uninstalls the filter
+        // before it installs the next one
+        if self.installed {
+            self.provider.uninstall_filter_later(self.id);
+        }
+    }
 }
 
 // Advances the filter's state machine
@@ -263,6 +286,31 @@ mod tests {
     use futures_util::{FutureExt, StreamExt};
     use std::{collections::HashSet, convert::TryFrom};
 
+    #[tokio::test]
+    async fn uninstalls_filters_of_dropped_watchers() {
+        let (provider, mock) = Provider::mocked();
+
+        let watcher = FilterWatcher::<_, TxHash>::new(1u64, &provider);
+        drop(watcher);
+
+        // uninstalls the dropped filter before installing the next one
+        mock.push(U256::from(2)).unwrap();
+        mock.push(true).unwrap();
+        let watcher = provider.watch_blocks().await.unwrap();
+        mock.assert_request("eth_uninstallFilter", [U256::from(1)]).unwrap();
+        mock.assert_request("eth_newBlockFilter", Vec::<U256>::new()).unwrap();
+
+        // explicitly uninstalled filters are not uninstalled again
+        mock.push(true).unwrap();
+        assert!(watcher.uninstall().await.unwrap());
+        mock.assert_request("eth_uninstallFilter", [U256::from(2)]).unwrap();
+
+        mock.push(U256::from(3)).unwrap();
+        let _watcher = provider.watch_blocks().await.unwrap();
+        mock.assert_request("eth_newBlockFilter", Vec::<U256>::new()).unwrap();
+        assert!(mock.assert_request("eth_uninstallFilter", [U256::from(2)]).is_err());
+    }
+
     #[tokio::test]
     async fn can_stream_pending_transactions() {
         let num_txs = 5;
@@ -8,7 +8,10 @@ use serde::{
 use serde_json::{value::RawValue, Value};
 use thiserror::Error;
 
-use ethers_core::types::U256;
+use ethers_core::{
+    abi::{self, ParamType, Token},
+    types::{Bytes, U256},
+};
 
 #[derive(Deserialize, Debug, Clone, Error)]
 /// A JSON-RPC 2.0 error
@@ -21,6 +24,121 @@ pub struct JsonRpcError {
     pub data: Option<Value>,
 }
 
+/// The selector of `Error(string)`
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// The selector of `Panic(uint256)`
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Error messages of nodes rejecting an `eth_getLogs` request because its block range or result set
+/// is too large
+const LOG_QUERY_TOO_LARGE_ERRORS: &[&str] = &[
+    "query returned more than",
+    "response size exceeded",
+    "response size should not",
+    "block range is too wide",
+    "range too large",
+    "exceed maximum block range",
+];
+
+/// The decoded data a reverted call returned
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevertReason {
+    /// Reverted with `Error(string)`, e.g. by a failed `require(condition, "reason")`
+    Message(String),
+    /// Reverted with `Panic(uint256)`, e.g. by a failed `assert` or an arithmetic overflow, with
+    /// the panic code
+    Panic(U256),
+    /// Reverted with a custom error, which can be decoded with the abi of the contract, or
+    /// without any data, e.g. by `revert()`
+    Custom(Bytes),
+}
+
+impl RevertReason {
+    /// Decodes the data a reverted call returned
+    pub fn decode(data: &[u8]) -> Self {
+        let decoded = match data.get(..4) {
+            Some(selector) if selector == ERROR_STRING_SELECTOR => {
+                match abi::decode(&[ParamType::String], &data[4..]).ok().and_then(|mut t| t.pop()) {
+                    Some(Token::String(reason)) => Some(RevertReason::Message(reason)),
+                    _ => None,
+                }
+            }
+            Some(selector) if selector == PANIC_SELECTOR => {
+                match abi::decode(&[ParamType::Uint(256)], &data[4..])
+                    .ok()
+                    .and_then(|mut t| t.pop())
+                {
+                    Some(Token::Uint(code)) => Some(RevertReason::Panic(code)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        decoded.unwrap_or_else(|| RevertReason::Custom(data.to_vec().into()))
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Message(reason) => write!(f, "reverted: {}", reason),
+            RevertReason::Panic(code) => write!(f, "panicked: 0x{:02x}", code),
+            RevertReason::Custom(data) if data.is_empty() => write!(f, "reverted"),
+            RevertReason::Custom(data) => write!(f, "reverted with custom error: {}", data),
+        }
+    }
+}
+
+impl JsonRpcError {
+    /// Returns the data a reverted call returned, if the node included it in the `data` field.
+    ///
+    /// This is the ABI encoded `Error(string)`, `Panic(uint256)` or custom error the contract
+    /// reverted with. Nodes which wrap the hex string in an object with a `data` field are
+    /// supported as well.
+    pub fn as_revert_data(&self) -> Option<Bytes> {
+        fn revert_data(value: &Value) -> Option<Bytes> {
+            match value {
+                Value::String(data) => {
+                    let hex = data.trim_start_matches("Reverted ").strip_prefix("0x")?;
+                    // the data must at least contain a selector
+                    if hex.len() < 8 {
+                        return None
+                    }
+                    hex::decode(hex).ok().map(Into::into)
+                }
+                Value::Object(map) => revert_data(map.get("data")?),
+                _ => None,
+            }
+        }
+        revert_data(self.data.as_ref()?)
+    }
+
+    /// Returns the message of a call which reverted with `Error(string)`, e.g. by a failed
+    /// `require(condition, "reason")`
+    pub fn revert_reason(&self) -> Option<String> {
+        match RevertReason::decode(&self.as_revert_data()?) {
+            RevertReason::Message(reason) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the error is returned for a reverted call, with or without revert data
+    pub fn is_revert(&self) -> bool {
+        self.as_revert_data().is_some() || self.message.contains("revert")
+    }
+
+    /// Returns whether the node rejected an `eth_getLogs` request because its block range or
+    /// result set is too large, i.e. the request may succeed for a smaller block range.
+    ///
+    /// Nodes don't agree on an error code for this, and e.g. Infura's `-32005` is also returned
+    /// for rate limited requests, so the message decides.
+    pub fn is_log_query_too_large(&self) -> bool {
+        let message = self.message.to_lowercase();
+        LOG_QUERY_TOO_LARGE_ERRORS.iter().any(|msg| message.contains(msg))
+    }
+}
+
 impl fmt::Display for JsonRpcError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "(code: {}, message: {}, data: {:?})", self.code, self.message, self.data)
@@ -263,6 +381,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn detects_log_queries_too_large() {
+        let err = |code, message: &str| JsonRpcError { code, message: message.into(), data: None };
+
+        assert!(err(-32005, "query returned more than 10000 results").is_log_query_too_large());
+        assert!(err(-32602, "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range").is_log_query_too_large());
+        assert!(err(-32000, "block range is too wide").is_log_query_too_large());
+        // rate limits share the error code, but not the message
+        assert!(!err(-32005, "daily request count exceeded, request rate limited")
+            .is_log_query_too_large());
+        assert!(!err(429, "Too many requests, limit exceeded").is_log_query_too_large());
+        assert!(!err(-32000, "header not found").is_log_query_too_large());
+    }
+
+    #[test]
+    fn extracts_revert_data() {
+        let reason = "0x08c379a000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000004706f6f7200000000000000000000000000000000000000000000000000000000";
+
+        let err: JsonRpcError = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted: poor",
+            "data": reason
+        }))
+        .unwrap();
+        assert_eq!(err.as_revert_data().unwrap(), reason.parse::<Bytes>().unwrap());
+        assert_eq!(err.revert_reason().unwrap(), "poor");
+
+        let err: JsonRpcError = serde_json::from_value(serde_json::json!({
+            "code": -32603,
+            "message": "Error: VM Exception while processing transaction",
+            "data": { "message": "revert", "data": reason }
+        }))
+        .unwrap();
+        assert_eq!(err.revert_reason().unwrap(), "poor");
+
+        // custom errors are returned, but not decoded
+        let err: JsonRpcError = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": "0xdeadbeef"
+        }))
+        .unwrap();
+        assert_eq!(err.as_revert_data().unwrap(), Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert!(err.revert_reason().is_none());
+
+        let err: JsonRpcError = serde_json::from_value(serde_json::json!({
+            "code": -32000,
+            "message": "header not found"
+        }))
+        .unwrap();
+        assert!(err.as_revert_data().is_none());
+    }
+
+    #[test]
+    fn decodes_revert_reasons() {
+        let message = RevertReason::decode(&hex::decode("08c379a000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000004706f6f7200000000000000000000000000000000000000000000000000000000").unwrap());
+        assert_eq!(message, RevertReason::Message("poor".to_string()));
+        assert_eq!(message.to_string(), "reverted: poor");
+
+        // division by zero
+        let panic = RevertReason::decode(
+            &hex::decode(
+                "4e487b710000000000000000000000000000000000000000000000000000000000000012",
+            )
+            .unwrap(),
+        );
+        assert_eq!(panic, RevertReason::Panic(0x12.into()));
+        assert_eq!(panic.to_string(), "panicked: 0x12");
+
+        let custom = RevertReason::decode(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(custom, RevertReason::Custom(vec![0xde, 0xad, 0xbe, 0xef].into()));
+        assert_eq!(RevertReason::decode(&[]).to_string(), "reverted");
+    }
+
     #[test]
     fn ser_request() {
         let request: Request<()> = Request::new(0, "eth_chainId", ());
@@ -2,11 +2,16 @@
 use crate::{provider::ProviderError, JsonRpcClient};
 
 use async_trait::async_trait;
-use reqwest::{header::HeaderValue, Client, Error as ReqwestError};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client, Error as ReqwestError,
+};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
+    collections::HashMap,
     str::FromStr,
     sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
 };
 use thiserror::Error;
 use url::Url;
@@ -33,6 +38,10 @@ pub struct Provider {
     id: AtomicU64,
     client: Client,
     url: Url,
+    /// Timeout applied to every request which has no method specific timeout
+    timeout: Option<Duration>,
+    /// Method specific timeouts which take precedence over `timeout`
+    method_timeouts: HashMap<String, Duration>,
 }
 
 #[derive(Error, Debug)]
@@ -52,7 +61,10 @@ pub enum ClientError {
 
 impl From<ClientError> for ProviderError {
     fn from(src: ClientError) -> Self {
-        ProviderError::JsonRpcClientError(Box::new(src))
+        match src {
+            ClientError::ReqwestError(err) if err.is_timeout() => ProviderError::Timeout,
+            src => ProviderError::JsonRpcClientError(Box::new(src)),
+        }
     }
 }
 
@@ -71,7 +83,14 @@ impl JsonRpcClient for Provider {
         let next_id = self.id.fetch_add(1, Ordering::SeqCst);
         let payload = Request::new(next_id, method, params);
 
-        let res = self.client.post(self.url.as_ref()).json(&payload).send().await?;
+        let req = self.client.post(self.url.as_ref()).json(&payload);
+        #[cfg(not(target_arch = "wasm32"))]
+        let req = match self.timeout_for(method) {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
+        };
+
+        let res = req.send().await?;
         let text = res.text().await?;
 
         let raw = match serde_json::from_str(&text) {
@@ -128,15 +147,44 @@ impl Provider {
         let mut auth_value = HeaderValue::from_str(&auth.to_string())?;
         auth_value.set_sensitive(true);
 
-        let mut headers = reqwest::header::HeaderMap::new();
+        let mut headers = HeaderMap::new();
         headers.insert(reqwest::header::AUTHORIZATION, auth_value);
 
+        Self::new_with_headers(url, headers)
+    }
+
+    /// Initializes a new HTTP Client which sends the provided headers with every request, e.g.
+    /// the API key of a gateway.
+    ///
+    /// To authenticate as well, insert the `Authorization` header into the headers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::Http;
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    /// use url::Url;
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-api-key", HeaderValue::from_static("secret"));
+    ///
+    /// let url = Url::parse("http://localhost:8545").unwrap();
+    /// let provider = Http::new_with_headers(url, headers).unwrap();
+    /// ```
+    pub fn new_with_headers(
+        url: impl Into<Url>,
+        headers: HeaderMap,
+    ) -> Result<Self, HttpClientError> {
         let client = Client::builder().default_headers(headers).build()?;
 
         Ok(Self::new_with_client(url, client))
     }
 
-    /// Allows to customize the provider by providing your own http client
+    /// Allows to customize the provider by providing your own http client, e.g. to configure a
+    /// proxy or the connection pool.
+    ///
+    /// Clones of a [`reqwest::Client`] share their connection pool, so a single client can be
+    /// shared by many providers.
     ///
     /// # Example
     ///
@@ -149,7 +197,72 @@ impl Provider {
     /// let provider = Http::new_with_client(url, client);
     /// ```
     pub fn new_with_client(url: impl Into<Url>, client: reqwest::Client) -> Self {
-        Self { id: AtomicU64::new(1), client, url: url.into() }
+        Self {
+            id: AtomicU64::new(1),
+            client,
+            url: url.into(),
+            timeout: None,
+            method_timeouts: HashMap::new(),
+        }
+    }
+
+    /// Sets the timeout that is applied to every request, unless a timeout for the request's
+    /// method was configured via [`Provider::with_method_timeout`].
+    ///
+    /// Requests which time out fail with [`ProviderError::Timeout`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::Http;
+    /// use std::{str::FromStr, time::Duration};
+    ///
+    /// let provider = Http::from_str("http://localhost:8545")
+    ///     .unwrap()
+    ///     .with_timeout(Duration::from_secs(5));
+    /// ```
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for all requests of the given JSON-RPC method, overriding the global
+    /// timeout.
+    ///
+    /// This is useful for methods that legitimately take longer than others, like tracing or
+    /// `eth_getLogs` over large ranges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::Http;
+    /// use std::{str::FromStr, time::Duration};
+    ///
+    /// let provider = Http::from_str("http://localhost:8545")
+    ///     .unwrap()
+    ///     .with_timeout(Duration::from_secs(5))
+    ///     .with_method_timeout("trace_block", Duration::from_secs(60));
+    /// ```
+    #[must_use]
+    pub fn with_method_timeout(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.method_timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Returns the http client the requests are sent with
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Returns the url of the JSON-RPC endpoint
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the timeout that applies to requests of the given method, if any
+    pub fn timeout_for(&self, method: &str) -> Option<Duration> {
+        self.method_timeouts.get(method).copied().or(self.timeout)
     }
 }
 
@@ -164,7 +277,13 @@ impl FromStr for Provider {
 
 impl Clone for Provider {
     fn clone(&self) -> Self {
-        Self { id: AtomicU64::new(1), client: self.client.clone(), url: self.url.clone() }
+        Self {
+            id: AtomicU64::new(1),
+            client: self.client.clone(),
+            url: self.url.clone(),
+            timeout: self.timeout,
+            method_timeouts: self.method_timeouts.clone(),
+        }
     }
 }
 
@@ -179,3 +298,167 @@ pub enum HttpClientError {
     #[error(transparent)]
     ClientBuild(#[from] reqwest::Error),
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use ethers_core::types::U64;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    /// Spawns a JSON-RPC server that answers every request with `0x1` after sleeping for the
+    /// delay configured for the request's method.
+    ///
+    /// The head of every request is sent to the returned receiver.
+    fn spawn_slow_server(delays: HashMap<&'static str, Duration>) -> (Url, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let (heads, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let delays = delays.clone();
+                let heads = heads.clone();
+                thread::spawn(move || {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    // read until the entire body has been received
+                    let body = loop {
+                        let n = stream.read(&mut chunk).unwrap();
+                        buf.extend_from_slice(&chunk[..n]);
+                        let req = String::from_utf8_lossy(&buf).to_string();
+                        if let Some(idx) = req.find("\r\n\r\n") {
+                            let len = req[..idx]
+                                .lines()
+                                .find_map(|line| {
+                                    let line = line.to_ascii_lowercase();
+                                    line.strip_prefix("content-length:")
+                                        .map(|len| len.trim().parse::<usize>().unwrap())
+                                })
+                                .unwrap_or_default();
+                            if buf.len() >= idx + 4 + len {
+                                let _ = heads.send(req[..idx].to_string());
+                                break buf[idx + 4..idx + 4 + len].to_vec()
+                            }
+                        }
+                        if n == 0 {
+                            return
+                        }
+                    };
+
+                    let req: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    if let Some(delay) = delays.get(req["method"].as_str().unwrap()) {
+                        thread::sleep(*delay);
+                    }
+                    let res = format!(r#"{{"jsonrpc":"2.0","id":{},"result":"0x1"}}"#, req["id"]);
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        res.len(),
+                        res
+                    );
+                });
+            }
+        });
+        (url, rx)
+    }
+
+    #[tokio::test]
+    async fn sends_auth_and_custom_headers() {
+        let (url, heads) = spawn_slow_server(HashMap::new());
+
+        let provider =
+            Provider::new_with_auth(url.clone(), Authorization::basic("user", "secret")).unwrap();
+        for _ in 0..2 {
+            let _: U64 = provider.request("eth_blockNumber", ()).await.unwrap();
+            let head = heads.recv().unwrap().to_ascii_lowercase();
+            // base64 of `user:secret`
+            assert!(head.contains("authorization: basic dxnlcjpzzwnyzxq="), "{}", head);
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("key"));
+        headers.insert(reqwest::header::AUTHORIZATION, HeaderValue::from_static("Bearer token"));
+        let provider = Provider::new_with_headers(url, headers).unwrap();
+        let _: U64 = provider.request("eth_blockNumber", ()).await.unwrap();
+        let head = heads.recv().unwrap().to_ascii_lowercase();
+        assert!(head.contains("x-api-key: key"), "{}", head);
+        assert!(head.contains("authorization: bearer token"), "{}", head);
+    }
+
+    #[tokio::test]
+    async fn uses_provided_client() {
+        let (url, heads) = spawn_slow_server(HashMap::new());
+
+        let client = Client::builder().user_agent("custom-agent").build().unwrap();
+        let providers = [
+            Provider::new_with_client(url.clone(), client.clone()),
+            Provider::new_with_client(url.clone(), client),
+        ];
+        for provider in &providers {
+            assert_eq!(provider.url(), &url);
+            let _: U64 = provider.request("eth_blockNumber", ()).await.unwrap();
+            let head = heads.recv().unwrap().to_ascii_lowercase();
+            assert!(head.contains("user-agent: custom-agent"), "{}", head);
+        }
+    }
+
+    #[tokio::test]
+    async fn surfaces_timeouts() {
+        let (url, _heads) =
+            spawn_slow_server(HashMap::from([("eth_getLogs", Duration::from_millis(500))]));
+        let provider =
+            crate::Provider::new(Provider::new(url).with_timeout(Duration::from_millis(100)));
+
+        // a slow request times out while others are unaffected
+        let err = provider.request::<_, U64>("eth_getLogs", ()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout), "{:?}", err);
+        let res: U64 = provider.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(res, U64::one());
+    }
+
+    #[test]
+    fn method_timeout_falls_back_to_global() {
+        let provider = Provider::from_str("http://localhost:8545").unwrap();
+        assert_eq!(provider.timeout_for("eth_blockNumber"), None);
+
+        let provider = provider
+            .with_timeout(Duration::from_secs(5))
+            .with_method_timeout("trace_block", Duration::from_secs(60));
+        assert_eq!(provider.timeout_for("eth_blockNumber"), Some(Duration::from_secs(5)));
+        assert_eq!(provider.timeout_for("trace_block"), Some(Duration::from_secs(60)));
+    }
+
+    #[tokio::test]
+    async fn respects_method_timeouts() {
+        let delay = Duration::from_millis(500);
+        let (url, _heads) = spawn_slow_server(HashMap::from([
+            ("trace_block", delay),
+            ("eth_blockNumber", delay),
+            ("eth_chainId", delay),
+        ]));
+
+        let provider = Provider::new(url)
+            .with_timeout(Duration::from_millis(200))
+            .with_method_timeout("trace_block", Duration::from_secs(5))
+            .with_method_timeout("eth_blockNumber", Duration::from_millis(50));
+
+        // slow method within its elevated timeout
+        let res: U64 = provider.request("trace_block", ()).await.unwrap();
+        assert_eq!(res, U64::one());
+
+        // slow method exceeding its lowered timeout
+        let err = provider.request::<_, U64>("eth_blockNumber", ()).await.unwrap_err();
+        assert!(matches!(err, ClientError::ReqwestError(ref err) if err.is_timeout()));
+
+        // slow method without an override exceeds the global timeout
+        let err = provider.request::<_, U64>("eth_chainId", ()).await.unwrap_err();
+        assert!(matches!(err, ClientError::ReqwestError(ref err) if err.is_timeout()));
+        assert!(matches!(ProviderError::from(err), ProviderError::Timeout));
+    }
+}
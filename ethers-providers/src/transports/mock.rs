@@ -1,3 +1,4 @@
+use super::common::JsonRpcError;
 use crate::{JsonRpcClient, ProviderError};
 
 use async_trait::async_trait;
@@ -12,9 +13,35 @@ use thiserror::Error;
 
 #[derive(Clone, Debug)]
 /// Mock transport used in test environments.
+///
+/// Responses are pushed up front and returned to requests in reverse order, i.e. the response
+/// pushed last answers the next request. Every request is recorded and can be checked in the order
+/// the requests were made with [`MockProvider::assert_request`].
+///
+/// ```
+/// use ethers_core::types::U64;
+/// use ethers_providers::{Middleware, Provider};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let (provider, mock) = Provider::mocked();
+/// mock.push(U64::from(12))?;
+///
+/// let block = provider.get_block_number().await?;
+/// assert_eq!(block.as_u64(), 12);
+/// mock.assert_request("eth_blockNumber", ())?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct MockProvider {
     requests: Arc<Mutex<VecDeque<(String, Value)>>>,
-    responses: Arc<Mutex<VecDeque<Value>>>,
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+}
+
+/// A response pushed to the [`MockProvider`]
+#[derive(Clone, Debug)]
+enum MockResponse {
+    Value(Value),
+    Error(JsonRpcError),
 }
 
 impl Default for MockProvider {
@@ -37,7 +64,10 @@ impl JsonRpcClient for MockProvider {
     ) -> Result<R, MockError> {
         self.requests.lock().unwrap().push_back((method.to_owned(), serde_json::to_value(input)?));
         let mut data = self.responses.lock().unwrap();
-        let element = data.pop_back().ok_or(MockError::EmptyResponses)?;
+        let element = match data.pop_back().ok_or(MockError::EmptyResponses)? {
+            MockResponse::Value(value) => value,
+            MockResponse::Error(err) => return Err(MockError::JsonRpcError(err)),
+        };
         let res: R = serde_json::from_value(element)?;
 
         Ok(res)
@@ -68,9 +98,21 @@ impl MockProvider {
     /// Pushes the data to the responses
     pub fn push<T: Serialize + Send + Sync, K: Borrow<T>>(&self, data: K) -> Result<(), MockError> {
         let value = serde_json::to_value(data.borrow())?;
-        self.responses.lock().unwrap().push_back(value);
+        self.responses.lock().unwrap().push_back(MockResponse::Value(value));
         Ok(())
     }
+
+    /// Pushes a JSON-RPC error to the responses, which fails the request it answers like an
+    /// error returned by a node
+    pub fn push_error(&self, code: i64, message: impl Into<String>) {
+        self.push_json_rpc_error(JsonRpcError { code, message: message.into(), data: None })
+    }
+
+    /// Pushes a JSON-RPC error with additional data to the responses, e.g. the revert data of a
+    /// reverted call
+    pub fn push_json_rpc_error(&self, err: JsonRpcError) {
+        self.responses.lock().unwrap().push_back(MockResponse::Error(err));
+    }
 }
 
 #[derive(Error, Debug)]
@@ -79,11 +121,15 @@ pub enum MockError {
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
 
-    #[error("empty responses array, please push some requests")]
+    #[error("empty requests array, no request was made")]
     EmptyRequests,
 
     #[error("empty responses array, please push some responses")]
     EmptyResponses,
+
+    /// A JSON-RPC error pushed via [`MockProvider::push_error`]
+    #[error(transparent)]
+    JsonRpcError(JsonRpcError),
 }
 
 impl From<MockError> for ProviderError {
@@ -130,6 +176,23 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn pushes_errors() {
+        let mock = MockProvider::new();
+        mock.push(U64::from(12)).unwrap();
+        mock.push_error(-32000, "header not found");
+
+        let err = mock.request::<_, U64>("eth_blockNumber", ()).await.unwrap_err();
+        match err {
+            MockError::JsonRpcError(ref err) => assert_eq!(err.code, -32000),
+            _ => panic!("expected JSON-RPC error"),
+        };
+        assert_eq!(err.to_string(), "(code: -32000, message: header not found, data: None)");
+
+        let block: U64 = mock.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block.as_u64(), 12);
+    }
+
     #[tokio::test]
     async fn composes_with_provider() {
         let (provider, mock) = crate::Provider::mocked();
@@ -1,5 +1,5 @@
 mod common;
-pub use common::Authorization;
+pub use common::{Authorization, JsonRpcError, RevertReason};
 
 // only used with WS
 #[cfg(feature = "ws")]
@@ -43,5 +43,15 @@ mod retry;
 #[cfg(not(target_arch = "wasm32"))]
 pub use retry::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod tracing_client;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tracing_client::TracingClient;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod rate_limit;
+#[cfg(not(target_arch = "wasm32"))]
+pub use rate_limit::RateLimitClient;
+
 mod mock;
 pub use mock::{MockError, MockProvider};
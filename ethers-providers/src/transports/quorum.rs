@@ -4,6 +4,7 @@ use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use crate::{provider::ProviderError, JsonRpcClient, PubsubClient};
@@ -15,9 +16,20 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{value::RawValue, Value};
 use thiserror::Error;
 
+#[cfg(not(target_arch = "wasm32"))]
+use futures_timer::Delay;
+#[cfg(target_arch = "wasm32")]
+use wasm_timer::Delay;
+
 /// A provider that bundles multiple providers and only returns a value to the
 /// caller once the quorum has been reached.
 ///
+/// Requests are sent to all providers at once. With a
+/// [`stage_timeout`](QuorumProviderBuilder::stage_timeout), a request is first sent only to the
+/// fewest providers, in their order, whose weight can reach the quorum, and to the next providers
+/// if they fail, disagree or don't respond within the timeout. Use
+/// [`QuorumProvider::rank_endpoints`] to order the providers by their latency.
+///
 /// # Example
 ///
 /// Create a `QuorumProvider` that uses a homogenous `Provider` type only returns a value if the
@@ -80,6 +92,9 @@ pub struct QuorumProvider<T = Box<dyn JsonRpcClientWrapper>> {
     quorum_weight: u64,
     /// All the internal providers this providers runs
     providers: Vec<WeightedProvider<T>>,
+    /// How long to wait for the providers asked so far before asking the next ones, if requests
+    /// are sent in stages
+    stage_timeout: Option<Duration>,
 }
 
 impl QuorumProvider<Box<dyn JsonRpcClientWrapper>> {
@@ -125,11 +140,12 @@ impl<T> QuorumProvider<T> {
 pub struct QuorumProviderBuilder<T> {
     quorum: Quorum,
     providers: Vec<WeightedProvider<T>>,
+    stage_timeout: Option<Duration>,
 }
 
 impl<T> Default for QuorumProviderBuilder<T> {
     fn default() -> Self {
-        Self { quorum: Default::default(), providers: Vec::new() }
+        Self { quorum: Default::default(), providers: Vec::new(), stage_timeout: None }
     }
 }
 
@@ -154,9 +170,24 @@ impl<T> QuorumProviderBuilder<T> {
         self
     }
 
+    /// Sends requests in stages instead of to all providers at once: first to the fewest
+    /// providers, in their order, whose weight can reach the quorum. If they fail or disagree, or
+    /// if not all of them responded after `timeout`, the request is sent to the next providers.
+    ///
+    /// This saves requests to the providers at the end, see [`QuorumProvider::rank_endpoints`].
+    pub fn stage_timeout(mut self, timeout: Duration) -> Self {
+        self.stage_timeout = Some(timeout);
+        self
+    }
+
     pub fn build(self) -> QuorumProvider<T> {
         let quorum_weight = self.quorum.weight(&self.providers);
-        QuorumProvider { quorum: self.quorum, quorum_weight, providers: self.providers }
+        QuorumProvider {
+            quorum: self.quorum,
+            quorum_weight,
+            providers: self.providers,
+            stage_timeout: self.stage_timeout,
+        }
     }
 }
 
@@ -180,6 +211,31 @@ impl<T: JsonRpcClientWrapper> QuorumProvider<T> {
             .ok_or_else(|| ProviderError::CustomError("No Providers".to_string()))
     }
 
+    /// Measures the round-trip latency of every provider with an `eth_blockNumber` request and
+    /// reorders the providers fastest-first. Providers which fail to respond are moved to the end.
+    /// If requests are sent in stages, see [`QuorumProviderBuilder::stage_timeout`], the fastest
+    /// providers answer the following requests.
+    ///
+    /// Returns the measured latencies in the new order of the providers, `None` for providers
+    /// that failed. Call this periodically to keep the order up to date.
+    pub async fn rank_endpoints(&mut self) -> Vec<Option<Duration>> {
+        let latencies = join_all(self.providers.iter().map(|provider| async move {
+            let start = Instant::now();
+            let res = provider.inner.request("eth_blockNumber", serde_json::json!(())).await;
+            res.ok().map(|_| start.elapsed())
+        }))
+        .await;
+
+        let mut ranked =
+            latencies.into_iter().zip(std::mem::take(&mut self.providers)).collect::<Vec<_>>();
+        // stable, so equally fast providers keep their order
+        ranked.sort_by_key(|(latency, _)| latency.unwrap_or(Duration::MAX));
+
+        let (latencies, providers) = ranked.into_iter().unzip();
+        self.providers = providers;
+        latencies
+    }
+
     /// Normalizes the request payload depending on the call
     async fn normalize_request(&self, method: &str, params: &mut Value) {
         match method {
@@ -271,61 +327,119 @@ type PendingRequest<'a> =
 /// reached a quorum.
 struct QuorumRequest<'a, T> {
     inner: &'a QuorumProvider<T>,
+    method: &'a str,
+    params: Value,
+    /// The index of the next provider the request is sent to
+    next: usize,
     /// The different answers with their cumulative weight
     responses: Vec<(Value, u64)>,
     /// All the errors the provider yielded
     errors: Vec<ProviderError>,
     // Requests currently pending
     requests: Vec<PendingRequest<'a>>,
+    /// When to ask the next providers, if requests are sent in stages
+    deadline: Option<Pin<Box<Delay>>>,
 }
 
-impl<'a, T> QuorumRequest<'a, T> {
-    fn new(inner: &'a QuorumProvider<T>, requests: Vec<PendingRequest<'a>>) -> Self {
-        Self { responses: Vec::new(), errors: Vec::new(), inner, requests }
+impl<'a, T: JsonRpcClientWrapper> QuorumRequest<'a, T> {
+    fn new(inner: &'a QuorumProvider<T>, method: &'a str, params: Value) -> Self {
+        let mut request = Self {
+            inner,
+            method,
+            params,
+            next: 0,
+            responses: Vec::new(),
+            errors: Vec::new(),
+            requests: Vec::new(),
+            deadline: None,
+        };
+        // without stages, the request is sent to all providers at once
+        let weight = if inner.stage_timeout.is_some() { inner.quorum_weight } else { u64::MAX };
+        request.dispatch(weight);
+        request
+    }
+
+    /// Sends the request to the next providers until their weight adds up to `weight`. Returns
+    /// `false` if there are no providers left.
+    fn dispatch(&mut self, weight: u64) -> bool {
+        let inner = self.inner;
+        let mut dispatched = 0;
+        while dispatched < weight {
+            let idx = self.next;
+            let provider = match inner.providers.get(idx) {
+                Some(provider) => provider,
+                None => break,
+            };
+            self.next += 1;
+            dispatched += provider.weight;
+            let fut = provider.inner.request(self.method, self.params.clone());
+            self.requests.push(Box::pin(fut.map(move |res| (res, idx))));
+        }
+        if dispatched > 0 {
+            self.deadline = self.inner.stage_timeout.map(|timeout| Box::pin(Delay::new(timeout)));
+        }
+        dispatched > 0
     }
 }
 
-impl<'a, T> Future for QuorumRequest<'a, T> {
+impl<'a, T: JsonRpcClientWrapper> Future for QuorumRequest<'a, T> {
     type Output = Result<Value, QuorumError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
-        for n in (0..this.requests.len()).rev() {
-            let mut request = this.requests.swap_remove(n);
-            match request.poll_unpin(cx) {
-                Poll::Ready((Ok(val), idx)) => {
-                    let response_weight = this.inner.providers[idx].weight;
-                    if let Some((_, weight)) = this.responses.iter_mut().find(|(v, _)| &val == v) {
-                        // add the weight to equal response value
-                        *weight += response_weight;
-                        if *weight >= this.inner.quorum_weight {
-                            // reached quorum with multiple responses
+        loop {
+            for n in (0..this.requests.len()).rev() {
+                let mut request = this.requests.swap_remove(n);
+                match request.poll_unpin(cx) {
+                    Poll::Ready((Ok(val), idx)) => {
+                        let response_weight = this.inner.providers[idx].weight;
+                        if let Some((_, weight)) =
+                            this.responses.iter_mut().find(|(v, _)| &val == v)
+                        {
+                            // add the weight to equal response value
+                            *weight += response_weight;
+                            if *weight >= this.inner.quorum_weight {
+                                // reached quorum with multiple responses
+                                return Poll::Ready(Ok(val))
+                            }
+                        } else if response_weight >= this.inner.quorum_weight {
+                            // reached quorum with single response
                             return Poll::Ready(Ok(val))
                         } else {
                             this.responses.push((val, response_weight));
                         }
-                    } else if response_weight >= this.inner.quorum_weight {
-                        // reached quorum with single response
-                        return Poll::Ready(Ok(val))
-                    } else {
-                        this.responses.push((val, response_weight));
+                    }
+                    Poll::Ready((Err(err), _)) => this.errors.push(err),
+                    _ => {
+                        this.requests.push(request);
                     }
                 }
-                Poll::Ready((Err(err), _)) => this.errors.push(err),
-                _ => {
-                    this.requests.push(request);
+            }
+
+            let best = this.responses.iter().map(|(_, weight)| *weight).max().unwrap_or_default();
+            if !this.requests.is_empty() {
+                // the providers asked so far take too long, ask the next ones
+                let timed_out = this
+                    .deadline
+                    .as_mut()
+                    .map_or(false, |deadline| deadline.poll_unpin(cx).is_ready());
+                if timed_out && this.dispatch(this.inner.quorum_weight - best) {
+                    continue
                 }
+                if timed_out {
+                    this.deadline = None;
+                }
+                return Poll::Pending
             }
-        }
 
-        if this.requests.is_empty() {
-            // No more requests and no quorum reached
-            this.responses.sort_by(|a, b| b.1.cmp(&a.1));
-            let values = std::mem::take(&mut this.responses).into_iter().map(|r| r.0).collect();
-            let errors = std::mem::take(&mut this.errors);
-            Poll::Ready(Err(QuorumError::NoQuorumReached { values, errors }))
-        } else {
-            Poll::Pending
+            // the providers asked so far failed or disagreed, ask the next ones
+            if !this.dispatch(this.inner.quorum_weight - best) {
+                // No more requests and no quorum reached
+                this.responses.sort_by(|a, b| b.1.cmp(&a.1));
+                let values = std::mem::take(&mut this.responses).into_iter().map(|r| r.0).collect();
+                let errors = std::mem::take(&mut this.errors);
+                return Poll::Ready(Err(QuorumError::NoQuorumReached { values, errors }))
+            }
         }
     }
 }
@@ -358,7 +472,17 @@ pub enum QuorumError {
 
 impl From<QuorumError> for ProviderError {
     fn from(src: QuorumError) -> Self {
-        ProviderError::JsonRpcClientError(Box::new(src))
+        match src {
+            // no provider responded in time
+            QuorumError::NoQuorumReached { ref values, ref errors }
+                if values.is_empty() &&
+                    !errors.is_empty() &&
+                    errors.iter().all(|err| matches!(err, ProviderError::Timeout)) =>
+            {
+                ProviderError::Timeout
+            }
+            src => ProviderError::JsonRpcClientError(Box::new(src)),
+        }
     }
 }
 
@@ -440,18 +564,7 @@ where
         let mut params = serde_json::to_value(params)?;
         self.normalize_request(method, &mut params).await;
 
-        let requests = self
-            .providers
-            .iter()
-            .enumerate()
-            .map(|(idx, provider)| {
-                let params = params.clone();
-                let fut = provider.inner.request(method, params).map(move |res| (res, idx));
-                Box::pin(fut) as PendingRequest
-            })
-            .collect::<Vec<_>>();
-
-        let value = QuorumRequest::new(self, requests).await?;
+        let value = QuorumRequest::new(self, method, params).await?;
         Ok(serde_json::from_value(value)?)
     }
 }
@@ -559,9 +672,43 @@ where
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
-    use super::{Quorum, QuorumProvider, WeightedProvider};
-    use crate::{Middleware, MockProvider, Provider};
+    use super::{JsonRpcClientWrapper, Quorum, QuorumProvider, WeightedProvider};
+    use crate::{Middleware, MockProvider, Provider, ProviderError};
+    use async_trait::async_trait;
     use ethers_core::types::U64;
+    use serde_json::Value;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    /// An endpoint that responds after a delay, or fails if it has none
+    #[derive(Debug)]
+    struct DelayedEndpoint {
+        id: u64,
+        delay: Option<Duration>,
+    }
+
+    #[async_trait]
+    impl JsonRpcClientWrapper for DelayedEndpoint {
+        async fn request(&self, _method: &str, _params: Value) -> Result<Value, ProviderError> {
+            let delay =
+                self.delay.ok_or_else(|| ProviderError::CustomError("unreachable".to_string()))?;
+            tokio::time::sleep(delay).await;
+            Ok(serde_json::to_value(U64::from(self.id))?)
+        }
+    }
+
+    /// An endpoint whose requests always time out
+    #[derive(Debug)]
+    struct TimedOutEndpoint;
+
+    #[async_trait]
+    impl JsonRpcClientWrapper for TimedOutEndpoint {
+        async fn request(&self, _method: &str, _params: Value) -> Result<Value, ProviderError> {
+            Err(ProviderError::Timeout)
+        }
+    }
 
     async fn test_quorum(q: Quorum) {
         let num = 5u64;
@@ -629,4 +776,142 @@ mod tests {
     async fn all_quorum() {
         test_quorum(Quorum::All).await
     }
+
+    #[tokio::test]
+    async fn ranks_endpoints_by_latency() {
+        let endpoints =
+            [(0, Some(150)), (1, None), (2, Some(10)), (3, Some(75))].map(|(id, ms)| {
+                WeightedProvider::new(DelayedEndpoint { id, delay: ms.map(Duration::from_millis) })
+            });
+        let mut quorum = QuorumProvider::builder().add_providers(endpoints).build();
+
+        let latencies = quorum.rank_endpoints().await;
+
+        let order = quorum.providers().iter().map(|p| p.inner.id).collect::<Vec<_>>();
+        assert_eq!(order, vec![2, 3, 0, 1]);
+        assert!(latencies[0].unwrap() < latencies[1].unwrap());
+        assert!(latencies[1].unwrap() < latencies[2].unwrap());
+        assert!(latencies[3].is_none());
+    }
+
+    #[tokio::test]
+    async fn times_out_if_all_endpoints_time_out() {
+        let endpoints = [TimedOutEndpoint, TimedOutEndpoint].map(WeightedProvider::new);
+        let provider = Provider::quorum(QuorumProvider::builder().add_providers(endpoints).build());
+
+        let err = provider.get_block_number().await.unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout), "{:?}", err);
+    }
+
+    /// An endpoint that always responds with the same block number after a delay, counting the
+    /// requests it received
+    #[derive(Debug)]
+    struct CountingEndpoint {
+        delay: Duration,
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl JsonRpcClientWrapper for CountingEndpoint {
+        async fn request(&self, _method: &str, _params: Value) -> Result<Value, ProviderError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            Ok(serde_json::to_value(U64::from(7))?)
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_requests_to_the_fastest_endpoints() {
+        let endpoints = [100, 1, 5].map(|ms| {
+            WeightedProvider::new(CountingEndpoint {
+                delay: Duration::from_millis(ms),
+                requests: AtomicUsize::new(0),
+            })
+        });
+        let mut quorum = QuorumProvider::builder()
+            .add_providers(endpoints)
+            .stage_timeout(Duration::from_secs(1))
+            .build();
+        quorum.rank_endpoints().await;
+
+        let provider = Provider::quorum(quorum);
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(7));
+
+        // only the two fastest endpoints are needed for the majority
+        let requests = provider
+            .as_ref()
+            .providers()
+            .iter()
+            .map(|p| (p.inner.delay.as_millis(), p.inner.requests.load(Ordering::SeqCst)))
+            .collect::<Vec<_>>();
+        assert_eq!(requests, vec![(1, 2), (5, 2), (100, 1)]);
+    }
+
+    #[tokio::test]
+    async fn asks_more_endpoints_if_the_first_disagree() {
+        let mocks = [MockProvider::new(), MockProvider::new(), MockProvider::new()];
+        mocks[0].push(U64::from(1)).unwrap();
+        mocks[1].push(U64::from(2)).unwrap();
+        mocks[2].push(U64::from(2)).unwrap();
+        let provider = Provider::quorum(
+            QuorumProvider::builder()
+                .add_providers(mocks.clone().map(WeightedProvider::new))
+                .stage_timeout(Duration::from_secs(1))
+                .build(),
+        );
+
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(2));
+        for mock in &mocks {
+            mock.assert_request("eth_blockNumber", ()).unwrap();
+        }
+
+        // the third endpoint is not asked if the first two agree
+        mocks[0].push(U64::from(3)).unwrap();
+        mocks[1].push(U64::from(3)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(3));
+        mocks[0].assert_request("eth_blockNumber", ()).unwrap();
+        mocks[1].assert_request("eth_blockNumber", ()).unwrap();
+        assert!(mocks[2].assert_request("eth_blockNumber", ()).is_err());
+    }
+
+    /// An endpoint that never responds
+    #[derive(Debug)]
+    struct HangingEndpoint;
+
+    #[async_trait]
+    impl JsonRpcClientWrapper for HangingEndpoint {
+        async fn request(&self, _method: &str, _params: Value) -> Result<Value, ProviderError> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn reaches_quorum_without_hanging_endpoints() {
+        let quorum = |stage_timeout: Option<Duration>| {
+            let mut builder = QuorumProvider::dyn_rpc()
+                .add_provider(WeightedProvider::new(Box::new(HangingEndpoint)))
+                .add_provider(WeightedProvider::new(Box::new(DelayedEndpoint {
+                    id: 7,
+                    delay: Some(Duration::from_millis(1)),
+                })))
+                .add_provider(WeightedProvider::new(Box::new(DelayedEndpoint {
+                    id: 7,
+                    delay: Some(Duration::from_millis(1)),
+                })));
+            if let Some(timeout) = stage_timeout {
+                builder = builder.stage_timeout(timeout);
+            }
+            Provider::quorum(builder.build())
+        };
+
+        // sent to all endpoints at once, and in stages once the first stage timed out
+        for stage_timeout in [None, Some(Duration::from_millis(10))] {
+            let provider = quorum(stage_timeout);
+            let block = tokio::time::timeout(Duration::from_secs(5), provider.get_block_number())
+                .await
+                .expect("quorum request hangs")
+                .unwrap();
+            assert_eq!(block, U64::from(7));
+        }
+    }
 }
@@ -0,0 +1,155 @@
+//! A [JsonRpcClient] implementation that paces requests with a token bucket
+
+use crate::JsonRpcClient;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A client that wraps another [JsonRpcClient] and limits the number of requests per second.
+///
+/// Requests are paced with a token bucket which holds up to one second worth of requests. When the
+/// bucket is empty, requests are queued until enough time has passed instead of failing, so bursts
+/// are smoothed out to the configured rate.
+///
+/// The rate can be adjusted at runtime, e.g. to back off after the endpoint responded with
+/// `429 Too Many Requests`.
+///
+/// ```no_run
+/// use ethers_providers::{Http, Provider, RateLimitClient};
+/// use std::convert::TryFrom;
+///
+/// let http = Http::try_from("http://localhost:8545").unwrap();
+/// let provider = Provider::new(RateLimitClient::new(http, 10));
+///
+/// // back off
+/// provider.as_ref().set_requests_per_second(5);
+/// ```
+#[derive(Debug)]
+pub struct RateLimitClient<T> {
+    inner: T,
+    bucket: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens added per second, which is also the capacity of the bucket
+    rate: f64,
+    /// Available tokens, negative if requests are queued
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: u32) -> Self {
+        let rate = requests_per_second.max(1) as f64;
+        Self { rate, tokens: rate, last_refill: Instant::now() }
+    }
+
+    /// Takes a token and returns how long to wait until it is available
+    fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+impl<T> RateLimitClient<T> {
+    /// Wraps the client, allowing `requests_per_second` requests per second (at least one)
+    pub fn new(inner: T, requests_per_second: u32) -> Self {
+        Self { inner, bucket: Mutex::new(TokenBucket::new(requests_per_second)) }
+    }
+
+    /// Returns the wrapped client
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the number of requests allowed per second
+    pub fn requests_per_second(&self) -> u32 {
+        self.bucket.lock().unwrap().rate as u32
+    }
+
+    /// Changes the number of requests allowed per second (at least one).
+    ///
+    /// Requests which are already queued keep their slot.
+    pub fn set_requests_per_second(&self, requests_per_second: u32) {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.rate = requests_per_second.max(1) as f64;
+        bucket.tokens = bucket.tokens.min(bucket.rate);
+    }
+}
+
+#[async_trait]
+impl<T> JsonRpcClient for RateLimitClient<T>
+where
+    T: JsonRpcClient,
+{
+    type Error = T::Error;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let wait = self.bucket.lock().unwrap().reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        self.inner.request(method, params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockProvider, Provider};
+    use ethers_core::types::U64;
+
+    #[test]
+    fn reserves_tokens() {
+        let mut bucket = TokenBucket::new(2);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+
+        // queued requests wait for their token
+        let wait = bucket.reserve();
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+        let wait = bucket.reserve();
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn paces_requests() {
+        let mock = MockProvider::new();
+        let provider = Provider::new(RateLimitClient::new(mock.clone(), 10));
+        assert_eq!(provider.as_ref().requests_per_second(), 10);
+
+        for _ in 0..15 {
+            mock.push(U64::one()).unwrap();
+        }
+        let start = Instant::now();
+        for _ in 0..15 {
+            let _: U64 = provider.request("eth_blockNumber", ()).await.unwrap();
+        }
+        // the first 10 requests are served from the full bucket, the rest at 10 per second
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(400), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(2), "{:?}", elapsed);
+
+        provider.as_ref().set_requests_per_second(0);
+        assert_eq!(provider.as_ref().requests_per_second(), 1);
+    }
+}
@@ -117,7 +117,14 @@ where
     <T as JsonRpcClient>::Error: Sync + Send + 'static,
 {
     fn from(src: RetryClientError<T>) -> Self {
-        ProviderError::JsonRpcClientError(Box::new(src))
+        match src {
+            // keep the error of the inner client, so e.g. timeouts and JSON-RPC errors stay typed
+            RetryClientError::ProviderError(err) => err.into(),
+            RetryClientError::SerdeJson(err) => err.into(),
+            src @ RetryClientError::TimeoutError => {
+                ProviderError::JsonRpcClientError(Box::new(src))
+            }
+        }
     }
 }
 
@@ -267,6 +274,24 @@ mod tests {
     const AVG_COST: u64 = 17u64;
     const COMPUTE_UNITS: u64 = 330u64;
 
+    struct NeverRetry;
+
+    impl RetryPolicy<crate::MockError> for NeverRetry {
+        fn should_retry(&self, _error: &crate::MockError) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_errors_of_the_inner_client() {
+        let mock = crate::MockProvider::new();
+        mock.push_error(-32000, "header not found");
+        let provider = crate::Provider::new(RetryClient::new(mock, Box::new(NeverRetry), 3, 1));
+
+        let err = crate::Middleware::get_block_number(&provider).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().code, -32000);
+    }
+
     fn compute_offset(current_queued_requests: u64, ahead_in_queue: u64) -> u64 {
         compute_unit_offset_in_secs(
             AVG_COST,
@@ -91,7 +91,11 @@ where
     <Write as JsonRpcClient>::Error: Sync + Send + 'static,
 {
     fn from(src: RwClientError<Read, Write>) -> Self {
-        ProviderError::JsonRpcClientError(Box::new(src))
+        // keep the error of the inner client, so e.g. timeouts and JSON-RPC errors stay typed
+        match src {
+            RwClientError::Read(err) => err.into(),
+            RwClientError::Write(err) => err.into(),
+        }
     }
 }
 
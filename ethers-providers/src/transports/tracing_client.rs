@@ -0,0 +1,113 @@
+//! A [JsonRpcClient] implementation that emits a [`tracing`] span for every request
+
+use crate::{provider::json_rpc_error, JsonRpcClient};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt, time::Instant};
+use tracing::field::Empty;
+use tracing_futures::Instrument;
+
+/// A client that wraps another [JsonRpcClient] and emits a `jsonrpc` span at the `DEBUG` level
+/// for every request.
+///
+/// The span carries the `method`, the size of the serialized `params` in bytes, the `latency_ms`
+/// of the request and, if the request failed, the JSON-RPC `error_code`. If the span is disabled
+/// by the subscriber, requests are passed through without any overhead.
+///
+/// ```no_run
+/// use ethers_providers::{Http, Provider, TracingClient};
+/// use std::convert::TryFrom;
+///
+/// let http = Http::try_from("http://localhost:8545").unwrap();
+/// let provider = Provider::new(TracingClient::new(http));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TracingClient<T> {
+    inner: T,
+}
+
+impl<T> TracingClient<T> {
+    /// Wraps the client
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped client
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T> JsonRpcClient for TracingClient<T>
+where
+    T: JsonRpcClient,
+    T::Error: 'static,
+{
+    type Error = T::Error;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let span = tracing::debug_span!(
+            "jsonrpc",
+            method,
+            params_size = Empty,
+            latency_ms = Empty,
+            error_code = Empty
+        );
+        if span.is_disabled() {
+            return self.inner.request(method, params).await
+        }
+
+        let params_size =
+            serde_json::to_vec(&params).map(|params| params.len()).unwrap_or_default();
+        span.record("params_size", &params_size);
+
+        let start = Instant::now();
+        let res = self.inner.request(method, params).instrument(span.clone()).await;
+        span.record("latency_ms", &(start.elapsed().as_millis() as u64));
+
+        if let Err(err) = &res {
+            if let Some(response) = json_rpc_error(err) {
+                span.record("error_code", &response.code);
+            }
+            tracing::debug!(parent: &span, error = %err, "request failed");
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockError, MockProvider, Provider};
+    use ethers_core::types::U64;
+
+    #[tokio::test]
+    async fn passes_requests_through() {
+        let mock = MockProvider::new();
+        let provider = Provider::new(TracingClient::new(mock.clone()));
+
+        mock.push(U64::from(12u64)).unwrap();
+        let block = provider.request::<_, U64>("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block.as_u64(), 12);
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+
+        // no response to return
+        assert!(provider.request::<_, U64>("eth_blockNumber", ()).await.is_err());
+    }
+
+    #[test]
+    fn extracts_error_code() {
+        let response = JsonRpcError { code: -32000, message: "nonce too low".into(), data: None };
+        let err = MockError::JsonRpcError(response);
+        assert_eq!(json_rpc_error(&err).map(|err| err.code), Some(-32000));
+
+        let err = MockError::EmptyResponses;
+        assert!(json_rpc_error(&err).is_none());
+    }
+}
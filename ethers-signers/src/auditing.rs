@@ -0,0 +1,245 @@
+use crate::Signer;
+
+use async_trait::async_trait;
+use ethers_core::{
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Bytes, Signature, H256,
+    },
+    utils::hash_message,
+};
+use std::{fmt, sync::Arc, time::SystemTime};
+
+/// The payload of a signing operation recorded by an [`AuditingSigner`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignedPayload {
+    /// A message signed via [`Signer::sign_message`]
+    Message(Bytes),
+    /// A transaction signed via [`Signer::sign_transaction`]
+    Transaction(TypedTransaction),
+    /// EIP-712 typed data signed via [`Signer::sign_typed_data`]
+    TypedData,
+}
+
+/// Metadata about a single signing operation.
+///
+/// Only public information is recorded: the payload, the signer's address, the resulting hash
+/// and signature. Key material never leaves the wrapped signer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningRecord {
+    /// What was signed
+    pub payload: SignedPayload,
+    /// The address of the signer
+    pub signer: Address,
+    /// The chain id the signer was configured with
+    pub chain_id: u64,
+    /// The resulting hash: the `Ethereum Signed Message` hash for messages, the hash of the signed
+    /// transaction for transactions and the EIP-712 digest for typed data
+    pub hash: H256,
+    /// The produced signature
+    pub signature: Signature,
+    /// When the signature was produced
+    pub timestamp: SystemTime,
+}
+
+type AuditCallback = Arc<dyn Fn(&SigningRecord) + Send + Sync>;
+
+/// A [`Signer`] which reports every successful signing operation of the wrapped signer to a
+/// callback, e.g. to keep an audit trail.
+///
+/// The callback is invoked with a [`SigningRecord`] before the signature is returned to the
+/// caller. Failed signing attempts are not reported.
+///
+/// ```
+/// use ethers_core::rand::thread_rng;
+/// use ethers_signers::{AuditingSigner, LocalWallet, Signer};
+/// use std::sync::{
+///     atomic::{AtomicUsize, Ordering},
+///     Arc,
+/// };
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let count = Arc::new(AtomicUsize::new(0));
+/// let counter = count.clone();
+/// let signer = AuditingSigner::new(LocalWallet::new(&mut thread_rng()), move |record| {
+///     println!("{:?} signed {:?}", record.signer, record.hash);
+///     counter.fetch_add(1, Ordering::SeqCst);
+/// });
+///
+/// signer.sign_message("hello").await?;
+/// assert_eq!(count.load(Ordering::SeqCst), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct AuditingSigner<S> {
+    signer: S,
+    callback: AuditCallback,
+}
+
+impl<S: Signer> AuditingSigner<S> {
+    /// Wraps `signer`, invoking `callback` for every signature it produces
+    pub fn new<F>(signer: S, callback: F) -> Self
+    where
+        F: Fn(&SigningRecord) + Send + Sync + 'static,
+    {
+        Self { signer, callback: Arc::new(callback) }
+    }
+
+    /// Returns a reference to the wrapped signer
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+
+    /// Consumes the auditing signer and returns the wrapped signer
+    pub fn into_inner(self) -> S {
+        self.signer
+    }
+
+    fn record(&self, payload: SignedPayload, hash: H256, signature: Signature) {
+        let record = SigningRecord {
+            payload,
+            signer: self.signer.address(),
+            chain_id: self.signer.chain_id(),
+            hash,
+            signature,
+            timestamp: SystemTime::now(),
+        };
+        (self.callback)(&record);
+    }
+}
+
+impl<S: Clone> Clone for AuditingSigner<S> {
+    fn clone(&self) -> Self {
+        Self { signer: self.signer.clone(), callback: self.callback.clone() }
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for AuditingSigner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditingSigner").field("signer", &self.signer).finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: Signer> Signer for AuditingSigner<S> {
+    type Error = S::Error;
+
+    async fn sign_message<M: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: M,
+    ) -> Result<Signature, Self::Error> {
+        let message = message.as_ref();
+        let signature = self.signer.sign_message(message).await?;
+        self.record(
+            SignedPayload::Message(message.to_vec().into()),
+            hash_message(message),
+            signature,
+        );
+        Ok(signature)
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let signature = self.signer.sign_transaction(tx).await?;
+        self.record(SignedPayload::Transaction(tx.clone()), tx.hash(&signature), signature);
+        Ok(signature)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        let digest = match payload.encode_eip712() {
+            Ok(digest) => digest,
+            // the wrapped signer fails to encode the payload as well, so there is nothing to record
+            Err(_) => return self.signer.sign_typed_data(payload).await,
+        };
+        let signature = self.signer.sign_typed_data(payload).await?;
+        self.record(SignedPayload::TypedData, digest.into(), signature);
+        Ok(signature)
+    }
+
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.signer.chain_id()
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        Self { signer: self.signer.with_chain_id(chain_id), callback: self.callback }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::LocalWallet;
+    use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn records_signed_transaction_hashes() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let wallet: LocalWallet =
+            "4646464646464646464646464646464646464646464646464646464646464646".parse().unwrap();
+        let signer = AuditingSigner::new(wallet.with_chain_id(1u64), move |record| {
+            sink.lock().unwrap().push(record.clone())
+        });
+
+        let to: Address = "0x3535353535353535353535353535353535353535".parse().unwrap();
+        let txs: Vec<TypedTransaction> = vec![
+            TransactionRequest::pay(to, 1).nonce(0).gas(21000).gas_price(1).chain_id(1).into(),
+            Eip1559TransactionRequest::new()
+                .to(to)
+                .value(2)
+                .nonce(1)
+                .gas(21000)
+                .max_fee_per_gas(2)
+                .max_priority_fee_per_gas(1)
+                .chain_id(1)
+                .into(),
+        ];
+
+        let mut expected = Vec::new();
+        for tx in &txs {
+            let signature = signer.sign_transaction(tx).await.unwrap();
+            expected.push(tx.hash(&signature));
+        }
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), txs.len());
+        for ((record, tx), hash) in records.iter().zip(&txs).zip(expected) {
+            assert_eq!(record.payload, SignedPayload::Transaction(tx.clone()));
+            assert_eq!(record.hash, hash);
+            assert_eq!(
+                record.hash,
+                H256::from(ethers_core::utils::keccak256(tx.rlp_signed(&record.signature)))
+            );
+            assert_eq!(record.signer, signer.address());
+            assert_eq!(record.chain_id, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn records_signed_messages() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let signer = AuditingSigner::new(
+            LocalWallet::new(&mut ethers_core::rand::thread_rng()),
+            move |record| sink.lock().unwrap().push(record.clone()),
+        );
+
+        let signature = signer.sign_message("audit me").await.unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].payload, SignedPayload::Message(b"audit me".to_vec().into()));
+        assert_eq!(records[0].hash, hash_message("audit me"));
+        assert_eq!(records[0].signature, signature);
+        assert_eq!(signature.recover(records[0].hash).unwrap(), signer.address());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{to_eip155_v, Signer};
+
+use async_trait::async_trait;
+use ethers_core::{
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature, H256,
+    },
+    utils::hash_message,
+};
+use std::{error::Error, fmt, future::Future, pin::Pin, sync::Arc};
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+type SignHashFuture = Pin<Box<dyn Future<Output = Result<Signature, BoxError>> + Send>>;
+
+type SignHashCallback = Arc<dyn Fn(H256) -> SignHashFuture + Send + Sync>;
+
+/// Errors produced by the [`CallbackSigner`]
+#[derive(thiserror::Error, Debug)]
+pub enum CallbackSignerError {
+    /// Thrown when one of the callbacks fails
+    #[error("callback failed: {0}")]
+    Callback(BoxError),
+    /// Error type from Eip712Error message
+    #[error("error encoding eip712 struct: {0:?}")]
+    Eip712Error(String),
+}
+
+/// A [`Signer`] which defers signing to an async callback, e.g. to bridge to a remote signing
+/// service, an MPC cluster or a custodian without implementing [`Signer`] by hand.
+///
+/// The callback receives the 32 byte digest to sign and returns its signature. The digest is
+///
+/// - for messages: the [EIP-191](https://eips.ethereum.org/EIPS/eip-191) hash
+///   `keccak256("\x19Ethereum Signed Message:\n" ++ len(message) ++ message)`, see [`hash_message`]
+/// - for legacy transactions: the [EIP-155](https://eips.ethereum.org/EIPS/eip-155) sighash
+///   `keccak256(rlp([nonce, gasPrice, gas, to, value, data, chainId, 0, 0]))`
+/// - for EIP-2930 transactions: `keccak256(0x01 ++ rlp([chainId, nonce, gasPrice, gas, to, value,
+///   data, accessList]))`
+/// - for EIP-1559 transactions: `keccak256(0x02 ++ rlp([chainId, nonce, maxPriorityFeePerGas,
+///   maxFeePerGas, gas, to, value, data, accessList]))`
+/// - for typed data: the [EIP-712](https://eips.ethereum.org/EIPS/eip-712) digest `keccak256(0x19
+///   ++ 0x01 ++ domainSeparator ++ hashStruct(message))`
+///
+/// The transaction digests are [`TypedTransaction::sighash`], computed with the signer's chain id
+/// if the transaction has none. The digest must be signed as is, without hashing or prefixing it
+/// again. The `v` of the returned signature may either be the recovery id (0 or 1) or the
+/// recovery id + 27, the signer converts it to the `v` expected for the signed payload.
+///
+/// ```
+/// use ethers_core::{rand::thread_rng, types::H256};
+/// use ethers_signers::{CallbackSigner, LocalWallet, Signer};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// // stands in for a remote signing service
+/// let wallet = LocalWallet::new(&mut thread_rng());
+/// let address = wallet.address();
+///
+/// let signer = CallbackSigner::new(address, 1, move |hash: H256| {
+///     let wallet = wallet.clone();
+///     async move { Ok::<_, std::io::Error>(wallet.sign_hash(hash)) }
+/// });
+///
+/// let signature = signer.sign_message("hello").await?;
+/// signature.verify("hello", address)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CallbackSigner {
+    address: Address,
+    chain_id: u64,
+    sign_hash: SignHashCallback,
+}
+
+impl CallbackSigner {
+    /// Creates a signer for `address` on `chain_id`, which signs digests with `sign_hash`
+    pub fn new<F, Fut, E>(address: Address, chain_id: u64, sign_hash: F) -> Self
+    where
+        F: Fn(H256) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Signature, E>> + Send + 'static,
+        E: Into<BoxError>,
+    {
+        let sign_hash = move |hash: H256| {
+            let fut = sign_hash(hash);
+            Box::pin(async move { fut.await.map_err(Into::into) }) as SignHashFuture
+        };
+        Self { address, chain_id, sign_hash: Arc::new(sign_hash) }
+    }
+
+    /// Creates a signer which fetches its address and chain id once from the `address` and
+    /// `chain_id` callbacks, and signs digests with `sign_hash`
+    pub async fn connect<F, Fut, E, A, AFut, AE, C, CFut, CE>(
+        address: A,
+        chain_id: C,
+        sign_hash: F,
+    ) -> Result<Self, CallbackSignerError>
+    where
+        F: Fn(H256) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Signature, E>> + Send + 'static,
+        E: Into<BoxError>,
+        A: FnOnce() -> AFut,
+        AFut: Future<Output = Result<Address, AE>>,
+        AE: Into<BoxError>,
+        C: FnOnce() -> CFut,
+        CFut: Future<Output = Result<u64, CE>>,
+        CE: Into<BoxError>,
+    {
+        let address = address().await.map_err(|e| CallbackSignerError::Callback(e.into()))?;
+        let chain_id = chain_id().await.map_err(|e| CallbackSignerError::Callback(e.into()))?;
+        Ok(Self::new(address, chain_id, sign_hash))
+    }
+
+    /// Signs `hash` with the callback and returns the signature's recovery id
+    async fn request_signature(&self, hash: H256) -> Result<(Signature, u8), CallbackSignerError> {
+        let signature = (self.sign_hash)(hash).await.map_err(CallbackSignerError::Callback)?;
+        let recovery_id = if signature.v >= 27 { signature.v - 27 } else { signature.v };
+        Ok((signature, recovery_id as u8))
+    }
+
+    /// Signs `hash` with the callback, setting `v` to the recovery id + 27
+    async fn sign_digest(&self, hash: H256) -> Result<Signature, CallbackSignerError> {
+        let (signature, recovery_id) = self.request_signature(hash).await?;
+        Ok(Signature { v: recovery_id as u64 + 27, ..signature })
+    }
+}
+
+impl fmt::Debug for CallbackSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackSigner")
+            .field("address", &self.address)
+            .field("chain_id", &self.chain_id)
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for CallbackSigner {
+    type Error = CallbackSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_digest(hash_message(message)).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        // rlp (for sighash) must have the same chain id as v in the signature
+        let chain_id = tx.chain_id().map(|id| id.as_u64()).unwrap_or(self.chain_id);
+        let mut tx = tx.clone();
+        tx.set_chain_id(chain_id);
+
+        let (signature, recovery_id) = self.request_signature(tx.sighash()).await?;
+        Ok(Signature { v: to_eip155_v(recovery_id, chain_id), ..signature })
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        let encoded =
+            payload.encode_eip712().map_err(|e| Self::Error::Eip712Error(e.to_string()))?;
+        self.sign_digest(H256::from(encoded)).await
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::LocalWallet;
+    use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+
+    fn remote(wallet: LocalWallet) -> CallbackSigner {
+        let address = wallet.address();
+        CallbackSigner::new(address, wallet.chain_id(), move |hash| {
+            let wallet = wallet.clone();
+            async move {
+                // remote services commonly return the bare recovery id
+                let signature = wallet.sign_hash(hash);
+                Ok::<_, std::io::Error>(Signature { v: signature.v - 27, ..signature })
+            }
+        })
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn signs_like_the_wrapped_wallet() {
+        let wallet: LocalWallet =
+            "4646464646464646464646464646464646464646464646464646464646464646".parse().unwrap();
+        let wallet = wallet.with_chain_id(5u64);
+        let signer = remote(wallet.clone());
+
+        assert_eq!(
+            signer.sign_message("hello").await.unwrap(),
+            wallet.sign_message("hello").await.unwrap()
+        );
+
+        let to: Address = "0x3535353535353535353535353535353535353535".parse().unwrap();
+        let txs: Vec<TypedTransaction> = vec![
+            TransactionRequest::pay(to, 1).nonce(0).gas(21000).gas_price(1).into(),
+            TransactionRequest::pay(to, 1).nonce(0).gas(21000).gas_price(1).chain_id(1).into(),
+            Eip1559TransactionRequest::new()
+                .to(to)
+                .value(2)
+                .nonce(1)
+                .gas(21000)
+                .max_fee_per_gas(2)
+                .max_priority_fee_per_gas(1)
+                .into(),
+        ];
+        for tx in &txs {
+            assert_eq!(
+                signer.sign_transaction(tx).await.unwrap(),
+                wallet.sign_transaction(tx).await.unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn connects_and_propagates_callback_errors() {
+        let wallet: LocalWallet =
+            "4646464646464646464646464646464646464646464646464646464646464646".parse().unwrap();
+        let address = wallet.address();
+
+        let signer = CallbackSigner::connect(
+            || async move { Ok::<_, std::io::Error>(address) },
+            || async { Ok::<_, std::io::Error>(137) },
+            |_| async {
+                Err::<Signature, _>(std::io::Error::new(std::io::ErrorKind::Other, "offline"))
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(signer.address(), address);
+        assert_eq!(signer.chain_id(), 137);
+
+        let err = signer.sign_message("hello").await.unwrap_err();
+        assert!(matches!(err, CallbackSignerError::Callback(_)));
+        assert_eq!(err.to_string(), "callback failed: offline");
+    }
+}
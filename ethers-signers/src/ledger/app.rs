@@ -16,14 +16,37 @@ use ethers_core::{
 use std::convert::TryFrom;
 use thiserror::Error;
 
+#[cfg(feature = "ledger-emulator")]
+use super::emulator::LedgerEmulator;
 use super::types::*;
 
+/// The device, or an emulator of it, that APDUs are exchanged with
+#[derive(Debug)]
+enum Transport {
+    Device(Ledger),
+    #[cfg(feature = "ledger-emulator")]
+    Emulator(LedgerEmulator),
+}
+
+impl Transport {
+    async fn exchange(
+        &self,
+        command: &APDUCommand,
+    ) -> Result<APDUAnswer, coins_ledger::errors::LedgerError> {
+        match self {
+            Transport::Device(ledger) => ledger.exchange(command).await,
+            #[cfg(feature = "ledger-emulator")]
+            Transport::Emulator(emulator) => emulator.exchange(command),
+        }
+    }
+}
+
 /// A Ledger Ethereum App.
 ///
 /// This is a simple wrapper around the [Ledger transport](Ledger)
 #[derive(Debug)]
 pub struct LedgerEthereum {
-    transport: Mutex<Ledger>,
+    transport: Mutex<Transport>,
     derivation: DerivationType,
     pub(crate) chain_id: u64,
     pub(crate) address: Address,
@@ -44,7 +67,27 @@ impl LedgerEthereum {
     /// # }
     /// ```
     pub async fn new(derivation: DerivationType, chain_id: u64) -> Result<Self, LedgerError> {
-        let transport = Ledger::init().await?;
+        Self::with_transport(Transport::Device(Ledger::init().await?), derivation, chain_id).await
+    }
+
+    /// Instantiate the application against an [emulator](LedgerEmulator) of the device, which
+    /// signs with a publicly known test key.
+    ///
+    /// **Only use this in tests.**
+    #[cfg(feature = "ledger-emulator")]
+    pub async fn emulated(
+        emulator: LedgerEmulator,
+        derivation: DerivationType,
+        chain_id: u64,
+    ) -> Result<Self, LedgerError> {
+        Self::with_transport(Transport::Emulator(emulator), derivation, chain_id).await
+    }
+
+    async fn with_transport(
+        transport: Transport,
+        derivation: DerivationType,
+        chain_id: u64,
+    ) -> Result<Self, LedgerError> {
         let address = Self::get_address_with_path_transport(&transport, &derivation).await?;
 
         Ok(Self { transport: Mutex::new(transport), derivation, chain_id, address })
@@ -68,8 +111,49 @@ impl LedgerEthereum {
         Self::get_address_with_path_transport(&transport, derivation).await
     }
 
+    /// Gets the accounts which correspond to the provided derivation paths, without requiring
+    /// a confirmation on the device.
+    ///
+    /// ```
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ethers_signers::{Ledger, HDPath};
+    ///
+    /// let ledger = Ledger::new(HDPath::LedgerLive(0), 1).await?;
+    /// let addresses =
+    ///     ledger.get_addresses((0..5).map(HDPath::LedgerLive)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_addresses(
+        &self,
+        paths: impl IntoIterator<Item = DerivationType>,
+    ) -> Result<Vec<Address>, LedgerError> {
+        let transport = self.transport.lock().await;
+        let mut addresses = Vec::new();
+        for derivation in paths {
+            addresses.push(Self::get_address_with_path_transport(&transport, &derivation).await?);
+        }
+        Ok(addresses)
+    }
+
+    /// Gets the first `count` accounts below the provided base path, i.e. `{base_path}/0` up to
+    /// `{base_path}/{count - 1}`.
+    ///
+    /// With a base path of `m/44'/60'/0'/0` these are the addresses of the `Legacy` derivation.
+    pub async fn get_addresses_range(
+        &self,
+        base_path: &str,
+        count: usize,
+    ) -> Result<Vec<Address>, LedgerError> {
+        let base_path = base_path.trim_end_matches('/');
+        self.get_addresses(
+            (0..count).map(|index| DerivationType::Other(format!("{}/{}", base_path, index))),
+        )
+        .await
+    }
+
     async fn get_address_with_path_transport(
-        transport: &Ledger,
+        transport: &Transport,
         derivation: &DerivationType,
     ) -> Result<Address, LedgerError> {
         let data = APDUData::new(&Self::path_to_bytes(derivation));
@@ -83,7 +167,7 @@ impl LedgerEthereum {
         };
 
         let answer = block_on(transport.exchange(&command))?;
-        let result = answer.data().ok_or(LedgerError::UnexpectedNullResponse)?;
+        let result = answer_data(&answer)?;
 
         let address = {
             // extract the address from the response
@@ -99,6 +183,26 @@ impl LedgerEthereum {
 
     /// Returns the semver of the Ethereum ledger app
     pub async fn version(&self) -> Result<String, LedgerError> {
+        Ok(self.get_app_configuration().await?.version)
+    }
+
+    /// Returns the configuration of the Ethereum ledger app.
+    ///
+    /// Fails with [`LedgerError::WrongApp`] if the Ethereum app is not open on the device.
+    ///
+    /// ```
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ethers_signers::{Ledger, HDPath};
+    ///
+    /// let ledger = Ledger::new(HDPath::LedgerLive(0), 1).await?;
+    /// let config = ledger.get_app_configuration().await?;
+    /// if !config.arbitrary_data_enabled {
+    ///     println!("enable blind signing to interact with contracts");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_app_configuration(&self) -> Result<AppConfiguration, LedgerError> {
         let transport = self.transport.lock().await;
 
         let command = APDUCommand {
@@ -110,9 +214,7 @@ impl LedgerEthereum {
         };
 
         let answer = block_on(transport.exchange(&command))?;
-        let result = answer.data().ok_or(LedgerError::UnexpectedNullResponse)?;
-
-        Ok(format!("{}.{}.{}", result[1], result[2], result[3]))
+        AppConfiguration::from_response(app_configuration_data(&answer)?)
     }
 
     /// Signs an Ethereum transaction (requires confirmation on the ledger)
@@ -125,7 +227,20 @@ impl LedgerEthereum {
         let mut payload = Self::path_to_bytes(&self.derivation);
         payload.extend_from_slice(tx_with_chain.rlp().as_ref());
 
-        let mut signature = self.sign_payload(INS::SIGN, payload).await?;
+        let mut signature = match self.sign_payload(INS::SIGN, payload).await {
+            // older versions of the app reject the type byte of typed transactions as invalid data
+            Err(LedgerError::ResponseStatus(status))
+                if status == SW_INVALID_DATA && !matches!(tx, TypedTransaction::Legacy(_)) =>
+            {
+                let tx_type = match tx {
+                    TypedTransaction::Eip2930(_) => 1,
+                    TypedTransaction::Eip7702(_) => 4,
+                    _ => 2,
+                };
+                return Err(LedgerError::UnsupportedTransactionType(tx_type))
+            }
+            res => res?,
+        };
 
         // modify `v` value of signature to match EIP-155 for chains with large chain ID
         // The logic is derived from Ledger's library
@@ -140,7 +255,9 @@ impl LedgerEthereum {
             };
 
             signature.v = match tx {
-                TypedTransaction::Eip2930(_) | TypedTransaction::Eip1559(_) => {
+                TypedTransaction::Eip2930(_) |
+                TypedTransaction::Eip1559(_) |
+                TypedTransaction::Eip7702(_) => {
                     if ecc_parity % 2 == 1 {
                         0
                     } else {
@@ -217,7 +334,7 @@ impl LedgerEthereum {
             command.data = APDUData::new(&data);
 
             let answer = block_on(transport.exchange(&command))?;
-            result = answer.data().ok_or(LedgerError::UnexpectedNullResponse)?.to_vec();
+            result = answer_data(&answer)?.to_vec();
 
             // We need more data
             command.p1 = P1::MORE as u8;
@@ -250,6 +367,25 @@ impl LedgerEthereum {
     }
 }
 
+/// Returns the data of a successful answer, or an error with the status word of a failed one
+fn answer_data(answer: &APDUAnswer) -> Result<&[u8], LedgerError> {
+    match answer.retcode() {
+        SW_OK => answer.data().ok_or(LedgerError::UnexpectedNullResponse),
+        status => Err(LedgerError::ResponseStatus(status)),
+    }
+}
+
+/// Returns the data of an app configuration answer, or [`LedgerError::WrongApp`] if the status
+/// word shows that the Ethereum app is not open
+fn app_configuration_data(answer: &APDUAnswer) -> Result<&[u8], LedgerError> {
+    match answer_data(answer) {
+        Err(LedgerError::ResponseStatus(status)) if SW_WRONG_APP.contains(&status) => {
+            Err(LedgerError::WrongApp(status))
+        }
+        res => res,
+    }
+}
+
 #[cfg(all(test, feature = "ledger"))]
 mod tests {
     use super::*;
@@ -278,6 +414,27 @@ mod tests {
         out: Address,
     }
 
+    #[test]
+    fn maps_wrong_app_status_words() {
+        for status in SW_WRONG_APP {
+            let answer = APDUAnswer::from_answer(status.to_be_bytes().to_vec()).unwrap();
+            assert!(
+                matches!(app_configuration_data(&answer), Err(LedgerError::WrongApp(s)) if s == status),
+                "{:#06x}",
+                status
+            );
+        }
+
+        let answer = APDUAnswer::from_answer(SW_INVALID_DATA.to_be_bytes().to_vec()).unwrap();
+        assert!(matches!(
+            app_configuration_data(&answer),
+            Err(LedgerError::ResponseStatus(SW_INVALID_DATA))
+        ));
+
+        let answer = APDUAnswer::from_answer(vec![1, 1, 9, 19, 0x90, 0x00]).unwrap();
+        assert_eq!(app_configuration_data(&answer).unwrap(), &[1, 1, 9, 19]);
+    }
+
     #[tokio::test]
     #[ignore]
     // Replace this with your ETH addresses.
@@ -0,0 +1,421 @@
+//! An in-process emulator of the Ethereum Ledger app.
+//!
+//! The emulator answers the same APDUs as the app running on a device and produces the same
+//! signatures a device would, including the one byte `v` of legacy transactions. This allows
+//! exercising the APDU construction, chunking and recovery id handling of
+//! [`LedgerEthereum`](super::app::LedgerEthereum) without a device attached.
+//!
+//! **The emulator signs with a fixed, publicly known test key. It must only ever be used in
+//! tests.**
+use coins_ledger::{
+    common::{APDUAnswer, APDUCommand},
+    errors::LedgerError as TransportError,
+};
+use ethers_core::{
+    types::{Address, Signature, H256},
+    utils::{hash_message, keccak256, rlp::Rlp},
+};
+use std::sync::Mutex;
+
+use super::types::*;
+use crate::{LocalWallet, Signer};
+
+/// The private key the emulator signs with, regardless of the derivation path.
+///
+/// This is the first account of the `test test test test test test test test test test test
+/// junk` mnemonic, which is also used by anvil and hardhat.
+pub const TEST_PRIVATE_KEY: &str =
+    "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d9bf4f2ff80";
+
+/// Status word returned by the app for unknown instructions
+const SW_INS_NOT_SUPPORTED: u16 = 0x6d00;
+/// The first version of the app which supports typed transactions
+const TYPED_TX_MIN_VERSION: [u8; 3] = [1, 9, 0];
+
+/// Emulates the Ethereum Ledger app, signing with [`TEST_PRIVATE_KEY`].
+///
+/// ```
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// use ethers_signers::{HDPath, Ledger, LedgerEmulator, Signer};
+///
+/// let emulator = LedgerEmulator::new();
+/// let address = emulator.address();
+/// let ledger = Ledger::emulated(emulator, HDPath::LedgerLive(0), 1).await?;
+/// assert_eq!(ledger.address(), address);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LedgerEmulator {
+    wallet: LocalWallet,
+    version: [u8; 3],
+    arbitrary_data_enabled: bool,
+    /// The instruction and data of a signing request which spans multiple APDUs
+    pending: Mutex<Option<(u8, Vec<u8>)>>,
+}
+
+impl Default for LedgerEmulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LedgerEmulator {
+    /// Creates an emulator of the latest Ethereum app
+    pub fn new() -> Self {
+        Self {
+            wallet: TEST_PRIVATE_KEY.parse().expect("valid test key"),
+            version: [1, 9, 19],
+            arbitrary_data_enabled: false,
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// Sets the version the emulated app reports
+    #[must_use]
+    pub fn version(mut self, major: u8, minor: u8, patch: u8) -> Self {
+        self.version = [major, minor, patch];
+        self
+    }
+
+    /// Enables blind signing in the settings of the emulated app
+    #[must_use]
+    pub fn arbitrary_data_enabled(mut self, enabled: bool) -> Self {
+        self.arbitrary_data_enabled = enabled;
+        self
+    }
+
+    /// Returns the address of the test key
+    pub fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    /// Answers the APDU like the Ethereum app would
+    pub fn exchange(&self, command: &APDUCommand) -> Result<APDUAnswer, TransportError> {
+        let data = command.data.data();
+        let response = match command.ins {
+            ins if ins == INS::GET_PUBLIC_KEY as u8 => self.public_key(data),
+            ins if ins == INS::GET_APP_CONFIGURATION as u8 => {
+                let [major, minor, patch] = self.version;
+                Ok(vec![self.arbitrary_data_enabled as u8, major, minor, patch])
+            }
+            ins if ins == INS::SIGN as u8 ||
+                ins == INS::SIGN_PERSONAL_MESSAGE as u8 ||
+                ins == INS::SIGN_ETH_EIP_712 as u8 =>
+            {
+                self.sign(command.ins, command.p1, data)
+            }
+            _ => Err(SW_INS_NOT_SUPPORTED),
+        };
+
+        let answer = match response {
+            Ok(mut response) => {
+                response.extend_from_slice(&SW_OK.to_be_bytes());
+                response
+            }
+            Err(status) => status.to_be_bytes().to_vec(),
+        };
+        APDUAnswer::from_answer(answer)
+    }
+
+    /// Returns the uncompressed public key followed by the hex encoded address
+    fn public_key(&self, data: &[u8]) -> Result<Vec<u8>, u16> {
+        split_path(data).ok_or(SW_INVALID_DATA)?;
+
+        let public_key = self.wallet.signer().verifying_key().to_encoded_point(false);
+        let public_key = public_key.as_bytes();
+        let address = hex::encode(self.address());
+
+        let mut response = vec![public_key.len() as u8];
+        response.extend_from_slice(public_key);
+        response.push(address.len() as u8);
+        response.extend_from_slice(address.as_bytes());
+        Ok(response)
+    }
+
+    /// Buffers the chunks of a signing request and signs it once it is complete.
+    ///
+    /// Intermediate chunks are answered with an empty response, like the app does.
+    fn sign(&self, ins: u8, p1: u8, data: &[u8]) -> Result<Vec<u8>, u16> {
+        let mut pending = self.pending.lock().unwrap();
+        let payload = if p1 == P1_FIRST {
+            &mut pending.insert((ins, data.to_vec())).1
+        } else if p1 == P1::MORE as u8 {
+            match pending.as_mut() {
+                Some((pending_ins, payload)) if *pending_ins == ins => {
+                    payload.extend_from_slice(data);
+                    payload
+                }
+                _ => return Err(SW_INVALID_DATA),
+            }
+        } else {
+            return Err(SW_INVALID_DATA)
+        };
+
+        let (_, body) = split_path(payload).ok_or(SW_INVALID_DATA)?;
+        if ins == INS::SIGN as u8 &&
+            body.first().map_or(false, |b| *b <= 0x7f) &&
+            self.version < TYPED_TX_MIN_VERSION
+        {
+            *pending = None;
+            return Err(SW_INVALID_DATA)
+        }
+        let signature = match ins {
+            ins if ins == INS::SIGN as u8 => self.sign_tx(body),
+            ins if ins == INS::SIGN_PERSONAL_MESSAGE as u8 => self.sign_message(body),
+            _ => self.sign_typed_struct(body),
+        };
+
+        match signature {
+            Some(signature) => {
+                *pending = None;
+                let mut response = vec![0u8; 65];
+                response[0] = signature.v as u8;
+                signature.r.to_big_endian(&mut response[1..33]);
+                signature.s.to_big_endian(&mut response[33..]);
+                Ok(response)
+            }
+            // wait for more data
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Signs the RLP encoded transaction, if it is complete.
+    ///
+    /// Like the app, `v` is the y parity for typed transactions and `chain_id * 2 + 35 + parity`
+    /// truncated to a single byte for legacy transactions.
+    fn sign_tx(&self, tx: &[u8]) -> Option<Signature> {
+        let typed = *tx.first()? <= 0x7f;
+        let rlp = Rlp::new(if typed { &tx[1..] } else { tx });
+        let info = rlp.payload_info().ok()?;
+        if info.header_len + info.value_len != rlp.as_raw().len() {
+            return None
+        }
+
+        let mut signature = self.wallet.sign_hash(H256(keccak256(tx)));
+        let parity = signature.v - 27;
+        signature.v = if typed {
+            parity
+        } else {
+            // unsigned EIP-155 transactions carry the chain id as their 7th element
+            let chain_id =
+                if rlp.item_count().ok()? == 9 { rlp.val_at::<u64>(6).ok()? } else { 0 };
+            if chain_id == 0 {
+                27 + parity
+            } else {
+                (chain_id * 2 + 35 + parity) % 256
+            }
+        };
+        Some(signature)
+    }
+
+    /// Signs the length prefixed message, if it is complete
+    fn sign_message(&self, body: &[u8]) -> Option<Signature> {
+        let mut len = [0u8; 4];
+        len.copy_from_slice(body.get(..4)?);
+        let message = &body[4..];
+        if message.len() != u32::from_be_bytes(len) as usize {
+            return None
+        }
+        Some(self.wallet.sign_hash(hash_message(message)))
+    }
+
+    /// Signs the domain separator and struct hash
+    fn sign_typed_struct(&self, body: &[u8]) -> Option<Signature> {
+        if body.len() != 64 {
+            return None
+        }
+        let mut digest = vec![0x19, 0x01];
+        digest.extend_from_slice(body);
+        Some(self.wallet.sign_hash(H256(keccak256(digest))))
+    }
+}
+
+/// Splits the serialized derivation path off the APDU data
+fn split_path(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let depth = *data.first()? as usize;
+    let len = 1 + depth * 4;
+    if data.len() < len {
+        return None
+    }
+    Some(data.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ledger;
+    use ethers_contract::EthAbiType;
+    use ethers_core::types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Eip1559TransactionRequest, TransactionRequest, U256,
+    };
+    use ethers_derive_eip712::*;
+
+    #[derive(Debug, Clone, Eip712, EthAbiType)]
+    #[eip712(
+        name = "Eip712Test",
+        version = "1",
+        chain_id = 1,
+        verifying_contract = "0x0000000000000000000000000000000000000001"
+    )]
+    struct Mail {
+        from: Address,
+        amount: U256,
+        contents: String,
+    }
+
+    fn test_address() -> Address {
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse().unwrap()
+    }
+
+    async fn ledger(chain_id: u64) -> Ledger {
+        Ledger::emulated(LedgerEmulator::new(), DerivationType::LedgerLive(0), chain_id)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_test_address() {
+        let ledger = ledger(1).await;
+        assert_eq!(ledger.address(), test_address());
+        assert_eq!(
+            ledger.get_address_with_path(&DerivationType::Legacy(3)).await.unwrap(),
+            test_address()
+        );
+        assert_eq!(ledger.version().await.unwrap(), "1.9.19");
+    }
+
+    #[tokio::test]
+    async fn reports_app_configuration() {
+        let config = ledger(1).await.get_app_configuration().await.unwrap();
+        assert_eq!(
+            config,
+            AppConfiguration {
+                version: "1.9.19".to_string(),
+                arbitrary_data_enabled: false,
+                erc20_provisioning_necessary: false,
+            }
+        );
+
+        let emulator = LedgerEmulator::new().version(1, 10, 0).arbitrary_data_enabled(true);
+        let ledger = Ledger::emulated(emulator, DerivationType::LedgerLive(0), 1).await.unwrap();
+        let config = ledger.get_app_configuration().await.unwrap();
+        assert_eq!(config.version, "1.10.0");
+        assert!(config.arbitrary_data_enabled);
+    }
+
+    #[tokio::test]
+    async fn scans_addresses() {
+        let ledger = ledger(1).await;
+        let addresses = ledger.get_addresses((0..2).map(DerivationType::LedgerLive)).await.unwrap();
+        assert_eq!(addresses, vec![test_address(); 2]);
+
+        let addresses = ledger.get_addresses_range("m/44'/60'/0'/0/", 5).await.unwrap();
+        assert_eq!(addresses, vec![test_address(); 5]);
+        assert!(ledger.get_addresses_range("m/44'/60'/0'/0", 0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn signs_message() {
+        let ledger = ledger(1).await;
+        let sig = ledger.sign_message("hello world").await.unwrap();
+        assert!(sig.v == 27 || sig.v == 28);
+        sig.verify("hello world", test_address()).unwrap();
+
+        // spans multiple APDUs
+        let message = vec![0xab; 600];
+        let sig = ledger.sign_message(&message).await.unwrap();
+        sig.verify(message, test_address()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn signs_typed_struct() {
+        let ledger = ledger(1).await;
+        let mail = Mail { from: test_address(), amount: 100u64.into(), contents: "hi".to_string() };
+        let sig = ledger.sign_typed_struct(&mail).await.unwrap();
+        sig.verify(mail.encode_eip712().unwrap(), test_address()).unwrap();
+
+        let ledger = Ledger::emulated(
+            LedgerEmulator::new().version(1, 5, 0),
+            DerivationType::LedgerLive(0),
+            1,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            ledger.sign_typed_struct(&mail).await.unwrap_err(),
+            LedgerError::UnsupportedAppVersion(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_typed_transactions_on_old_app() {
+        let ledger = Ledger::emulated(
+            LedgerEmulator::new().version(1, 8, 0),
+            DerivationType::LedgerLive(0),
+            1,
+        )
+        .await
+        .unwrap();
+        let tx: TypedTransaction =
+            Eip1559TransactionRequest::new().to(Address::repeat_byte(0x11)).chain_id(1u64).into();
+        assert!(matches!(
+            ledger.sign_tx(&tx).await.unwrap_err(),
+            LedgerError::UnsupportedTransactionType(2)
+        ));
+
+        // legacy transactions are still supported
+        let tx: TypedTransaction =
+            TransactionRequest::new().to(Address::repeat_byte(0x11)).chain_id(1u64).into();
+        let sig = ledger.sign_tx(&tx).await.unwrap();
+        assert_eq!(sig.recover(tx.sighash()).unwrap(), test_address());
+    }
+
+    #[tokio::test]
+    async fn signs_transactions() {
+        // 1337 needs more than one byte for `v`, which the app truncates
+        for chain_id in [1u64, 1337] {
+            let ledger = ledger(chain_id).await;
+
+            let legacy: TypedTransaction = TransactionRequest::new()
+                .to(Address::repeat_byte(0x11))
+                .gas(1_000_000)
+                .gas_price(400_000_000_000u64)
+                .nonce(5)
+                .value(100u64)
+                .data(vec![0xab; 600])
+                .chain_id(chain_id)
+                .into();
+            let sig = ledger.sign_tx(&legacy).await.unwrap();
+            assert!(sig.v == chain_id * 2 + 35 || sig.v == chain_id * 2 + 36);
+            assert_eq!(sig.recover(legacy.sighash()).unwrap(), test_address());
+
+            let typed: TypedTransaction = Eip1559TransactionRequest::new()
+                .to(Address::repeat_byte(0x11))
+                .gas(21_000)
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(1)
+                .nonce(5)
+                .value(100u64)
+                .chain_id(chain_id)
+                .into();
+            let sig = ledger.sign_tx(&typed).await.unwrap();
+            assert!(sig.v <= 1);
+            assert_eq!(sig.recover(typed.sighash()).unwrap(), test_address());
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_instructions() {
+        let command = APDUCommand {
+            ins: 0xff,
+            p1: P1::NON_CONFIRM as u8,
+            p2: P2::NO_CHAINCODE as u8,
+            data: coins_ledger::common::APDUData::new(&[]),
+            response_len: None,
+        };
+        let answer = LedgerEmulator::new().exchange(&command).unwrap();
+        assert!(answer.data().is_none());
+    }
+}
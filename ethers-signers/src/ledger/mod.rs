@@ -1,4 +1,6 @@
 pub mod app;
+#[cfg(feature = "ledger-emulator")]
+pub mod emulator;
 pub mod types;
 
 use crate::Signer;
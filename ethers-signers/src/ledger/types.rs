@@ -51,10 +51,52 @@ pub enum LedgerError {
     /// Error when signing EIP712 struct with not compatible Ledger ETH app
     #[error("Ledger ethereum app requires at least version: {0:?}")]
     UnsupportedAppVersion(String),
+    /// The device answered with an error status word
+    #[error("Ledger device responded with status {0:#06x}")]
+    ResponseStatus(u16),
+    /// The device answered with a status word which means that the Ethereum app is not open
+    #[error("Ledger ethereum app is not open (status {0:#06x}), please open it on the device")]
+    WrongApp(u16),
+    /// The Ledger ETH app rejected the type of the transaction, which requires a newer app
+    #[error("Ledger ethereum app does not support transactions of type {0}, please update it")]
+    UnsupportedTransactionType(u8),
+}
+
+/// The configuration of the Ethereum Ledger app
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppConfiguration {
+    /// The semver of the app
+    pub version: String,
+    /// Whether blind signing of contract data is enabled in the settings of the app
+    pub arbitrary_data_enabled: bool,
+    /// Whether the app requires ERC-20 token information to be provided before signing token
+    /// transfers
+    pub erc20_provisioning_necessary: bool,
+}
+
+impl AppConfiguration {
+    /// Parses the response to a `GET_APP_CONFIGURATION` APDU
+    pub(crate) fn from_response(data: &[u8]) -> Result<Self, LedgerError> {
+        match data {
+            [flags, major, minor, patch, ..] => Ok(Self {
+                version: format!("{}.{}.{}", major, minor, patch),
+                arbitrary_data_enabled: flags & 0x01 != 0,
+                erc20_provisioning_necessary: flags & 0x02 != 0,
+            }),
+            _ => Err(LedgerError::UnexpectedNullResponse),
+        }
+    }
 }
 
 pub const P1_FIRST: u8 = 0x00;
 
+/// Status word of a successful APDU exchange
+pub const SW_OK: u16 = 0x9000;
+/// Status word of an APDU with invalid data
+pub const SW_INVALID_DATA: u16 = 0x6a80;
+/// Status words returned when another app, or the dashboard, is open on the device
+pub const SW_WRONG_APP: [u16; 4] = [0x6511, 0x6d00, 0x6e00, 0x6e01];
+
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[allow(non_camel_case_types)]
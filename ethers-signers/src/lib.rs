@@ -3,7 +3,18 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 mod wallet;
-pub use wallet::{MnemonicBuilder, Wallet, WalletError};
+pub use wallet::{HdSigner, MnemonicBuilder, Wallet, WalletError};
+
+mod auditing;
+pub use auditing::{AuditingSigner, SignedPayload, SigningRecord};
+
+mod multi;
+pub use multi::MultiSigner;
+
+mod callback;
+pub use callback::{CallbackSigner, CallbackSignerError};
+
+pub mod siwe;
 
 /// Re-export the BIP-32 crate so that wordlists can be accessed conveniently.
 pub use coins_bip39;
@@ -17,10 +28,12 @@ pub type YubiWallet = Wallet<yubihsm::ecdsa::Signer<ethers_core::k256::Secp256k1
 
 #[cfg(feature = "ledger")]
 mod ledger;
+#[cfg(feature = "ledger-emulator")]
+pub use ledger::emulator::LedgerEmulator;
 #[cfg(feature = "ledger")]
 pub use ledger::{
     app::LedgerEthereum as Ledger,
-    types::{DerivationType as HDPath, LedgerError},
+    types::{AppConfiguration as LedgerAppConfiguration, DerivationType as HDPath, LedgerError},
 };
 
 #[cfg(feature = "trezor")]
@@ -0,0 +1,145 @@
+use crate::Signer;
+
+use ethers_core::types::{transaction::eip2718::TypedTransaction, Address, Signature};
+
+/// Collects independent signatures of several [`Signer`]s over the same payload, e.g. to submit
+/// them to an on-chain multisig.
+///
+/// This is not threshold cryptography: every signer produces a regular ECDSA signature. The
+/// signers are asked one after another, so that hardware wallets prompt for confirmation one at a
+/// time, and the signatures are returned in the order of the signers.
+///
+/// All signers must be of the same type. To combine e.g. local wallets and Ledgers, wrap them in
+/// an enum which implements [`Signer`].
+///
+/// ```
+/// use ethers_core::{rand::thread_rng, types::TransactionRequest};
+/// use ethers_signers::{LocalWallet, MultiSigner};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let signers = MultiSigner::new(vec![
+///     LocalWallet::new(&mut thread_rng()),
+///     LocalWallet::new(&mut thread_rng()),
+/// ]);
+///
+/// // without a chain id, each signer would sign the transaction with its own chain id
+/// let tx = TransactionRequest::new().nonce(0).chain_id(1u64).into();
+/// let signatures = signers.sign_transaction_all(&tx).await?;
+/// for (signature, address) in signatures.iter().zip(signers.addresses()) {
+///     signature.verify(tx.sighash(), address)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiSigner<S> {
+    signers: Vec<S>,
+}
+
+impl<S: Signer> MultiSigner<S> {
+    /// Creates a new multi signer, signing in the order of `signers`
+    pub fn new(signers: Vec<S>) -> Self {
+        Self { signers }
+    }
+
+    /// Returns the signers
+    pub fn signers(&self) -> &[S] {
+        &self.signers
+    }
+
+    /// Returns the addresses of the signers, in signing order
+    pub fn addresses(&self) -> Vec<Address> {
+        self.signers.iter().map(Signer::address).collect()
+    }
+
+    /// Signs the transaction with every signer and returns the signatures in signing order.
+    ///
+    /// If the transaction has no chain id, each signer signs it with its own chain id, which then
+    /// determines the `v` of its signature.
+    pub async fn sign_transaction_all(
+        &self,
+        tx: &TypedTransaction,
+    ) -> Result<Vec<Signature>, S::Error> {
+        let mut signatures = Vec::with_capacity(self.signers.len());
+        for signer in &self.signers {
+            let signature = if tx.chain_id().is_some() {
+                signer.sign_transaction(tx).await?
+            } else {
+                let mut tx = tx.clone();
+                tx.set_chain_id(signer.chain_id());
+                signer.sign_transaction(&tx).await?
+            };
+            signatures.push(signature);
+        }
+        Ok(signatures)
+    }
+
+    /// Signs the message with every signer and returns the signatures in signing order
+    pub async fn sign_message_all<M: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: M,
+    ) -> Result<Vec<Signature>, S::Error> {
+        let mut signatures = Vec::with_capacity(self.signers.len());
+        for signer in &self.signers {
+            signatures.push(signer.sign_message(message.as_ref()).await?);
+        }
+        Ok(signatures)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::LocalWallet;
+    use ethers_core::types::TransactionRequest;
+
+    fn signers() -> MultiSigner<LocalWallet> {
+        MultiSigner::new(vec![
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse::<LocalWallet>()
+                .unwrap()
+                .with_chain_id(1u64),
+            "4646464646464646464646464646464646464646464646464646464646464646"
+                .parse::<LocalWallet>()
+                .unwrap()
+                .with_chain_id(5u64),
+        ])
+    }
+
+    #[tokio::test]
+    async fn signs_messages_in_order() {
+        let signers = signers();
+        let signatures = signers.sign_message_all("hello").await.unwrap();
+        assert_eq!(signatures.len(), 2);
+        for (signature, address) in signatures.iter().zip(signers.addresses()) {
+            signature.verify("hello", address).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn signs_transactions_with_own_chain_ids() {
+        let signers = signers();
+        let tx: TypedTransaction =
+            TransactionRequest::pay(Address::zero(), 1u64).nonce(0u64).gas(21_000u64).into();
+
+        let signatures = signers.sign_transaction_all(&tx).await.unwrap();
+        for ((signature, signer), chain_id) in
+            signatures.iter().zip(signers.signers()).zip([1u64, 5])
+        {
+            let mut tx = tx.clone();
+            tx.set_chain_id(chain_id);
+            signature.verify(tx.sighash(), signer.address()).unwrap();
+            assert!(signature.v == chain_id * 2 + 35 || signature.v == chain_id * 2 + 36);
+        }
+
+        // a chain id set on the transaction is used by all signers
+        let mut tx = tx;
+        tx.set_chain_id(5u64);
+        for (signature, address) in
+            signers.sign_transaction_all(&tx).await.unwrap().iter().zip(signers.addresses())
+        {
+            signature.verify(tx.sighash(), address).unwrap();
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! [EIP-4361](https://eips.ethereum.org/EIPS/eip-4361) Sign-In with Ethereum messages
+use crate::Signer;
+
+use ethers_core::{
+    types::{Address, Signature, SignatureError},
+    utils::to_checksum,
+};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+/// Error produced when parsing a [`SiweMessage`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SiweError {
+    /// The message ended before the expected line
+    #[error("missing line: {0}")]
+    MissingLine(&'static str),
+    /// A line does not match the line expected at its position
+    #[error("invalid line, expected {expected}: {line:?}")]
+    InvalidLine { expected: &'static str, line: String },
+    /// The address is not EIP-55 checksummed
+    #[error("invalid address, expected an EIP-55 checksummed address: {0:?}")]
+    InvalidAddress(String),
+    /// The chain id is not a number
+    #[error("invalid chain id: {0:?}")]
+    InvalidChainId(String),
+    /// The nonce is not at least 8 alphanumeric characters
+    #[error("invalid nonce, expected at least 8 alphanumeric characters: {0:?}")]
+    InvalidNonce(String),
+}
+
+/// A Sign-In with Ethereum message as specified by
+/// [EIP-4361](https://eips.ethereum.org/EIPS/eip-4361).
+///
+/// The message is displayed as the canonical message text, which is what gets signed, and can be
+/// parsed back from it with [`FromStr`], e.g. to verify a message received from a frontend.
+///
+/// The timestamps are kept as the RFC 3339 strings of the message. Verifying a signature only
+/// checks that it was produced by the message's address: the server must additionally check that
+/// the domain, nonce and timestamps are the ones it expects.
+///
+/// ```
+/// use ethers_core::rand::thread_rng;
+/// use ethers_signers::{siwe::SiweMessage, LocalWallet, Signer};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let wallet = LocalWallet::new(&mut thread_rng());
+/// let message = SiweMessage::new(
+///     "example.com",
+///     wallet.address(),
+///     "https://example.com/login",
+///     1,
+///     "32891756",
+///     "2021-09-30T16:25:24Z",
+/// );
+/// let signature = message.sign(&wallet).await?;
+///
+/// // on the server
+/// let received: SiweMessage = message.to_string().parse()?;
+/// assert_eq!(received.nonce, "32891756");
+/// received.verify(&signature)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// The URI scheme of the origin of the request, e.g. `https`
+    pub scheme: Option<String>,
+    /// The RFC 3986 authority requesting the signing
+    pub domain: String,
+    /// The address performing the signing
+    pub address: Address,
+    /// A human-readable assertion, which must not contain newlines
+    pub statement: Option<String>,
+    /// The RFC 3986 URI referring to the resource that is the subject of the signing
+    pub uri: String,
+    /// The version of the message, which must be `1`
+    pub version: String,
+    /// The EIP-155 chain id to which the session is bound
+    pub chain_id: u64,
+    /// A randomized token to prevent replay attacks, at least 8 alphanumeric characters
+    pub nonce: String,
+    /// The RFC 3339 time when the message was generated
+    pub issued_at: String,
+    /// The RFC 3339 time when the signed message expires
+    pub expiration_time: Option<String>,
+    /// The RFC 3339 time when the signed message becomes valid
+    pub not_before: Option<String>,
+    /// A system-specific identifier used to uniquely refer to the sign-in request
+    pub request_id: Option<String>,
+    /// RFC 3986 URIs the user wishes to have resolved as part of authentication
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Creates a version `1` message without any of the optional fields
+    pub fn new(
+        domain: impl Into<String>,
+        address: Address,
+        uri: impl Into<String>,
+        chain_id: u64,
+        nonce: impl Into<String>,
+        issued_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            scheme: None,
+            domain: domain.into(),
+            address,
+            statement: None,
+            uri: uri.into(),
+            version: "1".to_string(),
+            chain_id,
+            nonce: nonce.into(),
+            issued_at: issued_at.into(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Signs the message text with `signer`
+    pub async fn sign<S: Signer>(&self, signer: &S) -> Result<Signature, S::Error> {
+        signer.sign_message(self.to_string()).await
+    }
+
+    /// Verifies that `signature` was produced by the message's address over the message text
+    pub fn verify(&self, signature: &Signature) -> Result<(), SignatureError> {
+        signature.verify(self.to_string(), self.address)
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{}://", scheme)?;
+        }
+        writeln!(f, "{}{}", self.domain, HEADER_SUFFIX)?;
+        writeln!(f, "{}", to_checksum(&self.address, None))?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", expiration_time)?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", not_before)?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.split('\n').peekable();
+        let mut next = |expected| lines.next().ok_or(SiweError::MissingLine(expected));
+
+        let header = next("header")?;
+        let origin = header.strip_suffix(HEADER_SUFFIX).ok_or_else(|| SiweError::InvalidLine {
+            expected: "header",
+            line: header.to_string(),
+        })?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain.to_string()),
+            None => (None, origin.to_string()),
+        };
+
+        let line = next("address")?;
+        let address: Address =
+            line.parse().map_err(|_| SiweError::InvalidAddress(line.to_string()))?;
+        if to_checksum(&address, None) != line {
+            return Err(SiweError::InvalidAddress(line.to_string()))
+        }
+
+        expect_empty(next("empty line")?)?;
+        let statement = match next("statement")? {
+            "" => None,
+            statement => {
+                expect_empty(next("empty line")?)?;
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = tagged(next("URI")?, "URI: ")?.to_string();
+        let version = tagged(next("Version")?, "Version: ")?;
+        if version != "1" {
+            return Err(SiweError::InvalidLine {
+                expected: "Version: 1",
+                line: format!("Version: {}", version),
+            })
+        }
+        let chain_id = tagged(next("Chain ID")?, "Chain ID: ")?;
+        let chain_id =
+            chain_id.parse().map_err(|_| SiweError::InvalidChainId(chain_id.to_string()))?;
+        let nonce = tagged(next("Nonce")?, "Nonce: ")?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SiweError::InvalidNonce(nonce.to_string()))
+        }
+        let issued_at = tagged(next("Issued At")?, "Issued At: ")?.to_string();
+
+        let mut optional = |tag: &'static str| {
+            let value = lines.peek().and_then(|line| line.strip_prefix(tag)).map(str::to_string);
+            if value.is_some() {
+                lines.next();
+            }
+            value
+        };
+        let expiration_time = optional("Expiration Time: ");
+        let not_before = optional("Not Before: ");
+        let request_id = optional("Request ID: ");
+
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&"Resources:") {
+            lines.next();
+            while let Some(resource) = lines.peek().and_then(|line| line.strip_prefix("- ")) {
+                resources.push(resource.to_string());
+                lines.next();
+            }
+        }
+
+        if let Some(line) = lines.next() {
+            return Err(SiweError::InvalidLine {
+                expected: "end of message",
+                line: line.to_string(),
+            })
+        }
+
+        Ok(Self {
+            scheme,
+            domain,
+            address,
+            statement,
+            uri,
+            version: version.to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+fn expect_empty(line: &str) -> Result<(), SiweError> {
+    if line.is_empty() {
+        Ok(())
+    } else {
+        Err(SiweError::InvalidLine { expected: "empty line", line: line.to_string() })
+    }
+}
+
+fn tagged<'a>(line: &'a str, tag: &'static str) -> Result<&'a str, SiweError> {
+    line.strip_prefix(tag).ok_or_else(|| SiweError::InvalidLine {
+        expected: tag.trim_end_matches(": "),
+        line: line.to_string(),
+    })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::LocalWallet;
+
+    // the example message of EIP-4361
+    const MESSAGE: &str = "service.invalid wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ServiceOrg Terms of Service: https://service.invalid/tos
+
+URI: https://service.invalid/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    #[test]
+    fn parses_and_formats_messages() {
+        let message: SiweMessage = MESSAGE.parse().unwrap();
+        let mut expected = SiweMessage::new(
+            "service.invalid",
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap(),
+            "https://service.invalid/login",
+            1,
+            "32891756",
+            "2021-09-30T16:25:24Z",
+        );
+        expected.statement =
+            Some("I accept the ServiceOrg Terms of Service: https://service.invalid/tos".into());
+        expected.resources = vec![
+            "ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/".into(),
+            "https://example.com/my-web2-claim.json".into(),
+        ];
+        assert_eq!(message, expected);
+        assert_eq!(message.to_string(), MESSAGE);
+
+        // all optional fields, but no statement
+        let mut message = expected;
+        message.scheme = Some("https".into());
+        message.statement = None;
+        message.expiration_time = Some("2021-10-01T16:25:24Z".into());
+        message.not_before = Some("2021-09-30T16:25:24Z".into());
+        message.request_id = Some("some-id".into());
+        message.resources.clear();
+        let text = message.to_string();
+        assert!(text.starts_with("https://service.invalid wants you"));
+        assert!(text.contains("Cc2\n\n\nURI: "));
+        assert_eq!(text.parse::<SiweMessage>().unwrap(), message);
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        let lowercase = MESSAGE.replace(
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        );
+        assert!(matches!(lowercase.parse::<SiweMessage>(), Err(SiweError::InvalidAddress(_))));
+
+        let short_nonce = MESSAGE.replace("Nonce: 32891756", "Nonce: 1234");
+        assert!(matches!(short_nonce.parse::<SiweMessage>(), Err(SiweError::InvalidNonce(_))));
+
+        let version = MESSAGE.replace("Version: 1", "Version: 2");
+        assert!(matches!(version.parse::<SiweMessage>(), Err(SiweError::InvalidLine { .. })));
+
+        let trailing = format!("{}\n", MESSAGE);
+        assert!(matches!(trailing.parse::<SiweMessage>(), Err(SiweError::InvalidLine { .. })));
+
+        let truncated = MESSAGE.split("\nNonce").next().unwrap();
+        assert_eq!(truncated.parse::<SiweMessage>(), Err(SiweError::MissingLine("Nonce")));
+    }
+
+    #[tokio::test]
+    async fn signs_and_verifies_messages() {
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let mut message: SiweMessage = MESSAGE.parse().unwrap();
+        message.address = wallet.address();
+
+        let signature = message.sign(&wallet).await.unwrap();
+        let received: SiweMessage = message.to_string().parse().unwrap();
+        received.verify(&signature).unwrap();
+
+        // a tampered message recovers a different address
+        let mut tampered = received;
+        tampered.nonce = "00000000".into();
+        assert!(matches!(
+            tampered.verify(&signature),
+            Err(SignatureError::VerificationError(expected, _)) if expected == wallet.address()
+        ));
+    }
+}
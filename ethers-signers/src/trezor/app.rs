@@ -185,6 +185,7 @@ impl TrezorEthereum {
                 transaction.max_priority_fee_per_gas,
                 transaction.access_list,
             )?,
+            TypedTransaction::Eip7702(_) => return Err(TrezorError::UnsupportedTransactionType(4)),
         };
 
         Ok(Signature { r: signature.r, s: signature.s, v: signature.v })
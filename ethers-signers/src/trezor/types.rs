@@ -53,6 +53,9 @@ pub enum TrezorError {
     NoENSSupport,
     #[error("Unable to access trezor cached session.")]
     CacheError(String),
+    /// The Trezor ethereum app cannot sign transactions of this type
+    #[error("Trezor ethereum app does not support transactions of type {0}")]
+    UnsupportedTransactionType(u8),
 }
 
 /// Trezor Transaction Struct
@@ -135,6 +138,7 @@ impl TrezorTransaction {
                     access_list,
                 })
             }
+            TypedTransaction::Eip7702(_) => Err(TrezorError::UnsupportedTransactionType(4)),
         }
     }
 }
@@ -0,0 +1,195 @@
+//! A signer deriving a fresh key from a BIP-32 master key for every signature
+use super::{mnemonic::DEFAULT_DERIVATION_PATH_PREFIX, Wallet, WalletError};
+
+use coins_bip32::{
+    path::DerivationPath,
+    xkeys::{Parent, XPriv},
+};
+use coins_bip39::{Mnemonic, Wordlist};
+use ethers_core::{
+    k256::{ecdsa::SigningKey, elliptic_curve::zeroize::Zeroize},
+    types::{transaction::eip2718::TypedTransaction, Address, Signature},
+    utils::{hash_message, secret_key_to_address},
+};
+use std::{fmt, str::FromStr};
+
+/// Signs with the child keys of a BIP-32 master key, deriving the key of the requested index
+/// just in time for each signature instead of keeping a wallet per address around.
+///
+/// The child keys are derived at `{prefix}{index}`, where the prefix defaults to
+/// `m/44'/60'/0'/0/`. A derived key only lives for the duration of a single signing operation and
+/// is zeroized when it is dropped.
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::types::TransactionRequest;
+/// use ethers_signers::{coins_bip39::English, HdSigner};
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+/// let signer = HdSigner::from_phrase::<English>(phrase, None)?.with_chain_id(1u64);
+///
+/// // sweep two accounts without instantiating a wallet for each of them
+/// for index in 0..2 {
+///     let from = signer.address_at(index)?;
+///     let tx = TransactionRequest::pay(from, 0).from(from).nonce(0).gas(21000).gas_price(1);
+///     let signature = signer.sign_transaction_at(index, &tx.into())?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct HdSigner {
+    /// The master key the child keys are derived from
+    root: XPriv,
+    /// The derivation path prefix, which the index is appended to
+    prefix: String,
+    /// The chain id (for EIP-155), used if a transaction does not specify one
+    chain_id: u64,
+}
+
+impl HdSigner {
+    /// Creates a signer from the master key derived from the BIP-39 mnemonic `phrase` and the
+    /// optional `password`
+    pub fn from_phrase<W: Wordlist>(
+        phrase: &str,
+        password: Option<&str>,
+    ) -> Result<Self, WalletError> {
+        let mnemonic = Mnemonic::<W>::new_from_phrase(phrase)?;
+        let mut seed = mnemonic.to_seed(password)?;
+        let signer = Self::from_seed(&seed);
+        seed.zeroize();
+        signer
+    }
+
+    /// Creates a signer from the master key derived from the BIP-32 `seed`
+    pub fn from_seed(seed: &[u8]) -> Result<Self, WalletError> {
+        Ok(Self {
+            root: XPriv::root_from_seed(seed, None)?,
+            prefix: DEFAULT_DERIVATION_PATH_PREFIX.to_string(),
+            chain_id: 1,
+        })
+    }
+
+    /// Sets the derivation path prefix the index is appended to, e.g. `m/44'/60'/1'/0/`
+    pub fn derivation_path_prefix(mut self, prefix: &str) -> Result<Self, WalletError> {
+        // ensure that the paths derived with this prefix are valid
+        DerivationPath::from_str(&format!("{}0", prefix))?;
+        self.prefix = prefix.to_string();
+        Ok(self)
+    }
+
+    /// Sets the chain id used for EIP-155 signing if a transaction does not specify one
+    #[must_use]
+    pub fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+
+    /// Returns the chain id
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Returns the address of the key at `index`
+    pub fn address_at(&self, index: u32) -> Result<Address, WalletError> {
+        Ok(self.wallet_at(index)?.address)
+    }
+
+    /// Signs the transaction with the key at `index`, normalizing the signature `v` value with
+    /// EIP-155 using the transaction's `chain_id`, or the signer's `chain_id` if the transaction
+    /// does not specify one
+    pub fn sign_transaction_at(
+        &self,
+        index: u32,
+        tx: &TypedTransaction,
+    ) -> Result<Signature, WalletError> {
+        Ok(self.wallet_at(index)?.sign_transaction_sync(tx))
+    }
+
+    /// Signs the hash of the provided message after prefixing it with the key at `index`
+    pub fn sign_message_at<S: AsRef<[u8]>>(
+        &self,
+        index: u32,
+        message: S,
+    ) -> Result<Signature, WalletError> {
+        Ok(self.wallet_at(index)?.sign_hash(hash_message(message)))
+    }
+
+    /// Derives the short-lived wallet of the key at `index`
+    fn wallet_at(&self, index: u32) -> Result<Wallet<SigningKey>, WalletError> {
+        let path = DerivationPath::from_str(&format!("{}{}", self.prefix, index))?;
+        let child = self.root.derive_path(&path)?;
+        let key: &coins_bip32::prelude::SigningKey = child.as_ref();
+
+        let mut bytes = key.to_bytes();
+        let signer = SigningKey::from_bytes(&bytes);
+        bytes.as_mut_slice().zeroize();
+        let signer = signer?;
+
+        let address = secret_key_to_address(&signer);
+        Ok(Wallet { signer, address, chain_id: self.chain_id, eip155: true })
+    }
+}
+
+impl fmt::Debug for HdSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HdSigner")
+            .field("prefix", &self.prefix)
+            .field("chain_id", &self.chain_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coins_bip39::English;
+    use ethers_core::types::TransactionRequest;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn signs_from_distinct_indices() {
+        let signer = HdSigner::from_phrase::<English>(PHRASE, None).unwrap().with_chain_id(1u64);
+        let expected: [Address; 2] = [
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94".parse().unwrap(),
+            "0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0".parse().unwrap(),
+        ];
+
+        for (index, expected) in expected.iter().enumerate() {
+            let index = index as u32;
+            assert_eq!(signer.address_at(index).unwrap(), *expected);
+
+            let tx: TypedTransaction =
+                TransactionRequest::pay(Address::zero(), 1).nonce(0).gas(21000).gas_price(1).into();
+            let signature = signer.sign_transaction_at(index, &tx).unwrap();
+            assert_eq!(signature.recover(tx.sighash()).unwrap(), *expected);
+            // EIP-155 with the signer's chain id
+            assert!(signature.v == 37 || signature.v == 38);
+
+            let signature = signer.sign_message_at(index, "sweep").unwrap();
+            assert_eq!(signature.recover("sweep").unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn matches_mnemonic_wallets() {
+        let prefix = "m/44'/60'/1'/0/";
+        let signer = HdSigner::from_phrase::<English>(PHRASE, Some("secret"))
+            .unwrap()
+            .derivation_path_prefix(prefix)
+            .unwrap();
+        let wallet = crate::MnemonicBuilder::<English>::default()
+            .phrase(PHRASE)
+            .password("secret")
+            .derivation_path(&format!("{}3", prefix))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(signer.address_at(3).unwrap(), wallet.address);
+
+        assert!(signer.derivation_path_prefix("not a path/").is_err());
+    }
+}
@@ -2,18 +2,21 @@
 //! specifications
 use crate::{Wallet, WalletError};
 
-use coins_bip32::path::DerivationPath;
+use coins_bip32::{
+    path::DerivationPath,
+    xkeys::{Parent, XPriv},
+};
 use coins_bip39::{Mnemonic, Wordlist};
 use ethers_core::{
-    k256::ecdsa::SigningKey,
+    k256::{ecdsa::SigningKey, elliptic_curve::zeroize::Zeroize},
     types::PathOrString,
     utils::{secret_key_to_address, to_checksum},
 };
 use rand::Rng;
-use std::{fs::File, io::Write, marker::PhantomData, path::PathBuf, str::FromStr};
+use std::{fs::File, io::Write, marker::PhantomData, ops::Range, path::PathBuf, str::FromStr};
 use thiserror::Error;
 
-const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
+pub(crate) const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
 
 /// Represents a structure that can resolve into a `Wallet<SigningKey>`.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -146,16 +149,47 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     /// Builds a `LocalWallet` using the parameters set in mnemonic builder. This method expects
     /// the phrase field to be set.
     pub fn build(&self) -> Result<Wallet<SigningKey>, WalletError> {
-        let mnemonic = match &self.phrase {
-            Some(path_or_string) => {
-                let phrase = path_or_string.read()?;
-                Mnemonic::<W>::new_from_phrase(&phrase)?
-            }
-            None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
-        };
+        let mnemonic = self.mnemonic()?;
         self.mnemonic_to_wallet(&mnemonic)
     }
 
+    /// Builds the `LocalWallet`s at `{base_path}/{index}` for every index in `indices`, e.g. the
+    /// first 10 accounts with `derive_range("m/44'/60'/0'/0", 0..10)`. This method expects the
+    /// phrase field to be set, the derivation path set on the builder is ignored.
+    ///
+    /// Unlike building a wallet per index, the seed is only computed once from the phrase, which
+    /// is the expensive part, and every child key is derived from the key at `base_path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_signers::{MnemonicBuilder, coins_bip39::English};
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let wallets = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .derive_range("m/44'/60'/0'/0", 0..10)?;
+    /// assert_eq!(wallets.len(), 10);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn derive_range(
+        &self,
+        base_path: &str,
+        indices: Range<u32>,
+    ) -> Result<Vec<Wallet<SigningKey>>, WalletError> {
+        let base_path = DerivationPath::from_str(base_path.trim_end_matches('/'))?;
+        let mnemonic = self.mnemonic()?;
+
+        let mut seed = mnemonic.to_seed(self.password.as_deref())?;
+        let root = XPriv::root_from_seed(&seed, None);
+        seed.zeroize();
+
+        let parent = root?.derive_path(&base_path)?;
+        indices.map(|index| key_to_wallet(&parent.derive_child(index)?)).collect()
+    }
+
     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
     /// the phrase using the provided random number generator.
     pub fn build_random<R: Rng>(&self, rng: &mut R) -> Result<Wallet<SigningKey>, WalletError> {
@@ -174,20 +208,34 @@ impl<W: Wordlist> MnemonicBuilder<W> {
         Ok(wallet)
     }
 
+    fn mnemonic(&self) -> Result<Mnemonic<W>, WalletError> {
+        match &self.phrase {
+            Some(path_or_string) => {
+                let phrase = path_or_string.read()?;
+                Ok(Mnemonic::<W>::new_from_phrase(&phrase)?)
+            }
+            None => Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
+        }
+    }
+
     fn mnemonic_to_wallet(
         &self,
         mnemonic: &Mnemonic<W>,
     ) -> Result<Wallet<SigningKey>, WalletError> {
         let derived_priv_key =
             mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?;
-        let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
-        let signer = SigningKey::from_bytes(&key.to_bytes())?;
-        let address = secret_key_to_address(&signer);
-
-        Ok(Wallet::<SigningKey> { signer, address, chain_id: 1 })
+        key_to_wallet(&derived_priv_key)
     }
 }
 
+fn key_to_wallet(xpriv: &XPriv) -> Result<Wallet<SigningKey>, WalletError> {
+    let key: &coins_bip32::prelude::SigningKey = xpriv.as_ref();
+    let signer = SigningKey::from_bytes(&key.to_bytes())?;
+    let address = secret_key_to_address(&signer);
+
+    Ok(Wallet::<SigningKey> { signer, address, chain_id: 1, eip155: true })
+}
+
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
@@ -247,6 +295,26 @@ mod tests {
         })
     }
 
+    #[test]
+    fn derives_range_like_single_indices() {
+        let phrase =
+            "work man father plunge mystery proud hollow address reunion sauce theory bonus";
+        let builder = MnemonicBuilder::<English>::default().phrase(phrase).password("TREZOR123");
+
+        let wallets = builder.derive_range("m/44'/60'/0'/0/", 2..5).unwrap();
+        assert_eq!(wallets.len(), 3);
+        for (wallet, index) in wallets.iter().zip(2u32..) {
+            let expected = builder.clone().index(index).unwrap().build().unwrap();
+            assert_eq!(wallet.address, expected.address);
+        }
+
+        let no_phrase = MnemonicBuilder::<English>::default().derive_range("m/44'/60'/0'/0", 0..1);
+        assert!(matches!(
+            no_phrase,
+            Err(WalletError::MnemonicBuilderError(MnemonicBuilderError::ExpectedPhraseNotFound))
+        ));
+    }
+
     #[tokio::test]
     async fn mnemonic_write_read() {
         let dir = tempdir().unwrap();
@@ -1,5 +1,8 @@
 mod hash;
 
+mod hd;
+pub use hd::HdSigner;
+
 mod mnemonic;
 pub use mnemonic::{MnemonicBuilder, MnemonicBuilderError};
 
@@ -65,6 +68,8 @@ pub struct Wallet<D: DigestSigner<Sha256Proxy, RecoverableSignature>> {
     pub(crate) address: Address,
     /// The wallet's chain id (for EIP-155)
     pub(crate) chain_id: u64,
+    /// Whether legacy transactions are signed with EIP-155 replay protection
+    pub(crate) eip155: bool,
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -118,10 +123,36 @@ impl<D: Sync + Send + DigestSigner<Sha256Proxy, RecoverableSignature>> Signer fo
 }
 
 impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> Wallet<D> {
+    /// Sets whether legacy transactions are signed with EIP-155 replay protection, which is
+    /// enabled by default.
+    ///
+    /// If disabled, legacy transactions are signed without a chain id and the signature's `v` is
+    /// 27 or 28, as required by chains which don't support EIP-155. Such transactions can be
+    /// replayed on any chain. Typed transactions always commit to their chain id.
+    pub fn with_eip155(mut self, enabled: bool) -> Self {
+        self.eip155 = enabled;
+        self
+    }
+
+    /// Returns whether legacy transactions are signed with EIP-155 replay protection
+    pub fn eip155(&self) -> bool {
+        self.eip155
+    }
+
     /// Synchronously signs the provided transaction, normalizing the signature `v` value with
     /// EIP-155 using the transaction's `chain_id`, or the signer's `chain_id` if the transaction
     /// does not specify one.
+    ///
+    /// If EIP-155 is disabled (see [Self::with_eip155]), legacy transactions are signed without
+    /// a chain id instead.
     pub fn sign_transaction_sync(&self, tx: &TypedTransaction) -> Signature {
+        if let (false, TypedTransaction::Legacy(tx)) = (self.eip155, tx) {
+            let mut tx = tx.clone();
+            tx.chain_id = None;
+            // the `v` of pre EIP-155 signatures is recid + 27, as set by sign_hash
+            return self.sign_hash(tx.sighash())
+        }
+
         // rlp (for sighash) must have the same chain id as v in the signature
         let chain_id = tx.chain_id().map(|id| id.as_u64()).unwrap_or(self.chain_id);
         let mut tx = tx.clone();
@@ -135,7 +166,26 @@ impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> Wallet<D> {
         sig
     }
 
+    /// Signs the EIP-712 `payload` with a precomputed domain separator, e.g. the result of
+    /// [`Eip712::domain_separator`].
+    ///
+    /// This is equivalent to `sign_typed_data`, but skips computing the domain separator when
+    /// signing many payloads of the same domain.
+    pub fn sign_typed_data_with_domain_separator<T: Eip712>(
+        &self,
+        payload: &T,
+        domain_separator: [u8; 32],
+    ) -> Result<Signature, WalletError> {
+        let encoded = payload
+            .encode_eip712_with_domain_separator(domain_separator)
+            .map_err(|e| WalletError::Eip712Error(e.to_string()))?;
+        Ok(self.sign_hash(H256::from(encoded)))
+    }
+
     /// Signs the provided hash.
+    ///
+    /// The hash is signed as is, so this can also be used to sign digests which were computed
+    /// in advance, e.g. EIP-712 payloads.
     pub fn sign_hash(&self, hash: H256) -> Signature {
         let recoverable_sig: RecoverableSignature =
             self.signer.sign_digest(Sha256Proxy::from(hash));
@@ -162,6 +212,7 @@ impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> fmt::Debug for Wallet<D
         f.debug_struct("Wallet")
             .field("address", &self.address)
             .field("chain_Id", &self.chain_id)
+            .field("eip155", &self.eip155)
             .finish()
     }
 }
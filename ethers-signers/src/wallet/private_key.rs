@@ -15,7 +15,10 @@ use ethers_core::{
 };
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
-use std::str::FromStr;
+use std::{
+    convert::{TryFrom, TryInto},
+    str::FromStr,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -46,6 +49,9 @@ pub enum WalletError {
     /// Error type from Eip712Error message
     #[error("error encoding eip712 struct: {0:?}")]
     Eip712Error(String),
+    /// The bytes of a private key are not a nonzero scalar below the order of secp256k1
+    #[error("invalid private key: expected 32 bytes of a nonzero scalar below the curve order")]
+    InvalidPrivateKey,
 }
 
 impl Clone for Wallet<SigningKey> {
@@ -55,6 +61,7 @@ impl Clone for Wallet<SigningKey> {
             signer: SigningKey::from_bytes(&self.signer.to_bytes()).unwrap(),
             address: self.address,
             chain_id: self.chain_id,
+            eip155: self.eip155,
         }
     }
 }
@@ -79,7 +86,7 @@ impl Wallet<SigningKey> {
         let (secret, uuid) = eth_keystore::new(dir, rng, password, name)?;
         let signer = SigningKey::from_bytes(secret.as_slice())?;
         let address = secret_key_to_address(&signer);
-        Ok((Self { signer, address, chain_id: 1 }, uuid))
+        Ok((Self { signer, address, chain_id: 1, eip155: true }, uuid))
     }
 
     /// Decrypts an encrypted JSON from the provided path to construct a Wallet instance
@@ -92,14 +99,36 @@ impl Wallet<SigningKey> {
         let secret = eth_keystore::decrypt_key(keypath, password)?;
         let signer = SigningKey::from_bytes(secret.as_slice())?;
         let address = secret_key_to_address(&signer);
-        Ok(Self { signer, address, chain_id: 1 })
+        Ok(Self { signer, address, chain_id: 1, eip155: true })
     }
 
-    /// Creates a new random keypair seeded with the provided RNG
+    /// Creates a new random keypair seeded with the provided RNG.
+    ///
+    /// The private key is sampled uniformly from the nonzero scalars below the order of secp256k1,
+    /// so a deterministically seeded RNG always produces the same wallet.
     pub fn new<R: Rng + CryptoRng>(rng: &mut R) -> Self {
         let signer = SigningKey::random(rng);
         let address = secret_key_to_address(&signer);
-        Self { signer, address, chain_id: 1 }
+        Self { signer, address, chain_id: 1, eip155: true }
+    }
+
+    /// Creates a new random keypair seeded with the provided RNG, for use on the provided chain
+    pub fn new_with_chain_id<R: Rng + CryptoRng>(rng: &mut R, chain_id: u64) -> Self {
+        Self { chain_id, ..Self::new(rng) }
+    }
+
+    /// Creates a wallet from the big endian bytes of a private key.
+    ///
+    /// Fails with [`WalletError::InvalidPrivateKey`] if the key is zero or not below the order of
+    /// secp256k1.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, WalletError> {
+        let signer = SigningKey::from_bytes(bytes).map_err(|_| WalletError::InvalidPrivateKey)?;
+        Ok(signer.into())
+    }
+
+    /// Creates a wallet from a signing key
+    pub fn from_signing_key(signer: SigningKey) -> Self {
+        signer.into()
     }
 }
 
@@ -107,7 +136,8 @@ impl PartialEq for Wallet<SigningKey> {
     fn eq(&self, other: &Self) -> bool {
         self.signer.to_bytes().eq(&other.signer.to_bytes()) &&
             self.address == other.address &&
-            self.chain_id == other.chain_id
+            self.chain_id == other.chain_id &&
+            self.eip155 == other.eip155
     }
 }
 
@@ -115,7 +145,7 @@ impl From<SigningKey> for Wallet<SigningKey> {
     fn from(signer: SigningKey) -> Self {
         let address = secret_key_to_address(&signer);
 
-        Self { signer, address, chain_id: 1 }
+        Self { signer, address, chain_id: 1, eip155: true }
     }
 }
 
@@ -126,7 +156,16 @@ impl From<K256SecretKey> for Wallet<SigningKey> {
         let signer = key.into();
         let address = secret_key_to_address(&signer);
 
-        Self { signer, address, chain_id: 1 }
+        Self { signer, address, chain_id: 1, eip155: true }
+    }
+}
+
+impl TryFrom<&[u8]> for Wallet<SigningKey> {
+    type Error = WalletError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: &[u8; 32] = bytes.try_into().map_err(|_| WalletError::InvalidPrivateKey)?;
+        Self::from_bytes(bytes)
     }
 }
 
@@ -145,7 +184,10 @@ impl FromStr for Wallet<SigningKey> {
 mod tests {
     use super::*;
     use crate::Signer;
-    use ethers_core::types::Address;
+    use ethers_core::{
+        types::{Address, Signature},
+        utils::hash_message,
+    };
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -192,6 +234,241 @@ mod tests {
         assert_eq!(recovered2, address);
     }
 
+    // half the order of secp256k1, signatures with a larger `s` are malleable
+    const HALF_N: &str = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0";
+
+    fn assert_sig(sig: Signature, r: &str, s: &str, v: u64) {
+        assert_eq!(sig, Signature { r: r.parse().unwrap(), s: s.parse().unwrap(), v });
+        assert!(sig.s <= HALF_N.parse().unwrap());
+    }
+
+    #[test]
+    fn random_wallet_from_seeded_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let wallet = Wallet::<SigningKey>::new(&mut StdRng::seed_from_u64(42));
+        assert_eq!(wallet, Wallet::<SigningKey>::new(&mut StdRng::seed_from_u64(42)));
+        assert_ne!(wallet, Wallet::<SigningKey>::new(&mut StdRng::seed_from_u64(43)));
+        assert_eq!(wallet.chain_id, 1);
+
+        let wallet = Wallet::<SigningKey>::new_with_chain_id(&mut StdRng::seed_from_u64(42), 5);
+        assert_eq!(wallet.chain_id, 5);
+        assert_eq!(
+            wallet.address,
+            Wallet::<SigningKey>::new(&mut StdRng::seed_from_u64(42)).address
+        );
+    }
+
+    #[test]
+    fn wallet_from_bytes() {
+        let bytes: [u8; 32] =
+            hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let wallet = Wallet::<SigningKey>::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            wallet.address,
+            "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse::<Address>().unwrap()
+        );
+        assert_eq!(wallet.signer().to_bytes().as_slice(), &bytes[..]);
+        assert_eq!(Wallet::<SigningKey>::try_from(&bytes[..]).unwrap(), wallet);
+        let signer = SigningKey::from_bytes(&bytes).unwrap();
+        assert_eq!(Wallet::<SigningKey>::from_signing_key(signer), wallet);
+
+        // zero, the curve order and too short keys are rejected
+        let order = hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+            .unwrap();
+        for bytes in [&[0u8; 32][..], &order[..], &bytes[1..]] {
+            assert!(matches!(
+                Wallet::<SigningKey>::try_from(bytes),
+                Err(WalletError::InvalidPrivateKey)
+            ));
+        }
+    }
+
+    #[test]
+    fn deterministic_message_signatures() {
+        // https://web3js.readthedocs.io/en/v1.2.0/web3-eth-accounts.html#sign, also produced by
+        // ethers.js
+        let wallet: Wallet<SigningKey> =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let sig = wallet.sign_hash(hash_message("Some data"));
+        assert_sig(
+            sig,
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd",
+            "6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029",
+            28,
+        );
+        // signing is deterministic
+        assert_eq!(wallet.sign_hash(hash_message("Some data")), sig);
+
+        // the nonces of these produce a high `s`, which must be normalized
+        let wallet: Wallet<SigningKey> =
+            "0000000000000000000000000000000000000000000000000000000000000001".parse().unwrap();
+        assert_sig(
+            wallet.sign_hash(hash_message("message 4")),
+            "79013a11f0a07a6088ba4037c6ab2eb36c257f0c96bea3f170be759d68325b6e",
+            "5617fb2e14e47d5cdb8fc705b23a585100c8fdbba8e8e134b6dca78d3e68c641",
+            27,
+        );
+        let wallet: Wallet<SigningKey> =
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140".parse().unwrap();
+        assert_sig(
+            wallet.sign_hash(hash_message("message 3")),
+            "e5aecd996399eb2271f02f5729310e7f98be8969046917036fe146580a1c741c",
+            "217714346a10d910bc766e78958f912beec606af4305393e9860880fb575d567",
+            27,
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn deterministic_transaction_signatures() {
+        use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+
+        let wallet: Wallet<SigningKey> =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let to = "F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse::<Address>().unwrap();
+
+        // https://web3js.readthedocs.io/en/v1.2.0/web3-eth-accounts.html#signtransaction
+        let tx = TransactionRequest::pay(to, 1_000_000_000u64)
+            .gas(2_000_000u64)
+            .gas_price(21_000_000_000u64)
+            .nonce(0u64)
+            .chain_id(1u64)
+            .into();
+        assert_sig(
+            wallet.sign_transaction_sync(&tx),
+            "c9cf86333bcb065d140032ecaab5d9281bde80f21b9687b3e94161de42d51895",
+            "727a108a0b8d101465414033c3f705a9c7b826e596766046ee1183dbc8aeaa68",
+            37,
+        );
+
+        let tx = Eip1559TransactionRequest::new()
+            .to(to)
+            .value(1u64)
+            .gas(21_000u64)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .max_fee_per_gas(2_000_000_000u64)
+            .nonce(0u64)
+            .chain_id(1u64)
+            .into();
+        assert_sig(
+            wallet.sign_transaction_sync(&tx),
+            "b20cf9a5b42e1d80ae728e164c063b0aaa9085a0093c730bf744111703cbd9af",
+            "3354e42d1fb227de9352f41d0aad9a6640b1922a301e3ff582c06294f5bc8b6a",
+            // recovery id 1 as EIP-155 `v`, it is normalized when encoding the transaction
+            38,
+        );
+    }
+
+    #[tokio::test]
+    async fn signs_typed_data_with_domain_separator() {
+        use ethers_core::{
+            abi::{self, Token},
+            types::{
+                transaction::eip712::{EIP712Domain, Eip712, Eip712Error},
+                U256,
+            },
+            utils::keccak256,
+        };
+
+        #[derive(Clone)]
+        struct Mail(u64);
+
+        impl Eip712 for Mail {
+            type Error = Eip712Error;
+
+            fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+                Ok(EIP712Domain {
+                    name: "Ether Mail".to_string(),
+                    version: "1".to_string(),
+                    chain_id: 1u64.into(),
+                    verifying_contract: Address::repeat_byte(1),
+                    salt: None,
+                })
+            }
+
+            fn type_hash() -> Result<[u8; 32], Self::Error> {
+                Ok(keccak256("Mail(uint256 id)"))
+            }
+
+            fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+                Ok(keccak256(abi::encode(&[
+                    Token::Uint(U256::from(Self::type_hash()?)),
+                    Token::Uint(self.0.into()),
+                ])))
+            }
+        }
+
+        let wallet: Wallet<SigningKey> =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let domain_separator = Mail(0).domain_separator().unwrap();
+        for id in 0..3 {
+            let mail = Mail(id);
+            let sig =
+                wallet.sign_typed_data_with_domain_separator(&mail, domain_separator).unwrap();
+            assert_eq!(sig, wallet.sign_typed_data(&mail).await.unwrap());
+            sig.verify(mail.encode_eip712().unwrap(), wallet.address).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn signs_with_and_without_eip155() {
+        use crate::TypedTransaction;
+        use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest, U256};
+
+        // https://eips.ethereum.org/EIPS/eip-155#example
+        let wallet: Wallet<SigningKey> =
+            "4646464646464646464646464646464646464646464646464646464646464646".parse().unwrap();
+        let tx: TypedTransaction =
+            TransactionRequest::pay(Address::repeat_byte(0x35), U256::exp10(18))
+                .gas(21_000u64)
+                .gas_price(20_000_000_000u64)
+                .nonce(9u64)
+                .chain_id(1u64)
+                .into();
+
+        assert!(wallet.eip155());
+        let sig = wallet.sign_transaction_sync(&tx);
+        assert_sig(
+            sig,
+            "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+            "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+            37,
+        );
+        assert_eq!(sig.recover(tx.sighash()).unwrap(), wallet.address);
+
+        // the chain id of the transaction is ignored without replay protection
+        let wallet = wallet.with_eip155(false);
+        let sig = wallet.sign_transaction(&tx).await.unwrap();
+        assert_sig(
+            sig,
+            "8383adc8b8ae116f918fb44ca7ff9dfd8012596a5c130c6246a2cc717ba41cda",
+            "53ddfacf5bd4aa7e46d1575acf52636ea659b91f29e2fb91c75567a279738f38",
+            27,
+        );
+        let mut unprotected = tx.as_legacy_ref().unwrap().clone();
+        unprotected.chain_id = None;
+        let unprotected: TypedTransaction = unprotected.into();
+        assert_eq!(sig.recover(unprotected.sighash()).unwrap(), wallet.address);
+
+        // typed transactions always use their chain id
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::repeat_byte(0x35))
+            .gas(21_000u64)
+            .max_fee_per_gas(20_000_000_000u64)
+            .nonce(9u64)
+            .chain_id(1u64)
+            .into();
+        assert_eq!(
+            wallet.sign_transaction_sync(&tx),
+            wallet.with_eip155(true).sign_transaction_sync(&tx)
+        );
+    }
+
     #[tokio::test]
     #[cfg(not(feature = "celo"))]
     async fn signs_tx() {
@@ -293,6 +570,54 @@ mod tests {
         assert!(sig.verify(sighash, wallet.address).is_ok());
     }
 
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn signs_typed_txs() {
+        use crate::TypedTransaction;
+        use ethers_core::types::{
+            transaction::eip2930::{AccessList, AccessListItem},
+            Eip1559TransactionRequest, TransactionRequest, H256,
+        };
+
+        // EIP-155 example key and transaction
+        let wallet: Wallet<SigningKey> =
+            "4646464646464646464646464646464646464646464646464646464646464646".parse().unwrap();
+        let to = "3535353535353535353535353535353535353535".parse::<Address>().unwrap();
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::from_low_u64_be(1),
+            storage_keys: vec![H256::from_low_u64_le(1)],
+        }]);
+        let legacy = TransactionRequest::new()
+            .nonce(9)
+            .to(to)
+            .value(1_000_000_000_000_000_000u64)
+            .gas_price(20_000_000_000u64)
+            .gas(21_000)
+            .chain_id(1);
+        let eip2930 = legacy.clone().with_access_list(access_list.clone());
+        let eip1559 = Eip1559TransactionRequest::new()
+            .nonce(9)
+            .to(to)
+            .value(1_000_000_000_000_000_000u64)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .max_fee_per_gas(20_000_000_000u64)
+            .gas(21_000)
+            .chain_id(1)
+            .access_list(access_list);
+
+        let vectors: [(TypedTransaction, &str); 3] = [
+            (legacy.into(), "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"),
+            (eip2930.into(), "01f8a701098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7940000000000000000000000000000000000000001e1a0010000000000000000000000000000000000000000000000000000000000000001a0996d057846bcdd1f37c474c82cbdf91602c9d050205f07470c47fe345bf10f7aa06f68c2a8fc360191aef86c59c2408d73c7dea7072702fbe6150f1e4edc2e85c3"),
+            (eip1559.into(), "02f8ac0109843b9aca008504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838f7940000000000000000000000000000000000000001e1a0010000000000000000000000000000000000000000000000000000000000000080a06cd061a916bc216a27e2ab1f26c84498074bbe22dd89c343daa4f5c6627d8b85a00717a93539b9b61749cd1b34f1acad55e5121346285ed7d5af6891d75ace1c0c"),
+        ];
+
+        for (tx, expected) in vectors {
+            let sig = wallet.sign_transaction(&tx).await.unwrap();
+            assert!(sig.verify(tx.sighash(), wallet.address).is_ok());
+            assert_eq!(hex::encode(tx.rlp_signed(&sig)), expected);
+        }
+    }
+
     #[test]
     fn key_to_address() {
         let wallet: Wallet<SigningKey> =